@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Rounds of range checks interleaved with arithmetic, for a domain of
+/// `2^16`
+const ROUNDS: usize = 2_000;
+
+#[derive(Debug)]
+struct MixedCircuit {
+    values: Vec<BlsScalar>,
+}
+
+impl Default for MixedCircuit {
+    fn default() -> Self {
+        Self {
+            values: vec![BlsScalar::zero(); ROUNDS],
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for MixedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let values: Vec<_> = self
+            .values
+            .iter()
+            .map(|v| composer.append_witness(*v))
+            .collect();
+
+        let mut sum = Plonk::<JubjubAffine>::ZERO;
+        for v in values {
+            composer.component_range(v, 32);
+
+            let constraint =
+                Constraint::default().mult(1).fourth(1).a(v).b(v).d(sum);
+            sum = composer.gate_mul(constraint);
+
+            composer.append_logic_xor(v, v, 32);
+        }
+
+        Ok(())
+    }
+}
+
+fn locality(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::setup_for_circuit(
+            &MixedCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+    let (reordered_prover, reordered_verifier) = PlonkKey::<
+        TatePairing,
+        JubjubAffine,
+        MixedCircuit,
+    >::compile_for_locality(
+        &pp,
+        b"plonk",
+        &MixedCircuit::default(),
+        InitConfig::default(),
+    )
+    .expect("failed to compile circuit");
+
+    let circuit = MixedCircuit {
+        values: (0..ROUNDS as u64).map(BlsScalar::from).collect(),
+    };
+
+    let mut group = c.benchmark_group("locality");
+    group.sample_size(10);
+
+    for (name, prover, verifier) in [
+        ("prove in synthesis order", &prover, &verifier),
+        ("prove reordered", &reordered_prover, &reordered_verifier),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        group.bench_function(name, |b| {
+            b.iter(|| prover.create_proof(&mut rng, &circuit))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, locality);
+criterion_main!(benches);
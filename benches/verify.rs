@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+
+/// Verifications measured by every sample
+const VERIFICATIONS: u64 = 10_000;
+
+#[derive(Debug, Default)]
+struct BenchCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for BenchCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let constraint = Constraint::default().mult(1).a(w_a).b(w_b);
+        let product = composer.gate_mul(constraint);
+        composer.assert_equal_constant(product, 0, Some(-self.a * self.b));
+
+        composer.component_range(w_a, 64);
+        composer.append_logic_xor(w_a, w_b, 64);
+
+        Ok(())
+    }
+}
+
+fn verify(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, BenchCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    let circuit = BenchCircuit {
+        a: BlsScalar::from(3u64),
+        b: BlsScalar::from(5u64),
+    };
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    let mut group = c.benchmark_group("verifier");
+    group.sample_size(10);
+
+    group.bench_function("verify 10k proofs", |b| {
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+
+            for _ in 0..iters {
+                let start = Instant::now();
+
+                for _ in 0..VERIFICATIONS {
+                    verifier
+                        .verify(&proof, &public_inputs)
+                        .expect("failed to verify proof");
+                }
+
+                elapsed += start.elapsed();
+            }
+
+            elapsed
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, verify);
+criterion_main!(benches);
@@ -15,35 +15,159 @@
 //! linearizer
 
 use poly_commit::{powers_of, Commitment, EvaluationKey, Proof};
+use rand_core::RngCore;
 #[cfg(feature = "std")]
 use rayon::prelude::*;
 use zksnarks::error::Error;
 use zksnarks::plonk::{Transcript, TranscriptProtocol};
-use zkstd::common::{CurveGroup, Group, Pairing, PairingRange, Vec};
+use zkstd::common::{CurveGroup, Group, Pairing, PairingRange, Ring, Vec};
 
 pub(crate) fn batch_check<P: Pairing>(
     evaluation_key: &EvaluationKey<P>,
-    points: &[P::ScalarField],
-    proofs: &[Proof<P>],
+    claims: &[OpeningClaim<P>],
     transcript: &mut Transcript,
 ) -> Result<(), Error> {
+    let u_challenge = batch_challenge::<P>(transcript);
+
+    batch_check_with(evaluation_key, claims, &u_challenge)
+}
+
+/// Challenge the opening claims are batched with
+pub(crate) fn batch_challenge<P: Pairing>(
+    transcript: &mut Transcript,
+) -> P::ScalarField {
+    // XXX: Verifier can add their own randomness at this point
+    <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+        transcript, b"batch",
+    )
+}
+
+/// Batch check the claims with the powers of an already derived
+/// `u_challenge`
+pub(crate) fn batch_check_with<P: Pairing>(
+    evaluation_key: &EvaluationKey<P>,
+    claims: &[OpeningClaim<P>],
+    u_challenge: &P::ScalarField,
+) -> Result<(), Error> {
+    let powers = powers_of(u_challenge, claims.len() - 1);
+
+    pairing_check(evaluation_key, claims.iter().copied().zip(powers))
+}
+
+/// Claim that a committed polynomial evaluates to `evaluation` at `point`,
+/// along with the commitment to its opening witness.
+#[derive(Debug)]
+pub struct OpeningClaim<P: Pairing> {
+    /// Point the polynomial is evaluated at
+    pub point: P::ScalarField,
+    /// Commitment to the polynomial
+    pub commitment: Commitment<P::G1Affine>,
+    /// Claimed evaluation of the polynomial at `point`
+    pub evaluation: P::ScalarField,
+    /// Commitment to the opening witness polynomial
+    pub witness: Commitment<P::G1Affine>,
+}
+
+impl<P: Pairing> Clone for OpeningClaim<P> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point,
+            commitment: self.commitment,
+            evaluation: self.evaluation,
+            witness: self.witness,
+        }
+    }
+}
+
+impl<P: Pairing> Copy for OpeningClaim<P> {}
+
+impl<P: Pairing> OpeningClaim<P> {
+    /// Bind the claim to the transcript
+    pub(crate) fn append_to(&self, transcript: &mut Transcript) {
+        <Transcript as TranscriptProtocol<P>>::append_scalar(
+            transcript,
+            b"claim_point",
+            &self.point,
+        );
+        <Transcript as TranscriptProtocol<P>>::append_commitment(
+            transcript,
+            b"claim_comm",
+            &self.commitment,
+        );
+        <Transcript as TranscriptProtocol<P>>::append_scalar(
+            transcript,
+            b"claim_eval",
+            &self.evaluation,
+        );
+        <Transcript as TranscriptProtocol<P>>::append_commitment(
+            transcript,
+            b"claim_w",
+            &self.witness,
+        );
+    }
+}
+
+/// Check many opening claims from unrelated sources with a single pairing.
+///
+/// The claims are combined with coefficients sampled from `rng` instead of a
+/// transcript, so claims produced with different transcripts can be batched
+/// together. The coefficients must be unpredictable to whoever produced the
+/// claims, otherwise invalid claims can be crafted to cancel each other out.
+pub fn batch_check_independent<P, R>(
+    evaluation_key: &EvaluationKey<P>,
+    claims: &[OpeningClaim<P>],
+    rng: &mut R,
+) -> Result<(), Error>
+where
+    P: Pairing,
+    R: RngCore,
+{
+    let coefficients: Vec<_> = claims
+        .iter()
+        .map(|_| P::ScalarField::random(&mut *rng))
+        .collect();
+
+    pairing_check(evaluation_key, claims.iter().copied().zip(coefficients))
+}
+
+/// Return the index of the first claim that doesn't verify on its own.
+///
+/// Fallback to locate the invalid claim when
+/// [`batch_check_independent`] fails.
+pub fn find_invalid_claim<P: Pairing>(
+    evaluation_key: &EvaluationKey<P>,
+    claims: &[OpeningClaim<P>],
+) -> Option<usize> {
+    claims.iter().position(|claim| {
+        let claim = core::iter::once((*claim, P::ScalarField::one()));
+
+        pairing_check(evaluation_key, claim).is_err()
+    })
+}
+
+/// Check `e(-Σ u·W, β·H) · e(Σ u·(C + z·W) - Σ u·v·G, H) = 1` for the
+/// claims and their coefficients `u`
+fn pairing_check<P, I>(
+    evaluation_key: &EvaluationKey<P>,
+    claims: I,
+) -> Result<(), Error>
+where
+    P: Pairing,
+    I: IntoIterator<Item = (OpeningClaim<P>, P::ScalarField)>,
+{
     let mut total_c = P::G1Projective::ADDITIVE_IDENTITY;
     let mut total_w = P::G1Projective::ADDITIVE_IDENTITY;
 
-    let u_challenge = <Transcript as TranscriptProtocol<P>>::challenge_scalar(
-        transcript, b"batch",
-    ); // XXX: Verifier can add their own randomness at this point
-    let powers = powers_of(&u_challenge, proofs.len() - 1);
     // Instead of multiplying g and gamma_g in each turn, we simply
     // accumulate their coefficients and perform a final
     // multiplication at the end.
     let mut g_multiplier = P::ScalarField::zero();
 
-    for ((proof, u_challenge), point) in proofs.iter().zip(powers).zip(points) {
-        let mut c = P::G1Projective::from(proof.commitment_to_polynomial.0);
-        let w = P::G1Projective::from(proof.commitment_to_witness.0);
-        c += w * *point;
-        g_multiplier += u_challenge * proof.evaluated_point;
+    for (claim, u_challenge) in claims {
+        let mut c = P::G1Projective::from(claim.commitment.0);
+        let w = P::G1Projective::from(claim.witness.0);
+        c += w * claim.point;
+        g_multiplier += u_challenge * claim.evaluation;
 
         total_c += c * u_challenge;
         total_w += w * u_challenge;
@@ -102,16 +226,11 @@ impl<P: Pairing> AggregateProof<P> {
     }
 
     /// Flattens an `AggregateProof` into a `Proof`.
-    /// The transcript must have the same view as the transcript that was
-    /// used to aggregate the witness in the proving stage.
-    pub(crate) fn flatten(&self, transcript: &mut Transcript) -> Proof<P> {
-        let v_challenge: P::ScalarField = <Transcript as TranscriptProtocol<
-            P,
-        >>::challenge_scalar(
-            transcript, b"v_challenge"
-        );
+    /// `v_challenge` must be the challenge that was used to aggregate the
+    /// witness in the proving stage.
+    pub(crate) fn flatten(&self, v_challenge: &P::ScalarField) -> Proof<P> {
         let powers =
-            powers_of(&v_challenge, self.commitments_to_polynomials.len() - 1);
+            powers_of(v_challenge, self.commitments_to_polynomials.len() - 1);
 
         #[cfg(not(feature = "std"))]
         let flattened_poly_commitments_iter =
@@ -0,0 +1,165 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Gates registered by the user next to the standard widgets.
+//!
+//! A [`CustomGate`] is an identity over the wires of a gate and of the next
+//! one, enabled by a selector polynomial of its own. The selector is `1` on
+//! the gates appended with [`Plonk::append_registered_gate`] and `0`
+//! elsewhere, and the keys are compiled with
+//! [`PlonkKey::compile_with_custom_gates`].
+//!
+//! The gate contributes `q(X) · f(wires)` to the quotient, where `f` is
+//! [`CustomGate::evaluate`] scaled by a separation challenge drawn right
+//! after the ones of the standard widgets, with the label of the gate. `f`
+//! only reads the wires the proof opens, so the verifier evaluates it at
+//! `z` and scales the commitment to the selector by it in the linearization
+//! commitment: the same evaluation serves the quotient, at every point of
+//! the coset, and the linearization.
+//!
+//! The commitments to the selectors are appended to the transcript of the
+//! circuit, and the challenges are only drawn if there are custom gates, so
+//! the proofs of circuits without them are unchanged.
+//!
+//! [`Plonk::append_registered_gate`]: crate::Plonk::append_registered_gate
+//! [`PlonkKey::compile_with_custom_gates`]: crate::PlonkKey::compile_with_custom_gates
+
+use poly_commit::{Coefficients, Commitment, PointsValue};
+use zksnarks::plonk::{
+    Evaluations as ProofEvaluations, Transcript, TranscriptProtocol,
+};
+use zkstd::common::{Pairing, PrimeField, Vec};
+
+/// Wire values a [`CustomGate`] is evaluated at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GateWires<F> {
+    /// Left wire
+    pub a: F,
+    /// Right wire
+    pub b: F,
+    /// Output wire
+    pub c: F,
+    /// Fourth wire
+    pub d: F,
+    /// Left wire of the next gate
+    pub a_next: F,
+    /// Right wire of the next gate
+    pub b_next: F,
+    /// Fourth wire of the next gate
+    pub d_next: F,
+}
+
+impl<F: Copy> GateWires<F> {
+    /// Wires opened by a proof at the evaluation challenge `z`
+    pub(crate) fn opened(evaluations: &ProofEvaluations<F>) -> Self {
+        Self {
+            a: evaluations.a_eval,
+            b: evaluations.b_eval,
+            c: evaluations.c_eval,
+            d: evaluations.d_eval,
+            a_next: evaluations.a_next_eval,
+            b_next: evaluations.b_next_eval,
+            d_next: evaluations.d_next_eval,
+        }
+    }
+}
+
+/// Identity enforced on the gates appended with
+/// [`Plonk::append_registered_gate`](crate::Plonk::append_registered_gate)
+pub trait CustomGate<F: PrimeField>: Send + Sync {
+    /// Label the commitment to the selector is appended to the transcript
+    /// with, and the separation challenge is drawn with.
+    ///
+    /// It must differ from the labels of the other gates of the circuit.
+    fn label(&self) -> &'static [u8];
+
+    /// Maximum number of wires multiplied in a term of the identity.
+    ///
+    /// As for the standard widgets, the quotient only fits the proof up to
+    /// `4` wires, or `5` wires if split in
+    /// [`QuotientParts::Six`](crate::QuotientParts::Six).
+    fn wire_degree(&self) -> usize;
+
+    /// Evaluate the identity at `wires`, scaled by `separation`.
+    ///
+    /// The identity must be zero at the wires of every gate the selector is
+    /// set on. Several equations are combined with the powers of
+    /// `separation`, as in `ξ · e_0 + ξ² · e_1`, so none of them cancels
+    /// another one or the standard widgets.
+    fn evaluate(&self, separation: &F, wires: &GateWires<F>) -> F;
+}
+
+/// Selector of a custom gate, as needed by the prover
+#[derive(Clone)]
+pub(crate) struct CustomProvingKey<P: Pairing> {
+    pub(crate) gate: &'static dyn CustomGate<P::ScalarField>,
+    /// Selector polynomial and its evaluations over the `8n` coset
+    pub(crate) q: (Coefficients<P::ScalarField>, PointsValue<P::ScalarField>),
+}
+
+impl<P: Pairing> CustomProvingKey<P> {
+    /// Contribution of the gate to the quotient at the point `i` of the
+    /// `8n` coset
+    pub(crate) fn compute_quotient_i(
+        &self,
+        i: usize,
+        separation: &P::ScalarField,
+        wires: &GateWires<P::ScalarField>,
+    ) -> P::ScalarField {
+        self.q.1 .0[i] * self.gate.evaluate(separation, wires)
+    }
+
+    /// Contribution of the gate to the linearization polynomial
+    pub(crate) fn linearize(
+        &self,
+        separation: &P::ScalarField,
+        wires: &GateWires<P::ScalarField>,
+    ) -> Coefficients<P::ScalarField> {
+        &self.q.0 * &self.gate.evaluate(separation, wires)
+    }
+}
+
+/// Selector of a custom gate, as needed by the verifier
+#[derive(Clone)]
+pub(crate) struct CustomVerificationKey<P: Pairing> {
+    pub(crate) gate: &'static dyn CustomGate<P::ScalarField>,
+    pub(crate) q: Commitment<P::G1Affine>,
+}
+
+/// Append the commitments to the selectors of the custom gates to the
+/// transcript of a circuit
+pub(crate) fn append_selectors<P: Pairing>(
+    transcript: &mut Transcript,
+    keys: &[CustomVerificationKey<P>],
+) {
+    keys.iter().for_each(|key| {
+        <Transcript as TranscriptProtocol<P>>::append_commitment(
+            transcript,
+            key.gate.label(),
+            &key.q,
+        )
+    });
+}
+
+/// Draw the separation challenges of the custom gates with the given
+/// labels, in order
+pub(crate) fn separation_challenges<P, I>(
+    transcript: &mut Transcript,
+    labels: I,
+) -> Vec<P::ScalarField>
+where
+    P: Pairing,
+    I: IntoIterator<Item = &'static [u8]>,
+{
+    labels
+        .into_iter()
+        .map(|label| {
+            <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                transcript, label,
+            )
+        })
+        .collect()
+}
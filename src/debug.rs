@@ -0,0 +1,87 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Development aid to compare the witnesses of a circuit against the values
+//! of the native computation it mirrors.
+//!
+//! Assertions are only recorded with the `debug-assertions` feature. They
+//! never append gates nor witnesses, so the circuit description is the same
+//! with the feature on and off.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+/// Expected value of a witness recorded by
+/// [`Plonk::append_debug_assertion`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "debug-assertions"), allow(dead_code))]
+pub(crate) struct DebugAssertion<F> {
+    pub(crate) wire: PrivateWire,
+    pub(crate) expected: F,
+    pub(crate) label: &'static str,
+    pub(crate) gate: usize,
+}
+
+/// Witness that diverged from its native expectation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugMismatch<F> {
+    /// Label provided with the assertion
+    pub label: &'static str,
+    /// Index of the witness
+    pub wire: usize,
+    /// Number of gates appended when the assertion was recorded
+    pub gate: usize,
+    /// Value of the native computation
+    pub expected: F,
+    /// Value of the witness
+    pub actual: F,
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Record that the witness `w` is expected to equal the natively computed
+    /// `expected`.
+    ///
+    /// This is a no-op without the `debug-assertions` feature, and never adds
+    /// gates to the circuit.
+    #[allow(unused_variables)]
+    pub fn append_debug_assertion(
+        &mut self,
+        w: PrivateWire,
+        expected: C::Range,
+        label: &'static str,
+    ) {
+        #[cfg(feature = "debug-assertions")]
+        self.debug_assertions.push(DebugAssertion {
+            wire: w,
+            expected,
+            label,
+            gate: self.constraints.len(),
+        });
+    }
+
+    /// Compare the recorded debug assertions against the witnesses and
+    /// return the ones that don't match
+    pub fn check_debug_assertions(&self) -> Vec<DebugMismatch<C::Range>> {
+        #[cfg(feature = "debug-assertions")]
+        let assertions = self.debug_assertions.as_slice();
+        #[cfg(not(feature = "debug-assertions"))]
+        let assertions: &[DebugAssertion<C::Range>] = &[];
+
+        assertions
+            .iter()
+            .filter(|a| self[a.wire] != a.expected)
+            .map(|a| DebugMismatch {
+                label: a.label,
+                wire: a.wire.index(),
+                gate: a.gate,
+                expected: a.expected,
+                actual: self[a.wire],
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,43 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Digests of external data bound to a circuit as public inputs.
+//!
+//! Data too large to be public inputs is hashed off-circuit, and the circuit
+//! exposes the digest with
+//! [`Plonk::append_public_digest`](crate::Plonk::append_public_digest). The
+//! verifier hashes the data itself with
+//! [`Verifier::verify_with_preimage`](crate::Verifier::verify_with_preimage).
+//!
+//! The digest is the 64 bytes of the Blake2b-512 hash of the data, read as a
+//! little-endian integer and reduced modulo the order of the scalar field.
+//! The hash is twice as wide as the modulus, so the bias of the reduction is
+//! negligible.
+
+use blake2::{Blake2b512, Digest};
+use zkstd::common::PrimeField;
+
+use crate::prelude::BlsScalar;
+
+/// Name of the hash algorithm of the digests
+pub const DIGEST_ALGORITHM: &str = "blake2b-512";
+
+/// Digest of `data` as a scalar, see the [module](self) documentation for
+/// the reduction
+pub fn public_digest(data: &[u8]) -> BlsScalar {
+    digest_to_scalar(data)
+}
+
+/// [`public_digest`] over any scalar field
+pub(crate) fn digest_to_scalar<F: PrimeField>(data: &[u8]) -> F {
+    let hash = Blake2b512::digest(data);
+    let radix = F::from(256u64);
+
+    // horner evaluation from the most significant byte
+    hash.iter()
+        .rev()
+        .fold(F::zero(), |acc, byte| acc * radix + F::from(*byte as u64))
+}
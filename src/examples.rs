@@ -0,0 +1,188 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Worked circuits that go through the whole workflow: size the params
+//! with [`PlonkKey::setup_for_circuit`], compile, prove and verify.
+//!
+//! - `transfer`: confidential transfer with committed amounts, see
+//!   [`TransferCircuit`].
+//! - `merkle`: membership in a Merkle tree, see [`MerkleCircuit`].
+//! - `schnorr`: verification of a Schnorr signature, see [`SchnorrCircuit`].
+//!
+//! Each of them is also a binary, `cargo run --features examples --example
+//! <name>`, printing the report [`run_example`] returns.
+
+mod merkle;
+mod schnorr;
+mod transfer;
+
+pub use merkle::{compress, MerkleCircuit, MERKLE_DEPTH};
+pub use schnorr::SchnorrCircuit;
+pub use transfer::{blinding_generator, TransferCircuit};
+
+use std::time::{Duration, Instant};
+
+use ec_pairing::TatePairing;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+
+use crate::prelude::JubjubAffine;
+use crate::{CircuitStats, InitConfig, Plonk, PlonkKey};
+
+/// Names accepted by [`run_example`]
+pub const EXAMPLES: [&str; 3] = ["transfer", "merkle", "schnorr"];
+
+/// Seed of the params and the witnesses, so the reports are reproducible
+const SEED: [u8; 32] = [0x2a; 32];
+
+/// Gates and timings of a run of an example
+#[derive(Debug, Clone, Copy)]
+pub struct ExampleReport {
+    /// Name of the example
+    pub name: &'static str,
+    /// Gates of the compiled circuit
+    pub stats: CircuitStats,
+    /// `k` the params were set up with
+    pub domain_log2: u64,
+    /// Time to compile the circuit
+    pub compile: Duration,
+    /// Time to create the proof
+    pub prove: Duration,
+    /// Time to verify the proof
+    pub verify: Duration,
+}
+
+/// Error returned by [`run_example`]
+#[derive(Debug)]
+pub enum ExampleError {
+    /// The name isn't one of [`EXAMPLES`]
+    Unknown(String),
+    /// Compiling, proving or verifying the circuit failed
+    Plonk(Error),
+}
+
+impl core::fmt::Display for ExampleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(
+                f,
+                "unknown example `{}`, expected one of {:?}",
+                name, EXAMPLES
+            ),
+            Self::Plonk(e) => write!(f, "plonk error: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExampleError {}
+
+impl From<Error> for ExampleError {
+    fn from(e: Error) -> Self {
+        Self::Plonk(e)
+    }
+}
+
+impl core::fmt::Display for ExampleReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let stats = self.stats;
+
+        writeln!(f, "example: {}", self.name)?;
+        writeln!(
+            f,
+            "  gates: {} (domain 2^{}, {} witnesses, {} public inputs)",
+            stats.gates, self.domain_log2, stats.witnesses, stats.public_inputs
+        )?;
+        writeln!(
+            f,
+            "  families: {} arithmetic, {} range, {} logic, {} fixed base, {} \
+             variable base",
+            stats.arithmetic,
+            stats.range,
+            stats.logic,
+            stats.fixed_base,
+            stats.variable_base
+        )?;
+        writeln!(f, "  compile: {:?}", self.compile)?;
+        writeln!(f, "  prove: {:?}", self.prove)?;
+        write!(f, "  verify: {:?}", self.verify)
+    }
+}
+
+/// Run the example `name` and return its gate counts and timings.
+///
+/// [`ExampleError::Unknown`] is returned if `name` isn't one of
+/// [`EXAMPLES`].
+pub fn run_example(name: &str) -> Result<ExampleReport, ExampleError> {
+    let mut rng = ChaCha20Rng::from_seed(SEED);
+
+    let report = match name {
+        "transfer" => {
+            let circuit = TransferCircuit::random(&mut rng);
+            report("transfer", &circuit, &mut rng)
+        }
+        "merkle" => {
+            let circuit = MerkleCircuit::random(&mut rng);
+            report("merkle", &circuit, &mut rng)
+        }
+        "schnorr" => {
+            let circuit = SchnorrCircuit::random(&mut rng);
+            report("schnorr", &circuit, &mut rng)
+        }
+        _ => return Err(ExampleError::Unknown(name.into())),
+    };
+
+    Ok(report?)
+}
+
+fn report<C>(
+    name: &'static str,
+    circuit: &C,
+    rng: &mut ChaCha20Rng,
+) -> Result<ExampleReport, Error>
+where
+    C: Circuit<JubjubAffine, ConstraintSystem = Plonk<JubjubAffine>> + Default,
+{
+    let domain_log2 =
+        PlonkKey::<TatePairing, JubjubAffine, C>::required_domain_log2(
+            &C::default(),
+        )?;
+    let pp = PlonkKey::<TatePairing, JubjubAffine, C>::setup_for_circuit(
+        &C::default(),
+        &mut *rng,
+    )?;
+
+    let mut stats = CircuitStats::default();
+
+    let start = Instant::now();
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, C>::compile_with_stats(
+            &pp,
+            name.as_bytes(),
+            &C::default(),
+            InitConfig::default(),
+            |s| stats = *s,
+        )?;
+    let compile = start.elapsed();
+
+    let start = Instant::now();
+    let (proof, public_inputs) = prover.create_proof(&mut *rng, circuit)?;
+    let prove = start.elapsed();
+
+    let start = Instant::now();
+    verifier.verify(&proof, &public_inputs)?;
+    let verify = start.elapsed();
+
+    Ok(ExampleReport {
+        name,
+        stats,
+        domain_log2,
+        compile,
+        prove,
+        verify,
+    })
+}
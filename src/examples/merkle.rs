@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand_core::RngCore;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::Group;
+
+use crate::prelude::{BlsScalar, JubjubAffine};
+use crate::Plonk;
+
+/// Depth of the tree of the example, so it has `2^16` leaves
+pub const MERKLE_DEPTH: usize = 16;
+
+/// Compression of two nodes into their parent, `(l + 7·r + 3)^5 + l`.
+///
+/// The crate has no hash gadget, so this toy compression stands for one. It
+/// is cheap to prove, but it is NOT a vetted hash function and mustn't be
+/// used outside the example.
+pub fn compress(left: BlsScalar, right: BlsScalar) -> BlsScalar {
+    (left + right * BlsScalar::from(7u64) + BlsScalar::from(3u64)).pow(5) + left
+}
+
+/// Proves a secret leaf belongs to the tree of a public root.
+///
+/// The witnesses are the leaf, the siblings along its path and the bits of
+/// its position, least significant first: a set bit means the node is the
+/// right child of its parent.
+#[derive(Debug, Clone, Copy)]
+pub struct MerkleCircuit {
+    leaf: BlsScalar,
+    siblings: [BlsScalar; MERKLE_DEPTH],
+    position: u64,
+    root: BlsScalar,
+}
+
+impl MerkleCircuit {
+    /// Opening of `leaf` at `position` with the given siblings
+    pub fn new(
+        leaf: BlsScalar,
+        siblings: [BlsScalar; MERKLE_DEPTH],
+        position: u64,
+    ) -> Self {
+        let root =
+            siblings.iter().enumerate().fold(
+                leaf,
+                |node, (i, s)| match position >> i & 1 == 1 {
+                    true => compress(*s, node),
+                    false => compress(node, *s),
+                },
+            );
+
+        Self {
+            leaf,
+            siblings,
+            position,
+            root,
+        }
+    }
+
+    /// Opening of a random leaf at a random position
+    pub fn random<R: RngCore>(rng: &mut R) -> Self {
+        let leaf = BlsScalar::random(&mut *rng);
+        let siblings = [(); MERKLE_DEPTH].map(|_| BlsScalar::random(&mut *rng));
+        let position = rng.next_u64() % (1 << MERKLE_DEPTH);
+
+        Self::new(leaf, siblings, position)
+    }
+
+    /// Root of the tree
+    pub const fn root(&self) -> BlsScalar {
+        self.root
+    }
+}
+
+impl Default for MerkleCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero(), [BlsScalar::zero(); MERKLE_DEPTH], 0)
+    }
+}
+
+/// Circuit twin of [`compress`]
+fn gadget_compress(
+    composer: &mut Plonk<JubjubAffine>,
+    left: PrivateWire,
+    right: PrivateWire,
+) -> PrivateWire {
+    let constraint = Constraint::default()
+        .left(1)
+        .right(7)
+        .constant(3)
+        .a(left)
+        .b(right);
+    let t = composer.gate_add(constraint);
+    let t = composer.component_exp_const(t, 5);
+
+    let constraint = Constraint::default().left(1).right(1).a(t).b(left);
+    composer.gate_add(constraint)
+}
+
+impl Circuit<JubjubAffine> for MerkleCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let mut node = composer.append_witness(self.leaf);
+
+        for (i, sibling) in self.siblings.iter().enumerate() {
+            let bit = BlsScalar::from(self.position >> i & 1);
+            let bit = composer.append_witness(bit);
+            let sibling = composer.append_witness(*sibling);
+
+            let (left, right) =
+                composer.component_swap(bit, node, sibling, true);
+            node = gadget_compress(composer, left, right);
+        }
+
+        let root = composer.append_public(self.root);
+        composer.assert_equal(node, root);
+
+        Ok(())
+    }
+}
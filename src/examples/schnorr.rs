@@ -0,0 +1,98 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand_core::RngCore;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zkstd::common::Group;
+
+use crate::prelude::{JubjubAffine, JubjubScalar};
+use crate::Plonk;
+
+/// Verifies a Schnorr signature `(R, s)` over Jubjub, `s·G = R + e·PK`.
+///
+/// The public key, `R` and the challenge `e` are public inputs, and `s` is
+/// the witness. The challenge is derived from `R`, the public key and the
+/// message outside the circuit, by the application that checks the public
+/// inputs; the example draws it at random instead.
+#[derive(Debug, Clone, Copy)]
+pub struct SchnorrCircuit {
+    public_key: JubjubAffine,
+    r: JubjubAffine,
+    e: JubjubScalar,
+    s: JubjubScalar,
+}
+
+impl SchnorrCircuit {
+    /// Signature of the challenge `e` with the secret key `sk` and the
+    /// nonce `k`
+    pub fn sign(sk: JubjubScalar, k: JubjubScalar, e: JubjubScalar) -> Self {
+        let public_key = (JubjubAffine::ADDITIVE_GENERATOR * sk).into();
+        let r = (JubjubAffine::ADDITIVE_GENERATOR * k).into();
+        let s = k + e * sk;
+
+        Self {
+            public_key,
+            r,
+            e,
+            s,
+        }
+    }
+
+    /// Signature of a random challenge with a random key
+    pub fn random<R: RngCore>(rng: &mut R) -> Self {
+        Self::sign(
+            JubjubScalar::random(&mut *rng),
+            JubjubScalar::random(&mut *rng),
+            JubjubScalar::random(&mut *rng),
+        )
+    }
+
+    /// Public key the signature is checked against
+    pub const fn public_key(&self) -> JubjubAffine {
+        self.public_key
+    }
+}
+
+impl Default for SchnorrCircuit {
+    fn default() -> Self {
+        let one = JubjubScalar::one();
+
+        Self::sign(one, one, one)
+    }
+}
+
+impl Circuit<JubjubAffine> for SchnorrCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let public_key = composer.append_point(self.public_key);
+        composer.assert_equal_public_point(public_key, self.public_key);
+
+        let r = composer.append_point(self.r);
+        composer.assert_equal_public_point(r, self.r);
+
+        let e = composer.append_public(self.e);
+        let s = composer.append_witness(self.s);
+        let s = composer.component_assert_jubjub_scalar(s);
+
+        // s·G
+        let lhs = composer.component_mul_generator_typed(
+            s,
+            JubjubAffine::ADDITIVE_GENERATOR,
+        )?;
+
+        // R + e·PK
+        let e_pk = composer.component_mul_point(e, public_key);
+        let rhs = composer.component_add_point(r, e_pk);
+
+        composer.assert_equal_point(lhs, rhs);
+
+        Ok(())
+    }
+}
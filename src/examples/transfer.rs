@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand_core::RngCore;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::Constraint;
+use zkstd::common::Group;
+
+use crate::prelude::{BlsScalar, JubjubAffine, JubjubScalar};
+use crate::Plonk;
+
+/// Bits of the amounts, so the balance equation can't wrap around the field
+const AMOUNT_BITS: usize = 64;
+
+/// Generator the amounts are blinded with.
+///
+/// Its discrete logarithm is known, so it is only fit for the example: a
+/// real application derives it with a hash to the curve.
+pub fn blinding_generator() -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::from(0x5eed_u64)).into()
+}
+
+/// Spends a committed input into a committed output and a public fee.
+///
+/// The public inputs are the Pedersen commitments to the input and output
+/// amounts, and the fee. The circuit proves both amounts fit 64 bits and
+/// `input = output + fee`, without revealing the amounts.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferCircuit {
+    input: u64,
+    input_blinding: JubjubScalar,
+    output: u64,
+    output_blinding: JubjubScalar,
+    fee: u64,
+}
+
+impl TransferCircuit {
+    /// Transfer of `input` paying `fee`, with the change as the output.
+    ///
+    /// # Panics
+    ///
+    /// If `fee` is larger than `input`.
+    pub fn new(
+        input: u64,
+        input_blinding: JubjubScalar,
+        fee: u64,
+        output_blinding: JubjubScalar,
+    ) -> Self {
+        let output = input.checked_sub(fee).expect("the fee exceeds the input");
+
+        Self {
+            input,
+            input_blinding,
+            output,
+            output_blinding,
+            fee,
+        }
+    }
+
+    /// Transfer of random amounts and blindings
+    pub fn random<R: RngCore>(rng: &mut R) -> Self {
+        let input = rng.next_u64();
+        let fee = rng.next_u64() % (input / 100 + 1);
+
+        Self::new(
+            input,
+            JubjubScalar::random(&mut *rng),
+            fee,
+            JubjubScalar::random(&mut *rng),
+        )
+    }
+}
+
+impl Default for TransferCircuit {
+    fn default() -> Self {
+        Self::new(10, JubjubScalar::one(), 1, JubjubScalar::one())
+    }
+}
+
+impl Circuit<JubjubAffine> for TransferCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let input = composer.append_committed_public(
+            JubjubScalar::from(self.input),
+            self.input_blinding,
+            JubjubAffine::ADDITIVE_GENERATOR,
+            blinding_generator(),
+        )?;
+        let output = composer.append_committed_public(
+            JubjubScalar::from(self.output),
+            self.output_blinding,
+            JubjubAffine::ADDITIVE_GENERATOR,
+            blinding_generator(),
+        )?;
+        let fee = composer.append_public(BlsScalar::from(self.fee));
+
+        composer.component_range(input, AMOUNT_BITS);
+        composer.component_range(output, AMOUNT_BITS);
+        composer.component_range(fee, AMOUNT_BITS);
+
+        // input - output - fee = 0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(-BlsScalar::one())
+            .fourth(-BlsScalar::one())
+            .a(input)
+            .b(output)
+            .d(fee);
+        composer.append_gate(constraint);
+
+        Ok(())
+    }
+}
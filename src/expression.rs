@@ -0,0 +1,234 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Polynomial expressions of degree two, split into gates by the composer.
+//!
+//! An [`Expression`] collects linear terms `q · w`, products `q · a · b` and
+//! a constant. The terms are merged by wire, so a wire added twice ends with
+//! the sum of its coefficients, and the terms whose coefficient is zero are
+//! dropped.
+//!
+//! The composer packs the expression greedily: every product takes the left
+//! and right wires of a gate of its own, along with the linear terms of its
+//! factors. The linear terms left take two wires per gate, and the partial
+//! sum is carried to the next gate in the fourth wire. The first gate also
+//! takes the constant and a linear term in its fourth wire, and the last gate
+//! of [`Plonk::enforce_zero`] takes a linear term in its output wire.
+
+use sp_std::collections::btree_map::BTreeMap;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+/// Expression `Σ q_i · w_i + Σ q_j · a_j · b_j + c`, evaluated or asserted to
+/// be zero by the composer
+#[derive(Debug, Clone)]
+pub struct Expression<F: PrimeField> {
+    linear: BTreeMap<usize, (F, PrivateWire)>,
+    products: BTreeMap<(usize, usize), (F, PrivateWire, PrivateWire)>,
+    constant: F,
+}
+
+impl<F: PrimeField> Default for Expression<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> Expression<F> {
+    /// Empty expression, evaluating to zero
+    pub fn new() -> Self {
+        Self {
+            linear: BTreeMap::new(),
+            products: BTreeMap::new(),
+            constant: F::zero(),
+        }
+    }
+
+    /// Add the term `coefficient · w`
+    pub fn term<T: Into<F>>(mut self, coefficient: T, w: PrivateWire) -> Self {
+        let coefficient = coefficient.into();
+
+        let (q, _) = self.linear.entry(w.index()).or_insert((F::zero(), w));
+        *q = *q + coefficient;
+
+        self
+    }
+
+    /// Add the term `coefficient · a · b`
+    pub fn product<T: Into<F>>(
+        mut self,
+        coefficient: T,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> Self {
+        let coefficient = coefficient.into();
+
+        // a · b and b · a are the same product
+        let (a, b) = match a.index() <= b.index() {
+            true => (a, b),
+            false => (b, a),
+        };
+
+        let (q, _, _) = self
+            .products
+            .entry((a.index(), b.index()))
+            .or_insert((F::zero(), a, b));
+        *q = *q + coefficient;
+
+        self
+    }
+
+    /// Add the constant `constant`
+    pub fn constant<T: Into<F>>(mut self, constant: T) -> Self {
+        self.constant = self.constant + constant.into();
+
+        self
+    }
+
+    /// Split the expression into its products, with the linear terms of
+    /// their factors, the linear terms left, and the constant
+    fn into_parts(self) -> (Vec<Row<F>>, Vec<(F, PrivateWire)>, F) {
+        let Self {
+            mut linear,
+            products,
+            constant,
+        } = self;
+
+        linear.retain(|_, (q, _)| *q != F::zero());
+
+        let rows = products
+            .into_values()
+            .filter(|(q_m, _, _)| *q_m != F::zero())
+            .map(|(q_m, a, b)| {
+                let mut take =
+                    |w: PrivateWire| linear.remove(&w.index()).map(|(q, _)| q);
+
+                let q_l = take(a).unwrap_or_else(F::zero);
+                let q_r = take(b).unwrap_or_else(F::zero);
+
+                Row {
+                    q_m,
+                    q_l,
+                    q_r,
+                    a,
+                    b,
+                }
+            })
+            .collect();
+
+        (rows, linear.into_values().collect(), constant)
+    }
+}
+
+/// Gate of a product `q_m · a · b`, with the linear terms of its factors
+#[derive(Debug, Clone, Copy)]
+struct Row<F> {
+    q_m: F,
+    q_l: F,
+    q_r: F,
+    a: PrivateWire,
+    b: PrivateWire,
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Assert `expr = 0`.
+    ///
+    /// Consume one gate per product, plus one gate per two linear terms not
+    /// sharing a wire with a product, where the first gate takes one more
+    /// linear term in its fourth wire and the last gate one more in its
+    /// output wire. An expression with a single gate worth of terms,
+    /// including an expression of a constant only, consumes `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn enforce_zero(&mut self, expr: Expression<C::Range>) {
+        self.append_expression(expr, true);
+    }
+
+    /// Evaluate `expr`.
+    ///
+    /// Consume one gate per product, plus one gate per two linear terms not
+    /// sharing a wire with a product, where the first gate takes one more
+    /// linear term in its fourth wire. An expression of a constant only
+    /// consumes `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn evaluate(&mut self, expr: Expression<C::Range>) -> PrivateWire {
+        self.append_expression(expr, false)
+            .expect("the evaluation appends an output")
+    }
+
+    /// Append the gates of `expr`, either asserting it's zero or returning
+    /// its evaluation
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn append_expression(
+        &mut self,
+        expr: Expression<C::Range>,
+        enforce: bool,
+    ) -> Option<PrivateWire> {
+        let (rows, linear, constant) = expr.into_parts();
+
+        let mut rows = rows.into_iter();
+        let mut linear = linear.into_iter();
+        let mut acc = None;
+
+        loop {
+            let mut constraint = match rows.next() {
+                Some(Row {
+                    q_m,
+                    q_l,
+                    q_r,
+                    a,
+                    b,
+                }) => Constraint::default()
+                    .mult(q_m)
+                    .left(q_l)
+                    .right(q_r)
+                    .a(a)
+                    .b(b),
+                None => {
+                    let mut constraint = Constraint::default();
+                    if let Some((q_l, a)) = linear.next() {
+                        constraint = constraint.left(q_l).a(a);
+                    }
+                    if let Some((q_r, b)) = linear.next() {
+                        constraint = constraint.right(q_r).b(b);
+                    }
+                    constraint
+                }
+            };
+
+            constraint = match acc {
+                Some(acc) => constraint.fourth(1).d(acc),
+                None => {
+                    constraint = constraint.constant(constant);
+                    match linear.next() {
+                        Some((q_4, d)) => constraint.fourth(q_4).d(d),
+                        None => constraint,
+                    }
+                }
+            };
+
+            // the output wire of the last asserted gate holds a term
+            let last = rows.len() == 0 && linear.len() <= enforce as usize;
+            if last && enforce {
+                if let Some((q_o, o)) = linear.next() {
+                    constraint = constraint.output(q_o).o(o);
+                }
+                self.append_gate(constraint);
+
+                return None;
+            }
+
+            let sum = self.gate_add(constraint);
+            if last {
+                return Some(sum);
+            }
+
+            acc = Some(sum);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Byte oriented facade to compile, prove and verify circuits across a
+//! boundary that doesn't know about the types of the crate, such as wasm.
+//!
+//! Circuits stay in Rust: they are registered with [`register`] under a
+//! circuit id, and only SCALE encoded data crosses the boundary.
+//!
+//! - `params_bytes` are the encoded [`PlonkParams`].
+//! - `witness_bytes` are the encoded circuit struct, filled with the witnesses
+//!   to prove.
+//! - `public_inputs_bytes` are the encoded `Vec<BlsScalar>` returned by
+//!   [`prove`].
+//!
+//! The proving and verifying keys are derived from the params, so the
+//! prover and verifier bytes hold the circuit id and the params trimmed to
+//! the circuit, and the keys are recompiled when they are decoded.
+
+use std::string::{String, ToString};
+use std::sync::RwLock;
+
+use codec::{Decode, Encode};
+use ec_pairing::TatePairing;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zkstd::common::Vec;
+
+use crate::prelude::{BlsScalar, JubjubAffine, Proof, ProofDecodeError};
+use crate::{Plonk, PlonkKey, Prover, Verifier};
+
+type Params = PlonkParams<TatePairing>;
+type CircuitProver = Prover<TatePairing, JubjubAffine>;
+type CircuitVerifier = Verifier<TatePairing>;
+
+/// Size in bytes of the seed of the prover randomness
+pub const SEED_SIZE: usize = 32;
+
+/// Error of the byte oriented facade
+#[derive(Debug)]
+pub enum FfiError {
+    /// No circuit is registered with the given id
+    UnknownCircuit(String),
+    /// A circuit is already registered with the given id
+    DuplicateCircuit(String),
+    /// The named argument couldn't be decoded
+    Malformed(&'static str),
+    /// The proof couldn't be decoded
+    Proof(ProofDecodeError),
+    /// The seed isn't [`SEED_SIZE`] bytes long
+    InvalidSeed(usize),
+    /// Compiling or proving the circuit failed
+    Plonk(Error),
+}
+
+impl core::fmt::Display for FfiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownCircuit(id) => write!(f, "unknown circuit `{}`", id),
+            Self::DuplicateCircuit(id) => {
+                write!(f, "circuit `{}` is already registered", id)
+            }
+            Self::Malformed(arg) => write!(f, "malformed `{}` bytes", arg),
+            Self::Proof(e) => write!(f, "{}", e),
+            Self::InvalidSeed(len) => {
+                write!(f, "seed must be {} bytes long, got {}", SEED_SIZE, len)
+            }
+            Self::Plonk(e) => write!(f, "plonk error: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for FfiError {}
+
+impl From<FfiError> for String {
+    fn from(e: FfiError) -> Self {
+        e.to_string()
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(e: Error) -> Self {
+        Self::Plonk(e)
+    }
+}
+
+impl From<ProofDecodeError> for FfiError {
+    fn from(e: ProofDecodeError) -> Self {
+        Self::Proof(e)
+    }
+}
+
+/// Circuit registered under an id
+#[derive(Clone)]
+struct Entry {
+    id: String,
+    compile:
+        fn(&Params, &[u8]) -> Result<(CircuitProver, CircuitVerifier), Error>,
+    prove: fn(
+        &CircuitProver,
+        &[u8],
+        &mut ChaCha20Rng,
+    ) -> Result<(Proof<TatePairing>, Vec<BlsScalar>), FfiError>,
+}
+
+static REGISTRY: RwLock<Vec<Entry>> = RwLock::new(Vec::new());
+
+fn compile_circuit<C>(
+    params: &Params,
+    label: &[u8],
+) -> Result<(CircuitProver, CircuitVerifier), Error>
+where
+    C: Circuit<JubjubAffine, ConstraintSystem = Plonk<JubjubAffine>>,
+{
+    PlonkKey::<TatePairing, JubjubAffine, C>::compile_with_circuit(
+        params,
+        label,
+        &C::default(),
+    )
+}
+
+fn prove_circuit<C>(
+    prover: &CircuitProver,
+    mut witness: &[u8],
+    rng: &mut ChaCha20Rng,
+) -> Result<(Proof<TatePairing>, Vec<BlsScalar>), FfiError>
+where
+    C: Circuit<JubjubAffine, ConstraintSystem = Plonk<JubjubAffine>> + Decode,
+{
+    let circuit =
+        C::decode(&mut witness).map_err(|_| FfiError::Malformed("witness"))?;
+
+    if !witness.is_empty() {
+        return Err(FfiError::Malformed("witness"));
+    }
+
+    Ok(prover.create_proof(rng, &circuit)?)
+}
+
+/// Register the circuit `C` under `circuit_id`.
+///
+/// The witnesses of a proof are passed as the SCALE encoding of `C`. The
+/// circuit id is also the transcript label of its proofs.
+pub fn register<C>(circuit_id: &str) -> Result<(), FfiError>
+where
+    C: Circuit<JubjubAffine, ConstraintSystem = Plonk<JubjubAffine>> + Decode,
+{
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+
+    if registry.iter().any(|e| e.id == circuit_id) {
+        return Err(FfiError::DuplicateCircuit(circuit_id.to_string()));
+    }
+
+    registry.push(Entry {
+        id: circuit_id.to_string(),
+        compile: compile_circuit::<C>,
+        prove: prove_circuit::<C>,
+    });
+
+    Ok(())
+}
+
+/// Role of encoded keys, so prover and verifier bytes can't be mixed up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+enum Role {
+    Prover,
+    Verifier,
+}
+
+/// Encoded representation of the prover and verifier bytes
+#[derive(Encode, Decode)]
+struct Keys {
+    role: Role,
+    circuit_id: String,
+    params: Params,
+}
+
+impl Keys {
+    fn from_bytes(
+        mut bytes: &[u8],
+        role: Role,
+        arg: &'static str,
+    ) -> Result<Self, FfiError> {
+        let keys =
+            Self::decode(&mut bytes).map_err(|_| FfiError::Malformed(arg))?;
+
+        match keys.role == role && bytes.is_empty() {
+            true => Ok(keys),
+            false => Err(FfiError::Malformed(arg)),
+        }
+    }
+
+    /// Compile the circuit of the keys with their params
+    fn compile(
+        &self,
+    ) -> Result<(CircuitProver, CircuitVerifier, Entry), FfiError> {
+        let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+        let entry = registry
+            .iter()
+            .find(|e| e.id == self.circuit_id)
+            .ok_or_else(|| FfiError::UnknownCircuit(self.circuit_id.clone()))?;
+
+        let (prover, verifier) =
+            (entry.compile)(&self.params, self.circuit_id.as_bytes())?;
+
+        Ok((prover, verifier, entry.clone()))
+    }
+}
+
+/// Compile the circuit registered as `circuit_id` with the encoded
+/// [`PlonkParams`], returning the prover and verifier bytes
+pub fn compile(
+    params_bytes: &[u8],
+    circuit_id: &str,
+) -> Result<(Vec<u8>, Vec<u8>), FfiError> {
+    let mut bytes = params_bytes;
+    let params = Params::decode(&mut bytes)
+        .map_err(|_| FfiError::Malformed("params"))?;
+
+    if !bytes.is_empty() {
+        return Err(FfiError::Malformed("params"));
+    }
+
+    let keys = Keys {
+        role: Role::Prover,
+        circuit_id: circuit_id.to_string(),
+        params,
+    };
+
+    // the prover holds the params trimmed to the circuit
+    let (prover, _, _) = keys.compile()?;
+    let params = prover.keypair;
+
+    let prover = Keys {
+        role: Role::Prover,
+        circuit_id: circuit_id.to_string(),
+        params: params.clone(),
+    };
+    let verifier = Keys {
+        role: Role::Verifier,
+        circuit_id: circuit_id.to_string(),
+        params,
+    };
+
+    Ok((prover.encode(), verifier.encode()))
+}
+
+/// Prove the circuit of `prover_bytes` with the encoded circuit struct
+/// `witness_bytes`, returning the encoded proof and public inputs.
+///
+/// `seed` must be [`SEED_SIZE`] uniformly random bytes, since it seeds the
+/// blinding factors of the proof.
+pub fn prove(
+    prover_bytes: &[u8],
+    witness_bytes: &[u8],
+    seed: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), FfiError> {
+    let seed: [u8; SEED_SIZE] = seed
+        .try_into()
+        .map_err(|_| FfiError::InvalidSeed(seed.len()))?;
+    let mut rng = ChaCha20Rng::from_seed(seed);
+
+    let keys = Keys::from_bytes(prover_bytes, Role::Prover, "prover")?;
+    let (prover, _, entry) = keys.compile()?;
+
+    let (proof, public_inputs) =
+        (entry.prove)(&prover, witness_bytes, &mut rng)?;
+
+    Ok((proof.encode(), public_inputs.encode()))
+}
+
+/// Verify the encoded proof against the encoded public inputs.
+///
+/// Returns `Ok(false)` if the proof is well formed but invalid, and an
+/// error if any of the arguments can't be decoded.
+pub fn verify(
+    verifier_bytes: &[u8],
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+) -> Result<bool, FfiError> {
+    let keys = Keys::from_bytes(verifier_bytes, Role::Verifier, "verifier")?;
+    let proof = Proof::<TatePairing>::try_from(proof_bytes)?;
+
+    let mut bytes = public_inputs_bytes;
+    let public_inputs = Vec::<BlsScalar>::decode(&mut bytes)
+        .map_err(|_| FfiError::Malformed("public_inputs"))?;
+
+    if !bytes.is_empty() {
+        return Err(FfiError::Malformed("public_inputs"));
+    }
+
+    let (_, verifier, _) = keys.compile()?;
+
+    Ok(verifier.verify(&proof, &public_inputs).is_ok())
+}
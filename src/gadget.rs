@@ -9,7 +9,24 @@
 //! tools and abstractions, used by the Composer to generate,
 //! build, preprocess circuits.
 
+pub mod bigint;
+pub mod blake2s;
+pub(crate) mod chain;
+pub(crate) mod compare;
+pub mod compression;
 pub(crate) mod ecc;
+pub mod elligator;
+pub mod fixed_base;
+pub mod fixed_point;
+pub mod hash;
+pub mod nonnative;
+pub mod pedersen;
+pub mod poseidon;
+pub(crate) mod range;
+pub(crate) mod set;
+pub mod sponge;
+pub mod typed;
+pub mod uint32;
 pub(crate) mod witness;
 
 pub(crate) use witness::WireType;
@@ -0,0 +1,338 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Unsigned integers wider than the scalar field, as limbs of 64 bits.
+//!
+//! A [`BigIntWitness`] holds `LIMBS` wires, least significant first, each of
+//! them constrained to fit 64 bits. Every operation splits its limbs into
+//! their low 64 bits and a carry, and range checks both, so the limb
+//! equations hold over the integers: a limb sum never gets close to the
+//! field modulus, and a prover can't move an overflow from a limb into the
+//! next one.
+//!
+//! - [`BigIntWitness::add`] and [`BigIntWitness::sub`] propagate a boolean
+//!   carry or borrow and return the one out of the top limb;
+//! - [`BigIntWitness::mul`] is the schoolbook product, one column of partial
+//!   products per limb of the result;
+//! - [`BigIntWitness::less_than`] is the final borrow of the subtraction.
+
+use sp_std::vec;
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{FftField, PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+/// Bits of a limb
+pub const LIMB_BITS: usize = 64;
+
+/// Unsigned integer of `LIMBS` limbs of 64 bits, least significant first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigIntWitness<const LIMBS: usize> {
+    limbs: [PrivateWire; LIMBS],
+}
+
+impl<const LIMBS: usize> BigIntWitness<LIMBS> {
+    /// Integer of limbs already constrained to fit 64 bits.
+    ///
+    /// The constraint isn't checked: an unconstrained limb makes every
+    /// operation on the integer unsound.
+    pub const fn assume(limbs: [PrivateWire; LIMBS]) -> Self {
+        Self { limbs }
+    }
+
+    /// Limbs of the integer, least significant first
+    pub const fn limbs(&self) -> [PrivateWire; LIMBS] {
+        self.limbs
+    }
+
+    /// Append a witness of the limbs `value`, each range checked to 64 bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn witness<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        value: [u64; LIMBS],
+    ) -> Self {
+        let limbs = value.map(|limb| {
+            let limb = composer.append_witness(C::Range::from(limb));
+            composer.component_range(limb, LIMB_BITS);
+
+            limb
+        });
+
+        Self::assume(limbs)
+    }
+
+    /// Append the constant limbs `value`
+    pub fn constant<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        value: [u64; LIMBS],
+    ) -> Self {
+        Self::assume(
+            value.map(|limb| composer.append_constant(C::Range::from(limb))),
+        )
+    }
+
+    /// Limbs of the integer in the composer
+    pub fn value<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &Plonk<C>,
+    ) -> [u64; LIMBS] {
+        self.limbs.map(|limb| low_limb::<C>(composer[limb]))
+    }
+
+    /// Evaluate `self + other` modulo `2^(64 · LIMBS)`, and the carry out of
+    /// the top limb as a bit.
+    ///
+    /// Consume `1` gate for the first limb, `2` for every other, plus the
+    /// range check of every limb and one gate per carry
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn add<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> (Self, PrivateWire) {
+        let (limbs, carry) = add_limbs(composer, &self.limbs, &other.limbs);
+
+        (Self::assume(to_array(limbs)), carry)
+    }
+
+    /// Evaluate `self - other` modulo `2^(64 · LIMBS)`, and the borrow out
+    /// of the top limb as a bit, set if `self < other`.
+    ///
+    /// Consume `1` gate for the first limb, `2` for every other, plus the
+    /// range check of every limb and one gate per borrow
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn sub<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> (Self, PrivateWire) {
+        let (limbs, borrow) = sub_limbs(composer, &self.limbs, &other.limbs);
+
+        (Self::assume(to_array(limbs)), borrow)
+    }
+
+    /// Evaluate `self · other` modulo `2^(64 · OUT)`, so the whole product
+    /// for `OUT = 2 · LIMBS`.
+    ///
+    /// Every limb of the result sums its column of partial products with
+    /// the carry of the previous column, and splits the total into the limb
+    /// and the carry into the next column. The carries are range checked to
+    /// `64 + ⌈log2 (LIMBS + 1)⌉ + 1` bits, rounded up to an even number, which
+    /// bounds any column.
+    ///
+    /// Consume one gate per partial product and one per limb of the result,
+    /// plus the range checks of the limbs and of the carries
+    ///
+    /// # Panics
+    ///
+    /// If `OUT` exceeds `2 · LIMBS`.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn mul<C: TwistedEdwardsAffine, const OUT: usize>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> BigIntWitness<OUT> {
+        let limbs = mul_limbs(composer, &self.limbs, &other.limbs, OUT);
+
+        BigIntWitness::assume(to_array(limbs))
+    }
+
+    /// Assert `self = other`.
+    ///
+    /// Consume `LIMBS` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_eq<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) {
+        self.limbs
+            .iter()
+            .zip(other.limbs.iter())
+            .for_each(|(a, b)| composer.assert_equal(*a, *b));
+    }
+
+    /// Evaluate `self < other` as a bit.
+    ///
+    /// Consume the gates of [`Self::sub`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn less_than<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> PrivateWire {
+        self.sub(composer, other).1
+    }
+}
+
+/// Limbs and carry of [`BigIntWitness::add`], for limbs of equal length
+#[cfg_attr(feature = "track-callers", track_caller)]
+pub(crate) fn add_limbs<C: TwistedEdwardsAffine>(
+    composer: &mut Plonk<C>,
+    a: &[PrivateWire],
+    b: &[PrivateWire],
+) -> (Vec<PrivateWire>, PrivateWire) {
+    let mut carry = None;
+    let limbs = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let mut terms = Vec::with_capacity(4);
+            terms.push((C::Range::one(), *a));
+            terms.push((C::Range::one(), *b));
+            terms.extend(carry.map(|c| (C::Range::one(), c)));
+
+            let total = terms.iter().fold(0u128, |acc, (_, w)| {
+                acc + low_limb::<C>(composer[*w]) as u128
+            });
+            let c = C::Range::from((total >> LIMB_BITS) as u64);
+
+            let (limb, c) = split_limb(composer, terms, c, -pow_limb::<C>());
+            composer.component_boolean(c);
+            carry = Some(c);
+
+            limb
+        })
+        .collect();
+
+    (limbs, carry.unwrap_or(Plonk::<C>::ZERO))
+}
+
+/// Limbs and borrow of [`BigIntWitness::sub`], for limbs of equal length
+#[cfg_attr(feature = "track-callers", track_caller)]
+pub(crate) fn sub_limbs<C: TwistedEdwardsAffine>(
+    composer: &mut Plonk<C>,
+    a: &[PrivateWire],
+    b: &[PrivateWire],
+) -> (Vec<PrivateWire>, PrivateWire) {
+    let mut borrow = None;
+    let limbs = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let mut terms = Vec::with_capacity(4);
+            terms.push((C::Range::one(), *a));
+            terms.push((-C::Range::one(), *b));
+            terms.extend(borrow.map(|c| (-C::Range::one(), c)));
+
+            let value = |w: PrivateWire| low_limb::<C>(composer[w]) as u128;
+            let subtrahend = value(*b) + borrow.map_or(0, value);
+            let c = C::Range::from((value(*a) < subtrahend) as u64);
+
+            let (limb, c) = split_limb(composer, terms, c, pow_limb::<C>());
+            composer.component_boolean(c);
+            borrow = Some(c);
+
+            limb
+        })
+        .collect();
+
+    (limbs, borrow.unwrap_or(Plonk::<C>::ZERO))
+}
+
+/// Limbs of [`BigIntWitness::mul`] modulo `2^(64 · out)`, for limbs of
+/// equal length
+///
+/// # Panics
+///
+/// If `out` exceeds twice the limbs of the operands.
+#[cfg_attr(feature = "track-callers", track_caller)]
+pub(crate) fn mul_limbs<C: TwistedEdwardsAffine>(
+    composer: &mut Plonk<C>,
+    a: &[PrivateWire],
+    b: &[PrivateWire],
+    out: usize,
+) -> Vec<PrivateWire> {
+    let limbs = a.len();
+    assert!(
+        out <= 2 * limbs,
+        "the product has at most {} limbs",
+        2 * limbs
+    );
+
+    let carry_bits = LIMB_BITS + bit_length(limbs) + 1;
+    let carry_bits = carry_bits + carry_bits % 2;
+
+    let mut carry = Plonk::<C>::ZERO;
+    (0..out)
+        .map(|k| {
+            let column = (0..limbs)
+                .filter(|i| k >= *i && k - *i < limbs)
+                .map(|i| (a[i], b[k - i]));
+
+            let total =
+                column.fold(carry, |acc, (a, b)| composer.gate_madd(a, b, acc));
+
+            let c = high_limbs::<C>(composer[total]);
+            let terms = vec![(C::Range::one(), total)];
+            let (limb, c) = split_limb(composer, terms, c, -pow_limb::<C>());
+            composer.range_any(c, carry_bits, "BigIntWitness::mul");
+            carry = c;
+
+            limb
+        })
+        .collect()
+}
+
+/// Constrain `Σ terms + coefficient · carry` to fit a limb, and return it
+/// with the appended carry.
+///
+/// The limb is range checked to 64 bits; the carry is left to the caller.
+#[cfg_attr(feature = "track-callers", track_caller)]
+fn split_limb<C: TwistedEdwardsAffine>(
+    composer: &mut Plonk<C>,
+    mut terms: Vec<(C::Range, PrivateWire)>,
+    carry: C::Range,
+    coefficient: C::Range,
+) -> (PrivateWire, PrivateWire) {
+    let carry = composer.append_witness(carry);
+    terms.push((coefficient, carry));
+
+    let limb = composer.gate_sum(&terms, C::Range::zero());
+    composer.component_range(limb, LIMB_BITS);
+
+    (limb, carry)
+}
+
+/// `2^64`
+fn pow_limb<C: TwistedEdwardsAffine>() -> C::Range {
+    C::Range::pow_of_2(LIMB_BITS as u64)
+}
+
+/// Low 64 bits of the canonical representation of `scalar`
+fn low_limb<C: TwistedEdwardsAffine>(scalar: C::Range) -> u64 {
+    let bytes = Plonk::<C>::canonical(scalar).to_raw_bytes();
+
+    let mut limb = [0u8; 8];
+    limb.copy_from_slice(&bytes[..8]);
+
+    u64::from_le_bytes(limb)
+}
+
+/// Canonical representation of `scalar` shifted right by 64 bits
+fn high_limbs<C: TwistedEdwardsAffine>(scalar: C::Range) -> C::Range {
+    let radix = C::Range::from(256u64);
+
+    Plonk::<C>::canonical(scalar).to_raw_bytes()[8..]
+        .iter()
+        .rev()
+        .fold(C::Range::zero(), |acc, byte| {
+            acc * radix + C::Range::from(*byte as u64)
+        })
+}
+
+/// Array of the limbs, of the length they were evaluated with
+fn to_array<const N: usize>(limbs: Vec<PrivateWire>) -> [PrivateWire; N] {
+    let mut array = [PrivateWire::new(0); N];
+    array.copy_from_slice(&limbs);
+
+    array
+}
+
+/// Bits of `n`
+const fn bit_length(n: usize) -> usize {
+    (usize::BITS - n.leading_zeros()) as usize
+}
@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! BLAKE2s-256 of RFC 7693, without a key, over [`Word32`] words.
+//!
+//! The message is given as 32-bit little-endian words and its length in
+//! bytes, which is a constant of the circuit. The length fixes the number of
+//! blocks, the byte counter of every block and which block is the final one,
+//! so the counter and the final-block flag are folded into constant words of
+//! the working vector instead of taking gates.
+//!
+//! The message is zero padded to a multiple of 64 bytes, with at least one
+//! block for the empty message: the words past the message are the reserved
+//! zero, and the unused bytes of a partial last word are constrained to be
+//! zero by its range check.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{TwistedEdwardsAffine, Vec};
+
+use super::uint32::Word32;
+use crate::Plonk;
+
+/// Initialization vector, the one of SHA-256
+const IV: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+/// Message word permutations of the rounds
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Bytes of a block
+const BLOCK_BYTES: usize = 64;
+
+/// Bytes of the digest
+const DIGEST_BYTES: u32 = 32;
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate the BLAKE2s-256 digest of the first `byte_len` bytes of the
+    /// little-endian words of `input`, as 8 little-endian words.
+    ///
+    /// Every word of `input` is range checked to 32 bits, or to the bytes of
+    /// the message it holds for a partial last word. See the
+    /// [module documentation](self) for the padding.
+    ///
+    /// # Panics
+    ///
+    /// If `input` doesn't have exactly `⌈byte_len / 4⌉` words.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_blake2s(
+        &mut self,
+        input: &[PrivateWire],
+        byte_len: usize,
+    ) -> [PrivateWire; 8] {
+        assert_eq!(
+            input.len(),
+            (byte_len + 3) / 4,
+            "the input must have a word per 4 bytes of the message"
+        );
+        self.assert_reserved_zero_one("component_blake2s");
+
+        let partial = byte_len % 4;
+        let words: Vec<Word32> = input
+            .iter()
+            .enumerate()
+            .map(|(i, w)| match i + 1 == input.len() && partial != 0 {
+                true => {
+                    self.component_range(*w, 8 * partial);
+                    Word32::assume(*w)
+                }
+                false => Word32::constrain(self, *w),
+            })
+            .collect();
+
+        // parameter block of a digest of 32 bytes, without a key, fanout and
+        // depth 1
+        let mut h = IV;
+        h[0] ^= 0x0101_0000 ^ DIGEST_BYTES;
+        let mut h = h.map(|x| Word32::constant(self, x));
+
+        let blocks = ((byte_len + BLOCK_BYTES - 1) / BLOCK_BYTES).max(1);
+        for block in 0..blocks {
+            let mut m = [Word32::assume(Self::ZERO); 16];
+            m.iter_mut()
+                .zip(words.iter().skip(16 * block))
+                .for_each(|(m, w)| *m = *w);
+
+            let last = block + 1 == blocks;
+            let counter = match last {
+                true => byte_len,
+                false => (block + 1) * BLOCK_BYTES,
+            } as u64;
+
+            h = self.blake2s_compress(h, m, counter, last);
+        }
+
+        h.map(PrivateWire::from)
+    }
+
+    /// Compression function `F` of BLAKE2s over the state `h` and the
+    /// message block `m`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn blake2s_compress(
+        &mut self,
+        h: [Word32; 8],
+        m: [Word32; 16],
+        counter: u64,
+        last: bool,
+    ) -> [Word32; 8] {
+        // the bottom half of the working vector only depends on constants
+        let mut iv = IV;
+        iv[4] ^= counter as u32;
+        iv[5] ^= (counter >> 32) as u32;
+        if last {
+            iv[6] ^= u32::MAX;
+        }
+
+        let mut v = [Word32::assume(Self::ZERO); 16];
+        v[..8].copy_from_slice(&h);
+        v[8..]
+            .iter_mut()
+            .zip(iv)
+            .for_each(|(v, x)| *v = Word32::constant(self, x));
+
+        for s in SIGMA {
+            self.blake2s_mix(&mut v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+            self.blake2s_mix(&mut v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+            self.blake2s_mix(&mut v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+            self.blake2s_mix(&mut v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+
+            self.blake2s_mix(&mut v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+            self.blake2s_mix(&mut v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+            self.blake2s_mix(&mut v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+            self.blake2s_mix(&mut v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+        }
+
+        let mut next = h;
+        next.iter_mut().enumerate().for_each(|(i, h)| {
+            let x = h.xor(self, &v[i]);
+            *h = x.xor(self, &v[i + 8]);
+        });
+
+        next
+    }
+
+    /// Mixing function `G` of BLAKE2s over the words `a`, `b`, `c` and `d`
+    /// of `v`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn blake2s_mix(
+        &mut self,
+        v: &mut [Word32; 16],
+        [a, b, c, d]: [usize; 4],
+        x: Word32,
+        y: Word32,
+    ) {
+        v[a] = Word32::add_many(self, &[v[a], v[b], x]);
+        v[d] = v[d].xor(self, &v[a]).rotr(self, 16);
+        v[c] = Word32::add_many(self, &[v[c], v[d]]);
+        v[b] = v[b].xor(self, &v[c]).rotr(self, 12);
+
+        v[a] = Word32::add_many(self, &[v[a], v[b], y]);
+        v[d] = v[d].xor(self, &v[a]).rotr(self, 8);
+        v[c] = Word32::add_many(self, &[v[c], v[d]]);
+        v[b] = v[b].xor(self, &v[c]).rotr(self, 7);
+    }
+}
@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Addition chains for the exponentiation by a constant.
+//!
+//! A chain for `e` is a sequence of exponents starting at `1` and ending at
+//! `e`, where every exponent is the sum of two previous ones. It is
+//! represented by the indexes of the summed exponents, so step `k` computes
+//! the exponent at index `k + 1`.
+
+use sp_std::vec;
+use zkstd::common::Vec;
+
+/// Largest prime factor tried by the factor method
+const MAX_FACTOR: u64 = 1 << 10;
+
+/// Short addition chain for `e`, or an empty chain for `e <= 1`.
+///
+/// The shortest of the binary and factor methods is returned. Neither is
+/// optimal, but both are within a few steps of it for small exponents.
+pub(crate) fn addition_chain(e: u64) -> Vec<(usize, usize)> {
+    let binary = binary_chain(e);
+    let factor = factor_chain(e);
+
+    match factor.len() < binary.len() {
+        true => factor,
+        false => binary,
+    }
+}
+
+/// Left-to-right square-and-multiply
+fn binary_chain(e: u64) -> Vec<(usize, usize)> {
+    if e <= 1 {
+        return vec![];
+    }
+
+    let top = 63 - e.leading_zeros();
+    let mut chain = Vec::new();
+    let mut last = 0;
+
+    for bit in (0..top).rev() {
+        chain.push((last, last));
+        last += 1;
+
+        if e >> bit & 1 == 1 {
+            chain.push((last, 0));
+            last += 1;
+        }
+    }
+
+    chain
+}
+
+/// Factor method: `e = p · q` composes the chains of `p` and `q`, and a
+/// prime `e` extends the chain of `e - 1`
+fn factor_chain(e: u64) -> Vec<(usize, usize)> {
+    if e <= 1 {
+        return vec![];
+    }
+
+    if e % 2 == 0 {
+        let mut chain = factor_chain(e / 2);
+        let last = chain.len();
+        chain.push((last, last));
+
+        return chain;
+    }
+
+    let p = (3..MAX_FACTOR)
+        .step_by(2)
+        .take_while(|p| p * p <= e)
+        .find(|p| e % p == 0);
+
+    match p {
+        Some(p) => {
+            let mut chain = factor_chain(p);
+            let base = chain.len();
+
+            // the chain of `q` starts from `x^p` instead of `x`
+            let shift = |i: usize| base + i;
+            chain.extend(
+                factor_chain(e / p)
+                    .into_iter()
+                    .map(|(i, j)| (shift(i), shift(j))),
+            );
+
+            chain
+        }
+        None => {
+            let mut chain = factor_chain(e - 1);
+            let last = chain.len();
+            chain.push((last, 0));
+
+            chain
+        }
+    }
+}
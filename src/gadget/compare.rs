@@ -0,0 +1,380 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Comparison gadgets of the composer: equality, ordering of field
+//! elements and limb vectors, minimum and maximum.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine};
+
+use super::bigint::LIMB_BITS;
+use crate::Plonk;
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate `a == b` as a bit, `1` if the values are equal and `0`
+    /// otherwise.
+    ///
+    /// The difference `d = a - b` is appended as a witness and constrained in
+    /// the same gate that reads `a` and `b`, and the bit is the zero bit of
+    /// [`Self::component_inverse_or_zero`] of `d`. It's constrained to be
+    /// boolean, so it can feed [`Self::component_select`] directly.
+    ///
+    /// Consume `3` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_equal(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        let diff = self[a] - self[b];
+        let diff = self.append_witness(diff);
+
+        // a - b - d = 0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(-C::Range::one())
+            .fourth(-C::Range::one())
+            .a(a)
+            .b(b)
+            .d(diff);
+        self.append_gate(constraint);
+
+        let (_, eq) = self.component_inverse_or_zero(diff);
+
+        eq
+    }
+
+    /// Evaluate `a < b` as a bit for unsigned integers of `N` bits.
+    ///
+    /// The bit is the borrow of `t = 2^N + b - a - 1`: `t` splits into the
+    /// bit times `2^N` and a remainder range checked to `N` bits. `a` and `b`
+    /// are expected to be constrained to `N` bits, e.g. by
+    /// [`Self::component_range`], otherwise the difference can wrap around.
+    ///
+    /// Consume `⌈N / 8⌉ + 4` gates for an even `N`, or `2 · N + 3` gates
+    /// for an odd `N`, which has no quad range check
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero or exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_less_than<const N: usize>(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        assert!(
+            0 < N && N <= Self::MAX_BOUNDED_BITS,
+            "comparisons support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        let pow = C::Range::pow_of_2(N as u64);
+        let t = Self::canonical(pow + self[b] - self[a] - C::Range::one());
+        let lt = t.to_bits().iter().rev().nth(N).copied().unwrap_or(false);
+        let lt = C::Range::from(lt as u64);
+        let r = t - lt * pow;
+
+        let lt = self.append_witness(lt);
+        let r = self.append_witness(r);
+
+        self.component_boolean(lt);
+
+        // b - a + 2^N - 1 - 2^N · lt - r = 0
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .right(1)
+            .output(-pow)
+            .fourth(-C::Range::one())
+            .constant(pow - C::Range::one())
+            .a(a)
+            .b(b)
+            .o(lt)
+            .d(r);
+        self.append_gate(constraint);
+
+        self.range_any(r, N, "component_less_than");
+
+        lt
+    }
+
+    /// Evaluate `a < b` as a bit for signed integers in the two's complement
+    /// encoding of `BITS` bits.
+    ///
+    /// Both encodings are split into their sign bit and the `BITS - 1` bits
+    /// below it, which constrains them to `BITS` bits, so a negative integer
+    /// must be encoded as `2^BITS - |x|` and not as its field negation. The
+    /// encodings are compared as unsigned integers, which is the signed
+    /// comparison if the signs are equal. Otherwise the negative one is the
+    /// lower, so the result is selected to be the sign of `a`.
+    ///
+    /// Consume the gates of [`Self::component_less_than`] of `BITS` bits and
+    /// of two range checks of `BITS - 1` bits, plus `7` gates. Either of
+    /// them is of an odd number of bits, which falls back to a bit
+    /// decomposition
+    ///
+    /// # Panics
+    ///
+    /// If `BITS` is lower than `2` or exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_signed_less_than<const BITS: usize>(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        assert!(
+            1 < BITS && BITS <= Self::MAX_BOUNDED_BITS,
+            "signed comparisons support 2 up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        let sign_a = self.split_sign(a, BITS);
+        let sign_b = self.split_sign(b, BITS);
+
+        let lt = self.component_less_than::<BITS>(a, b);
+
+        // sign_a ^ sign_b
+        let constraint = Constraint::default()
+            .mult(-C::Range::from(2u64))
+            .left(1)
+            .right(1)
+            .a(sign_a)
+            .b(sign_b);
+        let differ = self.gate_mul(constraint);
+
+        self.select_fused(differ, sign_a, lt)
+    }
+
+    /// Split `w` into its bit at `bits - 1` and the bits below it, constraining
+    /// it to fit `bits` bits, and return the top bit
+    ///
+    /// Consume the gates of the range check of `bits - 1` bits plus `2`
+    fn split_sign(&mut self, w: PrivateWire, bits: usize) -> PrivateWire {
+        let (low, sign) = Self::split_at_bit(self[w], bits - 1);
+
+        let sign = self.append_witness(sign);
+        let low = self.append_witness(low);
+
+        self.component_boolean(sign);
+
+        // 2^(bits - 1) · sign + low - w = 0
+        let constraint = Constraint::default()
+            .left(C::Range::pow_of_2((bits - 1) as u64))
+            .right(1)
+            .fourth(-C::Range::one())
+            .a(sign)
+            .b(low)
+            .d(w);
+        self.append_gate(constraint);
+
+        self.range_any(low, bits - 1, "component_signed_less_than");
+
+        sign
+    }
+
+    /// Assert `a < b` for unsigned integers of `N` bits, range checking both
+    /// of them to `N` bits first.
+    ///
+    /// See [`Self::component_assert_less_than_unchecked`] for inputs that are
+    /// already constrained to `N` bits.
+    ///
+    /// Consume `3 · (⌈N / 8⌉ + 2) + 1` gates for an even `N`. An odd `N`
+    /// falls back to bit decompositions
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero or exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_less_than<const N: usize>(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) {
+        assert!(
+            0 < N && N <= Self::MAX_BOUNDED_BITS,
+            "comparisons support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        self.range_any(a, N, "component_assert_less_than");
+        self.range_any(b, N, "component_assert_less_than");
+
+        self.component_assert_less_than_unchecked::<N>(a, b);
+    }
+
+    /// Assert `a < b` for unsigned integers of `N` bits, by range checking
+    /// `b - a - 1` to `N` bits.
+    ///
+    /// This is only sound if `a` and `b` are constrained to `N` bits by the
+    /// caller. Then `b - a - 1` is below `2^N` for `a < b`, and wraps around
+    /// the field modulus above `p - 2^N` otherwise. Unconstrained inputs let a
+    /// prover pick a difference that fits, e.g. `a = -1` gives `b`.
+    ///
+    /// Consume `⌈N / 8⌉ + 3` gates for an even `N`. An odd `N` falls back to
+    /// a bit decomposition
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero or exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_less_than_unchecked<const N: usize>(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) {
+        assert!(
+            0 < N && N <= Self::MAX_BOUNDED_BITS,
+            "comparisons support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        // b - a - 1
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .right(1)
+            .constant(-C::Range::one())
+            .a(a)
+            .b(b);
+        let diff = self.gate_add(constraint);
+
+        self.range_any(diff, N, "component_assert_less_than");
+    }
+
+    /// Evaluate `a < b` as a bit for unsigned integers of four limbs of
+    /// [`LIMB_BITS`] bits, least significant first.
+    ///
+    /// The limbs are compared most significant first: the result is the
+    /// [`Self::component_less_than`] of the highest pair of limbs that
+    /// differ, selected by a chain of [`Self::component_equal`] over the
+    /// pairs above it. Equal integers evaluate to `0`.
+    ///
+    /// Every limb is expected to be constrained to [`LIMB_BITS`] bits, e.g.
+    /// by [`Self::component_range`]. If `constrain_limbs` is set, the limbs
+    /// of both integers are range checked here.
+    ///
+    /// Consume `63` gates, plus `80` if `constrain_limbs` is set
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_limbs_less_than(
+        &mut self,
+        a: &[PrivateWire; 4],
+        b: &[PrivateWire; 4],
+        constrain_limbs: bool,
+    ) -> PrivateWire {
+        if constrain_limbs {
+            a.iter()
+                .chain(b.iter())
+                .for_each(|limb| self.component_range(*limb, LIMB_BITS));
+        }
+
+        let lt = self.component_less_than::<LIMB_BITS>(a[0], b[0]);
+
+        // the lower limbs only decide if the limbs above them are equal
+        a.iter().zip(b.iter()).skip(1).fold(lt, |lt, (a_i, b_i)| {
+            let eq = self.component_equal(*a_i, *b_i);
+            let lt_i = self.component_less_than::<LIMB_BITS>(*a_i, *b_i);
+
+            self.select_fused(eq, lt, lt_i)
+        })
+    }
+
+    /// Assert `a = b` for unsigned integers of four limbs of [`LIMB_BITS`]
+    /// bits, least significant first.
+    ///
+    /// The limbs are asserted to be equal pairwise, which is the equality of
+    /// the integers if their limbs are constrained to [`LIMB_BITS`] bits.
+    ///
+    /// Consume `4` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_limbs_assert_equal(
+        &mut self,
+        a: &[PrivateWire; 4],
+        b: &[PrivateWire; 4],
+    ) {
+        a.iter()
+            .zip(b.iter())
+            .for_each(|(a_i, b_i)| self.assert_equal(*a_i, *b_i));
+    }
+
+    /// Assert `values` is sorted in ascending order as unsigned integers of
+    /// `BITS` bits.
+    ///
+    /// Every value is range checked to `BITS` bits once, and the difference
+    /// of every adjacent pair, `values[i + 1] - values[i]`, is range checked
+    /// to `BITS` bits as well. A pair out of order would wrap its difference
+    /// around the field modulus, far above `2^BITS`. Adjacent comparisons
+    /// share the range check of their common value, and equal values are
+    /// allowed. Empty and single value slices are sorted, except the single
+    /// value is still range checked.
+    ///
+    /// Consume `N · (⌈BITS / 8⌉ + 2) + (N - 1) · (⌈BITS / 8⌉ + 3)` gates
+    /// for `N` values and an even `BITS`. An odd `BITS` falls back to bit
+    /// decompositions
+    ///
+    /// # Panics
+    ///
+    /// If `BITS` is zero or exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_sorted<const BITS: usize>(
+        &mut self,
+        values: &[PrivateWire],
+    ) {
+        assert!(
+            0 < BITS && BITS <= Self::MAX_BOUNDED_BITS,
+            "comparisons support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        values
+            .iter()
+            .for_each(|v| self.range_any(*v, BITS, "component_assert_sorted"));
+
+        values.windows(2).for_each(|pair| {
+            let constraint = Constraint::default()
+                .left(-C::Range::one())
+                .right(1)
+                .a(pair[0])
+                .b(pair[1]);
+            let diff = self.gate_add(constraint);
+
+            self.range_any(diff, BITS, "component_assert_sorted");
+        });
+    }
+
+    /// Evaluate `min(a, b)` for unsigned integers of `N` bits.
+    ///
+    /// Selects between `a` and `b` with [`Self::component_less_than`], so the
+    /// same expectations on the inputs apply.
+    ///
+    /// Consume the gates of [`Self::component_less_than`] plus `4`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_min<const N: usize>(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        let lt = self.component_less_than::<N>(a, b);
+
+        self.component_select(lt, a, b)
+    }
+
+    /// Evaluate `max(a, b)` for unsigned integers of `N` bits.
+    ///
+    /// Selects between `a` and `b` with [`Self::component_less_than`], so the
+    /// same expectations on the inputs apply.
+    ///
+    /// Consume the gates of [`Self::component_less_than`] plus `4`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_max<const N: usize>(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        let lt = self.component_less_than::<N>(a, b);
+
+        self.component_select(lt, b, a)
+    }
+}
@@ -0,0 +1,65 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Points compressed to their `x` coordinate and the sign of `y`.
+//!
+//! The sign is the least significant bit of the canonical representation of
+//! `y`, so `(x, y)` and `(x, -y)` have distinct signs unless `y = 0`. The
+//! curve equation gives `y² = (1 + x²) / (1 - d · x²)`, where `1 - d · x²`
+//! is never zero since `d` isn't a square.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::TwistedEdwardsAffine;
+
+use super::ecc::{curve_d, parity, recover_y};
+use super::WitnessPoint;
+use crate::Plonk;
+
+/// Compress `point` to its `x` coordinate and the sign of its `y`
+pub fn compress_point<C: TwistedEdwardsAffine>(point: C) -> (C::Range, bool) {
+    (point.get_x(), parity(point.get_y()))
+}
+
+/// Native counterpart of [`Plonk::component_decompress_point`], or `None`
+/// if no point has the coordinate `x` and the sign `sign`
+pub fn decompress_point<C: TwistedEdwardsAffine>(
+    x: C::Range,
+    sign: bool,
+) -> Option<C> {
+    recover_y(curve_d::<C>(), x, sign).map(|y| C::from_raw_unchecked(x, y))
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate the point with the coordinate `x` and the sign `sign`.
+    ///
+    /// See the [module documentation](self) for the sign convention, and
+    /// [`decompress_point`] for its native counterpart. `y` is witnessed and
+    /// constrained by the curve equation, and its sign by
+    /// [`Self::component_parity`], which also constrains `sign` to be
+    /// boolean. An `x` without a `y` on the curve, or `y = 0` with the sign
+    /// `1`, can't satisfy the constraints, and the prover sets `y` to zero.
+    ///
+    /// Consume `4` gates plus the gates of [`Self::component_parity`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_decompress_point(
+        &mut self,
+        x: PrivateWire,
+        sign: PrivateWire,
+    ) -> WitnessPoint {
+        let y =
+            recover_y(curve_d::<C>(), self[x], self[sign] == C::Range::one())
+                .unwrap_or_else(C::Range::zero);
+
+        let y = self.append_witness(y);
+        let point = WitnessPoint::new(x, y);
+        self.assert_on_curve(point);
+
+        let bit = self.component_parity(y);
+        self.assert_equal(bit, sign);
+
+        point
+    }
+}
@@ -4,8 +4,17 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use zksnarks::plonk::wire::PrivateWire;
-use zkstd::common::PrimeField;
+use jub_jub::compute_windowed_naf;
+use jub_jub::Fp as JubjubScalar;
+use sp_std::vec;
+use zksnarks::error::Error;
+use zksnarks::{
+    constraint_system::ConstraintSystem, plonk::wire::PrivateWire, Constraint,
+};
+use zkstd::common::*;
+
+use super::fixed_base::FixedBaseTable;
+use crate::Plonk;
 
 /// Represents a JubJub point in the circuit
 #[derive(Debug, Clone, Copy)]
@@ -31,6 +40,19 @@ impl WitnessPoint {
     }
 }
 
+/// Wire indices of the coordinates, as `(x: wire 3, y: wire 4)`. The values
+/// are read with [`crate::Plonk::point_value`]
+impl core::fmt::Display for WitnessPoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "(x: wire {}, y: wire {})",
+            self.x.index(),
+            self.y.index()
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 /// Contains all of the components needed to verify that a bit scalar
 /// multiplication was computed correctly
@@ -59,3 +81,796 @@ pub(crate) struct WnafRound<T: Into<PrivateWire>, F: PrimeField> {
     /// This is the multiplication of x_\beta * y_\beta
     pub xy_beta: F,
 }
+
+/// Parameter `d` of the curve `-x² + y² = 1 + d · x² · y²` of `C`, recovered
+/// from its generator
+pub(crate) fn curve_d<C: TwistedEdwardsAffine>() -> C::Range {
+    let g = C::ADDITIVE_GENERATOR;
+    let x2 = g.get_x() * g.get_x();
+    let y2 = g.get_y() * g.get_y();
+
+    (y2 - x2 - C::Range::one()) * invert(x2 * y2)
+}
+
+/// `y` of the point with the coordinate `x` and the sign `sign`, or `None`
+/// if there's no such point
+pub(crate) fn recover_y<F: PrimeField>(d: F, x: F, sign: bool) -> Option<F> {
+    let x2 = x * x;
+    let y2 = (F::one() + x2) * invert(F::one() - d * x2);
+
+    if !is_square(y2) {
+        return None;
+    }
+
+    let y = sqrt(y2);
+    match parity(y) == sign {
+        true => Some(y),
+        false if y == F::zero() => None,
+        false => Some(-y),
+    }
+}
+
+/// Raise `base` to the integer of the little endian bytes `exp`
+fn pow_le<F: PrimeField>(base: F, exp: &[u8]) -> F {
+    exp.iter().rev().fold(F::one(), |acc, byte| {
+        (0..8).rev().fold(acc, |acc, i| match (byte >> i) & 1 {
+            1 => acc * acc * base,
+            _ => acc * acc,
+        })
+    })
+}
+
+/// Little endian bytes of `p - 1`
+fn p_minus_one<F: PrimeField>() -> Vec<u8> {
+    (-F::one()).to_raw_bytes().iter().copied().collect()
+}
+
+/// Shift the little endian integer `bytes` right by one bit
+fn halve(bytes: &mut [u8]) {
+    let mut carry = 0;
+    for byte in bytes.iter_mut().rev() {
+        let low = *byte & 1;
+        *byte = (*byte >> 1) | (carry << 7);
+        carry = low;
+    }
+}
+
+/// Inverse of `x`, or zero for `x = 0`
+pub(super) fn invert<F: PrimeField>(x: F) -> F {
+    x.invert().unwrap_or_else(F::zero)
+}
+
+/// Least significant bit of the canonical representation of `x`
+pub(crate) fn parity<F: PrimeField>(x: F) -> bool {
+    let radix = F::from(256u64);
+    let x = x
+        .to_raw_bytes()
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, byte| acc * radix + F::from(*byte as u64));
+
+    x.to_raw_bytes()[0] & 1 == 1
+}
+
+/// Euler's criterion, with zero taken as a square
+pub(super) fn is_square<F: PrimeField>(x: F) -> bool {
+    let mut exp = p_minus_one::<F>();
+    halve(&mut exp);
+
+    let legendre = pow_le(x, &exp);
+    legendre == F::one() || legendre == F::zero()
+}
+
+/// Square root of a square `x` with Tonelli-Shanks
+pub(super) fn sqrt<F: PrimeField>(x: F) -> F {
+    debug_assert!(is_square(x));
+
+    if x == F::zero() {
+        return x;
+    }
+
+    // p - 1 = q · 2^s, with q odd
+    let mut q = p_minus_one::<F>();
+    let mut s = 0;
+    while q[0] & 1 == 0 {
+        halve(&mut q);
+        s += 1;
+    }
+
+    let non_square = (2u64..)
+        .map(F::from)
+        .find(|z| !is_square(*z))
+        .expect("half of the field isn't a square");
+
+    let mut half = q.clone();
+    halve(&mut half);
+
+    let mut m = s;
+    let mut c = pow_le(non_square, &q);
+    let mut t = pow_le(x, &q);
+    let mut r = pow_le(x, &half) * x;
+
+    while t != F::one() {
+        let mut i = 0;
+        let mut t_pow = t;
+        while t_pow != F::one() {
+            t_pow = t_pow * t_pow;
+            i += 1;
+        }
+
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b * b;
+        }
+
+        m = i;
+        c = b * b;
+        t = t * c;
+        r = r * b;
+    }
+
+    r
+}
+
+/// Bits of the scalar of [`Plonk::component_mul_point_bits`], checked when
+/// the function is instantiated
+struct ScalarBits<const N: usize>;
+
+impl<const N: usize> ScalarBits<N> {
+    const VALID: () = assert!(
+        0 < N && N <= 252,
+        "the scalar of a multiplication fits 1 up to 252 bits"
+    );
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate `jubjub · Generator` as a [`WitnessPoint`]
+    ///
+    /// `generator` will be appended to the circuit description as constant.
+    ///
+    /// **The scalar is unconstrained.** `jubjub` isn't range checked, so the
+    /// wNAF of the proof may represent any integer congruent to it modulo
+    /// the circuit field, up to `2^256`, and the point may be the
+    /// multiplication by an integer other than the one of the wire. Use
+    /// [`Self::component_mul_generator_typed`] with a scalar checked by
+    /// [`Self::component_assert_jubjub_scalar`], or
+    /// [`Self::component_mul_generator_windowed`], which bounds the scalar
+    /// with its digits.
+    ///
+    /// The gate adds a digit of the wNAF per round, so the multiplication
+    /// takes a round per bit of the scalar.
+    /// [`Self::component_mul_generator_windowed`] looks the multiples of the
+    /// generator up a byte at a time instead.
+    ///
+    /// Will error if the wNAF of `jubjub` is invalid
+    #[deprecated(note = "the scalar is unconstrained, use \
+                `component_mul_generator_typed` or \
+                `component_mul_generator_windowed`")]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_generator<A: Into<C::Extended>>(
+        &mut self,
+        jubjub: PrivateWire,
+        generator: A,
+    ) -> Result<WitnessPoint, Error> {
+        self.mul_generator(jubjub, generator, false)
+    }
+
+    /// Evaluate `jubjub · Generator`, constraining the most significant
+    /// digits of the wNAF to be zero if `bounded` is set.
+    ///
+    /// Every round of the fixed base gate adds the digit `d' - 2 · d` of the
+    /// accumulated scalars `d` and `d'` of its row and of the next one, times
+    /// the multiple of the generator of its selectors, to the point
+    /// accumulator, and constrains the digit to `{-1, 0, 1}`. The gate after
+    /// the last round holds the final accumulators, and asserts the scalar
+    /// equals `jubjub`, so every digit is tied to the wire.
+    ///
+    /// The integer the digits represent is only congruent to `jubjub` modulo
+    /// the scalar field, and may differ from it by a multiple of the modulus.
+    /// With the digits above [`Self::JUBJUB_SCALAR_BITS`] zero, the integer
+    /// is below `2^253` in absolute value: for a `jubjub` range checked to
+    /// [`Self::JUBJUB_SCALAR_BITS`] bits, the difference is then smaller
+    /// than the modulus, so the integer is `jubjub` itself.
+    ///
+    /// The rounds of the zero digits are skipped, and the accumulators are
+    /// constrained to start from zero and the identity after them instead.
+    ///
+    /// Consume `260` gates, or `257` gates if `bounded` is set
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub(crate) fn mul_generator<A: Into<C::Extended>>(
+        &mut self,
+        jubjub: PrivateWire,
+        generator: A,
+        bounded: bool,
+    ) -> Result<WitnessPoint, Error> {
+        let table = FixedBaseTable::new(generator);
+
+        self.mul_generator_with_table(jubjub, &table, bounded)
+    }
+
+    /// [`Self::mul_generator`] with the multiples of the generator read from
+    /// `table`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub(crate) fn mul_generator_with_table(
+        &mut self,
+        jubjub: PrivateWire,
+        table: &FixedBaseTable<C>,
+        bounded: bool,
+    ) -> Result<WitnessPoint, Error> {
+        // the number of bits is truncated to the maximum possible
+        let bits: usize = FixedBaseTable::<C>::BITS;
+
+        // the top digits of a bounded scalar are zero, so their rounds are
+        // skipped
+        let skip = match bounded {
+            true => bits - Self::JUBJUB_SCALAR_BITS - 1,
+            false => 0,
+        };
+
+        // 2^iG, from the most significant digit
+        let wnaf_point_multiples = table.multiples();
+
+        // we should error instead of producing invalid proofs - otherwise this
+        // can easily become an attack vector to either shutdown prover
+        // services or create malicious statements
+        let scalar = self[jubjub];
+
+        let width = 2;
+        let wnaf_entries = compute_windowed_naf(scalar, width);
+
+        debug_assert_eq!(wnaf_entries.len(), bits);
+
+        // initialize the accumulators
+        let mut scalar_acc = vec![C::Range::zero()];
+        let mut point_acc = vec![C::ADDITIVE_IDENTITY];
+
+        // auxillary point to help with checks on the backend
+        let two = C::Range::from(2u64);
+        let xy_alphas: Vec<_> = wnaf_entries
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, entry)| {
+                let (scalar_to_add, point_to_add) = match entry {
+                    0 => (C::Range::zero(), C::ADDITIVE_IDENTITY),
+                    -1 => (C::Range::one().neg(), -wnaf_point_multiples[i]),
+                    1 => (C::Range::one(), wnaf_point_multiples[i]),
+                    _ => return Err(Error::UnsupportedWNAF2k),
+                };
+
+                let prev_accumulator = two * scalar_acc[i];
+                let scalar = prev_accumulator + scalar_to_add;
+                scalar_acc.push(scalar);
+
+                let point = point_acc[i] + point_to_add;
+                point_acc.push(C::from(point));
+
+                let x_alpha = point_to_add.get_x();
+                let y_alpha = point_to_add.get_y();
+
+                Ok(x_alpha * y_alpha)
+            })
+            .collect::<Result<_, Error>>()?;
+
+        for i in skip..bits {
+            let acc_x = self.append_witness(point_acc[i].get_x());
+            let acc_y = self.append_witness(point_acc[i].get_y());
+            let accumulated_bit = self.append_witness(scalar_acc[i]);
+
+            // the point accumulator must start from identity and its scalar
+            // from zero. the digits are in {-1, 0, 1}, so the accumulated
+            // scalar of the skipped digits is zero only if all of them are
+            if i == skip {
+                self.assert_equal_constant(acc_x, C::Range::zero(), None);
+                self.assert_equal_constant(acc_y, C::Range::one(), None);
+                self.assert_equal_constant(
+                    accumulated_bit,
+                    C::Range::zero(),
+                    None,
+                );
+            }
+
+            let x_beta = wnaf_point_multiples[i].get_x();
+            let y_beta = wnaf_point_multiples[i].get_y();
+
+            let xy_alpha = self.append_witness(xy_alphas[i]);
+            let xy_beta = x_beta * y_beta;
+
+            let wnaf_round = WnafRound::<PrivateWire, C::Range> {
+                acc_x,
+                acc_y,
+                accumulated_bit,
+                xy_alpha,
+                x_beta,
+                y_beta,
+                xy_beta,
+            };
+
+            let constraint =
+                Constraint::group_add_curve_scalar(Constraint::default())
+                    .left(wnaf_round.x_beta)
+                    .right(wnaf_round.y_beta)
+                    .constant(wnaf_round.xy_beta)
+                    .a(wnaf_round.acc_x)
+                    .b(wnaf_round.acc_y)
+                    .o(wnaf_round.xy_alpha)
+                    .d(wnaf_round.accumulated_bit);
+
+            self.append_custom_gate(constraint)
+        }
+
+        // the last round reads the final accumulators from the next gate,
+        // which asserts the accumulated scalar equals the input scalar
+        let acc_x = self.append_witness(point_acc[bits].get_x());
+        let acc_y = self.append_witness(point_acc[bits].get_y());
+        let last_accumulated_bit = self.append_witness(scalar_acc[bits]);
+
+        let constraint = Constraint::default()
+            .output(-C::Range::one())
+            .fourth(1)
+            .a(acc_x)
+            .b(acc_y)
+            .o(jubjub)
+            .d(last_accumulated_bit);
+        self.append_gate(constraint);
+
+        Ok(WitnessPoint::new(acc_x, acc_y))
+    }
+
+    /// Asserts `a == b` by appending two gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_equal_point(&mut self, a: WitnessPoint, b: WitnessPoint) {
+        self.assert_equal(*a.x(), *b.x());
+        self.assert_equal(*a.y(), *b.y());
+    }
+
+    /// Asserts `point == public`.
+    ///
+    /// Will add `public` affine coordinates `(x,y)` as public inputs
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_equal_public_point<A: Into<C>>(
+        &mut self,
+        point: WitnessPoint,
+        public: A,
+    ) {
+        let public = public.into();
+
+        self.assert_equal_constant(
+            *point.x(),
+            C::Range::zero(),
+            Some(C::Range::into(-public.get_x())),
+        );
+
+        self.assert_equal_constant(
+            *point.y(),
+            C::Range::zero(),
+            Some(C::Range::into(-public.get_y())),
+        );
+    }
+
+    /// Adds two curve points by consuming 2 gates.
+    ///
+    /// Both points must lie on the curve: the addition law is only complete
+    /// on the curve, and the witnesses are computed with the native addition
+    /// of the coordinates as they are, so the sum of a point off the curve
+    /// is unspecified. The points of [`Self::append_point`] aren't checked,
+    /// see [`Self::component_add_point_checked`]. With the
+    /// `debug-assertions` feature, an operand off the curve is reported by
+    /// [`Self::check_debug_assertions`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_add_point(
+        &mut self,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> WitnessPoint {
+        // In order to verify that two points were correctly added
+        // without going over a degree 4 polynomial, we will need
+        // x_1, y_1, x_2, y_2
+        // x_3, y_3, x_1 * y_2
+
+        self.debug_assert_on_curve(
+            a,
+            "component_add_point: a is off the curve",
+        );
+        self.debug_assert_on_curve(
+            b,
+            "component_add_point: b is off the curve",
+        );
+
+        let x_1 = *a.x();
+        let y_1 = *a.y();
+        let x_2 = *b.x();
+        let y_2 = *b.y();
+
+        let p1 = C::from_raw_unchecked(self[x_1], self[y_1]);
+        let p2 = C::from_raw_unchecked(self[x_2], self[y_2]);
+
+        let point = C::from(p1 + p2);
+
+        let x_3 = point.get_x();
+        let y_3 = point.get_y();
+
+        let x1_y2 = self[x_1] * self[y_2];
+
+        let x_1_y_2 = self.append_witness(x1_y2);
+        let x_3 = self.append_witness(x_3);
+        let y_3 = self.append_witness(y_3);
+
+        // Add the rest of the prepared points into the composer
+        let constraint = Constraint::default().a(x_1).b(y_1).o(x_2).d(y_2);
+        let constraint = Constraint::group_add_curve_addtion(constraint);
+
+        self.append_custom_gate(constraint);
+
+        let constraint = Constraint::default().a(x_3).b(y_3).d(x_1_y_2);
+
+        self.append_custom_gate(constraint);
+
+        WitnessPoint::new(x_3, y_3)
+    }
+
+    /// [`Self::component_add_point`], asserting both points lie on the
+    /// curve.
+    ///
+    /// The sum of points on the curve lies on the curve, so it isn't
+    /// checked.
+    ///
+    /// Consume `8` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_add_point_checked(
+        &mut self,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> WitnessPoint {
+        self.assert_on_curve(a);
+        self.assert_on_curve(b);
+
+        self.component_add_point(a, b)
+    }
+
+    /// Evaluate `2 · a` by consuming 2 gates.
+    ///
+    /// The addition law of the curve is complete, so the doubling is the
+    /// curve addition gate with both operands on the wires of `a`, and the
+    /// cross product `x · y` is shared by both of its terms. The gate reads
+    /// the result from the following row, so it can't take a single gate.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_double_point(&mut self, a: WitnessPoint) -> WitnessPoint {
+        self.debug_assert_on_curve(a, "component_double_point: off the curve");
+
+        let x_1 = *a.x();
+        let y_1 = *a.y();
+
+        let p = C::from_raw_unchecked(self[x_1], self[y_1]);
+        let point = C::from(p + p);
+
+        let x1_y1 = self[x_1] * self[y_1];
+
+        let x_1_y_1 = self.append_witness(x1_y1);
+        let x_3 = self.append_witness(point.get_x());
+        let y_3 = self.append_witness(point.get_y());
+
+        let constraint = Constraint::default().a(x_1).b(y_1).o(x_1).d(y_1);
+        let constraint = Constraint::group_add_curve_addtion(constraint);
+
+        self.append_custom_gate(constraint);
+
+        let constraint = Constraint::default().a(x_3).b(y_3).d(x_1_y_1);
+
+        self.append_custom_gate(constraint);
+
+        WitnessPoint::new(x_3, y_3)
+    }
+
+    /// Evaluate `-a` by consuming 1 gate.
+    ///
+    /// The negation of `(x, y)` on the twisted Edwards form is `(-x, y)`, so
+    /// `y` is returned as is.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_neg_point(&mut self, a: WitnessPoint) -> WitnessPoint {
+        let x = self.component_negate(*a.x());
+
+        WitnessPoint::new(x, *a.y())
+    }
+
+    /// Evaluate `a - b` by consuming 3 gates, the addition of `a` and the
+    /// negation of `b`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_sub_point(
+        &mut self,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> WitnessPoint {
+        let b = self.component_neg_point(b);
+
+        self.component_add_point(a, b)
+    }
+
+    /// Evaluate `p == (0, 1)` as a bit, `1` if `p` is the identity and `0`
+    /// otherwise.
+    ///
+    /// The bit is the product of the zero bits of
+    /// [`Self::component_inverse_or_zero`] of `x` and of `y - 1`, which are
+    /// constrained to be boolean, so the product is boolean as well.
+    ///
+    /// Consume `6` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_point_is_identity(
+        &mut self,
+        p: WitnessPoint,
+    ) -> PrivateWire {
+        let (_, x_is_zero) = self.component_inverse_or_zero(*p.x());
+
+        // y - 1
+        let constraint = Constraint::default()
+            .left(1)
+            .constant(-C::Range::one())
+            .a(*p.y());
+        let y_minus_one = self.gate_add(constraint);
+        let (_, y_is_one) = self.component_inverse_or_zero(y_minus_one);
+
+        let constraint = Constraint::default().mult(1).a(x_is_zero).b(y_is_one);
+        self.gate_mul(constraint)
+    }
+
+    /// Evaluate `a == b` as a bit, `1` if the points are equal and `0`
+    /// otherwise.
+    ///
+    /// The bit is the product of the bits of [`Self::component_equal`] of
+    /// both coordinates, which are constrained to be boolean, so the product
+    /// is boolean as well and can feed [`Self::component_select`] directly.
+    /// A point and its negation share `y`, so their `x` tells them apart.
+    ///
+    /// Consume `7` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_point_equal(
+        &mut self,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> PrivateWire {
+        let x_eq = self.component_equal(*a.x(), *b.x());
+        let y_eq = self.component_equal(*a.y(), *b.y());
+
+        let constraint = Constraint::default().mult(1).a(x_eq).b(y_eq);
+        self.gate_mul(constraint)
+    }
+
+    /// Assert `p` lies in the prime-order subgroup of the curve.
+    ///
+    /// The curve has cofactor `8`, so a point on it may carry a component of
+    /// small order. The point `q = 8⁻¹ · p`, with the inverse taken modulo
+    /// the order of the subgroup, is witnessed and asserted to lie on the
+    /// curve, and three doublings of it are asserted to equal `p`. Every
+    /// multiple of `8` of a point on the curve lies in the subgroup, so a
+    /// point outside of it, including a point of small order other than the
+    /// identity, can't be proven. This is cheaper than multiplying `p` by the
+    /// order of the subgroup and asserting the identity, which takes the
+    /// gates of a double-and-add over `252` bits.
+    ///
+    /// Consume `11` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_prime_order(&mut self, p: WitnessPoint) {
+        let point = C::from_raw_unchecked(self[*p.x()], self[*p.y()]);
+        let inverse = JubjubScalar::from(8u64)
+            .invert()
+            .expect("the cofactor is invertible modulo the order");
+
+        let q =
+            inverse
+                .to_bits()
+                .iter()
+                .fold(C::ADDITIVE_IDENTITY, |q, bit| {
+                    let q = C::from(q + q);
+                    match *bit as u8 == 1 {
+                        true => C::from(q + point),
+                        false => q,
+                    }
+                });
+
+        let q = self.append_point_checked(q);
+        let q = self.component_double_point(q);
+        let q = self.component_double_point(q);
+        let q = self.component_double_point(q);
+
+        self.assert_equal_point(q, p);
+    }
+
+    /// Conditionally selects identity as [`WitnessPoint`] based on an input
+    /// bit.
+    ///
+    /// bit == 1 => a,
+    /// bit == 0 => identity,
+    ///
+    /// `bit` is expected to be constrained by
+    /// [`Composer::component_boolean`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_identity(
+        &mut self,
+        bit: PrivateWire,
+        a: WitnessPoint,
+    ) -> WitnessPoint {
+        let x = self.component_select_zero(bit, *a.x());
+        let y = self.component_select_one(bit, *a.y());
+
+        WitnessPoint::new(x, y)
+    }
+
+    /// Evaluate `jubjub · point` as a [`WitnessPoint`]
+    ///
+    /// `jubjub` is only decomposed into [`Self::JUBJUB_SCALAR_BITS`] bits, so
+    /// any value of that many bits is accepted, including the ones at or
+    /// above the modulus of the scalar field of JubJub.
+    /// [`Self::component_mul_point_typed`] takes a scalar checked with
+    /// [`Self::component_assert_jubjub_scalar`] instead.
+    ///
+    /// Consume `2013` gates, the ones of [`Self::component_mul_point_bits`]
+    /// of `252` bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_point(
+        &mut self,
+        jubjub: PrivateWire,
+        point: WitnessPoint,
+    ) -> WitnessPoint {
+        self.component_mul_point_bits::<252>(jubjub, point)
+    }
+
+    /// Evaluate `jubjub · point`, delegating to [`Self::component_mul_point`].
+    ///
+    /// The GLV method splits `jubjub` into two halves of about `126` bits
+    /// with `jubjub = k₁ + λ · k₂`, and runs a single double-and-add over
+    /// `P` and `φ(P) = λ · P`. It is unavailable on JubJub, which has no
+    /// efficiently computable endomorphism, and [`TwistedEdwardsAffine`]
+    /// exposes none for the other curves, so the result, constraints and
+    /// gate count are always the ones of [`Self::component_mul_point`].
+    ///
+    /// Consume `2013` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_point_glv(
+        &mut self,
+        jubjub: PrivateWire,
+        point: WitnessPoint,
+    ) -> WitnessPoint {
+        self.component_mul_point(jubjub, point)
+    }
+
+    /// Evaluate `scalar · point` as a [`WitnessPoint`] for a scalar of `N`
+    /// bits.
+    ///
+    /// `scalar` is decomposed with [`Self::component_decomposition`], which
+    /// constrains it to `N` bits, and the double-and-add runs over these
+    /// bits only, so a scalar that doesn't fit `N` bits is unprovable. `N`
+    /// is checked to be between `1` and [`Self::JUBJUB_SCALAR_BITS`] when
+    /// the function is instantiated, so a wider scalar doesn't compile:
+    ///
+    /// ```compile_fail
+    /// use zkplonk::prelude::*;
+    /// use zksnarks::constraint_system::ConstraintSystem;
+    ///
+    /// let mut composer = Plonk::<JubjubAffine>::initialize();
+    ///
+    /// let scalar = composer.append_witness(BlsScalar::from(2u64));
+    /// let point = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+    ///
+    /// composer.component_mul_point_bits::<253>(scalar, point);
+    /// ```
+    ///
+    /// Consume `8 · N - 3` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_point_bits<const N: usize>(
+        &mut self,
+        scalar: PrivateWire,
+        point: WitnessPoint,
+    ) -> WitnessPoint {
+        #[allow(clippy::let_unit_value)]
+        let () = ScalarBits::<N>::VALID;
+
+        // Turn scalar into bits
+        self.assert_reserved_zero_one("component_mul_point");
+
+        let scalar_bits = self.component_decomposition::<N>(scalar);
+
+        // doubling the identity and adding to it leave the selected point
+        let (top, rest) = scalar_bits.split_last().expect("at least one bit");
+        let mut result = self.component_select_identity(*top, point);
+
+        for bit in rest.iter().rev() {
+            result = self.component_double_point(result);
+
+            let point_to_add = self.component_select_identity(*bit, point);
+            result = self.component_add_point(result, point_to_add);
+        }
+
+        result
+    }
+
+    /// Evaluate `Σ s_i · P_i` over the pairs `(s_i, P_i)` as a
+    /// [`WitnessPoint`].
+    ///
+    /// Every scalar is decomposed into [`Self::JUBJUB_SCALAR_BITS`] bits, as
+    /// in [`Self::component_mul_point`], and the multiplications are
+    /// interleaved: the accumulator is doubled once per bit, and every point
+    /// whose scalar has the bit set is added to it. The top bits select the
+    /// points into the accumulator without any doubling. An empty slice
+    /// returns the identity.
+    ///
+    /// Consume `1513 · K + 500` gates for `K` pairs, `502` less per pair
+    /// after the first than separate multiplications and additions
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_msm(
+        &mut self,
+        pairs: &[(PrivateWire, WitnessPoint)],
+    ) -> WitnessPoint {
+        self.assert_reserved_zero_one("component_msm");
+
+        if pairs.is_empty() {
+            return Self::IDENTITY;
+        }
+
+        let bits: Vec<_> = pairs
+            .iter()
+            .map(|(scalar, _)| self.component_decomposition::<252>(*scalar))
+            .collect();
+
+        // doubling the identity and adding to it leave the selected points
+        let top = Self::JUBJUB_SCALAR_BITS - 1;
+        let mut result =
+            self.component_select_identity(bits[0][top], pairs[0].1);
+        for (b, (_, point)) in bits.iter().zip(pairs.iter()).skip(1) {
+            let point = self.component_select_identity(b[top], *point);
+            result = self.component_add_point(result, point);
+        }
+
+        for i in (0..top).rev() {
+            result = self.component_double_point(result);
+
+            for (b, (_, point)) in bits.iter().zip(pairs.iter()) {
+                let point = self.component_select_identity(b[i], *point);
+                result = self.component_add_point(result, point);
+            }
+        }
+
+        result
+    }
+
+    /// Conditionally selects a [`WitnessPoint`] based on an input bit.
+    ///
+    /// bit == 1 => a,
+    /// bit == 0 => b,
+    ///
+    /// `bit` is expected to be constrained by
+    /// [`Composer::component_boolean`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_point(
+        &mut self,
+        bit: PrivateWire,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> WitnessPoint {
+        let x = self.component_select(bit, *a.x(), *b.x());
+        let y = self.component_select(bit, *a.y(), *b.y());
+
+        WitnessPoint::new(x, y)
+    }
+
+    /// Conditionally swaps two [`WitnessPoint`] based on an input bit.
+    ///
+    /// bit == 1 => (b, a),
+    /// bit == 0 => (a, b),
+    ///
+    /// `bit` is expected to be constrained by
+    /// [`Composer::component_boolean`], unless `constrain_bit` is set.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_swap_point(
+        &mut self,
+        bit: PrivateWire,
+        a: WitnessPoint,
+        b: WitnessPoint,
+        constrain_bit: bool,
+    ) -> (WitnessPoint, WitnessPoint) {
+        let (x_0, x_1) =
+            self.component_swap(bit, *a.x(), *b.x(), constrain_bit);
+        let (y_0, y_1) = self.component_swap(bit, *a.y(), *b.y(), false);
+
+        (WitnessPoint::new(x_0, y_0), WitnessPoint::new(x_1, y_1))
+    }
+}
@@ -0,0 +1,282 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Elligator 2 map from field elements to points of the embedded curve.
+//!
+//! The map follows `map_to_curve_elligator2` of RFC 9380 on the Montgomery
+//! form `K · t² = s³ + J · s² + s` of the twisted Edwards curve
+//! `-x² + y² = 1 + d · x² · y²`, with `J = 2 · (1 - d) / (1 + d)` and
+//! `K = -4 / (1 + d)`, and maps the result back to Edwards coordinates with
+//! `x = s / t` and `y = (s - 1) / (s + 1)`. `Z` is the smallest integer from
+//! `2` that isn't a square. The point is then multiplied by the cofactor `8`,
+//! so it lies in the prime-order subgroup.
+//!
+//! The exceptional inputs are handled as in the RFC, with `inv0(0) = 0`:
+//!
+//! - `1 + Z · u² = 0` sets `x1 = -J / K`. It can't happen when `-1` is a
+//!   square, as in the scalar field of BLS12-381, since `-1 / Z` isn't.
+//! - `t = 0` or `s = -1`, where the rational map to Edwards coordinates is
+//!   undefined, map to the identity `(0, 1)`.
+//!
+//! The choice of the square root is proven with a second square root: `r`
+//! with `r² = g(x1)` when `g(x1)` is a square and `r² = Z · g(x1)` otherwise,
+//! which holds for exactly one of the cases since `g(x1)` is never zero on a
+//! curve with a single point of order two, as JubJub.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine};
+
+use super::ecc::{curve_d, invert, is_square, sqrt};
+use super::WitnessPoint;
+use crate::Plonk;
+
+/// Constants of the map on the reduced Montgomery form
+/// `y² = x³ + A · x² + B · x`, with `A = J / K` and `B = 1 / K²`
+#[derive(Debug, Clone, Copy)]
+struct Params<F> {
+    a: F,
+    b: F,
+    k: F,
+    z: F,
+}
+
+impl<F: PrimeField> Params<F> {
+    /// Constants of the curve of `C`
+    fn new<C: TwistedEdwardsAffine<Range = F>>() -> Self {
+        let d = curve_d::<C>();
+        let two = F::from(2u64);
+        let four = F::from(4u64);
+
+        // A = J / K = (d - 1) / 2, B = 1 / K² = (1 + d)² / 16
+        let a = (d - F::one()) * invert(two);
+        let k = -four * invert(F::one() + d);
+        let b = invert(k * k);
+
+        let z = (2u64..)
+            .map(F::from)
+            .find(|z| !is_square(*z))
+            .expect("half of the field isn't a square");
+
+        Self { a, b, k, z }
+    }
+
+    /// Evaluate `x³ + A · x² + B · x`
+    fn g(&self, x: F) -> F {
+        x * (x * x + self.a * x + self.b)
+    }
+}
+
+/// Witnesses of the map of a field element, before the cofactor clearing
+#[derive(Debug, Clone, Copy)]
+struct Map<F> {
+    y: F,
+    r: F,
+    is_square: bool,
+    point: (F, F),
+}
+
+impl<F: PrimeField> Map<F> {
+    /// Native map of `u`, following the [module documentation](self)
+    fn new(params: &Params<F>, u: F) -> Self {
+        let Params { a, z, k, .. } = *params;
+
+        let x1 = match invert(F::one() + z * u * u) {
+            inv if inv == F::zero() => -a,
+            inv => -a * inv,
+        };
+        let x2 = -x1 - a;
+
+        let gx1 = params.g(x1);
+        let is_square = is_square(gx1);
+
+        let (x, y, r) = match is_square {
+            true => {
+                let y = sqrt(gx1);
+                (x1, y, y)
+            }
+            false => (x2, sqrt(params.g(x2)), sqrt(z * gx1)),
+        };
+
+        // sgn0(y) = 1 for g(x1) and 0 for g(x2)
+        let y = match parity(y) == is_square {
+            true => y,
+            false => -y,
+        };
+
+        let s = k * x;
+        let point = match y == F::zero() || s == -F::one() {
+            true => (F::zero(), F::one()),
+            false => (x * invert(y), (s - F::one()) * invert(s + F::one())),
+        };
+
+        Self {
+            y,
+            r,
+            is_square,
+            point,
+        }
+    }
+}
+
+/// Native counterpart of [`Plonk::component_hash_to_point`], mapping `t`
+/// to a point of the prime-order subgroup of the curve
+pub fn hash_to_point<C: TwistedEdwardsAffine>(t: C::Range) -> C {
+    let params = Params::new::<C>();
+    let (x, y) = Map::new(&params, t).point;
+
+    let mut point = C::from_raw_unchecked(x, y);
+    for _ in 0..3 {
+        point = C::from(point + point);
+    }
+
+    point
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Map `t` to a point of the prime-order subgroup of the curve with
+    /// Elligator 2.
+    ///
+    /// See the [module documentation](self) for the map and its exceptional
+    /// inputs, and [`hash_to_point`] for its native counterpart. The square
+    /// roots are witnessed and constrained by squaring, the sign of `y` by
+    /// [`Self::component_parity`], and the result is asserted to lie on the
+    /// curve before three doublings clear the cofactor.
+    ///
+    /// Consume `35` gates plus the gates of [`Self::component_parity`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_hash_to_point(&mut self, t: PrivateWire) -> WitnessPoint {
+        let params = Params::new::<C>();
+        let Params { a, k, z, .. } = params;
+        let map = Map::new(&params, self[t]);
+
+        let one = C::Range::one();
+        let two = C::Range::from(2u64);
+
+        // 1 + Z · u²
+        let constraint = Constraint::default().mult(z).constant(1).a(t).b(t);
+        let den = self.gate_mul(constraint);
+        let (inv, den_is_zero) = self.component_inverse_or_zero(den);
+
+        // x1 = -A · (inv0(den) + [den = 0])
+        let constraint = Constraint::default()
+            .left(-a)
+            .right(-a)
+            .a(inv)
+            .b(den_is_zero);
+        let x1 = self.gate_add(constraint);
+        let gx1 = self.append_curve_polynomial(&params, x1);
+
+        // x2 = -x1 - A
+        let constraint = Constraint::default().left(-one).constant(-a).a(x1);
+        let x2 = self.gate_add(constraint);
+        let gx2 = self.append_curve_polynomial(&params, x2);
+
+        let is_square = self.append_witness(map.is_square as u64);
+        self.component_boolean(is_square);
+
+        // r² = Z · g(x1) + (1 - Z) · e · g(x1)
+        let r = self.append_witness(map.r);
+        let constraint = Constraint::default().mult(1).a(is_square).b(gx1);
+        let e_gx1 = self.gate_mul(constraint);
+        let constraint = Constraint::default()
+            .mult(1)
+            .output(-z)
+            .fourth(z - one)
+            .a(r)
+            .b(r)
+            .o(gx1)
+            .d(e_gx1);
+        self.append_gate(constraint);
+
+        let x = self.select_fused(is_square, x1, x2);
+        let gx = self.select_fused(is_square, gx1, gx2);
+
+        // y² = g(x), with sgn0(y) = e
+        let y = self.append_witness(map.y);
+        let constraint =
+            Constraint::default().mult(1).output(-one).a(y).b(y).o(gx);
+        self.append_gate(constraint);
+        let sign = self.component_parity(y);
+        self.assert_equal(sign, is_square);
+
+        let (inv_y, y_is_zero) = self.component_inverse_or_zero(y);
+
+        // s + 1 = K · x + 1
+        let constraint = Constraint::default().left(k).constant(1).a(x);
+        let s_plus_one = self.gate_add(constraint);
+        let (inv_s, s_is_minus_one) =
+            self.component_inverse_or_zero(s_plus_one);
+
+        // X = x / y, zeroed for s = -1
+        let constraint = Constraint::default().mult(1).a(x).b(inv_y);
+        let x_0 = self.gate_mul(constraint);
+        let constraint = Constraint::default()
+            .mult(-one)
+            .right(1)
+            .a(s_is_minus_one)
+            .b(x_0);
+        let x_e = self.gate_add(constraint);
+
+        // (s - 1) / (s + 1) = 1 - 2 / (s + 1), with (s + 1) · inv_s = 1 for a
+        // regular input. The term 2 · [t = 0] lifts the -1 of s = 0 to 1, and
+        // s = -1 is left with 1 by inv0
+        let constraint = Constraint::default()
+            .left(-two)
+            .right(two)
+            .constant(1)
+            .a(inv_s)
+            .b(y_is_zero);
+        let y_e = self.gate_add(constraint);
+
+        let point = WitnessPoint::new(x_e, y_e);
+        self.assert_on_curve(point);
+
+        let point = self.component_double_point(point);
+        let point = self.component_double_point(point);
+        self.component_double_point(point)
+    }
+
+    /// Evaluate `x³ + A · x² + B · x` by consuming 2 gates
+    fn append_curve_polynomial(
+        &mut self,
+        params: &Params<C::Range>,
+        x: PrivateWire,
+    ) -> PrivateWire {
+        let constraint = Constraint::default().mult(1).a(x).b(x);
+        let x_sq = self.gate_mul(constraint);
+
+        // x · x² + A · x² + B · x
+        let constraint = Constraint::default()
+            .mult(1)
+            .left(params.b)
+            .right(params.a)
+            .a(x)
+            .b(x_sq);
+        self.gate_mul(constraint)
+    }
+
+    /// Assert `-x² + y² = 1 + d · x² · y²` by consuming 3 gates
+    pub(crate) fn assert_on_curve(&mut self, point: WitnessPoint) {
+        let d = curve_d::<C>();
+
+        let constraint =
+            Constraint::default().mult(1).a(*point.x()).b(*point.x());
+        let x_sq = self.gate_mul(constraint);
+        let constraint =
+            Constraint::default().mult(1).a(*point.y()).b(*point.y());
+        let y_sq = self.gate_mul(constraint);
+
+        let constraint = Constraint::default()
+            .mult(-d)
+            .left(-C::Range::one())
+            .right(1)
+            .constant(-C::Range::one())
+            .a(x_sq)
+            .b(y_sq);
+        self.append_gate(constraint);
+    }
+}
@@ -0,0 +1,231 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Multiples of a generator, shared by the fixed-base multiplications.
+//!
+//! [`Plonk::component_mul_generator`] doubles its generator once per digit
+//! of the wNAF and converts every multiple to affine form. A
+//! [`FixedBaseTable`] does it once, and
+//! [`Plonk::component_mul_generator_with_table`] reads the multiples from
+//! it, so the gates and the witnesses, and so the proofs, are the same as
+//! the ones of the generator.
+//!
+//! A [`WindowedBaseTable`] holds the multiples of a generator for the
+//! windowed multiplication instead: [`Plonk::append_windowed_base_table`]
+//! appends one lookup table per byte of the scalar, and
+//! [`Plonk::component_mul_generator_windowed`] looks the multiple of every
+//! byte up and adds them, taking one round per byte instead of one per bit.
+
+use zksnarks::error::Error;
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::*;
+
+use super::WitnessPoint;
+use crate::{Plonk, TableId};
+
+/// Multiples `2^i · G` of a generator, in affine form, from the most
+/// significant digit of the wNAF
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedBaseTable<C: TwistedEdwardsAffine> {
+    multiples: Vec<C>,
+}
+
+impl<C: TwistedEdwardsAffine> FixedBaseTable<C> {
+    /// Number of digits of the wNAF of a scalar, and of multiples in the
+    /// table
+    pub const BITS: usize = 256;
+
+    /// Compute the multiples of `generator`
+    pub fn new<A: Into<C::Extended>>(generator: A) -> Self {
+        let mut multiple = generator.into();
+        let mut multiples = Vec::with_capacity(Self::BITS);
+
+        for _ in 0..Self::BITS {
+            multiples.push(C::from(multiple));
+            multiple = multiple.double();
+        }
+        multiples.reverse();
+
+        Self { multiples }
+    }
+
+    /// Generator of the table
+    pub fn generator(&self) -> C {
+        self.multiples[Self::BITS - 1]
+    }
+
+    /// Multiples of the generator, from `2^255 · G` to `G`
+    pub fn multiples(&self) -> &[C] {
+        &self.multiples
+    }
+}
+
+/// Lookup tables of the multiples `k · 256^i · G` of a generator, one per
+/// byte `i` of a scalar, appended with
+/// [`Plonk::append_windowed_base_table`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowedBaseTable<C: TwistedEdwardsAffine> {
+    tables: Vec<TableId>,
+    /// Multiples of every window, indexed by the digit
+    multiples: Vec<Vec<C>>,
+}
+
+impl<C: TwistedEdwardsAffine> WindowedBaseTable<C> {
+    /// Bits of a digit
+    pub const WINDOW_BITS: usize = 8;
+
+    /// Number of digits of a scalar, the last one holding the bits above
+    /// `8 · 31`
+    pub const WINDOWS: usize = 32;
+
+    /// Bits of the scalars the table multiplies by.
+    ///
+    /// The digits of a scalar below `2^253` represent it as an integer
+    /// below the modulus of the circuit field, so they are unique.
+    pub const BITS: usize = 253;
+
+    /// Number of values of the digit `i`
+    const fn digits(i: usize) -> usize {
+        match i + 1 < Self::WINDOWS {
+            true => 1 << Self::WINDOW_BITS,
+            false => {
+                1 << (Self::BITS - Self::WINDOW_BITS * (Self::WINDOWS - 1))
+            }
+        }
+    }
+
+    /// Lookup tables of the windows, from the least significant digit
+    pub fn tables(&self) -> &[TableId] {
+        &self.tables
+    }
+
+    /// Generator of the table
+    pub fn generator(&self) -> C {
+        self.multiples[0][1]
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate `jubjub · Generator` as a [`WitnessPoint`], with the
+    /// multiples of the generator read from `table`.
+    ///
+    /// The constraints are the ones of [`Self::component_mul_generator`]
+    /// with [`FixedBaseTable::generator`].
+    ///
+    /// **The scalar is unconstrained**, as for
+    /// [`Self::component_mul_generator`]: use
+    /// [`Self::component_mul_generator_windowed`] instead.
+    ///
+    /// Will error if the wNAF of `jubjub` is invalid
+    #[deprecated(note = "the scalar is unconstrained, use \
+                `component_mul_generator_windowed`")]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_generator_with_table(
+        &mut self,
+        jubjub: PrivateWire,
+        table: &FixedBaseTable<C>,
+    ) -> Result<WitnessPoint, Error> {
+        self.mul_generator_with_table(jubjub, table, false)
+    }
+
+    /// Append the lookup tables of the multiples of `generator` to the
+    /// circuit description, for [`Self::component_mul_generator_windowed`].
+    ///
+    /// The table of the digit `i` holds the rows `(k, x, y)` of the points
+    /// `k · 256^i · G`. The tables take `7968` rows, so the domain of the
+    /// circuit holds at least `2^13` rows, and they are shared by every
+    /// multiplication reading them.
+    pub fn append_windowed_base_table<A: Into<C::Extended>>(
+        &mut self,
+        generator: A,
+    ) -> WindowedBaseTable<C> {
+        let mut base = C::from(generator.into());
+        let mut tables = Vec::with_capacity(WindowedBaseTable::<C>::WINDOWS);
+        let mut multiples = Vec::with_capacity(WindowedBaseTable::<C>::WINDOWS);
+
+        for i in 0..WindowedBaseTable::<C>::WINDOWS {
+            let mut multiple = C::ADDITIVE_IDENTITY;
+            let points: Vec<C> = (0..WindowedBaseTable::<C>::digits(i))
+                .map(|_| {
+                    let point = multiple;
+                    multiple = C::from(multiple + base);
+                    point
+                })
+                .collect();
+
+            let rows: Vec<_> = points
+                .iter()
+                .enumerate()
+                .map(|(k, p)| [C::Range::from(k as u64), p.get_x(), p.get_y()])
+                .collect();
+            tables.push(self.append_lookup_table(&rows));
+            multiples.push(points);
+
+            // 256^(i + 1) · G
+            base = multiple;
+        }
+
+        WindowedBaseTable { tables, multiples }
+    }
+
+    /// Evaluate `jubjub · Generator` as a [`WitnessPoint`] with the windowed
+    /// multiplication, reading the multiples of the generator from `table`.
+    ///
+    /// `jubjub` is split into the bytes `k_i`, and every byte is looked up
+    /// with its multiple `k_i · 256^i · G` in the table of its window, so
+    /// the digits are range checked by the lookups. The bytes are asserted
+    /// to sum to `jubjub`, and the multiples are added together. The last
+    /// digit only has [`WindowedBaseTable::BITS`] minus `248` bits, so the
+    /// digits represent `jubjub` as an integer below `2^253`: the point is
+    /// the multiplication by the integer of the wire, and a wire at or above
+    /// `2^253`, so any non canonical JubJub scalar above it, can't be
+    /// proven.
+    ///
+    /// Consume `111` gates: `32` lookups, `31` point additions and the
+    /// `17` gates of the sum of the digits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_generator_windowed(
+        &mut self,
+        jubjub: PrivateWire,
+        table: &WindowedBaseTable<C>,
+    ) -> WitnessPoint {
+        let bytes = Self::canonical(self[jubjub]).to_raw_bytes();
+        let radix = C::Range::from(256u64);
+
+        let mut power = C::Range::one();
+        let mut terms = Vec::with_capacity(WindowedBaseTable::<C>::WINDOWS);
+        let mut acc: Option<WitnessPoint> = None;
+
+        for (i, (id, multiples)) in
+            table.tables.iter().zip(table.multiples.iter()).enumerate()
+        {
+            // a wire above the bound has a digit out of the table, and its
+            // lookup fails. the identity only fills its witnesses
+            let k = bytes.get(i).copied().unwrap_or(0) as usize;
+            let point =
+                multiples.get(k).copied().unwrap_or(C::ADDITIVE_IDENTITY);
+
+            let digit = self.append_witness(C::Range::from(k as u64));
+            let x = self.append_witness(point.get_x());
+            let y = self.append_witness(point.get_y());
+            self.component_lookup(digit, x, y, *id);
+
+            terms.push((power, digit));
+            power *= radix;
+
+            let point = WitnessPoint::new(x, y);
+            acc = Some(match acc {
+                Some(acc) => self.component_add_point(acc, point),
+                None => point,
+            });
+        }
+
+        let sum = self.gate_sum(&terms, C::Range::zero());
+        self.assert_equal(sum, jubjub);
+
+        acc.expect("the table has a window")
+    }
+}
@@ -0,0 +1,265 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Unsigned fixed-point numbers with `FRAC_BITS` fractional bits.
+//!
+//! A [`Fixed`] is a wire holding the integer `x · 2^FRAC_BITS` for the
+//! number `x`, constrained to fit [`TOTAL_BITS`] bits. Every operation
+//! returns a number constrained the same way, so an operation whose result
+//! doesn't fit, including a subtraction below zero, can't be proven:
+//!
+//! - [`Fixed::add`] and [`Fixed::sub`] range check the sum and the difference;
+//! - [`Fixed::mul`] splits the product `a · b` into the result times
+//!   `2^FRAC_BITS` and a remainder range checked to `FRAC_BITS` bits;
+//! - [`Fixed::div`] splits `a · 2^FRAC_BITS` into the result times `b` and a
+//!   remainder asserted to be below `b`, so a division by zero can't be proven.
+//!
+//! The results are truncated toward zero, which for unsigned numbers is the
+//! floor. The remainders are constrained, so the prover can't round
+//! otherwise. Both the product and `a · 2^FRAC_BITS` fit `2^(TOTAL_BITS +
+//! FRAC_BITS)`, so none of the equations wraps around the field modulus.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine};
+
+use crate::Plonk;
+
+/// Bits the raw integer of a [`Fixed`] fits
+pub const TOTAL_BITS: usize = 64;
+
+/// Wire holding a number with `FRAC_BITS` fractional bits, constrained to
+/// fit [`TOTAL_BITS`] bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixed<const FRAC_BITS: usize> {
+    wire: PrivateWire,
+}
+
+impl<const FRAC_BITS: usize> Fixed<FRAC_BITS> {
+    /// Number of a wire already constrained to fit [`TOTAL_BITS`] bits.
+    ///
+    /// The constraint isn't checked: an unconstrained wire makes every
+    /// operation on the number unsound.
+    pub const fn assume(wire: PrivateWire) -> Self {
+        Self { wire }
+    }
+
+    /// Inner wire, holding the raw integer
+    pub const fn wire(&self) -> PrivateWire {
+        self.wire
+    }
+
+    /// Append a witness of the raw integer `raw`, range checked to
+    /// [`TOTAL_BITS`] bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn witness<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        raw: u64,
+    ) -> Self {
+        let wire = composer.append_witness(C::Range::from(raw));
+
+        Self::constrain(composer, wire)
+    }
+
+    /// Append the constant of the raw integer `raw`
+    pub fn constant<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        raw: u64,
+    ) -> Self {
+        let wire = composer.append_constant(C::Range::from(raw));
+
+        Self::assume(wire)
+    }
+
+    /// Range check `wire` to [`TOTAL_BITS`] bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn constrain<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        wire: PrivateWire,
+    ) -> Self {
+        composer.component_range(wire, TOTAL_BITS);
+
+        Self::assume(wire)
+    }
+
+    /// Raw integer of the number in the composer
+    pub fn value<C: TwistedEdwardsAffine>(&self, composer: &Plonk<C>) -> u64 {
+        let bytes = Plonk::<C>::canonical(composer[self.wire]).to_raw_bytes();
+
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(&bytes[..8]);
+
+        u64::from_le_bytes(raw)
+    }
+
+    /// Evaluate `self + other`.
+    ///
+    /// Consume `11` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn add<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> Self {
+        let constraint = Constraint::default()
+            .left(1)
+            .right(1)
+            .a(self.wire)
+            .b(other.wire);
+        let sum = composer.gate_add(constraint);
+
+        Self::constrain(composer, sum)
+    }
+
+    /// Evaluate `self - other`, which can't be proven if `other` is greater
+    /// than `self`.
+    ///
+    /// Consume `11` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn sub<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> Self {
+        let diff = composer.gate_sub(self.wire, other.wire);
+
+        Self::constrain(composer, diff)
+    }
+
+    /// Evaluate `self · other`, truncated to `FRAC_BITS` fractional bits.
+    ///
+    /// Consume `11` gates plus the range check of the remainder to
+    /// `FRAC_BITS` bits
+    ///
+    /// # Panics
+    ///
+    /// If `FRAC_BITS` exceeds [`TOTAL_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn mul<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> Self {
+        Self::assert_frac_bits();
+
+        let product =
+            self.value(composer) as u128 * other.value(composer) as u128;
+        let q = product >> FRAC_BITS;
+        let r = product - (q << FRAC_BITS);
+
+        let q = composer.append_witness(Self::scalar::<C>(q));
+        let r = composer.append_witness(Self::scalar::<C>(r));
+
+        // a · b - 2^FRAC_BITS · q - r = 0
+        let constraint = Constraint::default()
+            .mult(1)
+            .output(-C::Range::pow_of_2(FRAC_BITS as u64))
+            .fourth(-C::Range::one())
+            .a(self.wire)
+            .b(other.wire)
+            .o(q)
+            .d(r);
+        composer.append_gate(constraint);
+
+        composer.range_any(r, FRAC_BITS, "Fixed::mul");
+
+        Self::constrain(composer, q)
+    }
+
+    /// Evaluate `self / other`, truncated to `FRAC_BITS` fractional bits,
+    /// which can't be proven if `other` is zero.
+    ///
+    /// Consume `32` gates
+    ///
+    /// # Panics
+    ///
+    /// If `FRAC_BITS` exceeds [`TOTAL_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn div<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> Self {
+        Self::assert_frac_bits();
+
+        let a = (self.value(composer) as u128) << FRAC_BITS;
+        let b = other.value(composer) as u128;
+        let (q, r) = match b {
+            0 => (0, 0),
+            _ => (a / b, a % b),
+        };
+
+        let q = composer.append_witness(Self::scalar::<C>(q));
+        let r = composer.append_witness(Self::scalar::<C>(r));
+
+        // b · q + r - 2^FRAC_BITS · a = 0
+        let constraint = Constraint::default()
+            .mult(1)
+            .output(-C::Range::pow_of_2(FRAC_BITS as u64))
+            .fourth(1)
+            .a(other.wire)
+            .b(q)
+            .o(self.wire)
+            .d(r);
+        composer.append_gate(constraint);
+
+        composer.component_range(r, TOTAL_BITS);
+        composer
+            .component_assert_less_than_unchecked::<TOTAL_BITS>(r, other.wire);
+
+        Self::constrain(composer, q)
+    }
+
+    /// Evaluate `self < other` as a bit.
+    ///
+    /// Consume the gates of [`Plonk::component_less_than`] of
+    /// [`TOTAL_BITS`] bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn less_than<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) -> PrivateWire {
+        composer.component_less_than::<TOTAL_BITS>(self.wire, other.wire)
+    }
+
+    /// Assert `self < other`.
+    ///
+    /// Consume the gates of
+    /// [`Plonk::component_assert_less_than_unchecked`] of [`TOTAL_BITS`]
+    /// bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_less_than<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Self,
+    ) {
+        composer.component_assert_less_than_unchecked::<TOTAL_BITS>(
+            self.wire, other.wire,
+        )
+    }
+
+    /// Scalar of the integer `x`
+    fn scalar<C: TwistedEdwardsAffine>(x: u128) -> C::Range {
+        C::Range::from(x as u64)
+            + C::Range::from((x >> 64) as u64) * C::Range::pow_of_2(64)
+    }
+
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn assert_frac_bits() {
+        assert!(
+            FRAC_BITS <= TOTAL_BITS,
+            "the fractional bits must fit {} bits",
+            TOTAL_BITS
+        );
+    }
+}
+
+impl<const FRAC_BITS: usize> From<Fixed<FRAC_BITS>> for PrivateWire {
+    fn from(x: Fixed<FRAC_BITS>) -> Self {
+        x.wire
+    }
+}
@@ -0,0 +1,115 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Two-to-one hash functions with a native implementation and a circuit
+//! twin computing the same value.
+
+use sp_std::vec;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::digest::digest_to_scalar;
+use crate::Plonk;
+
+/// Hash compressing two scalars into one, both natively and in a circuit.
+///
+/// [`Self::gadget`] must constrain its output to the value [`Self::hash`]
+/// returns for the values of its inputs.
+pub trait CircuitHasher<F: PrimeField> {
+    /// Compress `left` and `right` into a single scalar
+    fn hash(left: F, right: F) -> F;
+
+    /// Circuit twin of [`Self::hash`]
+    fn gadget<C: TwistedEdwardsAffine<Range = F>>(
+        composer: &mut Plonk<C>,
+        left: PrivateWire,
+        right: PrivateWire,
+    ) -> PrivateWire;
+}
+
+/// MiMC with the `x^5` round function, in the Miyaguchi–Preneel mode.
+///
+/// The block cipher `E_k(x)` runs [`Self::ROUNDS`] rounds
+/// `x ← (x + k + c_i)^5` and returns `x + k`, and the compression of `left`
+/// and `right` is `E_right(left) + left`. The round constant `c_i` is the
+/// [`public_digest`](crate::public_digest) of `zkplonk.mimc5` followed by
+/// `i` as 8 little-endian bytes.
+///
+/// `x^5` is a permutation of the scalar field as long as `5` doesn't divide
+/// its order minus one, as for BLS12-381.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mimc;
+
+impl Mimc {
+    /// Rounds of the cipher, `⌈255 / log2(5)⌉`
+    pub const ROUNDS: usize = 110;
+
+    /// Gates of [`CircuitHasher::gadget`], three per round and one for each
+    /// of the first key addition and the feed-forward
+    pub const GATES: usize = 3 * Self::ROUNDS + 2;
+
+    fn round_constants<F: PrimeField>() -> Vec<F> {
+        (0..Self::ROUNDS as u64)
+            .map(|i| {
+                let seed = [&b"zkplonk.mimc5"[..], &i.to_le_bytes()].concat();
+                digest_to_scalar(&seed)
+            })
+            .collect()
+    }
+}
+
+impl<F: PrimeField> CircuitHasher<F> for Mimc {
+    fn hash(left: F, right: F) -> F {
+        let x = Self::round_constants::<F>().iter().fold(left, |x, c| {
+            let t = x + right + *c;
+            let t2 = t * t;
+
+            t2 * t2 * t
+        });
+
+        x + right + left
+    }
+
+    fn gadget<C: TwistedEdwardsAffine<Range = F>>(
+        composer: &mut Plonk<C>,
+        left: PrivateWire,
+        right: PrivateWire,
+    ) -> PrivateWire {
+        let constants = Self::round_constants::<F>();
+
+        // t = x + k + c_0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(1)
+            .constant(constants[0])
+            .a(left)
+            .b(right);
+        let mut t = composer.gate_add(constraint);
+
+        // the addition of the key and the next constant is folded into the
+        // last multiplication of the round
+        let next = constants[1..].iter().copied().map(Some).chain([None]);
+        for c in next {
+            let constraint = Constraint::default().mult(1).a(t).b(t);
+            let t2 = composer.gate_mul(constraint);
+            let constraint = Constraint::default().mult(1).a(t2).b(t2);
+            let t4 = composer.gate_mul(constraint);
+
+            let constraint = Constraint::default()
+                .mult(1)
+                .fourth(1)
+                .constant(c.unwrap_or_else(F::zero))
+                .a(t4)
+                .b(t)
+                .d(right);
+            t = composer.gate_mul(constraint);
+        }
+
+        let constraint = Constraint::default().left(1).right(1).a(t).b(left);
+        composer.gate_add(constraint)
+    }
+}
@@ -0,0 +1,224 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Arithmetic modulo a constant `q` other than the scalar field modulus,
+//! e.g. the base field of secp256k1.
+//!
+//! Elements are [`BigIntWitness`] integers of `LIMBS` limbs of 64 bits,
+//! reduced below `q`. A product `a · b` is proven with the quotient `k` and
+//! the remainder `r` of its division by `q`:
+//!
+//! - `a · b` and `k · q + r` are evaluated as integers of `2 · LIMBS` limbs,
+//!   with every limb and carry range checked, so both sides are exact and their
+//!   equality can't wrap around the scalar field;
+//! - `r < q` is constrained, so the remainder is unique.
+//!
+//! The quotient of reduced operands fits `LIMBS` limbs, so it is range
+//! checked like any other integer.
+
+use sp_std::vec;
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{TwistedEdwardsAffine, Vec};
+
+use super::bigint::{
+    add_limbs, mul_limbs, sub_limbs, BigIntWitness, LIMB_BITS,
+};
+use crate::Plonk;
+
+/// Constant modulus of `LIMBS` limbs of 64 bits, least significant first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonNativeModulus<const LIMBS: usize> {
+    limbs: [u64; LIMBS],
+}
+
+impl<const LIMBS: usize> NonNativeModulus<LIMBS> {
+    /// Modulus of the given limbs.
+    ///
+    /// # Panics
+    ///
+    /// If the modulus is lower than `2`.
+    pub fn new(limbs: [u64; LIMBS]) -> Self {
+        let trivial = limbs.iter().skip(1).all(|l| *l == 0) && limbs[0] < 2;
+        assert!(!trivial, "the modulus must be at least 2");
+
+        Self { limbs }
+    }
+
+    /// Limbs of the modulus, least significant first
+    pub const fn limbs(&self) -> [u64; LIMBS] {
+        self.limbs
+    }
+}
+
+/// Element of the field of a [`NonNativeModulus`], constrained to be reduced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonNativeFieldWitness<const LIMBS: usize> {
+    value: BigIntWitness<LIMBS>,
+}
+
+impl<const LIMBS: usize> NonNativeFieldWitness<LIMBS> {
+    /// Element of an integer already constrained to be below the modulus.
+    ///
+    /// The constraint isn't checked: an unreduced integer makes the
+    /// operations on the element unsound.
+    pub const fn assume(value: BigIntWitness<LIMBS>) -> Self {
+        Self { value }
+    }
+
+    /// Integer of the element
+    pub const fn value(&self) -> BigIntWitness<LIMBS> {
+        self.value
+    }
+
+    /// Append a witness of `value`, constrained to be below `modulus`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn witness<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        value: [u64; LIMBS],
+        modulus: &NonNativeModulus<LIMBS>,
+    ) -> Self {
+        let value = BigIntWitness::witness(composer, value);
+        let q = BigIntWitness::constant(composer, modulus.limbs);
+
+        composer.assert_reduced(&value.limbs(), &q.limbs());
+
+        Self::assume(value)
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate `a · b mod q` for the constant modulus `q`.
+    ///
+    /// The quotient and the remainder are witnessed and constrained as
+    /// described in the [module documentation](self). `a` and `b` are
+    /// expected to be reduced, which [`NonNativeFieldWitness`] guarantees
+    /// when built with [`NonNativeFieldWitness::witness`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_nonnative_mul<const LIMBS: usize>(
+        &mut self,
+        a: &NonNativeFieldWitness<LIMBS>,
+        b: &NonNativeFieldWitness<LIMBS>,
+        modulus: &NonNativeModulus<LIMBS>,
+    ) -> NonNativeFieldWitness<LIMBS> {
+        let a_value = a.value.value(self);
+        let b_value = b.value.value(self);
+
+        let product = mul_native(&a_value, &b_value);
+        let (quotient, remainder) = div_rem_native(&product, &modulus.limbs);
+
+        let mut k = [0u64; LIMBS];
+        let mut r = [0u64; LIMBS];
+        k.copy_from_slice(&quotient[..LIMBS]);
+        r.copy_from_slice(&remainder[..LIMBS]);
+
+        self.component_nonnative_mul_hinted(a, b, modulus, k, r)
+    }
+
+    /// Evaluate `a · b mod q` with the quotient `k` and the remainder `r`
+    /// provided by the caller.
+    ///
+    /// The hints aren't trusted: the constraints of
+    /// [`Self::component_nonnative_mul`] are the same, so wrong hints yield an
+    /// unsatisfied circuit.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_nonnative_mul_hinted<const LIMBS: usize>(
+        &mut self,
+        a: &NonNativeFieldWitness<LIMBS>,
+        b: &NonNativeFieldWitness<LIMBS>,
+        modulus: &NonNativeModulus<LIMBS>,
+        k: [u64; LIMBS],
+        r: [u64; LIMBS],
+    ) -> NonNativeFieldWitness<LIMBS> {
+        let k = BigIntWitness::witness(self, k);
+        let r = BigIntWitness::witness(self, r);
+        let q = BigIntWitness::constant(self, modulus.limbs);
+
+        let wide = |limbs: &[PrivateWire]| {
+            let mut wide = limbs.to_vec();
+            wide.resize(2 * LIMBS, Self::ZERO);
+            wide
+        };
+
+        // a · b = k · q + r, over 2 · LIMBS limbs
+        let ab = mul_limbs(self, &a.value.limbs(), &b.value.limbs(), 2 * LIMBS);
+        let kq = mul_limbs(self, &k.limbs(), &q.limbs(), 2 * LIMBS);
+        let (kq_r, carry) = add_limbs(self, &kq, &wide(&r.limbs()));
+
+        self.assert_equal(carry, Self::ZERO);
+        ab.iter()
+            .zip(kq_r.iter())
+            .for_each(|(x, y)| self.assert_equal(*x, *y));
+
+        self.assert_reduced(&r.limbs(), &q.limbs());
+
+        NonNativeFieldWitness::assume(r)
+    }
+
+    /// Constrain the integer of `limbs` to be below the one of `q`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn assert_reduced(&mut self, limbs: &[PrivateWire], q: &[PrivateWire]) {
+        self.assert_reserved_zero_one("the non-native field gadgets");
+
+        let (_, borrow) = sub_limbs(self, limbs, q);
+
+        self.assert_equal(borrow, Self::ONE);
+    }
+}
+
+/// Limbs of `a · b`
+fn mul_native(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut product = vec![0u64; a.len() + b.len()];
+
+    for (i, a) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, b) in b.iter().enumerate() {
+            let t = *a as u128 * *b as u128 + product[i + j] as u128 + carry;
+            product[i + j] = t as u64;
+            carry = t >> LIMB_BITS;
+        }
+        product[i + b.len()] = carry as u64;
+    }
+
+    product
+}
+
+/// Limbs of the quotient and the remainder of `n / q`, by long division over
+/// the bits of `n`. Both have the length of `n`.
+fn div_rem_native(n: &[u64], q: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let len = n.len();
+    let mut q = q.to_vec();
+    q.resize(len, 0);
+
+    let mut quotient = vec![0u64; len];
+    let mut remainder = vec![0u64; len];
+
+    for bit in (0..len * LIMB_BITS).rev() {
+        // remainder = 2 · remainder + bit, which can't overflow since the
+        // remainder is below q before the shift
+        let mut carry = (n[bit / LIMB_BITS] >> (bit % LIMB_BITS)) & 1;
+        for limb in remainder.iter_mut() {
+            let next = *limb >> (LIMB_BITS - 1);
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+
+        let below = remainder.iter().rev().cmp(q.iter().rev())
+            == core::cmp::Ordering::Less;
+        if !below {
+            let mut borrow = false;
+            for (limb, q) in remainder.iter_mut().zip(q.iter()) {
+                let (d, x) = limb.overflowing_sub(*q);
+                let (d, y) = d.overflowing_sub(borrow as u64);
+                *limb = d;
+                borrow = x || y;
+            }
+
+            quotient[bit / LIMB_BITS] |= 1 << (bit % LIMB_BITS);
+        }
+    }
+
+    (quotient, remainder)
+}
@@ -0,0 +1,75 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Pedersen commitments `value · G + blinder · H` over the embedded curve.
+//!
+//! Both scalars are range checked to
+//! [`Plonk::JUBJUB_SCALAR_BITS`] bits, and the fixed-base multiplications
+//! constrain the top digits of their wNAF to be zero, so each multiplication
+//! is by the integer of its scalar wire and not by another integer congruent
+//! to it modulo the scalar field.
+//!
+//! `H` must have an unknown discrete logarithm in respect to `G`, otherwise
+//! the commitment isn't binding.
+
+use zksnarks::error::Error;
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::TwistedEdwardsAffine;
+
+use super::WitnessPoint;
+use crate::Plonk;
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Evaluate the Pedersen commitment `value · g + blinder · h`.
+    ///
+    /// See the [module documentation](self) for the constraints on the
+    /// scalars. The generators are constants of the circuit.
+    ///
+    /// Will error if `value` or `blinder` doesn't fit `Fr`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_pedersen_commit<A: Into<C::Extended>>(
+        &mut self,
+        value: PrivateWire,
+        blinder: PrivateWire,
+        g: A,
+        h: A,
+    ) -> Result<WitnessPoint, Error> {
+        self.component_range(value, Self::JUBJUB_SCALAR_BITS);
+        self.component_range(blinder, Self::JUBJUB_SCALAR_BITS);
+
+        let value_point = self.mul_generator(value, g, true)?;
+        let blinder_point = self.mul_generator(blinder, h, true)?;
+
+        Ok(self.component_add_point(value_point, blinder_point))
+    }
+
+    /// Assert `commitment` opens to `value` and `blinder`, with the
+    /// generators `g` and `h`.
+    ///
+    /// The commitment is a public input, checked with
+    /// [`Self::assert_equal_public_point`] against
+    /// [`Self::component_pedersen_commit`].
+    ///
+    /// Will error if `value` or `blinder` doesn't fit `Fr`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_pedersen_opening<A, P>(
+        &mut self,
+        value: PrivateWire,
+        blinder: PrivateWire,
+        g: A,
+        h: A,
+        commitment: P,
+    ) -> Result<(), Error>
+    where
+        A: Into<C::Extended>,
+        P: Into<C>,
+    {
+        let point = self.component_pedersen_commit(value, blinder, g, h)?;
+        self.assert_equal_public_point(point, commitment);
+
+        Ok(())
+    }
+}
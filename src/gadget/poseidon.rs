@@ -0,0 +1,313 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Poseidon permutation and sponge hash with the `x^5` S-box, natively and
+//! in a circuit.
+//!
+//! A permutation of [`PoseidonParams`] runs `full_rounds / 2` full rounds,
+//! then `partial_rounds` partial rounds, then `full_rounds / 2` full rounds.
+//! Every round adds its constants to the state, applies the S-box to every
+//! element in a full round and to the first one in a partial round, then
+//! multiplies the state by the MDS matrix.
+//!
+//! In the circuit, the round constants are folded into the selectors: into
+//! the S-box gates for the elements going through the S-box, and into the
+//! constant of the MDS gates for the others. A row of the MDS matrix is a
+//! single [`Plonk::gate_sum`], so up to a width of `3` every state element
+//! costs a single gate per partial round.
+//!
+//! The hash absorbs its inputs in chunks of `WIDTH - 1` elements, added to
+//! the state after the first one, with a permutation after every chunk. The
+//! first element of the state starts at the number of inputs, so inputs of
+//! different lengths are domain separated without padding. The digest is the
+//! second element of the state.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::digest::digest_to_scalar;
+use crate::Plonk;
+
+/// Parameters of a Poseidon instantiation over a state of `WIDTH` scalars
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseidonParams<F, const WIDTH: usize> {
+    full_rounds: usize,
+    partial_rounds: usize,
+    round_constants: Vec<[F; WIDTH]>,
+    mds: [[F; WIDTH]; WIDTH],
+}
+
+impl<F: PrimeField, const WIDTH: usize> PoseidonParams<F, WIDTH> {
+    /// Instantiation of the given rounds, with a row of constants per round.
+    ///
+    /// The MDS matrix isn't checked, and must be invertible for the
+    /// permutation to be one.
+    ///
+    /// # Panics
+    ///
+    /// If `WIDTH` is lower than `2`, `full_rounds` is odd, or there isn't a
+    /// row of constants for every round.
+    pub fn new(
+        full_rounds: usize,
+        partial_rounds: usize,
+        round_constants: Vec<[F; WIDTH]>,
+        mds: [[F; WIDTH]; WIDTH],
+    ) -> Self {
+        assert!(WIDTH >= 2, "the state needs a capacity and a rate");
+        assert!(full_rounds % 2 == 0, "the full rounds must be even");
+        assert_eq!(
+            round_constants.len(),
+            full_rounds + partial_rounds,
+            "a row of constants is required for every round"
+        );
+
+        Self {
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// Instantiation of the given rounds with generated constants.
+    ///
+    /// The constant `i` of the round `r` is the
+    /// [`public_digest`](crate::public_digest) of `label` followed by `r`
+    /// and `i` as 8 little-endian bytes each. The MDS matrix is the Cauchy
+    /// matrix `M_ij = 1 / (i + WIDTH + j)`.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::new`].
+    pub fn generate(
+        label: &[u8],
+        full_rounds: usize,
+        partial_rounds: usize,
+    ) -> Self {
+        let round_constants = (0..full_rounds + partial_rounds)
+            .map(|r| {
+                let mut row = [F::zero(); WIDTH];
+                row.iter_mut().enumerate().for_each(|(i, c)| {
+                    let seed = [
+                        label,
+                        &(r as u64).to_le_bytes(),
+                        &(i as u64).to_le_bytes(),
+                    ]
+                    .concat();
+                    *c = digest_to_scalar(&seed);
+                });
+
+                row
+            })
+            .collect();
+
+        let mut mds = [[F::zero(); WIDTH]; WIDTH];
+        mds.iter_mut().enumerate().for_each(|(i, row)| {
+            row.iter_mut().enumerate().for_each(|(j, m)| {
+                let x = F::from((i + WIDTH + j) as u64);
+                *m = x.invert().expect("the Cauchy entries aren't zero");
+            })
+        });
+
+        Self::new(full_rounds, partial_rounds, round_constants, mds)
+    }
+
+    /// Number of full rounds
+    pub const fn full_rounds(&self) -> usize {
+        self.full_rounds
+    }
+
+    /// Number of partial rounds
+    pub const fn partial_rounds(&self) -> usize {
+        self.partial_rounds
+    }
+
+    /// Gates of [`Plonk::component_poseidon_perm`], three per S-box and the
+    /// gates of a [`Plonk::gate_sum`] of `WIDTH` terms per MDS row
+    pub fn gates(&self) -> usize {
+        let sum = match WIDTH {
+            0..=3 => 1,
+            _ => 1 + (WIDTH - 2) / 2,
+        };
+        let sboxes = self.full_rounds * WIDTH + self.partial_rounds;
+        let rounds = self.full_rounds + self.partial_rounds;
+
+        3 * sboxes + rounds * WIDTH * sum
+    }
+
+    /// Apply the permutation to `state`
+    pub fn permute(&self, mut state: [F; WIDTH]) -> [F; WIDTH] {
+        for (r, constants) in self.round_constants.iter().enumerate() {
+            state
+                .iter_mut()
+                .zip(constants.iter())
+                .for_each(|(s, c)| *s += *c);
+
+            state.iter_mut().take(self.sboxes(r)).for_each(|s| {
+                let s2 = *s * *s;
+                *s = s2 * s2 * *s;
+            });
+
+            let mut next = [F::zero(); WIDTH];
+            next.iter_mut().zip(self.mds.iter()).for_each(|(n, row)| {
+                *n = row
+                    .iter()
+                    .zip(state.iter())
+                    .fold(F::zero(), |acc, (m, s)| acc + *m * *s)
+            });
+            state = next;
+        }
+
+        state
+    }
+
+    /// Hash `inputs` with the sponge described in the
+    /// [module documentation](self)
+    pub fn hash(&self, inputs: &[F]) -> F {
+        let mut state = [F::zero(); WIDTH];
+        state[0] = F::from(inputs.len() as u64);
+
+        let mut chunks: Vec<&[F]> = inputs.chunks(WIDTH - 1).collect();
+        if chunks.is_empty() {
+            chunks.push(&[]);
+        }
+
+        for chunk in chunks {
+            state[1..]
+                .iter_mut()
+                .zip(chunk.iter())
+                .for_each(|(s, x)| *s += *x);
+            state = self.permute(state);
+        }
+
+        state[1]
+    }
+
+    /// Number of elements going through the S-box in the round `r`
+    fn sboxes(&self, r: usize) -> usize {
+        let half = self.full_rounds / 2;
+
+        match r < half || r >= half + self.partial_rounds {
+            true => WIDTH,
+            false => 1,
+        }
+    }
+}
+
+impl<F: PrimeField> Default for PoseidonParams<F, 3> {
+    /// Instantiation over a state of `3` scalars, with `8` full and `57`
+    /// partial rounds, and the constants generated from the label
+    /// `zkplonk.poseidon`
+    fn default() -> Self {
+        Self::generate(b"zkplonk.poseidon", 8, 57)
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Apply the Poseidon permutation of `params` to `state`.
+    ///
+    /// See the [module documentation](self) for the layout of the gates.
+    ///
+    /// Consume [`PoseidonParams::gates`] gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_poseidon_perm<const WIDTH: usize>(
+        &mut self,
+        params: &PoseidonParams<C::Range, WIDTH>,
+        mut state: [PrivateWire; WIDTH],
+    ) -> [PrivateWire; WIDTH] {
+        for (r, constants) in params.round_constants.iter().enumerate() {
+            let sboxes = params.sboxes(r);
+
+            let mut sboxed = state;
+            sboxed
+                .iter_mut()
+                .zip(constants.iter())
+                .take(sboxes)
+                .for_each(|(s, c)| *s = self.poseidon_sbox(*s, *c));
+
+            // the constants of the elements skipping the S-box are folded
+            // into the MDS rows
+            for (s, row) in state.iter_mut().zip(params.mds.iter()) {
+                let terms: Vec<_> =
+                    row.iter().copied().zip(sboxed.iter().copied()).collect();
+                let constant = row
+                    .iter()
+                    .zip(constants.iter())
+                    .skip(sboxes)
+                    .fold(C::Range::zero(), |acc, (m, c)| acc + *m * *c);
+
+                *s = self.gate_sum(&terms, constant);
+            }
+        }
+
+        state
+    }
+
+    /// Hash `inputs` with the Poseidon sponge of `params`, described in the
+    /// [module documentation](self).
+    ///
+    /// Consume [`PoseidonParams::gates`] gates per chunk of `WIDTH - 1`
+    /// inputs, `1` gate per input after the first chunk, and `1` gate for
+    /// the number of inputs
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_poseidon_hash<const WIDTH: usize>(
+        &mut self,
+        params: &PoseidonParams<C::Range, WIDTH>,
+        inputs: &[PrivateWire],
+    ) -> PrivateWire {
+        self.assert_reserved_zero_one("component_poseidon_hash");
+
+        let mut state = [Self::ZERO; WIDTH];
+        state[0] = self.append_constant(inputs.len() as u64);
+
+        let mut chunks: Vec<&[PrivateWire]> =
+            inputs.chunks(WIDTH - 1).collect();
+        if chunks.is_empty() {
+            chunks.push(&[]);
+        }
+
+        for (k, chunk) in chunks.into_iter().enumerate() {
+            for (s, x) in state[1..].iter_mut().zip(chunk.iter()) {
+                // the rate of the initial state is zero
+                *s = match k {
+                    0 => *x,
+                    _ => {
+                        let constraint =
+                            Constraint::default().left(1).right(1).a(*s).b(*x);
+                        self.gate_add(constraint)
+                    }
+                };
+            }
+
+            state = self.component_poseidon_perm(params, state);
+        }
+
+        state[1]
+    }
+
+    /// Evaluate `(x + c)^5`, with the constant folded into the first and the
+    /// last gates
+    ///
+    /// Consume `3` gates
+    fn poseidon_sbox(&mut self, x: PrivateWire, c: C::Range) -> PrivateWire {
+        // (x + c)^2 = x · x + 2c · x + c^2
+        let constraint = Constraint::default()
+            .mult(1)
+            .left(c + c)
+            .constant(c * c)
+            .a(x)
+            .b(x);
+        let t2 = self.gate_mul(constraint);
+
+        let constraint = Constraint::default().mult(1).a(t2).b(t2);
+        let t4 = self.gate_mul(constraint);
+
+        // (x + c)^5 = t4 · x + c · t4
+        let constraint = Constraint::default().mult(1).left(c).a(t4).b(x);
+        self.gate_mul(constraint)
+    }
+}
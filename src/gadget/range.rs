@@ -0,0 +1,653 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Range gadgets of the composer: bit ranges, bounded ranges, truncation
+//! and the small integer helpers built on top of them.
+
+use core::cmp;
+use sp_std::vec;
+use zksnarks::bit_iterator::BitIterator8;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Adds a range-constraint gate that checks and constrains a
+    /// [`PrivateWire`] to be inside of the range \[0,num_bits\].
+    ///
+    /// This function adds `num_bits/4` gates to the circuit description in
+    /// order to add the range constraint.
+    ///
+    ///# Panics
+    /// This function will panic if the num_bits specified is not even, ie.
+    /// `num_bits % 2 != 0`.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_range(&mut self, witness: PrivateWire, num_bits: usize) {
+        // convert witness to bit representation and reverse
+        let bits = Self::canonical(self[witness]);
+        let bit_iter = BitIterator8::new(bits.to_raw_bytes());
+        let mut bits: Vec<_> = bit_iter.collect();
+        bits.reverse();
+
+        // considering this is a width-4 program, one gate will contain 4
+        // accumulators. each accumulator proves that a single quad is a
+        // base-4 digit. accumulators are bijective to quads, and these
+        // are 2-bits each. given that, one gate accumulates 8 bits.
+        let mut num_gates = num_bits >> 3;
+
+        // given each gate accumulates 8 bits, its count must be padded
+        if num_bits % 8 != 0 {
+            num_gates += 1;
+        }
+
+        // a gate holds 4 quads
+        let num_quads = num_gates * 4;
+
+        // the wires are left-padded with the difference between the quads count
+        // and the bits argument
+        let pad = 1 + (((num_quads << 1) - num_bits) >> 1);
+
+        // last gate is reserved for either the genesis quad or the padding
+        let used_gates = num_gates + 1;
+
+        let base = Constraint::<C::Range>::default();
+        let base = Constraint::range(base);
+        let mut constraints = vec![base; used_gates];
+
+        // We collect the set of accumulators to return back to the user
+        // and keep a running count of the current accumulator
+        let mut accumulators: Vec<PrivateWire> = Vec::new();
+        let mut accumulator = C::Range::zero();
+        let four = C::Range::from(4);
+
+        for i in pad..=num_quads {
+            // convert each pair of bits to quads
+            let bit_index = (num_quads - i) << 1;
+            let q_0 = bits[bit_index] as u64;
+            let q_1 = bits[bit_index + 1] as u64;
+            let quad = q_0 + (2 * q_1);
+
+            accumulator = four * accumulator;
+            accumulator += C::Range::from(quad);
+
+            let accumulator_var = self.append_witness(accumulator);
+
+            accumulators.push(accumulator_var);
+
+            let idx = i / 4;
+            match i % 4 {
+                0 => {
+                    constraints[idx].w_d = accumulator_var;
+                }
+                1 => {
+                    constraints[idx].w_o = accumulator_var;
+                }
+                2 => {
+                    constraints[idx].w_b = accumulator_var;
+                }
+                3 => {
+                    constraints[idx].w_a = accumulator_var;
+                }
+                _ => unreachable!(),
+            };
+        }
+
+        // last constraint is zeroed as it is reserved for the genesis quad or
+        // padding
+        if let Some(c) = constraints.last_mut() {
+            *c = Constraint::default()
+        }
+
+        // the accumulators count is a function to the number of quads. hence,
+        // this optional gate will not cause different circuits depending on the
+        // witness because this computation is bound to the constant bits count
+        // alone.
+        if let Some(accumulator) = accumulators.last() {
+            if let Some(c) = constraints.last_mut() {
+                c.w_d = *accumulator
+            }
+        }
+
+        constraints
+            .into_iter()
+            .for_each(|c| self.append_custom_gate(c));
+
+        // the accumulators count is a function to the number of quads. hence,
+        // this optional gate will not cause different circuits depending on the
+        // witness because this computation is bound to the constant bits count
+        // alone.
+        if let Some(accumulator) = accumulators.last() {
+            self.assert_equal(*accumulator, witness);
+        }
+    }
+
+    /// Range check every witness of `items` to its number of bits, sharing
+    /// the range gates among them.
+    ///
+    /// The quads of all the witnesses go through a single running
+    /// accumulator, left padded with zeros as in [`Self::component_range`],
+    /// so the range gates are full but for the first one. Every witness is
+    /// then asserted to be the accumulator at its last quad minus the
+    /// accumulator before its first quad shifted by its bits: the quads of
+    /// the previous witnesses cancel out, and the witness is the sum of its
+    /// own quads. The gate asserting the last witness also holds the last
+    /// accumulator read by the range gates.
+    ///
+    /// Consume `⌈Q / 4⌉ + K` gates for `K` witnesses of `Q` quads in total,
+    /// half of their bits, while the same [`Self::component_range`] calls
+    /// consume `⌈bits / 8⌉ + 2` gates each. `100` witnesses of `8` bits cost
+    /// `200` gates instead of `300`, and of `4` bits `150` instead of `300`.
+    ///
+    /// # Panics
+    ///
+    /// If the number of bits of a witness is odd.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_range_batch(&mut self, items: &[(PrivateWire, usize)]) {
+        assert!(
+            items.iter().all(|(_, bits)| bits % 2 == 0),
+            "the range gates check an even number of bits"
+        );
+        if items.is_empty() {
+            return;
+        }
+        self.assert_reserved_zero_one("component_range_batch");
+
+        let quads: usize = items.iter().map(|(_, bits)| bits / 2).sum();
+        let num_gates = (quads + 3) / 4;
+
+        // accumulators by position in the gates, after the leading zeros,
+        // with the accumulators before and after every witness
+        let mut chain = vec![Self::ZERO; 4 * num_gates - quads + 1];
+        let mut bounds = Vec::with_capacity(items.len());
+        let mut accumulator = C::Range::zero();
+        let four = C::Range::from(4);
+
+        for (w, bits) in items {
+            let start = chain[chain.len() - 1];
+
+            // little endian bits, the quads are accumulated from the top
+            let le: Vec<u64> = Self::canonical(self[*w])
+                .to_bits()
+                .iter()
+                .rev()
+                .map(|b| *b as u64)
+                .collect();
+            let bit = |i: usize| le.get(i).copied().unwrap_or(0);
+
+            for j in (0..bits / 2).rev() {
+                let quad = bit(2 * j) + 2 * bit(2 * j + 1);
+
+                accumulator = four * accumulator + C::Range::from(quad);
+                chain.push(self.append_witness(accumulator));
+            }
+
+            bounds.push((start, chain[chain.len() - 1]));
+        }
+
+        let base = Constraint::range(Constraint::<C::Range>::default());
+        let mut constraints = vec![base; num_gates];
+        chain
+            .iter()
+            .take(4 * num_gates)
+            .enumerate()
+            .for_each(|(i, acc)| {
+                let c = &mut constraints[i / 4];
+                match i % 4 {
+                    0 => c.w_d = *acc,
+                    1 => c.w_o = *acc,
+                    2 => c.w_b = *acc,
+                    _ => c.w_a = *acc,
+                }
+            });
+
+        constraints
+            .into_iter()
+            .for_each(|c| self.append_custom_gate(c));
+
+        // end - 2^bits · start - w = 0, with the last witness first so its
+        // end is the fourth wire right after the range gates
+        let last = items.len() - 1;
+        for i in core::iter::once(last).chain(0..last) {
+            let (w, bits) = items[i];
+            let (start, end) = bounds[i];
+
+            let constraint = Constraint::default()
+                .right(-C::Range::pow_of_2(bits as u64))
+                .output(-C::Range::one())
+                .fourth(1)
+                .b(start)
+                .o(w)
+                .d(end);
+            self.append_gate(constraint);
+        }
+    }
+
+    /// Constrain `0 ≤ w < bound` for a constant `bound` that isn't
+    /// necessarily a power of two.
+    ///
+    /// Both `w` and `bound - 1 - w` are range checked to `num_bits` bits, so
+    /// any `w ≥ bound` would wrap the difference around the field modulus.
+    /// The bound is part of the circuit description.
+    ///
+    /// # Panics
+    ///
+    /// If `bound` is zero, doesn't fit `num_bits` bits, or `num_bits`
+    /// exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_range_bounded<A: Into<C::Range>>(
+        &mut self,
+        w: PrivateWire,
+        bound: A,
+        num_bits: usize,
+    ) {
+        let bound = bound.into();
+
+        assert!(bound != C::Range::zero(), "the bound must not be zero");
+        assert!(
+            num_bits <= Self::MAX_BOUNDED_BITS,
+            "bounded range checks support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+        assert!(
+            Self::bit_length(bound) <= num_bits,
+            "the bound doesn't fit {} bits",
+            num_bits
+        );
+
+        // d := bound - 1 - w
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .constant(bound - C::Range::one())
+            .a(w);
+        let d = self.gate_add(constraint);
+
+        self.component_range(w, num_bits);
+        self.component_range(d, num_bits);
+    }
+
+    /// Constrain `0 ≤ w < bound` with the least even number of bits that
+    /// fits the constant `bound`.
+    ///
+    /// See [`Self::component_range_bounded`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_range_bounded_auto<A: Into<C::Range>>(
+        &mut self,
+        w: PrivateWire,
+        bound: A,
+    ) {
+        let bound = bound.into();
+
+        // the range gates accumulate quads of bits
+        let num_bits = Self::bit_length(bound);
+        let num_bits = num_bits + num_bits % 2;
+
+        self.component_range_bounded(w, bound, num_bits.max(2))
+    }
+
+    /// Constrain `lo ≤ w < hi` for constants `lo` and `hi`.
+    ///
+    /// Both `w - lo` and `hi - 1 - w` are range checked to `num_bits` bits.
+    /// Their sum is the constant `hi - 1 - lo`, which must fit `num_bits`
+    /// bits too, so for any `w` out of the range one of the differences
+    /// wraps around the field modulus, far above `2^num_bits`. The bounds
+    /// are part of the circuit description.
+    ///
+    /// Consume `2` gates and the gates of two [`Self::component_range`] of
+    /// `num_bits` bits
+    ///
+    /// # Panics
+    ///
+    /// If `lo ≥ hi` as unsigned integers, `hi - 1 - lo` doesn't fit
+    /// `num_bits` bits, or `num_bits` exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_range_between<A: Into<C::Range>>(
+        &mut self,
+        w: PrivateWire,
+        lo: A,
+        hi: A,
+        num_bits: usize,
+    ) {
+        let lo = Self::canonical(lo.into());
+        let hi = Self::canonical(hi.into());
+
+        // little endian, so compared from the most significant byte
+        let ordered = lo
+            .to_raw_bytes()
+            .iter()
+            .rev()
+            .cmp(hi.to_raw_bytes().iter().rev())
+            == cmp::Ordering::Less;
+
+        assert!(ordered, "the lower bound must be below the upper bound");
+        assert!(
+            num_bits <= Self::MAX_BOUNDED_BITS,
+            "bounded range checks support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+        assert!(
+            Self::bit_length(hi - C::Range::one() - lo) <= num_bits,
+            "the range doesn't fit {} bits",
+            num_bits
+        );
+
+        // w - lo
+        let constraint = Constraint::default().left(1).constant(-lo).a(w);
+        let above = self.gate_add(constraint);
+
+        // hi - 1 - w
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .constant(hi - C::Range::one())
+            .a(w);
+        let below = self.gate_add(constraint);
+
+        self.component_range(above, num_bits);
+        self.component_range(below, num_bits);
+    }
+
+    /// Range check `w` to `num_bits` bits, falling back to a bit
+    /// decomposition for an odd or zero `num_bits` since the quad range gates
+    /// can't check it
+    pub(crate) fn range_any(
+        &mut self,
+        w: PrivateWire,
+        num_bits: usize,
+        gadget: &str,
+    ) {
+        if num_bits > 0 && num_bits % 2 == 0 {
+            self.component_range(w, num_bits);
+        } else {
+            self.assert_reserved_zero_one(gadget);
+            self.decompose(w, num_bits);
+        }
+    }
+
+    /// Evaluate `a + b` over unsigned integers of 64 bits, returning the sum
+    /// modulo `2^64` and the carry bit.
+    ///
+    /// The constraint `a + b = sum + 2^64 · carry` holds with the carry
+    /// constrained to be boolean and the sum range checked to 64 bits, so the
+    /// carry is set exactly when the addition overflows. `a` and `b` are
+    /// expected to be constrained to 64 bits, e.g. by
+    /// [`Self::component_range`], otherwise the sum doesn't fit 65 bits and
+    /// the constraints can't be satisfied.
+    ///
+    /// Consume `12` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_add_u64(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> (PrivateWire, PrivateWire) {
+        let pow = C::Range::pow_of_2(64);
+        let total = Self::canonical(self[a] + self[b]);
+        let carry = total.to_bits().iter().rev().nth(64).copied();
+        let carry = C::Range::from(carry.unwrap_or(false) as u64);
+
+        let sum = self.append_witness(total - carry * pow);
+        let carry = self.append_witness(carry);
+
+        self.component_boolean(carry);
+
+        // a + b - sum - 2^64 · carry = 0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(1)
+            .output(-C::Range::one())
+            .fourth(-pow)
+            .a(a)
+            .b(b)
+            .o(sum)
+            .d(carry);
+        self.append_gate(constraint);
+
+        self.component_range(sum, 64);
+
+        (sum, carry)
+    }
+
+    /// Evaluate the low `num_bits` bits of the canonical representation of
+    /// `a`, discarding the high part.
+    ///
+    /// The low part `l` and the high part `h` are witnessed and constrained
+    /// to `a = l + 2^num_bits · h`, with `l` range checked to `num_bits` bits.
+    /// The split is only unique if `l + 2^num_bits · h` is below the field
+    /// modulus `p`, otherwise the split of `a + p` also satisfies the
+    /// equation. With `(H, L)` the split of `p - 1`, it is enforced by:
+    ///
+    /// - `H - h` range checked to the bits of `H`, so `h ≤ H`;
+    /// - `e · (L - l)` range checked to `num_bits` bits, where `e` is set if `h
+    ///   = H`, so `l ≤ L` when the high part is the largest one.
+    ///
+    /// Consume `5` gates plus the range checks of `num_bits` bits, twice, and
+    /// of the bits of `H`, twice, see [`Self::component_range`], or
+    /// `2 · bits + 1` gates for every odd width
+    ///
+    /// # Panics
+    ///
+    /// If `num_bits` is zero or isn't below the bits of the field modulus.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_truncate(
+        &mut self,
+        a: PrivateWire,
+        num_bits: usize,
+    ) -> PrivateWire {
+        let modulus_bits = Self::bit_length(-C::Range::one());
+        assert!(
+            0 < num_bits && num_bits < modulus_bits,
+            "truncations support up to {} bits",
+            modulus_bits - 1
+        );
+
+        let (max_low, max_high) =
+            Self::split_at_bit(-C::Range::one(), num_bits);
+        let high_bits = modulus_bits - num_bits;
+
+        let (low, high) = Self::split_at_bit(self[a], num_bits);
+        let low = self.append_witness(low);
+        let high = self.append_witness(high);
+
+        // a - l - 2^n · h = 0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(-C::Range::one())
+            .fourth(-C::Range::pow_of_2(num_bits as u64))
+            .a(a)
+            .b(low)
+            .d(high);
+        self.append_gate(constraint);
+
+        self.range_any(low, num_bits, "component_truncate");
+        self.range_any(high, high_bits, "component_truncate");
+
+        // H - h
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .constant(max_high)
+            .a(high);
+        let slack = self.gate_add(constraint);
+        self.range_any(slack, high_bits, "component_truncate");
+
+        // e · (L - l), with e set if h = H
+        let (_, is_max) = self.component_inverse_or_zero(slack);
+        let constraint = Constraint::default()
+            .mult(-C::Range::one())
+            .left(max_low)
+            .a(is_max)
+            .b(low);
+        let slack = self.gate_mul(constraint);
+        self.range_any(slack, num_bits, "component_truncate");
+
+        low
+    }
+
+    /// Evaluate the least significant bit of the canonical representation of
+    /// `a`.
+    ///
+    /// The bit is the truncation of `a` to a single bit, see
+    /// [`Self::component_truncate`], so the halved value is bounded by the
+    /// one of `p - 1` and `a + p` can't pass for a value of the other
+    /// parity. The bit is constrained to be boolean, so it can select
+    /// directly with [`Self::component_select`].
+    ///
+    /// Consume the gates of [`Self::component_truncate`] for a single bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_parity(&mut self, a: PrivateWire) -> PrivateWire {
+        self.component_truncate(a, 1)
+    }
+
+    /// Evaluate the least significant bit of `a` for a value of `num_bits`
+    /// bits.
+    ///
+    /// The bit `b` and the halved value `h` are witnessed, with `b`
+    /// constrained to be boolean, `h` range checked to `num_bits - 1` bits,
+    /// and `a = 2h + b`. Since `num_bits` can't exceed
+    /// [`Self::MAX_BOUNDED_BITS`], `2h + b` can't wrap around the modulus,
+    /// and the check also constrains `a` to `num_bits` bits.
+    ///
+    /// Consume `2` gates plus the range check of `num_bits - 1` bits, see
+    /// [`Self::component_range`], or `2 · (num_bits - 1) + 1` gates for an
+    /// even `num_bits`
+    ///
+    /// # Panics
+    ///
+    /// If `num_bits` is zero or exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_parity_bounded(
+        &mut self,
+        a: PrivateWire,
+        num_bits: usize,
+    ) -> PrivateWire {
+        assert!(
+            0 < num_bits && num_bits <= Self::MAX_BOUNDED_BITS,
+            "parities support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        let (bit, half) = Self::split_at_bit(self[a], 1);
+        let bit = self.append_witness(bit);
+        let half = self.append_witness(half);
+
+        self.component_boolean(bit);
+
+        // a - 2h - b = 0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(-C::Range::from(2u64))
+            .fourth(-C::Range::one())
+            .a(a)
+            .b(half)
+            .d(bit);
+        self.append_gate(constraint);
+
+        self.range_any(half, num_bits - 1, "component_parity_bounded");
+
+        bit
+    }
+
+    /// Evaluate the magnitude of `a` in the signed encoding of the field,
+    /// where the values above `(p - 1) / 2` are the negative ones.
+    ///
+    /// The sign bit `s` is witnessed and constrained to be boolean, and the
+    /// magnitude `m` to satisfy `a = m · (1 - 2s)` and fit `num_bits` bits.
+    /// Since `num_bits` can't exceed [`Self::MAX_BOUNDED_BITS`], only one sign
+    /// gives a magnitude in range, so a wrong sign bit doesn't satisfy the
+    /// constraints. Zero has the magnitude zero for either sign.
+    ///
+    /// The magnitude of `(p - 1) / 2`, the largest positive value, and of its
+    /// negation doesn't fit any supported `num_bits`, so they can't be proven.
+    ///
+    /// Consume `2` gates plus the range check of `num_bits`, see
+    /// [`Self::component_range`], or `2 · num_bits + 1` gates for an odd
+    /// `num_bits`
+    ///
+    /// # Panics
+    ///
+    /// If `num_bits` exceeds [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_abs(
+        &mut self,
+        a: PrivateWire,
+        num_bits: usize,
+    ) -> PrivateWire {
+        assert!(
+            num_bits <= Self::MAX_BOUNDED_BITS,
+            "magnitudes support up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        let value = Self::canonical(self[a]);
+        let half = -C::Range::one()
+            * C::Range::from(2u64)
+                .invert()
+                .expect("the field has an odd order");
+
+        // little endian, so compared from the most significant byte
+        let negative = value
+            .to_raw_bytes()
+            .iter()
+            .rev()
+            .cmp(half.to_raw_bytes().iter().rev())
+            == cmp::Ordering::Greater;
+
+        let (sign, magnitude) = match negative {
+            true => (C::Range::one(), -value),
+            false => (C::Range::zero(), value),
+        };
+
+        let sign = self.append_witness(sign);
+        let magnitude = self.append_witness(magnitude);
+
+        self.component_boolean(sign);
+
+        // 2 · s · m - m + a = 0
+        let constraint = Constraint::default()
+            .mult(2)
+            .right(-C::Range::one())
+            .fourth(1)
+            .a(sign)
+            .b(magnitude)
+            .d(a);
+        self.append_gate(constraint);
+
+        self.range_any(magnitude, num_bits, "component_abs");
+
+        magnitude
+    }
+
+    /// Split the canonical representation of `scalar` into its low
+    /// `num_bits` bits and the remaining high part
+    pub(crate) fn split_at_bit(
+        scalar: C::Range,
+        num_bits: usize,
+    ) -> (C::Range, C::Range) {
+        Self::canonical(scalar)
+            .to_bits()
+            .iter()
+            .rev()
+            .enumerate()
+            .filter(|(_, bit)| **bit as u8 == 1)
+            .fold(
+                (C::Range::zero(), C::Range::zero()),
+                |(l, h), (i, _)| match i < num_bits {
+                    true => (l + C::Range::pow_of_2(i as u64), h),
+                    false => (l, h + C::Range::pow_of_2((i - num_bits) as u64)),
+                },
+            )
+    }
+
+    /// Number of bits of the canonical representation of `scalar`
+    fn bit_length(scalar: C::Range) -> usize {
+        let bits = Self::canonical(scalar).to_bits();
+
+        bits.iter()
+            .position(|b| *b as u8 == 1)
+            .map(|p| bits.len() - p)
+            .unwrap_or(0)
+    }
+}
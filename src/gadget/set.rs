@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Set membership gadgets of the composer.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine};
+
+use crate::Plonk;
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Assert `value` is one of the constants of `set`, with the constraint
+    /// `∏ (value - s_i) = 0`.
+    ///
+    /// The running product is carried from a gate to the next, and the gate
+    /// of the last element asserts it is zero instead of evaluating it. The
+    /// set is part of the circuit description, not of the witness.
+    ///
+    /// The empty product is `1`, so an empty set appends the unsatisfiable
+    /// constraint `1 = 0`: no value belongs to the empty set, and the circuit
+    /// can't be proven.
+    ///
+    /// Consume `K` gates for `K` elements, or `1` gate for an empty set
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_in_set(
+        &mut self,
+        value: PrivateWire,
+        set: &[C::Range],
+    ) {
+        let constraint = match set.split_last() {
+            Some((last, set)) => {
+                let product = self.set_product(value, set);
+
+                Self::set_factor(value, *last, product)
+            }
+            None => Constraint::default().constant(1),
+        };
+
+        self.append_gate(constraint);
+    }
+
+    /// Assert `value` is none of the constants of `set`.
+    ///
+    /// The product `∏ (value - s_i)` is evaluated with a gate per element,
+    /// and constrained to be non zero with its inverse, `product · inv = 1`.
+    /// The inverse is computed from the witness values; it is set to zero if
+    /// `value` belongs to the set, so the circuit can't be proven. An empty
+    /// set is vacuously satisfied and appends no gate.
+    ///
+    /// Consume `K + 1` gates for `K` elements
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_not_in_set(
+        &mut self,
+        value: PrivateWire,
+        set: &[C::Range],
+    ) {
+        if let Some(product) = self.set_product(value, set) {
+            let inv = self[product].invert().unwrap_or_else(C::Range::zero);
+            let inv = self.append_witness(inv);
+
+            // product · inv - 1 = 0
+            let constraint = Constraint::default()
+                .mult(1)
+                .constant(-C::Range::one())
+                .a(product)
+                .b(inv);
+            self.append_gate(constraint);
+        }
+    }
+
+    /// Evaluate `∏ (value - s_i)` over `set`, or `None` for an empty set.
+    ///
+    /// Consume `K` gates for `K` elements
+    fn set_product(
+        &mut self,
+        value: PrivateWire,
+        set: &[C::Range],
+    ) -> Option<PrivateWire> {
+        set.iter().fold(None, |product, s| {
+            let constraint = Self::set_factor(value, *s, product);
+
+            Some(self.gate_mul(constraint))
+        })
+    }
+
+    /// Constraint of `(value - s) · product`, or of `value - s` for the first
+    /// factor of a product
+    fn set_factor(
+        value: PrivateWire,
+        s: C::Range,
+        product: Option<PrivateWire>,
+    ) -> Constraint<C::Range> {
+        match product {
+            // value · product - s · product
+            Some(product) => {
+                Constraint::default().mult(1).right(-s).a(value).b(product)
+            }
+            None => Constraint::default().left(1).constant(-s).a(value),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Running hash absorbing wires and squeezing challenges, for Fiat-Shamir
+//! transcripts inside a circuit.
+//!
+//! The state is a single scalar chained through a [`CircuitHasher`] `H`:
+//!
+//! - the state starts at the [`public_digest`](crate::public_digest) of the
+//!   label, so sponges with different labels are domain separated;
+//! - absorbing `n` values updates the state with `H(state, value)` for each of
+//!   them in order, then pads the call with `H(state, n)`;
+//! - squeezing updates the state with `H(state, -1)` and returns it.
+//!
+//! Reading the inputs of `H` backwards, the last one is either `-1` or the
+//! length of the last absorbed chunk, so different sequences of calls hash
+//! different inputs: `absorb([a, b])` differs from `absorb([a])` followed by
+//! `absorb([b])`, and every squeeze returns a new challenge.
+//!
+//! [`Sponge`] and [`CircuitSponge`] follow the same rule, so the challenges
+//! of a circuit can be predicted natively.
+
+use core::marker::PhantomData;
+
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine};
+
+use super::hash::{CircuitHasher, Mimc};
+use crate::digest::digest_to_scalar;
+use crate::Plonk;
+
+/// Native twin of [`CircuitSponge`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sponge<F, H = Mimc> {
+    state: F,
+    _hasher: PhantomData<H>,
+}
+
+impl<F: PrimeField, H: CircuitHasher<F>> Sponge<F, H> {
+    /// Sponge domain separated by `label`
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            state: digest_to_scalar(label),
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Absorb `values`, padded with their number
+    pub fn absorb(&mut self, values: &[F]) {
+        values
+            .iter()
+            .for_each(|v| self.state = H::hash(self.state, *v));
+
+        self.state = H::hash(self.state, F::from(values.len() as u64));
+    }
+
+    /// Squeeze a challenge
+    pub fn squeeze(&mut self) -> F {
+        self.state = H::hash(self.state, -F::one());
+
+        self.state
+    }
+}
+
+/// Running hash over the wires of a composer.
+///
+/// Every call to [`CircuitHasher::gadget`] appends the gates of one
+/// compression, [`Mimc::GATES`] by default, and every padding constant one
+/// more gate.
+#[derive(Debug)]
+pub struct CircuitSponge<'a, C, H = Mimc>
+where
+    C: TwistedEdwardsAffine,
+{
+    composer: &'a mut Plonk<C>,
+    state: PrivateWire,
+    _hasher: PhantomData<H>,
+}
+
+impl<'a, C, H> CircuitSponge<'a, C, H>
+where
+    C: TwistedEdwardsAffine,
+    H: CircuitHasher<C::Range>,
+{
+    /// Sponge domain separated by `label`, appending its gates to
+    /// `composer`.
+    ///
+    /// The label is a constant of the circuit.
+    ///
+    /// Consume 1 gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn new(composer: &'a mut Plonk<C>, label: &[u8]) -> Self {
+        let state = composer.append_constant(digest_to_scalar(label));
+
+        Self {
+            composer,
+            state,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Composer the sponge appends its gates to, to keep building the
+    /// circuit between calls
+    pub fn composer(&mut self) -> &mut Plonk<C> {
+        self.composer
+    }
+
+    /// Absorb `wires`, padded with their number
+    ///
+    /// Consume `wires.len() + 1` compressions and 1 gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn absorb(&mut self, wires: &[PrivateWire]) {
+        for w in wires {
+            self.state = H::gadget(self.composer, self.state, *w);
+        }
+
+        let len = self.composer.append_constant(wires.len() as u64);
+        self.state = H::gadget(self.composer, self.state, len);
+    }
+
+    /// Squeeze a challenge
+    ///
+    /// Consume 1 compression and 1 gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn squeeze(&mut self) -> PrivateWire {
+        let tag = self.composer.append_constant(-C::Range::one());
+        self.state = H::gadget(self.composer, self.state, tag);
+
+        self.state
+    }
+}
@@ -0,0 +1,288 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Wires carrying the constraint they were checked against in their type.
+//!
+//! The gadgets that expect a boolean wire have typed variants taking a
+//! [`BoolWire`], which is only returned by the gadgets constraining it:
+//!
+//! ```
+//! use zkplonk::prelude::*;
+//! use zksnarks::constraint_system::ConstraintSystem;
+//!
+//! let mut composer = Plonk::<JubjubAffine>::initialize();
+//!
+//! let bit = composer.append_witness(BlsScalar::from(1u64));
+//! let bit = composer.component_boolean_typed(bit);
+//!
+//! let a = composer.append_witness(BlsScalar::from(2u64));
+//! let b = composer.append_witness(BlsScalar::from(3u64));
+//!
+//! let selected = composer.component_select_typed(bit, a, b);
+//! assert_eq!(composer[selected], BlsScalar::from(2u64));
+//! ```
+//!
+//! Passing an unconstrained [`PrivateWire`] instead doesn't compile:
+//!
+//! ```compile_fail
+//! use zkplonk::prelude::*;
+//! use zksnarks::constraint_system::ConstraintSystem;
+//!
+//! let mut composer = Plonk::<JubjubAffine>::initialize();
+//!
+//! let bit = composer.append_witness(BlsScalar::from(1u64));
+//! let a = composer.append_witness(BlsScalar::from(2u64));
+//! let b = composer.append_witness(BlsScalar::from(3u64));
+//!
+//! composer.component_select_typed(bit, a, b);
+//! ```
+
+use jub_jub::Fp as JubjubScalar;
+use zksnarks::error::Error;
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{PrimeField, Ring, TwistedEdwardsAffine, Vec};
+
+use super::WitnessPoint;
+use crate::Plonk;
+
+/// Wire constrained to be either `0` or `1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoolWire(PrivateWire);
+
+impl BoolWire {
+    /// Wrap a wire that is known to be boolean without constraining it.
+    ///
+    /// The caller is responsible for the wire being constrained elsewhere in
+    /// the circuit, as with the untyped gadgets.
+    pub const fn assume(w: PrivateWire) -> Self {
+        Self(w)
+    }
+
+    /// Underlying wire
+    pub const fn wire(&self) -> PrivateWire {
+        self.0
+    }
+}
+
+impl From<BoolWire> for PrivateWire {
+    fn from(w: BoolWire) -> Self {
+        w.0
+    }
+}
+
+/// Wire constrained to be in the range `[0, 2^N)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeWire<const N: usize>(PrivateWire);
+
+impl<const N: usize> RangeWire<N> {
+    /// Wrap a wire that is known to fit `N` bits without constraining it.
+    ///
+    /// The caller is responsible for the wire being constrained elsewhere in
+    /// the circuit.
+    pub const fn assume(w: PrivateWire) -> Self {
+        Self(w)
+    }
+
+    /// Underlying wire
+    pub const fn wire(&self) -> PrivateWire {
+        self.0
+    }
+}
+
+impl<const N: usize> From<RangeWire<N>> for PrivateWire {
+    fn from(w: RangeWire<N>) -> Self {
+        w.0
+    }
+}
+
+/// Wire constrained to be a canonical scalar of JubJub, below the modulus of
+/// its scalar field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JubJubScalarWire(PrivateWire);
+
+impl JubJubScalarWire {
+    /// Wrap a wire that is known to be a canonical JubJub scalar without
+    /// constraining it.
+    ///
+    /// The caller is responsible for the wire being constrained elsewhere in
+    /// the circuit.
+    pub const fn assume(w: PrivateWire) -> Self {
+        Self(w)
+    }
+
+    /// Underlying wire
+    pub const fn wire(&self) -> PrivateWire {
+        self.0
+    }
+}
+
+impl From<JubJubScalarWire> for PrivateWire {
+    fn from(w: JubJubScalarWire) -> Self {
+        w.0
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Constrain `w` to be boolean, as [`Self::component_boolean`], and
+    /// return it as a [`BoolWire`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_boolean_typed(&mut self, w: PrivateWire) -> BoolWire {
+        self.component_boolean(w);
+
+        BoolWire(w)
+    }
+
+    /// Decompose `scalar` into `N` bits, as
+    /// [`Self::component_decomposition`], which constrains them to be boolean
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_decomposition_typed<const N: usize>(
+        &mut self,
+        scalar: PrivateWire,
+    ) -> [BoolWire; N] {
+        self.component_decomposition::<N>(scalar).map(BoolWire)
+    }
+
+    /// Constrain `w` to fit `N` bits, as [`Self::component_range`], and
+    /// return it as a [`RangeWire`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_range_typed<const N: usize>(
+        &mut self,
+        w: PrivateWire,
+    ) -> RangeWire<N> {
+        self.component_range(w, N);
+
+        RangeWire(w)
+    }
+
+    /// [`Self::component_select`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_typed(
+        &mut self,
+        bit: BoolWire,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        self.component_select(bit.0, a, b)
+    }
+
+    /// [`Self::component_select_one`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_one_typed(
+        &mut self,
+        bit: BoolWire,
+        value: PrivateWire,
+    ) -> PrivateWire {
+        self.component_select_one(bit.0, value)
+    }
+
+    /// [`Self::component_select_zero`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_zero_typed(
+        &mut self,
+        bit: BoolWire,
+        value: PrivateWire,
+    ) -> PrivateWire {
+        self.component_select_zero(bit.0, value)
+    }
+
+    /// [`Self::component_select_point`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_point_typed(
+        &mut self,
+        bit: BoolWire,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> WitnessPoint {
+        self.component_select_point(bit.0, a, b)
+    }
+
+    /// [`Self::component_select_identity`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_identity_typed(
+        &mut self,
+        bit: BoolWire,
+        a: WitnessPoint,
+    ) -> WitnessPoint {
+        self.component_select_identity(bit.0, a)
+    }
+
+    /// [`Self::component_swap`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_swap_typed(
+        &mut self,
+        bit: BoolWire,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> (PrivateWire, PrivateWire) {
+        self.component_swap(bit.0, a, b, false)
+    }
+
+    /// [`Self::component_swap_point`] with a constrained bit
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_swap_point_typed(
+        &mut self,
+        bit: BoolWire,
+        a: WitnessPoint,
+        b: WitnessPoint,
+    ) -> (WitnessPoint, WitnessPoint) {
+        self.component_swap_point(bit.0, a, b, false)
+    }
+
+    /// Constrain `w` to be a canonical JubJub scalar and return it as a
+    /// [`JubJubScalarWire`].
+    ///
+    /// `w` is decomposed into [`Self::JUBJUB_SCALAR_BITS`] bits, and the bits
+    /// are compared to the ones of the modulus minus one from the most
+    /// significant bit, so a value at or above the modulus can't be proven.
+    ///
+    /// Consume `3 · 252 + 1` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_jubjub_scalar(
+        &mut self,
+        w: PrivateWire,
+    ) -> JubJubScalarWire {
+        self.assert_reserved_zero_one("component_assert_jubjub_scalar");
+
+        let bits = self.decompose(w, Self::JUBJUB_SCALAR_BITS);
+        let max: Vec<bool> = (-JubjubScalar::one())
+            .to_bits()
+            .iter()
+            .rev()
+            .copied()
+            .collect();
+
+        self.assert_bits_at_most(&bits, &max);
+
+        JubJubScalarWire(w)
+    }
+
+    /// [`Self::component_mul_generator`] with a canonical scalar.
+    ///
+    /// The top digits of the wNAF are constrained to be zero, so the
+    /// multiplication is by the integer of the scalar wire.
+    ///
+    /// Consume `257` gates
+    ///
+    /// Will error if the wNAF of the scalar is invalid
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_generator_typed<A: Into<C::Extended>>(
+        &mut self,
+        jubjub: JubJubScalarWire,
+        generator: A,
+    ) -> Result<WitnessPoint, Error> {
+        self.mul_generator(jubjub.0, generator, true)
+    }
+
+    /// [`Self::component_mul_point`] with a canonical scalar
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_mul_point_typed(
+        &mut self,
+        jubjub: JubJubScalarWire,
+        point: WitnessPoint,
+    ) -> WitnessPoint {
+        self.component_mul_point(jubjub.0, point)
+    }
+}
@@ -0,0 +1,255 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! 32-bit words, for circuits of the SHA-2 family.
+//!
+//! A [`Word32`] is a wire constrained to fit 32 bits, and every operation
+//! returns a word constrained the same way:
+//!
+//! - [`Word32::add_many`] adds words modulo `2^32`, splitting the carry off and
+//!   range checking it;
+//! - [`Word32::xor`] and [`Word32::and`] use the logic gates;
+//! - [`Word32::not`] is a single gate;
+//! - [`Word32::rotr`] and [`Word32::shr`] recompose the bits of the word.
+//!
+//! The bits are decomposed the first time a rotation or a shift needs them
+//! and kept in the word, so chaining several of them on the same word, as
+//! the `Σ` functions of SHA-256 do, pays for a single decomposition.
+
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::Constraint;
+use zkstd::common::{FftField, PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+/// Wire constrained to fit 32 bits
+#[derive(Debug, Clone, Copy)]
+pub struct Word32 {
+    wire: PrivateWire,
+    bits: Option<[PrivateWire; 32]>,
+}
+
+impl Word32 {
+    /// Word of a wire already constrained to fit 32 bits.
+    ///
+    /// The constraint isn't checked: an unconstrained wire makes every
+    /// operation on the word unsound.
+    pub const fn assume(wire: PrivateWire) -> Self {
+        Self { wire, bits: None }
+    }
+
+    /// Inner wire
+    pub const fn wire(&self) -> PrivateWire {
+        self.wire
+    }
+
+    /// Append a witness of `value`, range checked to 32 bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn witness<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        value: u32,
+    ) -> Self {
+        let wire = composer.append_witness(C::Range::from(value as u64));
+
+        Self::constrain(composer, wire)
+    }
+
+    /// Append the constant `value`
+    pub fn constant<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        value: u32,
+    ) -> Self {
+        let wire = composer.append_constant(C::Range::from(value as u64));
+
+        Self::assume(wire)
+    }
+
+    /// Range check `wire` to 32 bits
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn constrain<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        wire: PrivateWire,
+    ) -> Self {
+        composer.component_range(wire, 32);
+
+        Self::assume(wire)
+    }
+
+    /// Value of the word in the composer
+    pub fn value<C: TwistedEdwardsAffine>(&self, composer: &Plonk<C>) -> u32 {
+        let bytes = Plonk::<C>::canonical(composer[self.wire]).to_raw_bytes();
+
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Evaluate `Σ words mod 2^32`.
+    ///
+    /// The sum is split into its low 32 bits and a carry of `⌈log2 n⌉` bits
+    /// for `n` words, both range checked. The words are summed with
+    /// [`Plonk::gate_sum`] together with the carry, so the low part is the
+    /// output of the sum and doesn't take a gate of its own.
+    ///
+    /// Consume the gates of [`Plonk::gate_sum`] over `n + 1` terms, plus the
+    /// range checks of the low part and of the carry
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn add_many<C: TwistedEdwardsAffine>(
+        composer: &mut Plonk<C>,
+        words: &[Word32],
+    ) -> Self {
+        let sum = words.iter().map(|w| w.value(composer) as u64).sum::<u64>();
+
+        let carry = composer.append_witness(C::Range::from(sum >> 32));
+        let carry_bits = (usize::BITS
+            - words.len().saturating_sub(1).leading_zeros())
+            as usize;
+
+        let mut terms = words
+            .iter()
+            .map(|w| (C::Range::one(), w.wire))
+            .collect::<Vec<_>>();
+        terms.push((-C::Range::pow_of_2(32), carry));
+
+        let lo = composer.gate_sum(&terms, C::Range::zero());
+
+        composer.range_any(carry, carry_bits, "Word32::add_many");
+
+        Self::constrain(composer, lo)
+    }
+
+    /// Evaluate `self ^ other`.
+    ///
+    /// Consume `19` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn xor<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Word32,
+    ) -> Self {
+        self.logic(composer, other, true)
+    }
+
+    /// Evaluate `self & other`.
+    ///
+    /// Consume `19` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn and<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Word32,
+    ) -> Self {
+        self.logic(composer, other, false)
+    }
+
+    /// Evaluate `!self`, that is `2^32 - 1 - self`.
+    ///
+    /// Consume `1` gate
+    pub fn not<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+    ) -> Self {
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .constant(C::Range::from(u32::MAX as u64))
+            .a(self.wire);
+
+        Self::assume(composer.gate_add(constraint))
+    }
+
+    /// Evaluate `self.rotate_right(k)`.
+    ///
+    /// Consume `16` gates, plus `65` to decompose the word if its bits aren't
+    /// known yet, or none if `k` is a multiple of `32`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn rotr<C: TwistedEdwardsAffine>(
+        &mut self,
+        composer: &mut Plonk<C>,
+        k: usize,
+    ) -> Self {
+        let k = k % 32;
+        if k == 0 {
+            return *self;
+        }
+
+        let bits = self.bits(composer);
+        let terms = bits
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| {
+                (C::Range::pow_of_2(((i + 32 - k) % 32) as u64), *bit)
+            })
+            .collect::<Vec<_>>();
+
+        Self::assume(composer.gate_sum(&terms, C::Range::zero()))
+    }
+
+    /// Evaluate `self >> k`.
+    ///
+    /// Consume `⌈(31 - k) / 2⌉` gates, at least one, plus `65` to decompose
+    /// the word if its bits aren't known yet, or none if `k` is at least `32`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn shr<C: TwistedEdwardsAffine>(
+        &mut self,
+        composer: &mut Plonk<C>,
+        k: usize,
+    ) -> Self {
+        if k >= 32 {
+            return Self::assume(Plonk::<C>::ZERO);
+        }
+
+        let bits = self.bits(composer);
+        let terms = bits[k..]
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| (C::Range::pow_of_2(i as u64), *bit))
+            .collect::<Vec<_>>();
+
+        Self::assume(composer.gate_sum(&terms, C::Range::zero()))
+    }
+
+    /// Bits of the word, least significant first, decomposed on the first
+    /// call
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn bits<C: TwistedEdwardsAffine>(
+        &mut self,
+        composer: &mut Plonk<C>,
+    ) -> [PrivateWire; 32] {
+        if let Some(bits) = self.bits {
+            return bits;
+        }
+
+        composer.assert_reserved_zero_one("Word32::bits");
+
+        let mut bits = [Plonk::<C>::ZERO; 32];
+        bits.copy_from_slice(&composer.decompose(self.wire, 32));
+        self.bits = Some(bits);
+
+        bits
+    }
+
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn logic<C: TwistedEdwardsAffine>(
+        &self,
+        composer: &mut Plonk<C>,
+        other: &Word32,
+        is_xor: bool,
+    ) -> Self {
+        let (a, b, d) =
+            composer.append_logic_component(self.wire, other.wire, 32, is_xor);
+
+        // the accumulators only hold the low 32 bits of the inputs, which
+        // are the whole words
+        composer.assert_equal(a, self.wire);
+        composer.assert_equal(b, other.wire);
+
+        Self::assume(d)
+    }
+}
+
+impl From<Word32> for PrivateWire {
+    fn from(w: Word32) -> Self {
+        w.wire
+    }
+}
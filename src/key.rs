@@ -4,11 +4,21 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+pub(crate) mod degree;
+
 use core::marker::PhantomData;
 
-use super::{Plonk, Prover, Verifier};
+use degree::QuotientParts;
+
+use super::{
+    CircuitStats, CustomGate, InitConfig, Plonk, Prover, SizeHint, Verifier,
+};
+use crate::custom_gate::{CustomProvingKey, CustomVerificationKey};
+use crate::lookup::{LookupProvingKey, LookupVerificationKey};
+use crate::prover::CompiledShape;
 
 use poly_commit::{Coefficients as Coeffs, Fft, PointsValue as Points};
+use rand_core::RngCore;
 use sp_std::vec;
 use zksnarks::plonk::keypair::{
     arithmetic,
@@ -16,10 +26,12 @@ use zksnarks::plonk::keypair::{
     logic, permutation, range, ProvingKey, VerificationKey,
 };
 use zksnarks::{
-    circuit::Circuit, constraint_system::ConstraintSystem, error::Error,
-    keypair::Keypair, plonk::PlonkParams,
+    circuit::Circuit, error::Error, keypair::Keypair, plonk::PlonkParams,
+    public_params::PublicParameters,
+};
+use zkstd::common::{
+    FftField, Group, Pairing, Ring, TwistedEdwardsAffine, Vec,
 };
-use zkstd::common::{Group, Pairing, Ring, TwistedEdwardsAffine};
 
 /// Generate the arguments to prove and verify a circuit
 pub struct PlonkKey<
@@ -32,6 +44,161 @@ pub struct PlonkKey<
     a: PhantomData<A>,
 }
 
+/// Bound on the scratch memory of the compilation.
+///
+/// Every selector and sigma polynomial is evaluated over a coset of size
+/// `8n`. Evaluated at once, this needs a scratch copy of the polynomial
+/// padded to `8n`; if that copy exceeds `max_scratch_bytes`, the coset is
+/// evaluated in chunks of size `n` instead, which is slower but keeps the
+/// scratch to a single chunk. The keys are the same either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompileBudget {
+    /// Largest scratch buffer of a single coset evaluation, in bytes
+    pub max_scratch_bytes: usize,
+}
+
+impl Default for CompileBudget {
+    fn default() -> Self {
+        Self {
+            max_scratch_bytes: usize::MAX,
+        }
+    }
+}
+
+/// Error returned when a circuit is larger than the params can commit to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitTooLarge {
+    /// Gates of the synthesized circuit, or rows of its lookup tables if
+    /// there are more
+    pub gates: usize,
+    /// Size of the committing key the circuit needs
+    pub padded: usize,
+    /// Maximum degree the params can commit to
+    pub params_max: usize,
+    /// `k` to set up the params with, see [`PlonkKey::setup_for_circuit`]
+    pub suggested_k: u64,
+}
+
+impl core::fmt::Display for CircuitTooLarge {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the circuit has {} gates and needs params of size {}, but the \
+             params support {}; set them up with k = {}",
+            self.gates, self.padded, self.params_max, self.suggested_k
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CircuitTooLarge {}
+
+/// Error returned by [`PlonkKey::compile_with_quotient_parts`]
+#[derive(Debug)]
+pub enum CompileError {
+    /// Synthesizing the circuit or computing its keys failed
+    Plonk(Error),
+    /// The quotient of the circuit doesn't fit the parts of the proof
+    QuotientDegreeTooHigh {
+        /// Degree of the quotient with the default blinding
+        degree: usize,
+        /// Maximum degree the parts fit
+        max: usize,
+        /// Parts the quotient is split into
+        parts: QuotientParts,
+    },
+}
+
+impl core::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Plonk(e) => write!(f, "plonk error: {:?}", e),
+            Self::QuotientDegreeTooHigh { degree, max, parts } => write!(
+                f,
+                "quotient degree {} exceeds the maximum {} of {} parts",
+                degree,
+                max,
+                parts.count()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompileError {}
+
+impl From<Error> for CompileError {
+    fn from(e: Error) -> Self {
+        Self::Plonk(e)
+    }
+}
+
+/// The compilation entry points returning [`Error`] report a quotient too
+/// high as [`Error::PolynomialDegreeTooLarge`]
+impl From<CompileError> for Error {
+    fn from(e: CompileError) -> Self {
+        match e {
+            CompileError::Plonk(e) => e,
+            CompileError::QuotientDegreeTooHigh { .. } => {
+                Error::PolynomialDegreeTooLarge
+            }
+        }
+    }
+}
+
+impl CompileBudget {
+    /// Whether the evaluation over a coset of `size` points is chunked
+    fn is_chunked<F>(&self, size: usize) -> bool {
+        size.saturating_mul(core::mem::size_of::<F>()) > self.max_scratch_bytes
+    }
+}
+
+/// Evaluations of `poly` over the coset of `fft_8n`, given its points
+/// `coset`.
+///
+/// If chunked, the coset is split into the cosets of the domain of `fft`
+/// it is made of: point `8j + i` is `coset[i] · ω^j`, so the evaluations of
+/// the `i`-th chunk are the DFT of `poly` scaled by the powers of
+/// `coset[i]`.
+fn coset_evaluations<F: FftField>(
+    fft: &Fft<F>,
+    fft_8n: &Fft<F>,
+    coset: &Points<F>,
+    poly: &Coeffs<F>,
+    budget: &CompileBudget,
+) -> Points<F> {
+    let n = fft.size();
+    let size = coset.0.len();
+
+    if !budget.is_chunked::<F>(size) || poly.0.len() > n {
+        return fft_8n.coset_dft(poly.clone());
+    }
+
+    let chunks = size / n;
+    let mut evaluations = vec![F::zero(); size];
+
+    for (i, shift) in coset.0[..chunks].iter().enumerate() {
+        let mut power = F::one();
+        let mut scaled: Vec<F> = poly
+            .0
+            .iter()
+            .map(|c| {
+                let scaled = *c * power;
+                power *= *shift;
+                scaled
+            })
+            .collect();
+        scaled.resize(n, F::zero());
+
+        let chunk = fft.dft(Coeffs::new(scaled));
+        for (j, eval) in chunk.0.into_iter().enumerate() {
+            evaluations[j * chunks + i] = eval;
+        }
+    }
+
+    Points::new(evaluations)
+}
+
 impl<
         P: Pairing,
         A: TwistedEdwardsAffine<Range = P::ScalarField>,
@@ -56,6 +223,67 @@ impl<
         C: Circuit<A, ConstraintSystem = Plonk<A>>,
     > PlonkKey<P, A, C>
 {
+    /// Size of the committing key needed by a circuit of `m` gates
+    fn committed_size(m: usize) -> usize {
+        (m + 6).next_power_of_two()
+    }
+
+    /// Smallest `k` such that [`PlonkParams`] set up with `k` can compile
+    /// and prove `circuit`, with the default [`InitConfig`]
+    pub fn required_domain_log2(circuit: &C) -> Result<u64, Error> {
+        let mut cs = Plonk::with_config(InitConfig::default());
+
+        circuit.synthesize(&mut cs)?;
+
+        Ok(Self::committed_size(cs.domain_rows()).trailing_zeros() as u64)
+    }
+
+    /// Number of gates and witnesses of `circuit` synthesized with the
+    /// default [`InitConfig`], to reserve through [`InitConfig::size_hint`]
+    pub fn size_hint(circuit: &C) -> Result<SizeHint, Error> {
+        let mut cs = Plonk::with_config(InitConfig::default());
+
+        circuit.synthesize(&mut cs)?;
+
+        Ok(cs.size())
+    }
+
+    /// Set up params of exactly the size [`Self::required_domain_log2`]
+    /// returns for `circuit`
+    pub fn setup_for_circuit<R: RngCore>(
+        circuit: &C,
+        rng: &mut R,
+    ) -> Result<PlonkParams<P>, Error> {
+        let k = Self::required_domain_log2(circuit)?;
+
+        Ok(PlonkParams::<P>::setup(k, rng))
+    }
+
+    /// Check that the params can commit to the polynomials of a synthesized
+    /// composer.
+    ///
+    /// The compilation runs this check right after the synthesis and
+    /// returns [`Error::PolynomialDegreeTooLarge`] on failure; this method
+    /// reports the sizes and the `k` to set up the params with instead.
+    pub fn check_size(
+        keypair: &PlonkParams<P>,
+        composer: &Plonk<A>,
+    ) -> Result<(), CircuitTooLarge> {
+        let gates = composer.domain_rows();
+        let padded = Self::committed_size(gates);
+        let params_max = keypair.max_degree();
+
+        match padded <= params_max {
+            true => Ok(()),
+            false => Err(CircuitTooLarge {
+                gates,
+                padded,
+                params_max,
+                suggested_k: padded.trailing_zeros() as u64,
+            }),
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     /// Create a new arguments set from a given circuit instance
     ///
@@ -71,14 +299,274 @@ impl<
         ),
         Error,
     > {
-        let mut cs = Plonk::initialize();
+        Self::compile_with_config(
+            keypair,
+            label,
+            circuit,
+            InitConfig::default(),
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Create a new arguments set from a given circuit instance
+    ///
+    /// The composer of both the compiler and the prover is created with
+    /// [`Plonk::with_config`]
+    pub fn compile_with_config(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        config: InitConfig,
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        Error,
+    > {
+        Self::compile_with_stats(keypair, label, circuit, config, |_| ())
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Create a new arguments set from a given circuit instance
+    ///
+    /// `on_stats` is called with the [`CircuitStats`] of the synthesized
+    /// circuit before its keys are computed
+    pub fn compile_with_stats<F>(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        config: InitConfig,
+        on_stats: F,
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        Error,
+    >
+    where
+        F: FnOnce(&CircuitStats),
+    {
+        Ok(Self::compile_inner(
+            keypair,
+            label,
+            circuit,
+            &[],
+            config,
+            CompileBudget::default(),
+            QuotientParts::default(),
+            false,
+            on_stats,
+        )?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Create a new arguments set from a given circuit instance, keeping the
+    /// scratch memory of the coset evaluations within `budget`
+    ///
+    /// The keys are identical to the ones of [`Self::compile_with_config`]
+    pub fn compile_with_budget(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        config: InitConfig,
+        budget: CompileBudget,
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        Error,
+    > {
+        Ok(Self::compile_inner(
+            keypair,
+            label,
+            circuit,
+            &[],
+            config,
+            budget,
+            QuotientParts::default(),
+            false,
+            |_| (),
+        )?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Create a new arguments set from a given circuit instance, with its
+    /// gates reordered by [`Plonk::reorder_for_locality`]
+    ///
+    /// The prover reorders the composers it synthesizes the same way. The
+    /// keys differ from the ones of [`Self::compile_with_config`], so the
+    /// proofs of one only verify against its own verifier.
+    pub fn compile_for_locality(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        config: InitConfig,
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        Error,
+    > {
+        Ok(Self::compile_inner(
+            keypair,
+            label,
+            circuit,
+            &[],
+            config,
+            CompileBudget::default(),
+            QuotientParts::default(),
+            true,
+            |_| (),
+        )?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Create a new arguments set from a given circuit instance, enforcing
+    /// the custom gates it appends with [`Plonk::append_registered_gate`]
+    ///
+    /// A circuit refers to a custom gate by its index in `gates`. The
+    /// commitments to their selectors are part of the circuit description,
+    /// and the transcript draws a separation challenge per gate after the
+    /// ones of the standard widgets.
+    ///
+    /// Will error with [`Error::PolynomialDegreeTooLarge`] if a gate
+    /// multiplies more than `4` wires, see
+    /// [`Self::compile_with_quotient_parts`] to split the quotient in more
+    /// parts.
+    ///
+    /// # Panics
+    ///
+    /// If the circuit appends a gate of an index out of `gates`.
+    pub fn compile_with_custom_gates(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        gates: &[&'static dyn CustomGate<P::ScalarField>],
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        Error,
+    > {
+        Ok(Self::compile_inner(
+            keypair,
+            label,
+            circuit,
+            gates,
+            InitConfig::default(),
+            CompileBudget::default(),
+            QuotientParts::default(),
+            false,
+            |_| (),
+        )?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Create a new arguments set from a given circuit instance, enforcing
+    /// its custom gates as [`Self::compile_with_custom_gates`], with the
+    /// quotient split into `parts`
+    ///
+    /// With [`QuotientParts::Six`] the proofs carry the commitments to two
+    /// more quotient parts, so a custom gate may multiply up to `5` wires.
+    /// The keys and proofs of [`QuotientParts::Four`] are the ones of
+    /// [`Self::compile_with_custom_gates`].
+    ///
+    /// Will error with [`CompileError::QuotientDegreeTooHigh`] if the
+    /// quotient doesn't fit the parts.
+    ///
+    /// # Panics
+    ///
+    /// If the circuit appends a gate of an index out of `gates`.
+    pub fn compile_with_quotient_parts(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        gates: &[&'static dyn CustomGate<P::ScalarField>],
+        parts: QuotientParts,
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        CompileError,
+    > {
+        Self::compile_inner(
+            keypair,
+            label,
+            circuit,
+            gates,
+            InitConfig::default(),
+            CompileBudget::default(),
+            parts,
+            false,
+            |_| (),
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn compile_inner<F>(
+        keypair: &PlonkParams<P>,
+        label: &[u8],
+        circuit: &C,
+        custom: &[&'static dyn CustomGate<P::ScalarField>],
+        config: InitConfig,
+        budget: CompileBudget,
+        parts: QuotientParts,
+        reorder: bool,
+        on_stats: F,
+    ) -> Result<
+        (
+            <Self as Keypair<P, A, C>>::Prover,
+            <Self as Keypair<P, A, C>>::Verifier,
+        ),
+        CompileError,
+    >
+    where
+        F: FnOnce(&CircuitStats),
+    {
+        let mut cs = Plonk::with_config(config);
 
         circuit.synthesize(&mut cs)?;
 
-        let m = cs.m();
+        if reorder {
+            cs.reorder_for_locality();
+        }
+
+        on_stats(&cs.statistics());
+
+        assert!(
+            cs.custom_gates.values().all(|gate| *gate < custom.len()),
+            "the circuit appends a custom gate that isn't registered"
+        );
+
+        // refuse circuits the params can't commit to before any FFT
+        Self::check_size(keypair, &cs)
+            .map_err(|_| Error::PolynomialDegreeTooLarge)?;
+
+        // the domain holds the rows of the lookup tables as well
+        let m = cs.domain_rows();
         let n = m.next_power_of_two();
+
+        // refuse circuits whose quotient won't fit the proof
+        let wire_degrees: Vec<usize> =
+            custom.iter().map(|gate| gate.wire_degree()).collect();
+        degree::check_quotient_degree(
+            n,
+            degree::active_families(&cs.constraints),
+            &wire_degrees,
+            !cs.lookup_tables.is_empty(),
+            parts,
+        )?;
+
         let k = n.trailing_zeros();
-        let additional_n = (m + 6).next_power_of_two();
+        // the committing key is trimmed to the padded circuit size so the
+        // prover MSMs never exceed it, regardless of the params size
+        let additional_n = Self::committed_size(m);
         let keypair = keypair.trim(additional_n);
         let fft = Fft::<P::ScalarField>::new(k as usize);
 
@@ -130,11 +618,6 @@ impl<
         let q_fixed_group_add_poly = fft.idft(q_fixed_group_add);
         let q_variable_group_add_poly = fft.idft(q_variable_group_add);
 
-        // 2. compute the sigma polynomials
-        let mut perm = cs.perm.clone();
-        let [s_sigma_1_poly, s_sigma_2_poly, s_sigma_3_poly, s_sigma_4_poly] =
-            perm.compute_sigma_polynomials(n, &fft);
-
         let q_m_poly_commit = keypair.commit(&q_m_poly).unwrap_or_default();
         let q_l_poly_commit = keypair.commit(&q_l_poly).unwrap_or_default();
         let q_r_poly_commit = keypair.commit(&q_r_poly).unwrap_or_default();
@@ -153,10 +636,35 @@ impl<
             .commit(&q_variable_group_add_poly)
             .unwrap_or_default();
 
-        let s_sigma_1_poly_commit = keypair.commit(&s_sigma_1_poly)?;
-        let s_sigma_2_poly_commit = keypair.commit(&s_sigma_2_poly)?;
-        let s_sigma_3_poly_commit = keypair.commit(&s_sigma_3_poly)?;
-        let s_sigma_4_poly_commit = keypair.commit(&s_sigma_4_poly)?;
+        // The polynomial needs an evaluation domain of 4n.
+        // Plus, adding the blinding factors translates to
+        // the polynomial not fitting in 4n, so now we need
+        // 8n, the next power of 2
+        let x8n = (8 * n).next_power_of_two();
+        let x8k = x8n.trailing_zeros();
+        let fft_8n = Fft::new(x8k as usize);
+        let min_p =
+            Coeffs::new(vec![P::ScalarField::zero(), P::ScalarField::one()]);
+        let linear_eval_8n = fft_8n.coset_dft(min_p);
+
+        let coset_dft = |poly: &Coeffs<P::ScalarField>| {
+            coset_evaluations(&fft, &fft_8n, &linear_eval_8n, poly, &budget)
+        };
+
+        // 2. compute the sigma polynomials, one column at a time so only the
+        // scratch data of a single column is alive next to the outputs
+        let sigma = |column| {
+            let poly = cs.perm.compute_sigma_polynomial(n, column, &fft);
+            let commit = keypair.commit(&poly)?;
+            let eval_8n = coset_dft(&poly);
+
+            Ok::<_, Error>((commit, (poly, eval_8n)))
+        };
+
+        let (s_sigma_1_poly_commit, s_sigma_1) = sigma(0)?;
+        let (s_sigma_2_poly_commit, s_sigma_2) = sigma(1)?;
+        let (s_sigma_3_poly_commit, s_sigma_3) = sigma(2)?;
+        let (s_sigma_4_poly_commit, s_sigma_4) = sigma(3)?;
 
         // verifier Key for arithmetic circuits
         let arithmetic_verifier_key = arithmetic::VerificationKey {
@@ -213,36 +721,18 @@ impl<
             permutation: permutation_verifier_key,
         };
 
-        // The polynomial needs an evaluation domain of 4n.
-        // Plus, adding the blinding factors translates to
-        // the polynomial not fitting in 4n, so now we need
-        // 8n, the next power of 2
-        let x8n = (8 * n).next_power_of_two();
-        let x8k = x8n.trailing_zeros();
-        let fft_8n = Fft::new(x8k as usize);
-        let min_p =
-            Coeffs::new(vec![P::ScalarField::zero(), P::ScalarField::one()]);
-
-        let q_m_eval_8n = fft_8n.coset_dft(q_m_poly.clone());
-        let q_l_eval_8n = fft_8n.coset_dft(q_l_poly.clone());
-        let q_r_eval_8n = fft_8n.coset_dft(q_r_poly.clone());
-        let q_o_eval_8n = fft_8n.coset_dft(q_o_poly.clone());
-        let q_c_eval_8n = fft_8n.coset_dft(q_c_poly.clone());
-        let q_4_eval_8n = fft_8n.coset_dft(q_d_poly.clone());
-        let q_arith_eval_8n = fft_8n.coset_dft(q_arith_poly.clone());
-        let q_range_eval_8n = fft_8n.coset_dft(q_range_poly.clone());
-        let q_logic_eval_8n = fft_8n.coset_dft(q_logic_poly.clone());
-        let q_fixed_group_add_eval_8n =
-            fft_8n.coset_dft(q_fixed_group_add_poly.clone());
+        let q_m_eval_8n = coset_dft(&q_m_poly);
+        let q_l_eval_8n = coset_dft(&q_l_poly);
+        let q_r_eval_8n = coset_dft(&q_r_poly);
+        let q_o_eval_8n = coset_dft(&q_o_poly);
+        let q_c_eval_8n = coset_dft(&q_c_poly);
+        let q_4_eval_8n = coset_dft(&q_d_poly);
+        let q_arith_eval_8n = coset_dft(&q_arith_poly);
+        let q_range_eval_8n = coset_dft(&q_range_poly);
+        let q_logic_eval_8n = coset_dft(&q_logic_poly);
+        let q_fixed_group_add_eval_8n = coset_dft(&q_fixed_group_add_poly);
         let q_variable_group_add_eval_8n =
-            fft_8n.coset_dft(q_variable_group_add_poly.clone());
-
-        let s_sigma_1_eval_8n = fft_8n.coset_dft(s_sigma_1_poly.clone());
-        let s_sigma_2_eval_8n = fft_8n.coset_dft(s_sigma_2_poly.clone());
-        let s_sigma_3_eval_8n = fft_8n.coset_dft(s_sigma_3_poly.clone());
-        let s_sigma_4_eval_8n = fft_8n.coset_dft(s_sigma_4_poly.clone());
-
-        let linear_eval_8n = fft_8n.coset_dft(min_p);
+            coset_dft(&q_variable_group_add_poly);
 
         let arithmetic_prover_key = arithmetic::ProvingKey {
             q_m: (q_m_poly, q_m_eval_8n),
@@ -274,10 +764,10 @@ impl<
         };
 
         let permutation_prover_key = permutation::ProvingKey {
-            s_sigma_1: (s_sigma_1_poly, s_sigma_1_eval_8n),
-            s_sigma_2: (s_sigma_2_poly, s_sigma_2_eval_8n),
-            s_sigma_3: (s_sigma_3_poly, s_sigma_3_eval_8n),
-            s_sigma_4: (s_sigma_4_poly, s_sigma_4_eval_8n),
+            s_sigma_1,
+            s_sigma_2,
+            s_sigma_3,
+            s_sigma_4,
             linear_evaluations: linear_eval_8n,
         };
 
@@ -301,7 +791,66 @@ impl<
             v_h_coset_8n,
         };
 
+        // selectors of the custom gates, set on the gates appended with them
+        let mut custom_prover_keys = Vec::with_capacity(custom.len());
+        let mut custom_verifier_keys = Vec::with_capacity(custom.len());
+        for (id, gate) in custom.iter().enumerate() {
+            let mut q = Points::new(vec![P::ScalarField::zero(); n]);
+            cs.custom_gates
+                .iter()
+                .filter(|(_, g)| **g == id)
+                .for_each(|(i, _)| q.0[*i] = P::ScalarField::one());
+
+            let q_poly = fft.idft(q);
+            let q_poly_commit = keypair.commit(&q_poly).unwrap_or_default();
+            let q_eval_8n = coset_dft(&q_poly);
+
+            custom_prover_keys.push(CustomProvingKey {
+                gate: *gate,
+                q: (q_poly, q_eval_8n),
+            });
+            custom_verifier_keys.push(CustomVerificationKey {
+                gate: *gate,
+                q: q_poly_commit,
+            });
+        }
+
+        // selectors of the lookup gates and columns of the lookup tables
+        let (lookup_prover_key, lookup_verifier_key) =
+            match cs.lookup_tables.is_empty() {
+                true => (None, None),
+                false => {
+                    let key = |values: Vec<P::ScalarField>| {
+                        let poly = fft.idft(Points::new(values));
+                        let commit = keypair.commit(&poly).unwrap_or_default();
+                        let eval_8n = coset_dft(&poly);
+
+                        (commit, (poly, eval_8n))
+                    };
+
+                    let [q_lookup, q_table] = cs.lookup_selectors(n);
+                    let (q_lookup_commit, q_lookup) = key(q_lookup);
+                    let (q_table_commit, q_table) = key(q_table);
+                    let [t_a, t_b, t_c, t_id] = cs.table_columns(n).map(key);
+
+                    let prover_key = LookupProvingKey {
+                        q_lookup,
+                        q_table,
+                        table: [t_a.1, t_b.1, t_c.1, t_id.1],
+                    };
+                    let verifier_key = LookupVerificationKey {
+                        q_lookup: q_lookup_commit,
+                        q_table: q_table_commit,
+                        table: [t_a.0, t_b.0, t_c.0, t_id.0],
+                    };
+
+                    (Some(prover_key), Some(verifier_key))
+                }
+            };
+
         let public_input_indexes = cs.public_input_indexes();
+        let public_input_layout = cs.public_input_layout();
+        let shape = CompiledShape::new(&cs, n);
 
         let label = label.to_vec();
 
@@ -310,17 +859,28 @@ impl<
             keypair.clone(),
             prover_key,
             verifier_key.clone(),
+            custom_prover_keys,
+            &custom_verifier_keys,
+            lookup_prover_key,
+            lookup_verifier_key.as_ref(),
+            parts,
+            config,
             n,
-            m,
+            shape,
+            reorder,
         );
 
         let verifier = Verifier::new(
             label,
             verifier_key,
+            custom_verifier_keys,
+            lookup_verifier_key,
+            parts,
             keypair.verification_key(),
             public_input_indexes,
+            public_input_layout,
             n,
-            m,
+            cs.m(),
         );
 
         Ok((prover, verifier))
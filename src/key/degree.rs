@@ -0,0 +1,371 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Degree accounting of the quotient polynomial.
+//!
+//! The quotient `t(X)` is committed as [`QuotientParts`] polynomials: the
+//! first parts hold `n` coefficients each and the last one holds the
+//! remainder. Every gate equation is a product of a selector and some wire
+//! polynomials, so its degree is known from the selector family alone and
+//! the compiler can refuse circuits whose quotient wouldn't fit the proof.
+
+use zksnarks::plonk::ProvingKey;
+use zksnarks::Constraint;
+use zkstd::common::{Pairing, PrimeField};
+
+use super::CompileError;
+use crate::prover::BlindingConfig;
+
+/// Number of polynomials the quotient is split into.
+///
+/// The standard widgets fit [`QuotientParts::Four`], which is the split of
+/// the proofs of the circuits compiled without
+/// [`PlonkKey::compile_with_quotient_parts`](crate::PlonkKey::compile_with_quotient_parts).
+/// [`QuotientParts::Six`] commits to two more parts, so the custom gates
+/// may multiply up to `5` wires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotientParts {
+    /// `t_low`, `t_mid`, `t_high` and `t_4`
+    Four,
+    /// `t_low`, `t_mid`, `t_high`, `t_4`, `t_5` and `t_6`
+    Six,
+}
+
+impl QuotientParts {
+    /// Number of polynomials
+    pub const fn count(&self) -> usize {
+        match self {
+            Self::Four => 4,
+            Self::Six => 6,
+        }
+    }
+}
+
+impl Default for QuotientParts {
+    fn default() -> Self {
+        Self::Four
+    }
+}
+
+/// Degree of a polynomial interpolated over a domain of size `n` and
+/// blinded with `terms` random multiples of the vanishing polynomial
+const fn blinded_degree(n: usize, terms: usize) -> usize {
+    n - 1 + terms
+}
+
+/// Families of gates sharing a selector polynomial
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectorFamily {
+    /// `q_arith · (q_m · a · b + q_l · a + ...)`
+    Arithmetic,
+    /// `q_range · Δ(d - 4c) + ...`
+    Range,
+    /// `q_logic · (...)`
+    Logic,
+    /// `q_fixed_group_add · (...)`
+    FixedBase,
+    /// `q_variable_group_add · (...)`
+    VariableBase,
+}
+
+impl SelectorFamily {
+    /// All the families supported by the proving key
+    pub const ALL: [Self; 5] = [
+        Self::Arithmetic,
+        Self::Range,
+        Self::Logic,
+        Self::FixedBase,
+        Self::VariableBase,
+    ];
+
+    /// Maximum number of wire polynomials multiplied in a term of the gate
+    /// equation, not counting the selector
+    pub(crate) const fn wire_degree(&self) -> usize {
+        match self {
+            Self::Arithmetic => 2,
+            Self::Range => 4,
+            Self::Logic => 4,
+            Self::FixedBase => 4,
+            Self::VariableBase => 4,
+        }
+    }
+
+    /// Family of the first selector set in the constraint, if any
+    pub fn of<F: PrimeField>(constraint: &Constraint<F>) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.is_active(constraint))
+    }
+
+    /// Check if the family reads the wires of the next gate, so the gate
+    /// following it may carry no selector
+    pub const fn reads_next_gate(&self) -> bool {
+        !matches!(self, Self::Arithmetic)
+    }
+
+    /// Check if the family selector is set in the constraint
+    pub fn is_active<F: PrimeField>(&self, constraint: &Constraint<F>) -> bool {
+        let selector = match self {
+            Self::Arithmetic => constraint.q_arith,
+            Self::Range => constraint.q_range,
+            Self::Logic => constraint.q_logic,
+            Self::FixedBase => constraint.q_fixed_group_add,
+            Self::VariableBase => constraint.q_variable_group_add,
+        };
+
+        selector != F::zero()
+    }
+
+    /// Degree of the quotient contribution of the family over a domain of
+    /// size `n`.
+    ///
+    /// The selector has degree `n - 1` and every wire is blinded on top of
+    /// it; dividing by the vanishing polynomial removes `n`.
+    pub(crate) const fn quotient_degree(
+        &self,
+        n: usize,
+        blinding: &BlindingConfig,
+    ) -> usize {
+        // the arithmetic equation also carries the `q_arith` selector
+        let selectors = match self {
+            Self::Arithmetic => 2,
+            _ => 1,
+        };
+        let wire = blinded_degree(n, blinding.wire_poly_terms);
+
+        selectors * (n - 1) + self.wire_degree() * wire - n
+    }
+}
+
+/// Degree of the quotient contribution of a custom gate multiplying up to
+/// `wire_degree` wires over its selector
+pub(crate) const fn custom_quotient_degree(
+    n: usize,
+    wire_degree: usize,
+    blinding: &BlindingConfig,
+) -> usize {
+    let wire = blinded_degree(n, blinding.wire_poly_terms);
+
+    (n - 1) + wire_degree * wire - n
+}
+
+/// Degree of the quotient contribution of the permutation argument.
+///
+/// The grand product multiplies the permutation polynomial with one factor
+/// per wire.
+pub(crate) const fn permutation_quotient_degree(
+    n: usize,
+    blinding: &BlindingConfig,
+) -> usize {
+    let wire = blinded_degree(n, blinding.wire_poly_terms);
+    let permutation = blinded_degree(n, blinding.perm_poly_terms);
+
+    permutation + 4 * wire - n
+}
+
+/// Degree of the quotient contribution of the lookup argument.
+///
+/// The step of the running sum multiplies its difference `φ(ωX) - φ(X)`,
+/// blinded as the permutation polynomial, with a compressed lookup of the
+/// wires and a compressed row of the fixed table columns.
+pub(crate) const fn lookup_quotient_degree(
+    n: usize,
+    blinding: &BlindingConfig,
+) -> usize {
+    let wire = blinded_degree(n, blinding.wire_poly_terms);
+    let phi = blinded_degree(n, blinding.perm_poly_terms);
+
+    phi + wire + (n - 1) - n
+}
+
+/// Maximum degree of the quotient polynomial given the active families, the
+/// wire degrees of the custom gates and whether the circuit has lookup
+/// tables
+pub(crate) fn max_quotient_degree<I>(
+    n: usize,
+    families: I,
+    custom: &[usize],
+    lookup: bool,
+    blinding: &BlindingConfig,
+) -> usize
+where
+    I: IntoIterator<Item = SelectorFamily>,
+{
+    let custom = custom
+        .iter()
+        .map(|wire_degree| custom_quotient_degree(n, *wire_degree, blinding));
+    let lookup = lookup.then(|| lookup_quotient_degree(n, blinding));
+
+    families
+        .into_iter()
+        .map(|f| f.quotient_degree(n, blinding))
+        .chain(custom)
+        .chain(lookup)
+        .fold(permutation_quotient_degree(n, blinding), usize::max)
+}
+
+/// Maximum degree of a quotient that fits the `parts` of a proof.
+///
+/// All the parts but the last have `n` coefficients, and the last one
+/// absorbs the blinding slack of the permutation argument with the default
+/// [`BlindingConfig`].
+pub(crate) const fn quotient_degree_budget(
+    n: usize,
+    parts: QuotientParts,
+) -> usize {
+    let blinding = BlindingConfig::DEFAULT;
+
+    permutation_quotient_degree(n, &blinding).max(parts.count() * n - 1)
+}
+
+/// Maximum degree of a quotient the prover can compute.
+///
+/// The quotient is evaluated over a coset of size `8n`, so raising the
+/// blinding terms is bounded by it rather than by the proof.
+pub(crate) const fn quotient_domain_budget(n: usize) -> usize {
+    (8 * n).next_power_of_two() - 1
+}
+
+/// Return an error if the quotient of the active families, the custom gates
+/// and the lookup argument won't fit the `parts` of the proof
+pub(crate) fn check_quotient_degree<I>(
+    n: usize,
+    families: I,
+    custom: &[usize],
+    lookup: bool,
+    parts: QuotientParts,
+) -> Result<(), CompileError>
+where
+    I: IntoIterator<Item = SelectorFamily>,
+{
+    let blinding = BlindingConfig::DEFAULT;
+    let degree = max_quotient_degree(n, families, custom, lookup, &blinding);
+    let max = quotient_degree_budget(n, parts);
+
+    match degree <= max {
+        true => Ok(()),
+        false => {
+            Err(CompileError::QuotientDegreeTooHigh { degree, max, parts })
+        }
+    }
+}
+
+/// Selector families with at least one active gate in `constraints`
+pub(crate) fn active_families<F: PrimeField>(
+    constraints: &[Constraint<F>],
+) -> impl Iterator<Item = SelectorFamily> + '_ {
+    SelectorFamily::ALL
+        .into_iter()
+        .filter(move |f| constraints.iter().any(|c| f.is_active(c)))
+}
+
+/// Selector families with at least one non-zero coefficient in the
+/// proving key
+pub(crate) fn key_families<P: Pairing>(
+    key: &ProvingKey<P>,
+) -> impl Iterator<Item = SelectorFamily> + '_ {
+    SelectorFamily::ALL.into_iter().filter(move |f| {
+        let selector = match f {
+            SelectorFamily::Arithmetic => &key.arithmetic.q_arith.0,
+            SelectorFamily::Range => &key.range.q_range.0,
+            SelectorFamily::Logic => &key.logic.q_logic.0,
+            SelectorFamily::FixedBase => &key.curve_scalar.q_fixed_group_add.0,
+            SelectorFamily::VariableBase => {
+                &key.curve_addtion.q_variable_group_add.0
+            }
+        };
+
+        selector.0.iter().any(|c| c != &P::ScalarField::zero())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn standard_families_fit_quotient() {
+        for k in 1..20 {
+            let n = 1 << k;
+
+            let blinding = BlindingConfig::default();
+
+            check_quotient_degree(
+                n,
+                SelectorFamily::ALL,
+                &[4],
+                true,
+                QuotientParts::Four,
+            )
+            .expect("standard widgets must fit the quotient");
+
+            assert_eq!(
+                max_quotient_degree(
+                    n,
+                    SelectorFamily::ALL,
+                    &[],
+                    false,
+                    &blinding
+                ),
+                permutation_quotient_degree(n, &blinding)
+            );
+        }
+    }
+
+    #[test]
+    fn quotient_degree_error() {
+        let n = 1 << 10;
+        let blinding = BlindingConfig::default();
+
+        // a synthetic widget with five wires over a selector
+        let wire = blinded_degree(n, blinding.wire_poly_terms);
+        let degree = (n - 1) + 5 * wire - n;
+
+        assert!(degree > quotient_degree_budget(n, QuotientParts::Four));
+        assert!(max_quotient_degree(n, [], &[], true, &blinding) < degree);
+        assert_eq!(custom_quotient_degree(n, 5, &blinding), degree);
+        assert!(matches!(
+            check_quotient_degree(n, [], &[5], false, QuotientParts::Four),
+            Err(CompileError::QuotientDegreeTooHigh {
+                degree: d,
+                parts: QuotientParts::Four,
+                ..
+            }) if d == degree
+        ));
+
+        // two more parts fit it, but not a sixth wire
+        check_quotient_degree(n, [], &[5], false, QuotientParts::Six)
+            .expect("five wires must fit six parts");
+        assert!(
+            check_quotient_degree(n, [], &[6], false, QuotientParts::Six)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn lookup_degree_is_counted() {
+        let n = 1 << 10;
+        let blinding = BlindingConfig::default();
+
+        // the running sum step over the lookup and the table rows
+        let phi = blinded_degree(n, blinding.perm_poly_terms);
+        let f = blinded_degree(n, blinding.wire_poly_terms);
+        let degree = phi + f + (n - 1) - n;
+        assert_eq!(lookup_quotient_degree(n, &blinding), degree);
+
+        let without = max_quotient_degree(n, [], &[], false, &blinding);
+        let with = max_quotient_degree(n, [], &[], true, &blinding);
+        assert_eq!(with, without.max(degree));
+
+        assert!(degree <= quotient_degree_budget(n, QuotientParts::Four));
+        check_quotient_degree(
+            n,
+            SelectorFamily::ALL,
+            &[],
+            true,
+            QuotientParts::Four,
+        )
+        .expect("the lookup argument must fit the quotient");
+    }
+}
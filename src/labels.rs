@@ -0,0 +1,116 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Named wires, to run a circuit as a function of its witnesses.
+//!
+//! The composer computes the value of every wire while the circuit is
+//! synthesized, so labeling the outputs of a circuit is enough to compare
+//! it against a reference implementation. Labels never append gates nor
+//! witnesses.
+
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::wire::PrivateWire;
+use zkstd::common::{TwistedEdwardsAffine, Vec};
+
+use crate::{CircuitStats, InitConfig, Plonk};
+
+/// Error returned when a wire can't be labeled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelError {
+    /// The label is already given to a wire
+    Duplicate {
+        /// The label
+        label: &'static str,
+        /// Index of the wire that has the label
+        wire: usize,
+    },
+}
+
+impl core::fmt::Display for LabelError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Duplicate { label, wire } => {
+                write!(f, "the label `{}` is given to wire {}", label, wire)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LabelError {}
+
+/// Gates and labeled values of a circuit synthesized by
+/// [`Plonk::dry_run`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRun<F> {
+    /// Gates of the circuit
+    pub stats: CircuitStats,
+    /// Value of every labeled wire, ordered by label
+    pub outputs: Vec<(&'static str, F)>,
+}
+
+impl<F: Copy> DryRun<F> {
+    /// Value of the wire labeled `label`
+    pub fn output(&self, label: &str) -> Option<F> {
+        self.outputs
+            .iter()
+            .find(|(l, _)| *l == label)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Give `label` to the wire `w`, so its value can be read with
+    /// [`Self::labeled_value`] once the circuit is synthesized.
+    ///
+    /// A wire can have several labels, but a label names a single wire.
+    pub fn label_wire(
+        &mut self,
+        label: &'static str,
+        w: PrivateWire,
+    ) -> Result<(), LabelError> {
+        match self.labels.get(label) {
+            Some(wire) => Err(LabelError::Duplicate {
+                label,
+                wire: wire.index(),
+            }),
+            None => {
+                self.labels.insert(label, w);
+                Ok(())
+            }
+        }
+    }
+
+    /// Value of the wire labeled `label`
+    pub fn labeled_value(&self, label: &str) -> Option<C::Range> {
+        self.labels.get(label).map(|w| self[*w])
+    }
+
+    /// Synthesize `circuit` in a composer created with
+    /// [`ConstraintSystem::initialize`](zksnarks::constraint_system::ConstraintSystem::initialize),
+    /// without compiling nor proving it, and return its gates and labeled
+    /// values.
+    pub fn dry_run<T>(circuit: &T) -> Result<DryRun<C::Range>, Error>
+    where
+        T: Circuit<C, ConstraintSystem = Self>,
+    {
+        let mut composer = Self::with_config(InitConfig::default());
+
+        circuit.synthesize(&mut composer)?;
+
+        let outputs = composer
+            .labels
+            .iter()
+            .map(|(label, w)| (*label, composer[*w]))
+            .collect();
+
+        Ok(DryRun {
+            stats: composer.statistics(),
+            outputs,
+        })
+    }
+}
@@ -51,15 +51,30 @@
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod custom_gate;
+mod debug;
+mod digest;
+mod expression;
+mod labels;
+mod locality;
+mod lookup;
 mod permutation;
+mod stats;
 
 mod key;
+#[cfg(feature = "test-utils")]
+mod mock;
 mod prover;
 mod verifier;
+mod witness_state;
 
 pub mod gadget;
 
 pub mod commitment_scheme;
+#[cfg(feature = "examples")]
+pub mod examples;
+#[cfg(feature = "wasm")]
+pub mod ffi;
 pub mod prelude;
 
 #[doc = include_str!("../docs/notes-intro.md")]
@@ -74,26 +89,47 @@ pub mod notes {
     pub mod kzg10_docs {}
 }
 
-pub use crate::key::PlonkKey;
-pub use crate::prover::Prover;
-pub use crate::verifier::Verifier;
+pub use crate::custom_gate::{CustomGate, GateWires};
+pub use crate::debug::DebugMismatch;
+pub use crate::digest::{public_digest, DIGEST_ALGORITHM};
+pub use crate::expression::Expression;
+pub use crate::key::{
+    degree::{QuotientParts, SelectorFamily},
+    CircuitTooLarge, CompileBudget, CompileError, PlonkKey,
+};
+pub use crate::labels::{DryRun, LabelError};
+pub use crate::lookup::TableId;
+#[cfg(feature = "test-utils")]
+pub use crate::mock::{MockError, MockProof, MockProver, MockVerifier};
+pub use crate::prover::{
+    BlindingConfig, BlindingError, Prover, ProverOptions, SanityError,
+    WirePosition,
+};
+#[cfg(feature = "export-keys")]
+pub use crate::prover::{Selector, SelectorView};
+pub use crate::stats::{is_copy_gate, CircuitStats};
+pub use crate::verifier::{Challenges, PublicInput, PublicInputKind, Verifier};
+pub use crate::witness_state::WitnessStateError;
 
 use bls_12_381::Fr as BlsScalar;
 use core::fmt::Debug;
+#[cfg(feature = "track-callers")]
+use core::panic::Location;
 use core::{cmp, ops};
-use hashbrown::HashMap;
-use jub_jub::compute_windowed_naf;
+use sp_std::collections::btree_map::BTreeMap;
 use sp_std::vec;
 use zksnarks::error::Error;
 use zksnarks::{
     constraint_system::ConstraintSystem, plonk::wire::PrivateWire, Constraint,
 };
 use zkstd::common::{
-    FftField, Group, Neg, PrimeField, Ring, TwistedEdwardsAffine,
+    FftField, Group, PrimeField, Ring, TwistedEdwardsAffine,
     TwistedEdwardsCurve, TwistedEdwardsExtended, Vec,
 };
 
-use crate::gadget::ecc::WnafRound;
+#[cfg(feature = "debug-assertions")]
+use crate::debug::DebugAssertion;
+use crate::gadget::chain::addition_chain;
 use crate::gadget::WitnessPoint;
 use crate::permutation::Permutation;
 use zksnarks::bit_iterator::BitIterator8;
@@ -104,33 +140,220 @@ pub struct Plonk<C: TwistedEdwardsAffine> {
     /// Constraint system gates
     pub(crate) constraints: Vec<Constraint<C::Range>>,
 
-    /// Sparse representation of the public inputs
-    pub(crate) instance: HashMap<usize, C::Range>,
+    /// Sparse representation of the public inputs, ordered by gate
+    pub(crate) instance: BTreeMap<usize, C::Range>,
+
+    /// Hash algorithm of the public inputs that are digests, by gate
+    pub(crate) digests: BTreeMap<usize, &'static str>,
+
+    /// Custom gate selected by the gates appended with
+    /// [`Self::append_registered_gate`], by gate
+    pub(crate) custom_gates: BTreeMap<usize, usize>,
+
+    /// Tables appended with [`Self::append_lookup_table`]
+    pub(crate) lookup_tables: Vec<Vec<[C::Range; 3]>>,
+
+    /// Table read by the gates appended with [`Self::component_lookup`], by
+    /// gate
+    pub(crate) lookups: BTreeMap<usize, usize>,
 
     /// Witness values
     pub(crate) witness: Vec<C::Range>,
 
+    /// Gates given a public input with [`Self::set_public_input`], in order,
+    /// so [`Self::rewind`] can take them back
+    pub(crate) public_input_log: Vec<usize>,
+
+    /// Wires named with [`Self::label_wire`]
+    pub(crate) labels: BTreeMap<&'static str, PrivateWire>,
+
     /// Permutation argument.
     pub(crate) perm: Permutation<C::Range>,
+
+    /// Initialization the composer was created with
+    pub(crate) config: InitConfig,
+
+    /// Whether non-canonical witnesses are refused
+    pub(crate) strict_witnesses: bool,
+
+    /// Expected witness values recorded for debugging
+    #[cfg(feature = "debug-assertions")]
+    pub(crate) debug_assertions: Vec<DebugAssertion<C::Range>>,
+
+    /// Source location that appended every gate
+    #[cfg(feature = "track-callers")]
+    pub(crate) constraint_locations: Vec<&'static Location<'static>>,
+
+    /// Source location that appended every witness
+    #[cfg(feature = "track-callers")]
+    pub(crate) witness_locations: Vec<&'static Location<'static>>,
 }
 
-impl<C: TwistedEdwardsAffine> ConstraintSystem<C> for Plonk<C> {
-    type Wire = PrivateWire;
-    type Constraints = Vec<Constraint<C::Range>>;
+/// State of a [`Plonk`] composer that it can be rewound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    constraints: usize,
+    witness: usize,
+    public_input_log: usize,
+    lookup_tables: usize,
+    #[cfg(feature = "debug-assertions")]
+    debug_assertions: usize,
+}
 
-    fn initialize() -> Self {
-        let mut slf = Self::new();
+/// Error returned when a public input can't be set on a gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicInputError {
+    /// The gate already has a public input
+    Duplicate {
+        /// Index of the gate
+        gate: usize,
+    },
+    /// The gate wasn't appended to the composer
+    MissingGate {
+        /// Index of the gate
+        gate: usize,
+    },
+}
+
+impl core::fmt::Display for PublicInputError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Duplicate { gate } => {
+                write!(f, "gate {} already has a public input", gate)
+            }
+            Self::MissingGate { gate } => {
+                write!(f, "gate {} wasn't appended", gate)
+            }
+        }
+    }
+}
 
-        let zero = slf.append_witness(0);
-        let one = slf.append_witness(1);
+#[cfg(feature = "std")]
+impl std::error::Error for PublicInputError {}
+
+/// Error returned when the divisor of [`Plonk::component_div`] is zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivisionByZero {
+    /// Index of the divisor wire
+    pub wire: usize,
+}
 
-        slf.assert_equal_constant(zero, 0, None);
-        slf.assert_equal_constant(one, 1, None);
+impl core::fmt::Display for DivisionByZero {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the divisor wire {} is zero", self.wire)
+    }
+}
 
-        slf.append_dummy_gates();
-        slf.append_dummy_gates();
+#[cfg(feature = "std")]
+impl std::error::Error for DivisionByZero {}
 
-        slf
+/// The witnesses of the circuit can't satisfy it, so a `synthesize` can
+/// propagate the error with `?`
+impl From<DivisionByZero> for Error {
+    fn from(_: DivisionByZero) -> Self {
+        Error::ProofVerificationError
+    }
+}
+
+/// Error returned when the constant divisor of [`Plonk::component_div_rem`]
+/// is zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroDivisor;
+
+impl core::fmt::Display for ZeroDivisor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the constant divisor is zero")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ZeroDivisor {}
+
+/// Error returned when the wire vectors of [`Plonk::component_dot`] have
+/// different lengths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// Length of the left vector
+    pub left: usize,
+    /// Length of the right vector
+    pub right: usize,
+}
+
+impl core::fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the vectors have {} and {} wires", self.left, self.right)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
+
+/// Error returned when a recomposition has more bits than the field holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositionOverflow {
+    /// Bits of the recomposition
+    pub bits: usize,
+    /// Maximum bits of a recomposition
+    pub max: usize,
+}
+
+impl core::fmt::Display for CompositionOverflow {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the recomposition of {} bits exceeds {} bits",
+            self.bits, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompositionOverflow {}
+
+/// Rows appended and capacity reserved by the composer before the circuit is
+/// synthesized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitConfig {
+    /// Sets of dummy gates appended to avoid zero selector polynomials and
+    /// the identity permutation
+    pub dummy_gate_sets: u8,
+
+    /// Reserve the first two witnesses for the `0` and `1` constants.
+    ///
+    /// Gadgets that rely on [`Plonk::ZERO`] or the identity point panic if
+    /// this is disabled.
+    pub reserve_zero_one: bool,
+
+    /// Gates and witnesses reserved before the circuit is synthesized, see
+    /// [`Plonk::reserve`]
+    pub size_hint: SizeHint,
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self {
+            dummy_gate_sets: 2,
+            reserve_zero_one: true,
+            size_hint: SizeHint::default(),
+        }
+    }
+}
+
+/// Number of gates and witnesses of a composer
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SizeHint {
+    /// Number of gates
+    pub gates: usize,
+    /// Number of witnesses
+    pub witnesses: usize,
+}
+
+impl<C: TwistedEdwardsAffine> ConstraintSystem<C> for Plonk<C> {
+    type Wire = PrivateWire;
+    type Constraints = Vec<Constraint<C::Range>>;
+
+    fn initialize() -> Self {
+        Self::with_config(InitConfig::default())
     }
 
     fn m(&self) -> usize {
@@ -158,12 +381,194 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     fn new() -> Self {
         Self {
             constraints: Vec::default(),
-            instance: HashMap::new(),
+            instance: BTreeMap::new(),
+            digests: BTreeMap::new(),
+            custom_gates: BTreeMap::new(),
+            lookup_tables: Vec::new(),
+            lookups: BTreeMap::new(),
             witness: Vec::default(),
+            public_input_log: Vec::new(),
+            labels: BTreeMap::new(),
             perm: Permutation::new(),
+            config: InitConfig::default(),
+            strict_witnesses: cfg!(debug_assertions),
+            #[cfg(feature = "debug-assertions")]
+            debug_assertions: Vec::new(),
+            #[cfg(feature = "track-callers")]
+            constraint_locations: Vec::new(),
+            #[cfg(feature = "track-callers")]
+            witness_locations: Vec::new(),
+        }
+    }
+
+    /// Create a composer with a custom initialization.
+    ///
+    /// [`InitConfig::default`] matches [`ConstraintSystem::initialize`].
+    pub fn with_config(config: InitConfig) -> Self {
+        let mut slf = Self::new();
+        slf.config = config;
+
+        let hint = config.size_hint;
+        slf.reserve(hint.gates, hint.witnesses);
+
+        if config.reserve_zero_one {
+            let zero = slf.append_witness(0);
+            let one = slf.append_witness(1);
+
+            slf.assert_equal_constant(zero, 0, None);
+            slf.assert_equal_constant(one, 1, None);
+        }
+
+        (0..config.dummy_gate_sets).for_each(|_| slf.append_dummy_gates());
+
+        slf
+    }
+
+    /// Initialization the composer was created with
+    pub fn config(&self) -> InitConfig {
+        self.config
+    }
+
+    /// Reserve the capacity of at least `additional_gates` more gates and
+    /// `additional_witnesses` more witnesses, so appending them doesn't
+    /// reallocate the gates, the witnesses nor the wires of the permutation.
+    ///
+    /// A circuit whose size is known ahead, such as by
+    /// [`PlonkKey::size_hint`], reserves it through
+    /// [`InitConfig::size_hint`] instead.
+    pub fn reserve(
+        &mut self,
+        additional_gates: usize,
+        additional_witnesses: usize,
+    ) {
+        self.constraints.reserve(additional_gates);
+        self.witness.reserve(additional_witnesses);
+        self.perm.reserve(additional_witnesses);
+
+        #[cfg(feature = "track-callers")]
+        {
+            self.constraint_locations.reserve(additional_gates);
+            self.witness_locations.reserve(additional_witnesses);
+        }
+    }
+
+    /// Number of gates and witnesses appended so far
+    pub fn size(&self) -> SizeHint {
+        SizeHint {
+            gates: self.constraints.len(),
+            witnesses: self.witness.len(),
+        }
+    }
+
+    /// Number of gates and witnesses the composer can hold without
+    /// reallocating
+    pub fn capacity(&self) -> SizeHint {
+        SizeHint {
+            gates: self.constraints.capacity(),
+            witnesses: self.witness.capacity(),
+        }
+    }
+
+    /// Refuse witnesses whose representation isn't reduced modulo the field
+    /// order.
+    ///
+    /// A scalar built from raw limbs can hold an integer larger than the
+    /// modulus; the gadgets decompose the canonical reduction of the
+    /// witnesses regardless, but such a witness is usually a bug of the code
+    /// that built it. If strict, appending one panics with its wire index.
+    /// The check is skipped entirely otherwise.
+    ///
+    /// Enabled by default in debug builds.
+    pub fn set_strict_witnesses(&mut self, strict: bool) {
+        self.strict_witnesses = strict;
+    }
+
+    /// Whether non-canonical witnesses are refused, see
+    /// [`Self::set_strict_witnesses`]
+    pub fn strict_witnesses(&self) -> bool {
+        self.strict_witnesses
+    }
+
+    /// Capture the current state of the composer, so the gates and witnesses
+    /// appended afterwards can be dropped with [`Self::rewind`]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            constraints: self.constraints.len(),
+            witness: self.witness.len(),
+            public_input_log: self.public_input_log.len(),
+            lookup_tables: self.lookup_tables.len(),
+            #[cfg(feature = "debug-assertions")]
+            debug_assertions: self.debug_assertions.len(),
+        }
+    }
+
+    /// Drop every gate, witness, public input and lookup table appended
+    /// after the checkpoint was taken.
+    ///
+    /// The gates and witnesses appended before the checkpoint are kept as
+    /// they are, so the suffix of a circuit can be synthesized again with
+    /// new values. A public input set with [`Self::set_public_input`] after
+    /// the checkpoint on one of these gates is removed as well.
+    ///
+    /// # Panics
+    /// This function will panic if the checkpoint is ahead of the composer,
+    /// such as one taken from another composer.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        let Checkpoint {
+            constraints,
+            witness,
+            public_input_log,
+            lookup_tables,
+            ..
+        } = checkpoint;
+
+        assert!(
+            constraints <= self.constraints.len()
+                && witness <= self.witness.len()
+                && public_input_log <= self.public_input_log.len()
+                && lookup_tables <= self.lookup_tables.len(),
+            "the checkpoint is ahead of the composer"
+        );
+
+        // the public inputs set since on the kept gates are taken back, and
+        // the other gates are dropped with theirs
+        for gate in self.public_input_log.split_off(public_input_log) {
+            if gate < constraints {
+                self.constraints[gate].public_input = None;
+                self.instance.remove(&gate);
+            }
+        }
+
+        self.constraints.truncate(constraints);
+        self.witness.truncate(witness);
+        self.instance.split_off(&constraints);
+        self.digests.split_off(&constraints);
+        self.custom_gates.split_off(&constraints);
+        self.lookups.split_off(&constraints);
+        self.lookup_tables.truncate(lookup_tables);
+        self.labels.retain(|_, w| w.index() < witness);
+        self.perm.truncate(witness, constraints);
+
+        #[cfg(feature = "debug-assertions")]
+        self.debug_assertions.truncate(checkpoint.debug_assertions);
+
+        #[cfg(feature = "track-callers")]
+        {
+            self.constraint_locations.truncate(constraints);
+            self.witness_locations.truncate(witness);
         }
     }
 
+    /// Panic if the gadget relies on the reserved `0` and `1` witnesses and
+    /// the composer was created without them
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn assert_reserved_zero_one(&self, gadget: &str) {
+        assert!(
+            self.config.reserve_zero_one,
+            "{gadget} requires the composer to reserve the zero and one witnesses"
+        );
+    }
+
     /// Zero representation inside the constraint system.
     ///
     /// A turbo composer expects the first witness to be always present and to
@@ -179,22 +584,61 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     /// Identity point representation inside the constraint system
     const IDENTITY: WitnessPoint = WitnessPoint::new(Self::ZERO, Self::ONE);
 
-    pub(crate) fn public_input_indexes(&self) -> Vec<usize> {
-        let mut public_input_indexes =
-            self.instance.keys().copied().collect::<Vec<_>>();
+    /// Maximum bits of [`Self::component_range_bounded`], so the difference
+    /// of a witness above the bound can't wrap around the field modulus into
+    /// the range
+    pub const MAX_BOUNDED_BITS: usize = 252;
 
-        public_input_indexes.as_mut_slice().sort();
+    /// Bits of the scalar field of JubJub, whose modulus is just below
+    /// `2^252`
+    pub const JUBJUB_SCALAR_BITS: usize = 252;
 
-        public_input_indexes
+    pub(crate) fn public_input_indexes(&self) -> Vec<usize> {
+        self.instance.keys().copied().collect()
     }
 
-    pub(crate) fn instance(&self) -> Vec<C::Range> {
-        self.public_input_indexes()
-            .iter()
-            .filter_map(|idx| self.instance.get(idx).copied())
+    /// Kind of every public input, in the order of the public inputs
+    pub(crate) fn public_input_layout(&self) -> Vec<PublicInputKind> {
+        self.instance
+            .keys()
+            .map(|gate| match self.digests.get(gate).copied() {
+                Some(algorithm) => PublicInputKind::Digest { algorithm },
+                None => PublicInputKind::Scalar,
+            })
             .collect()
     }
 
+    pub(crate) fn instance(&self) -> Vec<C::Range> {
+        self.instance.values().copied().collect()
+    }
+
+    /// Set the public input of an appended gate that doesn't have one yet.
+    ///
+    /// The public input is added to the gate equation, as if the gate had
+    /// been appended with it.
+    pub fn set_public_input(
+        &mut self,
+        gate: usize,
+        value: C::Range,
+    ) -> Result<(), PublicInputError> {
+        let constraint = self
+            .constraints
+            .get_mut(gate)
+            .ok_or(PublicInputError::MissingGate { gate })?;
+
+        if constraint.public_input.is_some()
+            || self.instance.contains_key(&gate)
+        {
+            return Err(PublicInputError::Duplicate { gate });
+        }
+
+        constraint.public_input = Some(value);
+        self.instance.insert(gate, value);
+        self.public_input_log.push(gate);
+
+        Ok(())
+    }
+
     pub(crate) fn dense_public_inputs(
         public_input_indexes: &[usize],
         public_inputs: &[C::Range],
@@ -214,7 +658,53 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
         self.constraints.len()
     }
 
+    /// Source location that appended the gate at index `gate`.
+    ///
+    /// Closures inside the gadgets don't forward the caller location, so a
+    /// gate appended from one reports the gadget source instead.
+    #[cfg(feature = "track-callers")]
+    pub fn constraint_location(
+        &self,
+        gate: usize,
+    ) -> Option<&'static Location<'static>> {
+        self.constraint_locations.get(gate).copied()
+    }
+
+    /// Source location that appended the witness `w`
+    #[cfg(feature = "track-callers")]
+    pub fn witness_location(
+        &self,
+        w: PrivateWire,
+    ) -> Option<&'static Location<'static>> {
+        self.witness_locations.get(w.index()).copied()
+    }
+
+    /// Value of the witness `w`, as read by the [`ops::Index`] of the
+    /// composer
+    pub fn witness_value(&self, w: PrivateWire) -> C::Range {
+        self[w]
+    }
+
+    /// Affine value of the witness point `p`.
+    ///
+    /// The point is built from its coordinates without a check, so the
+    /// value of a point appended with [`Self::append_point`] from untrusted
+    /// data may lie off the curve, which is asserted in debug builds only.
+    pub fn point_value(&self, p: &WitnessPoint) -> C {
+        let (x, y) = (self[*p.x()], self[*p.y()]);
+
+        let d = crate::gadget::ecc::curve_d::<C>();
+        let (x2, y2) = (x * x, y * y);
+        debug_assert!(
+            y2 - x2 == C::Range::one() + d * x2 * y2,
+            "the witness point {p} lies off the curve"
+        );
+
+        C::from_raw_unchecked(x, y)
+    }
+
     /// Allocate a witness value into the composer and return its index.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_witness<W: Into<C::Range>>(
         &mut self,
         witness: W,
@@ -223,28 +713,54 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     }
 
     /// Append a new width-4 poly gate/constraint.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_custom_gate(&mut self, constraint: Constraint<C::Range>) {
         #[allow(deprecated)]
         self.append_custom_gate_internal(constraint)
     }
 
+    /// Append `constraint` with the selector of a custom gate set.
+    ///
+    /// `gate` is the index of the [`CustomGate`] in the gates the keys are
+    /// compiled with by [`PlonkKey::compile_with_custom_gates`]. The
+    /// selectors of `constraint` still apply, so it usually only carries the
+    /// wires the custom gate reads.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_registered_gate(
+        &mut self,
+        gate: usize,
+        constraint: Constraint<C::Range>,
+    ) {
+        self.custom_gates.insert(self.constraints.len(), gate);
+        self.append_custom_gate(constraint);
+    }
+
     ///
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_witness_internal(
         &mut self,
         witness: C::Range,
     ) -> PrivateWire {
         let n = self.witness.len();
 
+        if self.strict_witnesses && !Self::is_canonical(&witness) {
+            panic!("the witness of wire {} isn't canonical", n);
+        }
+
         // Get a new Witness from the permutation
         self.perm.new_witness();
 
         // Bind the allocated witness
         self.witness.push(witness);
 
+        #[cfg(feature = "track-callers")]
+        self.witness_locations.push(Location::caller());
+
         PrivateWire::new(n)
     }
 
     ///
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_custom_gate_internal(
         &mut self,
         constraint: Constraint<C::Range>,
@@ -253,6 +769,10 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
 
         self.constraints.push(constraint);
 
+        #[cfg(feature = "track-callers")]
+        self.constraint_locations.push(Location::caller());
+
+        // the gate is new, so it can't have a public input yet
         if let Some(pi) = constraint.public_input {
             self.instance.insert(n, pi);
         }
@@ -270,37 +790,72 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     /// specified number of bits (counting from the least significant bit).
     ///
     /// Each logic gate adds `(num_bits / 2) + 1` gates to the circuit to
-    /// perform the whole operation.
+    /// perform the whole operation. An odd `num_bits` is padded with a zero
+    /// most significant bit, constrained by two more gates.
+    ///
+    /// Returns the last accumulators `(an, bn, dn)`: `an` and `bn` hold the
+    /// first `num_bits` of `a` and `b`, and `dn` holds the result.
     ///
     /// ## Constraint
     /// - is_component_xor = 1 -> Performs XOR between the first `num_bits` for
     ///   `a` and `b`.
     /// - is_component_xor = 0 -> Performs AND between the first `num_bits` for
     ///   `a` and `b`.
-    ///
-    /// # Panics
-    /// This function will panic if the num_bits specified is not even, ie.
-    /// `num_bits % 2 != 0`.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     fn append_logic_component(
         &mut self,
         a: PrivateWire,
         b: PrivateWire,
         num_bits: usize,
         is_component_xor: bool,
-    ) -> PrivateWire {
+    ) -> (PrivateWire, PrivateWire, PrivateWire) {
+        self.append_logic_rows(a, None, b, num_bits, is_component_xor)
+            .last()
+            .copied()
+            .unwrap_or((Self::ZERO, Self::ZERO, Self::ZERO))
+    }
+
+    /// Append the gates of [`Self::append_logic_component`], returning the
+    /// accumulators `(ai, bi, di)` of every quad.
+    ///
+    /// If `left` is set, its wires are the accumulators of `a` instead of
+    /// new witnesses, e.g. the output accumulators of a previous logic
+    /// component, so `a` is the value they accumulate and isn't read from a
+    /// wire of its own. A padded most significant bit of `left` is expected
+    /// to be constrained already.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    fn append_logic_rows(
+        &mut self,
+        a: PrivateWire,
+        left: Option<&[PrivateWire]>,
+        b: PrivateWire,
+        num_bits: usize,
+        is_component_xor: bool,
+    ) -> Vec<(PrivateWire, PrivateWire, PrivateWire)> {
         let num_bits = cmp::min(num_bits, 256);
-        let num_quads = num_bits >> 1;
+        let padded = num_bits + (num_bits & 1);
+        let num_quads = padded >> 1;
 
         let bls_four = C::Range::from(4u64);
         let mut left_acc = C::Range::zero();
         let mut right_acc = C::Range::zero();
         let mut out_acc = C::Range::zero();
+        let mut first_quads = None;
+        let mut rows = Vec::with_capacity(num_quads);
 
         // skip bits outside of argument `num_bits`
-        let a_bit_iter = BitIterator8::new(self[a].to_raw_bytes());
-        let a_bits = a_bit_iter.skip(256 - num_bits).collect::<Vec<_>>();
-        let b_bit_iter = BitIterator8::new(self[b].to_raw_bytes());
-        let b_bits = b_bit_iter.skip(256 - num_bits).collect::<Vec<_>>();
+        let a_bit_iter =
+            BitIterator8::new(Self::canonical(self[a]).to_raw_bytes());
+        let mut a_bits = a_bit_iter.skip(256 - padded).collect::<Vec<_>>();
+        let b_bit_iter =
+            BitIterator8::new(Self::canonical(self[b]).to_raw_bytes());
+        let mut b_bits = b_bit_iter.skip(256 - padded).collect::<Vec<_>>();
+
+        // an odd `num_bits` is padded with a zero most significant bit
+        if padded != num_bits {
+            a_bits[0] = false;
+            b_bits[0] = false;
+        }
 
         //
         // * +-----+-----+-----+-----+
@@ -362,7 +917,10 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
             right_acc = right_acc * bls_four + right_quad_bls;
             out_acc = out_acc * bls_four + out_quad_bls;
 
-            let wit_a = self.append_witness(left_acc);
+            let wit_a = match left {
+                Some(left) => left[i],
+                None => self.append_witness(left_acc),
+            };
             let wit_b = self.append_witness(right_acc);
             let wit_c = self.append_witness(prod_quad_bls);
             let wit_d = self.append_witness(out_acc);
@@ -372,6 +930,11 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
             self.append_custom_gate(constraint);
 
             constraint = constraint.a(wit_a).b(wit_b).d(wit_d);
+            rows.push((wit_a, wit_b, wit_d));
+
+            if i == 0 {
+                first_quads = Some((wit_a, wit_b));
+            }
         }
 
         // pad last output with `0`
@@ -384,174 +947,46 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
 
         self.append_custom_gate(constraint);
 
-        d
-    }
-
-    /// Evaluate `jubjub · Generator` as a [`WitnessPoint`]
-    ///
-    /// `generator` will be appended to the circuit description as constant
-    ///
-    /// Will error if `jubjub` doesn't fit `Fr`
-    pub fn component_mul_generator<A: Into<C::Extended>>(
-        &mut self,
-        jubjub: PrivateWire,
-        generator: A,
-    ) -> Result<WitnessPoint, Error> {
-        let generator = generator.into();
-
-        // the number of bits is truncated to the maximum possible. however, we
-        // could slice off 3 bits from the top of wnaf since Fr price is
-        // 252 bits. Alternatively, we could move to base4 and halve the
-        // number of gates considering that the product of wnaf adjacent
-        // entries is zero.
-        let bits: usize = 256;
-
-        // compute 2^iG
-        let mut wnaf_point_multiples = {
-            let mut multiples = vec![C::Extended::ADDITIVE_IDENTITY; bits];
-
-            multiples[0] = generator;
-
-            for i in 1..bits {
-                multiples[i] = multiples[i - 1].double();
-            }
-
-            multiples
-                .iter()
-                .map(|point| C::from(*point))
-                .collect::<Vec<_>>()
-        };
-
-        wnaf_point_multiples.reverse();
-
-        // we should error instead of producing invalid proofs - otherwise this
-        // can easily become an attack vector to either shutdown prover
-        // services or create malicious statements
-        let scalar = self[jubjub];
-
-        let width = 2;
-        let wnaf_entries = compute_windowed_naf(scalar, width);
-
-        debug_assert_eq!(wnaf_entries.len(), bits);
-
-        // initialize the accumulators
-        let mut scalar_acc = vec![C::Range::zero()];
-        let mut point_acc = vec![C::ADDITIVE_IDENTITY];
-
-        // auxillary point to help with checks on the backend
-        let two = C::Range::from(2u64);
-        let xy_alphas: Vec<_> = wnaf_entries
-            .iter()
-            .rev()
-            .enumerate()
-            .map(|(i, entry)| {
-                let (scalar_to_add, point_to_add) = match entry {
-                    0 => (C::Range::zero(), C::ADDITIVE_IDENTITY),
-                    -1 => (C::Range::one().neg(), -wnaf_point_multiples[i]),
-                    1 => (C::Range::one(), wnaf_point_multiples[i]),
-                    _ => return Err(Error::UnsupportedWNAF2k),
-                };
-
-                let prev_accumulator = two * scalar_acc[i];
-                let scalar = prev_accumulator + scalar_to_add;
-                scalar_acc.push(scalar);
-
-                let point = point_acc[i] + point_to_add;
-                point_acc.push(C::from(point));
-
-                let x_alpha = point_to_add.get_x();
-                let y_alpha = point_to_add.get_y();
-
-                Ok(x_alpha * y_alpha)
-            })
-            .collect::<Result<_, Error>>()?;
-
-        for i in 0..bits {
-            let acc_x = self.append_witness(point_acc[i].get_x());
-            let acc_y = self.append_witness(point_acc[i].get_y());
-            let accumulated_bit = self.append_witness(scalar_acc[i]);
-
-            // the point accumulator must start from identity and its scalar
-            // from zero
-            if i == 0 {
-                self.assert_equal_constant(acc_x, C::Range::zero(), None);
-                self.assert_equal_constant(acc_y, C::Range::one(), None);
-                self.assert_equal_constant(
-                    accumulated_bit,
-                    C::Range::zero(),
-                    None,
-                );
+        // the padded quad holds a single bit, so its accumulators are boolean.
+        // the logic gates read the next row, so these can't be interleaved
+        if let (true, Some((a1, b1))) = (padded != num_bits, first_quads) {
+            if left.is_none() {
+                self.component_boolean(a1);
             }
-
-            let x_beta = wnaf_point_multiples[i].get_x();
-            let y_beta = wnaf_point_multiples[i].get_y();
-
-            let xy_alpha = self.append_witness(xy_alphas[i]);
-            let xy_beta = x_beta * y_beta;
-
-            let wnaf_round = WnafRound::<PrivateWire, C::Range> {
-                acc_x,
-                acc_y,
-                accumulated_bit,
-                xy_alpha,
-                x_beta,
-                y_beta,
-                xy_beta,
-            };
-
-            let constraint =
-                Constraint::group_add_curve_scalar(Constraint::default())
-                    .left(wnaf_round.x_beta)
-                    .right(wnaf_round.y_beta)
-                    .constant(wnaf_round.xy_beta)
-                    .a(wnaf_round.acc_x)
-                    .b(wnaf_round.acc_y)
-                    .o(wnaf_round.xy_alpha)
-                    .d(wnaf_round.accumulated_bit);
-
-            self.append_custom_gate(constraint)
+            self.component_boolean(b1);
         }
 
-        // last gate isn't activated for ecc
-        let acc_x = self.append_witness(point_acc[bits].get_x());
-        let acc_y = self.append_witness(point_acc[bits].get_y());
-
-        // FIXME this implementation presents a plethora of vulnerabilities and
-        // requires reworking
-        //
-        // we are accepting any scalar argument and trusting it to be the
-        // expected input. it happens to be correct in this
-        // implementation, but can be exploited by malicious provers who
-        // might just input anything here
-        let last_accumulated_bit = self.append_witness(scalar_acc[bits]);
-
-        // FIXME the gate isn't checking anything. maybe remove?
-        let constraint = Constraint::default()
-            .a(acc_x)
-            .b(acc_y)
-            .d(last_accumulated_bit);
-        self.append_gate(constraint);
-
-        // constrain the last element in the accumulator to be equal to the
-        // input jubjub scalar
-        self.assert_equal(last_accumulated_bit, jubjub);
-
-        Ok(WitnessPoint::new(acc_x, acc_y))
+        rows
     }
 
     /// Append a new width-4 poly gate/constraint.
     ///
     /// The constraint added will enforce the following:
     /// `q_m · a · b  + q_l · a + q_r · b + q_o · o + q_4 · d + q_c + PI = 0`.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_gate(&mut self, constraint: Constraint<C::Range>) {
         let constraint = Constraint::arithmetic(constraint);
 
         self.append_custom_gate(constraint)
     }
 
+    /// Append every constraint with [`Self::append_gate`], reserving the
+    /// lower bound of the size of `constraints` first
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_gates<I>(&mut self, constraints: I)
+    where
+        I: IntoIterator<Item = Constraint<C::Range>>,
+    {
+        let constraints = constraints.into_iter();
+        self.reserve(constraints.size_hint().0, 0);
+
+        constraints.for_each(|constraint| self.append_gate(constraint));
+    }
+
     /// Evaluate the polynomial and append an output that satisfies the equation
     ///
     /// Return `None` if the output selector is zero
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_evaluated_output(
         &mut self,
         s: Constraint<C::Range>,
@@ -603,6 +1038,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
 
     /// Adds blinding factors to the witness polynomials with two dummy
     /// arithmetic constraints
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_dummy_gates(&mut self) {
         let six = self.append_witness(C::Range::from(6));
         let one = self.append_witness(C::Range::from(1));
@@ -641,6 +1077,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
 
     /// Constrain a scalar into the circuit description and return an allocated
     /// [`PrivateWire`] with its value
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_constant<A: Into<C::Range>>(
         &mut self,
         constant: A,
@@ -654,6 +1091,10 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     }
 
     /// Appends a point in affine form as [`WitnessPoint`]
+    ///
+    /// The point isn't constrained to lie on the curve, see
+    /// [`Self::append_point_checked`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_point<A: Into<C>>(&mut self, affine: A) -> WitnessPoint {
         let affine = affine.into();
 
@@ -665,6 +1106,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
 
     /// Constrain a point into the circuit description and return an allocated
     /// [`WitnessPoint`] with its coordinates
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_constant_point<A: Into<C>>(
         &mut self,
         affine: A,
@@ -680,6 +1122,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     /// Appends a point in affine form as [`WitnessPoint`]
     ///
     /// Creates two public inputs as `(x, y)`
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_public_point<A: Into<C>>(
         &mut self,
         affine: A,
@@ -702,9 +1145,70 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
         point
     }
 
+    /// [`Self::append_point`], asserting the point lies on the curve.
+    ///
+    /// The coordinates are constrained by the curve equation, so a point off
+    /// the curve, as one decoded from untrusted data without a check, can't
+    /// be proven.
+    ///
+    /// Consume `3` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_point_checked<A: Into<C>>(
+        &mut self,
+        affine: A,
+    ) -> WitnessPoint {
+        let point = self.append_point(affine);
+        self.assert_on_curve(point);
+
+        point
+    }
+
+    /// [`Self::append_public_point`], asserting the point lies on the curve.
+    ///
+    /// Consume `5` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_public_point_checked<A: Into<C>>(
+        &mut self,
+        affine: A,
+    ) -> WitnessPoint {
+        let point = self.append_public_point(affine);
+        self.assert_on_curve(point);
+
+        point
+    }
+
+    /// Record a debug assertion on the `y` coordinate of `point` if it
+    /// doesn't lie on the curve, expecting the `y` of the same sign on the
+    /// curve for its `x`, or zero if there's none.
+    ///
+    /// This is a no-op without the `debug-assertions` feature, and never adds
+    /// gates to the circuit.
+    #[allow(unused_variables)]
+    fn debug_assert_on_curve(
+        &mut self,
+        point: WitnessPoint,
+        label: &'static str,
+    ) {
+        #[cfg(feature = "debug-assertions")]
+        {
+            let d = crate::gadget::ecc::curve_d::<C>();
+            let (x, y) = (self[*point.x()], self[*point.y()]);
+
+            let (x2, y2) = (x * x, y * y);
+            if y2 - x2 != C::Range::one() + d * x2 * y2 {
+                let sign = crate::gadget::ecc::parity(y);
+                let expected = crate::gadget::ecc::recover_y(d, x, sign)
+                    .unwrap_or_else(C::Range::zero);
+
+                self.append_debug_assertion(*point.y(), expected, label);
+            }
+        }
+    }
+
     /// Allocate a witness value into the composer and return its index.
     ///
     /// Create a public input with the scalar
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_public<A: Into<C::Range>>(
         &mut self,
         public: A,
@@ -717,7 +1221,67 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
         witness
     }
 
+    /// Allocate a digest computed with [`public_digest`] into the composer
+    /// and return its index.
+    ///
+    /// The digest is a public input as with [`Self::append_public`], but it
+    /// is recorded as a digest in the layout of the public inputs, so
+    /// [`Verifier::verify_with_preimage`] hashes the data in its place.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_public_digest<A: Into<C::Range>>(
+        &mut self,
+        digest: A,
+    ) -> PrivateWire {
+        let witness = self.append_public(digest);
+
+        // the public input belongs to the last gate
+        self.digests
+            .insert(self.constraints.len() - 1, DIGEST_ALGORITHM);
+
+        witness
+    }
+
+    /// Allocate a witness value into the composer and return its index.
+    ///
+    /// Instead of the scalar, the public inputs are the coordinates of its
+    /// Pedersen commitment `value · G + blinding · H`, so the verifier checks
+    /// the proof against the commitment without learning the value. The
+    /// commitment is computed in the circuit with the constraints of
+    /// [`Self::component_mul_generator`], so the scalars are unconstrained.
+    ///
+    /// `blinding_generator` must have an unknown discrete logarithm in respect
+    /// to `value_generator`, otherwise the commitment isn't binding.
+    ///
+    /// Will error if `value` or `blinding` doesn't fit `Fr`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_committed_public<A, G>(
+        &mut self,
+        value: A,
+        blinding: A,
+        value_generator: G,
+        blinding_generator: G,
+    ) -> Result<PrivateWire, Error>
+    where
+        A: Into<C::Range>,
+        G: Into<C::Extended>,
+    {
+        let value = self.append_witness(value);
+        let blinding = self.append_witness(blinding);
+
+        let value_point = self.mul_generator(value, value_generator, false)?;
+        let blinding_point =
+            self.mul_generator(blinding, blinding_generator, false)?;
+        let commitment = self.component_add_point(value_point, blinding_point);
+
+        let public =
+            C::from_raw_unchecked(self[*commitment.x()], self[*commitment.y()]);
+        self.assert_equal_public_point(commitment, public);
+
+        Ok(value)
+    }
+
     /// Asserts `a == b` by appending a gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn assert_equal(&mut self, a: PrivateWire, b: PrivateWire) {
         let constraint = Constraint::default()
             .left(1)
@@ -728,125 +1292,325 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
         self.append_gate(constraint);
     }
 
-    /// Adds a logical AND gate that performs the bitwise AND between two values
-    /// for the specified first `num_bits` returning a [`PrivateWire`]
-    /// holding the result.
+    /// Asserts `a == b` only if `flag` is set, with the constraint
+    /// `flag · (a - b) = 0`.
+    ///
+    /// A gate has a single product term, so the difference takes a gate of
+    /// its own. `flag` is expected to be constrained by
+    /// [`Composer::component_boolean`].
+    ///
+    /// Consume `2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_equal_if(
+        &mut self,
+        flag: PrivateWire,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) {
+        let diff = self.gate_sub(a, b);
+
+        let constraint = Constraint::default().mult(1).a(flag).b(diff);
+        self.append_gate(constraint);
+    }
+
+    /// Append the arithmetic `constraint` only if `flag` is set, with the
+    /// constraint `flag · e = 0` where `e` is the evaluation of the gate
+    /// polynomial.
+    ///
+    /// All four wires of `constraint` may be used, so `e` is evaluated by two
+    /// auxiliary gates: the first one sums the terms of the output and the
+    /// fourth wires with the constant, and the second one adds the terms of
+    /// the left and right wires. `flag` is expected to be constrained by
+    /// [`Composer::component_boolean`].
+    ///
+    /// Consume `3` gates
     ///
     /// # Panics
     ///
-    /// If the `num_bits` specified in the fn params is odd.
+    /// If `constraint` carries a public input, which can't be disabled.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_gate_if(
+        &mut self,
+        flag: PrivateWire,
+        constraint: Constraint<C::Range>,
+    ) {
+        assert!(
+            constraint.public_input.is_none(),
+            "a conditional gate can't carry a public input"
+        );
+
+        // q_o · o + q_4 · d + q_c
+        let partial = Constraint::default()
+            .left(constraint.q_o)
+            .right(constraint.q_d)
+            .constant(constraint.q_c)
+            .a(constraint.w_o)
+            .b(constraint.w_d);
+        let partial = self.gate_add(partial);
+
+        // q_m · a · b + q_l · a + q_r · b + partial
+        let evaluation = Constraint::default()
+            .mult(constraint.q_m)
+            .left(constraint.q_l)
+            .right(constraint.q_r)
+            .fourth(1)
+            .a(constraint.w_a)
+            .b(constraint.w_b)
+            .d(partial);
+        let evaluation = self.gate_mul(evaluation);
+
+        let constraint = Constraint::default().mult(1).a(flag).b(evaluation);
+        self.append_gate(constraint);
+    }
+
+    /// Adds a logical AND gate that performs the bitwise AND between two values
+    /// for the specified first `num_bits` returning a [`PrivateWire`]
+    /// holding the result.
+    ///
+    /// Only the low `num_bits` bits are operated on, for any `num_bits` up to
+    /// 256; an odd `num_bits` is padded with a constrained zero bit.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_logic_and(
         &mut self,
         a: PrivateWire,
         b: PrivateWire,
         num_bits: usize,
     ) -> PrivateWire {
-        self.append_logic_component(a, b, num_bits, false)
+        self.append_logic_component(a, b, num_bits, false).2
     }
 
     /// Adds a logical XOR gate that performs the XOR between two values for the
     /// specified first `num_bits` returning a [`PrivateWire`] holding the
     /// result.
     ///
-    /// # Panics
-    ///
-    /// If the `num_bits` specified in the fn params is odd.
+    /// Only the low `num_bits` bits are operated on, for any `num_bits` up to
+    /// 256; an odd `num_bits` is padded with a constrained zero bit.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn append_logic_xor(
         &mut self,
         a: PrivateWire,
         b: PrivateWire,
         num_bits: usize,
     ) -> PrivateWire {
-        self.append_logic_component(a, b, num_bits, true)
+        self.append_logic_component(a, b, num_bits, true).2
     }
 
-    /// Constrain `a` to be equal to `constant + pi`.
+    /// Evaluate the XOR of the first `num_bits` of `a`, `b` and `c`,
+    /// returning a [`PrivateWire`] holding the result.
     ///
-    /// `constant` will be defined as part of the public circuit description.
-    pub fn assert_equal_constant<A: Into<C::Range>>(
+    /// The logic gates only have room for two operands, so this chains two
+    /// XOR components, but the second one takes the output accumulators of
+    /// the first one as its left accumulators instead of extracting the bits
+    /// of `a ^ b` again. The second XOR is then bound to the result of the
+    /// first one, which two calls to [`Self::append_logic_xor`] don't
+    /// constrain, and its padded bit is already boolean.
+    ///
+    /// Only the low `num_bits` bits are operated on, for any `num_bits` up to
+    /// 256; an odd `num_bits` is padded with a constrained zero bit.
+    ///
+    /// Consume `2 · (⌈num_bits / 2⌉ + 1)` gates, as two calls to
+    /// [`Self::append_logic_xor`], plus `3` gates if `num_bits` is odd
+    /// instead of `4`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_logic_xor3(
         &mut self,
         a: PrivateWire,
-        constant: A,
-        public: Option<C::Range>,
-    ) {
-        let constant = constant.into();
-        let constraint = Constraint::default().left(1).constant(-constant).a(a);
-        let constraint =
-            public.map(|p| constraint.public(p)).unwrap_or(constraint);
-
-        self.append_gate(constraint);
-    }
-
-    /// Asserts `a == b` by appending two gates
-    pub fn assert_equal_point(&mut self, a: WitnessPoint, b: WitnessPoint) {
-        self.assert_equal(*a.x(), *b.x());
-        self.assert_equal(*a.y(), *b.y());
+        b: PrivateWire,
+        c: PrivateWire,
+        num_bits: usize,
+    ) -> PrivateWire {
+        let rows = self.append_logic_rows(a, None, b, num_bits, true);
+        let left: Vec<_> = rows.iter().map(|(_, _, d)| *d).collect();
+        let ab = left.last().copied().unwrap_or(Self::ZERO);
+
+        self.append_logic_rows(ab, Some(&left), c, num_bits, true)
+            .last()
+            .map(|(_, _, d)| *d)
+            .unwrap_or(Self::ZERO)
     }
 
-    /// Asserts `point == public`.
+    /// Evaluate `a ^ b ^ c` for boolean wires, as `t = a + b - 2 · a · b`
+    /// and `t + c - 2 · t · c`.
     ///
-    /// Will add `public` affine coordinates `(x,y)` as public inputs
-    pub fn assert_equal_public_point<A: Into<C>>(
+    /// `a`, `b` and `c` are expected to be constrained by
+    /// [`Self::component_boolean`]
+    ///
+    /// Consume `2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_xor3(
         &mut self,
-        point: WitnessPoint,
-        public: A,
-    ) {
-        let public = public.into();
+        a: PrivateWire,
+        b: PrivateWire,
+        c: PrivateWire,
+    ) -> PrivateWire {
+        let two = C::Range::from(2u64);
 
-        self.assert_equal_constant(
-            *point.x(),
-            C::Range::zero(),
-            Some(C::Range::into(-public.get_x())),
-        );
+        let constraint =
+            Constraint::default().mult(-two).left(1).right(1).a(a).b(b);
+        let t = self.gate_mul(constraint);
 
-        self.assert_equal_constant(
-            *point.y(),
-            C::Range::zero(),
-            Some(C::Range::into(-public.get_y())),
-        );
+        let constraint =
+            Constraint::default().mult(-two).left(1).right(1).a(t).b(c);
+        self.gate_mul(constraint)
     }
 
-    /// Adds two curve points by consuming 2 gates.
-    pub fn component_add_point(
+    /// Evaluate the SHA-2 choice `ch(a, b, c) = (a & b) ^ (!a & c)` for
+    /// boolean wires, as `a · (b - c) + c`.
+    ///
+    /// The product `a · c` is folded with `- c` into a first gate, and the
+    /// second gate evaluates `a · b` minus it.
+    ///
+    /// `a`, `b` and `c` are expected to be constrained by
+    /// [`Self::component_boolean`]. Otherwise the result isn't a bit: a
+    /// non-boolean `a` scales `b - c` instead of selecting
+    ///
+    /// Consume `2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_ch(
         &mut self,
-        a: WitnessPoint,
-        b: WitnessPoint,
-    ) -> WitnessPoint {
-        // In order to verify that two points were correctly added
-        // without going over a degree 4 polynomial, we will need
-        // x_1, y_1, x_2, y_2
-        // x_3, y_3, x_1 * y_2
+        a: PrivateWire,
+        b: PrivateWire,
+        c: PrivateWire,
+    ) -> PrivateWire {
+        // a · c - c
+        let constraint = Constraint::default()
+            .mult(1)
+            .right(-C::Range::one())
+            .a(a)
+            .b(c);
+        let t = self.gate_mul(constraint);
 
-        let x_1 = *a.x();
-        let y_1 = *a.y();
-        let x_2 = *b.x();
-        let y_2 = *b.y();
+        // a · b - t
+        let constraint = Constraint::default()
+            .mult(1)
+            .fourth(-C::Range::one())
+            .a(a)
+            .b(b)
+            .d(t);
+        self.gate_mul(constraint)
+    }
 
-        let p1 = C::from_raw_unchecked(self[x_1], self[y_1]);
-        let p2 = C::from_raw_unchecked(self[x_2], self[y_2]);
+    /// Evaluate the SHA-2 majority `maj(a, b, c) = ab + bc + ca - 2abc` for
+    /// boolean wires.
+    ///
+    /// A gate holds a single product, and no two gates reach the degree `3`
+    /// term along with the three products of two inputs, so the majority is
+    /// evaluated as the choice `b + (b ^ c) · (a - b)`: `a` if `b` and `c`
+    /// differ, and `b` otherwise.
+    ///
+    /// `a`, `b` and `c` are expected to be constrained by
+    /// [`Self::component_boolean`]. Otherwise the result isn't a bit
+    ///
+    /// Consume `3` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_maj(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+        c: PrivateWire,
+    ) -> PrivateWire {
+        // b ^ c = b + c - 2 · b · c
+        let constraint = Constraint::default()
+            .mult(-C::Range::from(2u64))
+            .left(1)
+            .right(1)
+            .a(b)
+            .b(c);
+        let x = self.gate_mul(constraint);
 
-        let point = C::from(p1 + p2);
+        // x · b - b
+        let constraint = Constraint::default()
+            .mult(1)
+            .right(-C::Range::one())
+            .a(x)
+            .b(b);
+        let t = self.gate_mul(constraint);
 
-        let x_3 = point.get_x();
-        let y_3 = point.get_y();
+        // x · a - t
+        let constraint = Constraint::default()
+            .mult(1)
+            .fourth(-C::Range::one())
+            .a(x)
+            .b(a)
+            .d(t);
+        self.gate_mul(constraint)
+    }
 
-        let x1_y2 = self[x_1] * self[y_2];
+    /// Adds a logical OR gate that performs the bitwise OR between two values
+    /// for the specified first `num_bits` returning a [`PrivateWire`]
+    /// holding the result.
+    ///
+    /// The OR is derived from the AND accumulators, `a | b = a + b - (a & b)`,
+    /// so it costs a single gate on top of [`Self::append_logic_and`].
+    ///
+    /// Only the low `num_bits` bits are operated on, for any `num_bits` up to
+    /// 256; an odd `num_bits` is padded with a constrained zero bit.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn append_logic_or(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+        num_bits: usize,
+    ) -> PrivateWire {
+        let (a, b, d) = self.append_logic_component(a, b, num_bits, false);
 
-        let x_1_y_2 = self.append_witness(x1_y2);
-        let x_3 = self.append_witness(x_3);
-        let y_3 = self.append_witness(y_3);
+        let constraint = Constraint::default()
+            .left(1)
+            .right(1)
+            .fourth(-C::Range::one())
+            .a(a)
+            .b(b)
+            .d(d);
 
-        // Add the rest of the prepared points into the composer
-        let constraint = Constraint::default().a(x_1).b(y_1).o(x_2).d(y_2);
-        let constraint = Constraint::group_add_curve_addtion(constraint);
+        self.gate_add(constraint)
+    }
 
-        self.append_custom_gate(constraint);
+    /// Evaluate the bitwise complement of the first `num_bits` of `a`,
+    /// `(!a) & (2^num_bits - 1)`.
+    ///
+    /// The first `num_bits` of `a` are read from the accumulators of the logic
+    /// component and subtracted from the constant `2^num_bits - 1`, so no
+    /// constant witness is appended. For `num_bits = 0` the result is zero,
+    /// and for `num_bits = 256` the complement doesn't fit the field and is
+    /// reduced modulo its order.
+    ///
+    /// An odd `num_bits` is padded with a constrained zero bit.
+    ///
+    /// Consume `⌈num_bits / 2⌉ + 2` gates, two more if `num_bits` is odd
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_not(
+        &mut self,
+        a: PrivateWire,
+        num_bits: usize,
+    ) -> PrivateWire {
+        let num_bits = cmp::min(num_bits, 256);
+        let (a, _, _) = self.append_logic_component(a, a, num_bits, false);
+        let mask = C::Range::pow_of_2(num_bits as u64) - C::Range::one();
 
-        let constraint = Constraint::default().a(x_3).b(y_3).d(x_1_y_2);
+        let constraint = Constraint::default()
+            .left(-C::Range::one())
+            .constant(mask)
+            .a(a);
 
-        self.append_custom_gate(constraint);
+        self.gate_add(constraint)
+    }
+
+    /// Constrain `a` to be equal to `constant + pi`.
+    ///
+    /// `constant` will be defined as part of the public circuit description.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn assert_equal_constant<A: Into<C::Range>>(
+        &mut self,
+        a: PrivateWire,
+        constant: A,
+        public: Option<C::Range>,
+    ) {
+        let constant = constant.into();
+        let constraint = Constraint::default().left(1).constant(-constant).a(a);
+        let constraint =
+            public.map(|p| constraint.public(p)).unwrap_or(constraint);
 
-        WitnessPoint::new(x_3, y_3)
+        self.append_gate(constraint);
     }
 
     /// Adds a boolean constraint (also known as binary constraint) where the
@@ -856,6 +1620,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     /// Note that using this constraint with whatever [`PrivateWire`] that
     /// is not representing a value equalling 0 or 1, will always force the
     /// equation to fail.
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn component_boolean(&mut self, a: PrivateWire) {
         let zero = Self::ZERO;
         let constraint = Constraint::default()
@@ -866,87 +1631,662 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
             .o(a)
             .d(zero);
 
-        self.append_gate(constraint);
+        self.append_gate(constraint);
+    }
+
+    /// Evaluate `a⁻¹` and constrain `a · a⁻¹ = 1`.
+    ///
+    /// The constraint makes `a = 0` unprovable by design; use
+    /// [`Self::component_inverse_or_zero`] if `a` can be zero. Returns
+    /// [`DivisionByZero`] if the value of `a` is zero, instead of appending an
+    /// unsatisfiable gate.
+    ///
+    /// Consume `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_inverse(
+        &mut self,
+        a: PrivateWire,
+    ) -> Result<PrivateWire, DivisionByZero> {
+        let inv = self[a].invert().ok_or(DivisionByZero { wire: a.index() })?;
+        let inv = self.append_witness(inv);
+
+        // a · a⁻¹ - 1 = 0
+        let constraint = Constraint::default()
+            .mult(1)
+            .constant(-C::Range::one())
+            .a(a)
+            .b(inv);
+        self.append_gate(constraint);
+
+        Ok(inv)
+    }
+
+    /// Evaluate `(a⁻¹, 0)` for a non zero `a`, and `(0, 1)` for `a = 0`.
+    ///
+    /// The constraints are `z = 1 - a · inv` and `a · z = 0`: for a non zero
+    /// `a` they force `z = 0` and `inv = a⁻¹`, and for `a = 0` they force
+    /// `z = 1`, so the zero bit is boolean. The inverse of zero isn't
+    /// constrained, and is set to zero by the prover.
+    ///
+    /// Consume `2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_inverse_or_zero(
+        &mut self,
+        a: PrivateWire,
+    ) -> (PrivateWire, PrivateWire) {
+        let inv = self[a].invert().unwrap_or_else(C::Range::zero);
+        let is_zero = C::Range::one() - self[a] * inv;
+
+        let inv = self.append_witness(inv);
+        let is_zero = self.append_witness(is_zero);
+
+        // a · inv + z - 1 = 0
+        let constraint = Constraint::default()
+            .mult(1)
+            .output(1)
+            .constant(-C::Range::one())
+            .a(a)
+            .b(inv)
+            .o(is_zero);
+        self.append_gate(constraint);
+
+        // a · z = 0
+        let constraint = Constraint::default().mult(1).a(a).b(is_zero);
+        self.append_gate(constraint);
+
+        (inv, is_zero)
+    }
+
+    /// Decomposes `scalar` into an array truncated to `N` bits (max 256).
+    ///
+    /// Asserts the reconstruction of the bits to be equal to `scalar`.
+    ///
+    /// Consume `2 · N + 1` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_decomposition<const N: usize>(
+        &mut self,
+        scalar: PrivateWire,
+    ) -> [PrivateWire; N] {
+        // Static assertion
+        assert!(0 < N && N <= 256);
+
+        self.assert_reserved_zero_one("component_decomposition");
+
+        let mut decomposition = [Self::ZERO; N];
+        decomposition.copy_from_slice(&self.decompose(scalar, N));
+
+        decomposition
+    }
+
+    /// Assert the little endian `bits` encode an integer strictly below the
+    /// field modulus.
+    ///
+    /// A decomposition into 255 bits can encode both `v` and `v + p`, so the
+    /// bits are compared to those of `p - 1` from the most significant one,
+    /// carrying whether all the bits so far are equal to them. Where the bit
+    /// of `p - 1` is zero, the bit of `bits` must be zero while they are
+    /// equal, otherwise the carry is multiplied by the bit.
+    ///
+    /// The wires are expected to be boolean, as the ones of
+    /// [`Self::component_decomposition`], or constrained with
+    /// [`Self::component_boolean`].
+    ///
+    /// Consume `255` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_assert_canonical(&mut self, bits: &[PrivateWire; 255]) {
+        self.assert_reserved_zero_one("component_assert_canonical");
+
+        let max: Vec<bool> =
+            (-C::Range::one()).to_bits().iter().rev().copied().collect();
+
+        self.assert_bits_at_most(bits, &max);
+    }
+
+    /// Decomposes `scalar` into `N` little endian bytes (max 32).
+    ///
+    /// Every byte is range checked to 8 bits, and their weighted sum is
+    /// asserted to be equal to `scalar`, so for `N < 32` the bytes above `N`
+    /// are constrained to be zero. With `N = 32` the bytes of `scalar` plus
+    /// the field order also satisfy the constraints if they fit 32 bytes.
+    ///
+    /// Consume `3 · N + ⌈(N - 3) / 2⌉ + 2` gates for `N > 3`, and `3 · N + 2`
+    /// otherwise
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_decomposition_bytes<const N: usize>(
+        &mut self,
+        scalar: PrivateWire,
+    ) -> [PrivateWire; N] {
+        // Static assertion
+        assert!(0 < N && N <= 32);
+
+        let raw = Self::canonical(self[scalar]).to_raw_bytes();
+
+        let mut decomposition = [Self::ZERO; N];
+        decomposition
+            .iter_mut()
+            .zip(raw.iter())
+            .for_each(|(d, byte)| {
+                *d = self.append_witness(C::Range::from(*byte as u64));
+                self.component_range(*d, 8);
+            });
+
+        let radix = C::Range::from(256u64);
+        let terms: Vec<_> = decomposition
+            .iter()
+            .scan(C::Range::one(), |weight, byte| {
+                let term = (*weight, *byte);
+                *weight = *weight * radix;
+
+                Some(term)
+            })
+            .collect();
+
+        let sum = self.gate_sum(&terms, C::Range::zero());
+        self.assert_equal(sum, scalar);
+
+        decomposition
+    }
+
+    /// Decomposes `scalar` into `N` little endian digits in `base`.
+    ///
+    /// Every digit is constrained to `[0, base)`: a power of two base is
+    /// range checked to its bits, with [`Self::component_range`] for an even
+    /// number of bits or a bit decomposition otherwise, and any other base
+    /// asserts the digit belongs to the set of its digits with
+    /// [`Self::component_assert_in_set`]. Their
+    /// weighted sum is asserted to be equal to `scalar`, so the digits above
+    /// `N` are constrained to be zero. If `base^N` exceeds the field order,
+    /// the digits of `scalar` plus the field order also satisfy the
+    /// constraints if they fit `N` digits.
+    ///
+    /// Consume, per digit, the gates of the range check of a power of two
+    /// base or `base` gates otherwise, and the gates of [`Self::gate_sum`]
+    /// of `N` terms plus `1` gate for the recomposition
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero or `base` is lower than `2`.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_decomposition_base<const N: usize>(
+        &mut self,
+        scalar: PrivateWire,
+        base: u8,
+    ) -> [PrivateWire; N] {
+        // Static assertion
+        assert!(0 < N);
+        assert!(base >= 2, "the base must have at least two digits");
+
+        let digits = Self::digits_in_base(self[scalar], base, N);
+
+        let mut decomposition = [Self::ZERO; N];
+        decomposition
+            .iter_mut()
+            .zip(digits.iter())
+            .for_each(|(d, digit)| {
+                *d = self.append_witness(C::Range::from(*digit as u64))
+            });
+
+        let set: Vec<C::Range> = (0..base)
+            .map(|digit| C::Range::from(digit as u64))
+            .collect();
+        let bits = base.trailing_zeros() as usize;
+        for d in decomposition.iter() {
+            match base.is_power_of_two() {
+                true => {
+                    self.range_any(*d, bits, "component_decomposition_base")
+                }
+                false => self.component_assert_in_set(*d, &set),
+            }
+        }
+
+        let radix = C::Range::from(base as u64);
+        let terms: Vec<_> = decomposition
+            .iter()
+            .scan(C::Range::one(), |weight, digit| {
+                let term = (*weight, *digit);
+                *weight = *weight * radix;
+
+                Some(term)
+            })
+            .collect();
+
+        let sum = self.gate_sum(&terms, C::Range::zero());
+        self.assert_equal(sum, scalar);
+
+        decomposition
+    }
+
+    /// First `n` little endian digits in `base` of the canonical
+    /// representation of `scalar`
+    fn digits_in_base(scalar: C::Range, base: u8, n: usize) -> Vec<u8> {
+        let mut bytes = Self::canonical(scalar).to_raw_bytes();
+        let base = base as u16;
+
+        // long division of the little endian bytes by the base, from the
+        // most significant byte
+        (0..n)
+            .map(|_| {
+                let mut remainder = 0u16;
+                bytes.iter_mut().rev().for_each(|byte| {
+                    let acc = (remainder << 8) | *byte as u16;
+                    *byte = (acc / base) as u8;
+                    remainder = acc % base;
+                });
+
+                remainder as u8
+            })
+            .collect()
+    }
+
+    /// Maximum bits of [`Self::component_compose_bits`] and
+    /// [`Self::component_compose_bytes`], so the recomposition can't wrap
+    /// around the field modulus
+    pub const MAX_COMPOSE_BITS: usize = 255;
+
+    /// Evaluate `Σ 2^i · bits[i]`, the inverse of
+    /// [`Self::component_decomposition`].
+    ///
+    /// Every bit is expected to be constrained by
+    /// [`Composer::component_boolean`]. Returns [`CompositionOverflow`] for
+    /// more than [`Self::MAX_COMPOSE_BITS`] bits.
+    ///
+    /// Consume the gates of [`Self::gate_sum`] of `bits.len()` terms
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_compose_bits(
+        &mut self,
+        bits: &[PrivateWire],
+    ) -> Result<PrivateWire, CompositionOverflow> {
+        self.compose_weighted(bits, 1)
+    }
+
+    /// Evaluate `Σ 256^i · bytes[i]`, the inverse of
+    /// [`Self::component_decomposition_bytes`].
+    ///
+    /// Every byte is expected to be constrained to 8 bits, unless
+    /// `constrain_bytes` is set. Returns [`CompositionOverflow`] if the bytes
+    /// exceed [`Self::MAX_COMPOSE_BITS`] bits, so for more than 31 bytes.
+    ///
+    /// Consume the gates of [`Self::gate_sum`] of `bytes.len()` terms, plus
+    /// `3` per byte if `constrain_bytes` is set
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_compose_bytes(
+        &mut self,
+        bytes: &[PrivateWire],
+        constrain_bytes: bool,
+    ) -> Result<PrivateWire, CompositionOverflow> {
+        let composed = self.compose_weighted(bytes, 8)?;
+
+        if constrain_bytes {
+            bytes.iter().for_each(|byte| self.component_range(*byte, 8));
+        }
+
+        Ok(composed)
+    }
+
+    /// `Σ 2^(width · i) · limbs[i]` with [`Self::gate_sum`]
+    fn compose_weighted(
+        &mut self,
+        limbs: &[PrivateWire],
+        width: usize,
+    ) -> Result<PrivateWire, CompositionOverflow> {
+        let bits = limbs.len() * width;
+        if bits > Self::MAX_COMPOSE_BITS {
+            return Err(CompositionOverflow {
+                bits,
+                max: Self::MAX_COMPOSE_BITS,
+            });
+        }
+
+        let terms: Vec<_> = limbs
+            .iter()
+            .enumerate()
+            .map(|(i, limb)| (C::Range::pow_of_2((i * width) as u64), *limb))
+            .collect();
+
+        Ok(self.gate_sum(&terms, C::Range::zero()))
+    }
+
+    /// Decompose `scalar` into its first `num_bits` bits, least significant
+    /// first, and assert their reconstruction is equal to `scalar`
+    fn decompose(
+        &mut self,
+        scalar: PrivateWire,
+        num_bits: usize,
+    ) -> Vec<PrivateWire> {
+        let mut decomposition = vec![Self::ZERO; num_bits];
+
+        let acc = Self::ZERO;
+        let acc = Self::canonical(self[scalar])
+            .to_bits()
+            .iter()
+            .rev()
+            .enumerate()
+            .zip(decomposition.iter_mut())
+            .fold(acc, |acc, ((i, w), d)| {
+                *d = self.append_witness(C::Range::from(*w as u64));
+
+                self.component_boolean(*d);
+
+                let constraint = Constraint::default()
+                    .left(C::Range::pow_of_2(i as u64))
+                    .right(1)
+                    .a(*d)
+                    .b(acc);
+
+                self.gate_add(constraint)
+            });
+
+        self.assert_equal(acc, scalar);
+
+        decomposition
+    }
+
+    /// Assert the little endian boolean `bits` encode an integer at most the
+    /// one of the little endian `max`, carrying from the most significant bit
+    /// whether all the bits so far are equal to the ones of `max`
+    ///
+    /// Consume a gate per bit
+    fn assert_bits_at_most(&mut self, bits: &[PrivateWire], max: &[bool]) {
+        bits.iter()
+            .enumerate()
+            .rev()
+            .fold(Self::ONE, |eq, (i, bit)| {
+                let constraint = Constraint::default().mult(1).a(eq).b(*bit);
+
+                // a bit above a zero of `max` must be zero while equal
+                match max.get(i).copied().unwrap_or(false) {
+                    true => self.gate_mul(constraint),
+                    false => {
+                        self.append_gate(constraint);
+                        eq
+                    }
+                }
+            });
+    }
+
+    /// Evaluate `Σ 2^i · bit` for the constrained bits of `bits`
+    fn compose_bits<I>(&mut self, bits: I) -> PrivateWire
+    where
+        I: IntoIterator<Item = (usize, PrivateWire)>,
+    {
+        bits.into_iter().fold(Self::ZERO, |acc, (i, bit)| {
+            let constraint = Constraint::default()
+                .left(C::Range::pow_of_2(i as u64))
+                .right(1)
+                .a(bit)
+                .b(acc);
+
+            self.gate_add(constraint)
+        })
+    }
+
+    /// Evaluate `(a << shift) mod 2^num_bits` for a constant `shift`.
+    ///
+    /// `a` is decomposed into its first `num_bits` bits (max 256) and
+    /// constrained to fit them. The result is recomposed from the bits that
+    /// stay in the width, so it fits `num_bits` bits by construction. A
+    /// `shift ≥ num_bits` yields [`Self::ZERO`].
+    ///
+    /// Consume `3 · num_bits - shift + 1` gates, or `2 · num_bits + 1` if
+    /// `shift ≥ num_bits`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_shl(
+        &mut self,
+        a: PrivateWire,
+        shift: usize,
+        num_bits: usize,
+    ) -> PrivateWire {
+        assert!(num_bits <= 256);
+
+        self.assert_reserved_zero_one("component_shl");
+
+        let bits = self.decompose(a, num_bits);
+        let kept = num_bits.saturating_sub(shift);
+
+        self.compose_bits(
+            bits.into_iter()
+                .take(kept)
+                .enumerate()
+                .map(|(i, bit)| (i + shift, bit)),
+        )
+    }
+
+    /// Evaluate `a >> shift` for a constant `shift`, with `a` constrained to
+    /// fit its first `num_bits` bits (max 256).
+    ///
+    /// The result is recomposed from the bits of `a` above `shift`, so it fits
+    /// `num_bits` bits by construction. A `shift ≥ num_bits` yields
+    /// [`Self::ZERO`].
+    ///
+    /// Consume `3 · num_bits - shift + 1` gates, or `2 · num_bits + 1` if
+    /// `shift ≥ num_bits`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_shr(
+        &mut self,
+        a: PrivateWire,
+        shift: usize,
+        num_bits: usize,
+    ) -> PrivateWire {
+        assert!(num_bits <= 256);
+
+        self.assert_reserved_zero_one("component_shr");
+
+        let bits = self.decompose(a, num_bits);
+
+        self.compose_bits(
+            bits.into_iter()
+                .enumerate()
+                .skip(shift)
+                .map(|(i, bit)| (i - shift, bit)),
+        )
+    }
+
+    /// Evaluate the 32-bit word `a` rotated `r` bits to the left.
+    ///
+    /// `a` is constrained to fit 32 bits, and the result is recomposed from
+    /// its bits, so it fits 32 bits too and can feed the logic gates. The
+    /// rotation is taken modulo 32, so rotating by 0 or 32 is the identity.
+    ///
+    /// Consume `97` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_rotl_u32(
+        &mut self,
+        a: PrivateWire,
+        r: usize,
+    ) -> PrivateWire {
+        self.assert_reserved_zero_one("component_rotl_u32");
+
+        let bits = self.decompose(a, 32);
+
+        self.compose_bits(
+            bits.into_iter()
+                .enumerate()
+                .map(|(i, bit)| ((i + r) % 32, bit)),
+        )
+    }
+
+    /// Evaluate the 32-bit word `a` rotated `r` bits to the right.
+    ///
+    /// Same as [`Self::component_rotl_u32`] rotating `32 - r % 32` bits to the
+    /// left.
+    ///
+    /// Consume `97` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_rotr_u32(
+        &mut self,
+        a: PrivateWire,
+        r: usize,
+    ) -> PrivateWire {
+        self.component_rotl_u32(a, 32 - r % 32)
+    }
+
+    /// Evaluate `Σ coefficients[i] · x^i` using Horner's rule.
+    ///
+    /// Every step `acc := acc · x + c` is a single gate with `q_m` on
+    /// `(acc, x)` and `q_4` on `c`. An empty slice evaluates to
+    /// [`Self::ZERO`].
+    ///
+    /// Consume `coefficients.len() - 1` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_horner_eval(
+        &mut self,
+        coefficients: &[PrivateWire],
+        x: PrivateWire,
+    ) -> PrivateWire {
+        let (leading, rest) = match coefficients.split_last() {
+            Some(split) => split,
+            None => {
+                self.assert_reserved_zero_one("component_horner_eval");
+                return Self::ZERO;
+            }
+        };
+
+        rest.iter().rev().fold(*leading, |acc, c| {
+            let constraint =
+                Constraint::default().mult(1).fourth(1).a(acc).b(x).d(*c);
+
+            self.gate_mul(constraint)
+        })
     }
 
-    /// Decomposes `scalar` into an array truncated to `N` bits (max 256).
+    /// Evaluate `Σ coefficients[i] · x^i` using Horner's rule for a constant
+    /// `x`.
     ///
-    /// Asserts the reconstruction of the bits to be equal to `scalar`.
+    /// `x` is folded into the selectors, so every step `acc := acc · x + c`
+    /// is a single gate. An empty slice evaluates to [`Self::ZERO`].
     ///
-    /// Consume `2 · N + 1` gates
-    pub fn component_decomposition<const N: usize>(
+    /// Consume `coefficients.len() - 1` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_horner_eval_at_constant<A: Into<C::Range>>(
         &mut self,
-        scalar: PrivateWire,
-    ) -> [PrivateWire; N] {
-        // Static assertion
-        assert!(0 < N && N <= 256);
+        coefficients: &[PrivateWire],
+        x: A,
+    ) -> PrivateWire {
+        let x = x.into();
 
-        let mut decomposition = [Self::ZERO; N];
+        let (leading, rest) = match coefficients.split_last() {
+            Some(split) => split,
+            None => {
+                self.assert_reserved_zero_one(
+                    "component_horner_eval_at_constant",
+                );
+                return Self::ZERO;
+            }
+        };
 
-        let acc = Self::ZERO;
-        let acc = self[scalar]
-            .to_bits()
-            .iter()
-            .rev()
-            .enumerate()
-            .zip(decomposition.iter_mut())
-            .fold(acc, |acc, ((i, w), d)| {
-                *d = self.append_witness(C::Range::from(*w as u64));
+        rest.iter().rev().fold(*leading, |acc, c| {
+            let constraint =
+                Constraint::default().left(x).right(1).a(acc).b(*c);
 
-                self.component_boolean(*d);
+            self.gate_add(constraint)
+        })
+    }
 
-                let constraint = Constraint::default()
-                    .left(C::Range::pow_of_2(i as u64))
-                    .right(1)
-                    .a(*d)
-                    .b(acc);
+    /// Evaluate `a²`.
+    ///
+    /// Consume `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_square(&mut self, a: PrivateWire) -> PrivateWire {
+        let constraint = Constraint::default().mult(1).a(a).b(a);
 
-                self.gate_add(constraint)
-            });
+        self.gate_mul(constraint)
+    }
 
-        self.assert_equal(acc, scalar);
+    /// Evaluate `a³` as `a² · a`, where `a²` is the only intermediate
+    /// witness.
+    ///
+    /// Consume `2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_cube(&mut self, a: PrivateWire) -> PrivateWire {
+        let square = self.component_square(a);
+        let constraint = Constraint::default().mult(1).a(square).b(a);
 
-        decomposition
+        self.gate_mul(constraint)
     }
 
-    /// Conditionally selects identity as [`WitnessPoint`] based on an input
-    /// bit.
+    /// Evaluate `x^e` for a constant exponent `e`.
     ///
-    /// bit == 1 => a,
-    /// bit == 0 => identity,
+    /// The powers follow a short addition chain for `e`, so every step is a
+    /// single multiplication gate. `e = 0` returns the `1` witness and
+    /// `e = 1` returns `x`, without appending any gate.
     ///
-    /// `bit` is expected to be constrained by
-    /// [`Composer::component_boolean`]
-    pub fn component_select_identity(
+    /// Consume one gate per step of the addition chain, `3` for `e = 5`
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_exp_const(
         &mut self,
-        bit: PrivateWire,
-        a: WitnessPoint,
-    ) -> WitnessPoint {
-        let x = self.component_select_zero(bit, *a.x());
-        let y = self.component_select_one(bit, *a.y());
+        x: PrivateWire,
+        e: u64,
+    ) -> PrivateWire {
+        if e == 0 {
+            self.assert_reserved_zero_one("component_exp_const");
+            return Self::ONE;
+        }
 
-        WitnessPoint::new(x, y)
+        let mut powers = vec![x];
+
+        for (i, j) in addition_chain(e) {
+            let constraint =
+                Constraint::default().mult(1).a(powers[i]).b(powers[j]);
+            let power = self.gate_mul(constraint);
+
+            powers.push(power);
+        }
+
+        powers[powers.len() - 1]
     }
 
-    /// Evaluate `jubjub · point` as a [`WitnessPoint`]
-    pub fn component_mul_point(
+    /// Evaluate `x^e` for an exponent given by its bits, least significant
+    /// first.
+    ///
+    /// Every bit is expected to be constrained by
+    /// [`Composer::component_boolean`]. An empty slice returns the `1`
+    /// witness.
+    ///
+    /// Consume `3 · e_bits.len() - 2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_exp_bits(
         &mut self,
-        jubjub: PrivateWire,
-        point: WitnessPoint,
-    ) -> WitnessPoint {
-        // Turn scalar into bits
-        let scalar_bits = self.component_decomposition::<252>(jubjub);
+        x: PrivateWire,
+        e_bits: &[PrivateWire],
+    ) -> PrivateWire {
+        self.assert_reserved_zero_one("component_exp_bits");
 
-        let mut result = Self::IDENTITY;
+        let (top, rest) = match e_bits.split_last() {
+            Some(split) => split,
+            None => return Self::ONE,
+        };
 
-        for bit in scalar_bits.iter().rev() {
-            result = self.component_add_point(result, result);
+        // square-and-multiply, where the multiplier is `x` or `1`
+        let acc = self.component_select_one(*top, x);
 
-            let point_to_add = self.component_select_identity(*bit, point);
-            result = self.component_add_point(result, point_to_add);
-        }
+        rest.iter().rev().fold(acc, |acc, bit| {
+            let squared = self.component_square(acc);
+
+            let factor = self.component_select_one(*bit, x);
+            let constraint = Constraint::default().mult(1).a(squared).b(factor);
+
+            self.gate_mul(constraint)
+        })
+    }
+
+    /// Evaluate `x^e` for an exponent witness `e` of `EXP_BITS` bits.
+    ///
+    /// `e` is decomposed with [`Self::component_decomposition`], which
+    /// asserts the bits reconstruct it, then raised with
+    /// [`Self::component_exp_bits`]. An `e` that doesn't fit `EXP_BITS` bits
+    /// is unprovable.
+    ///
+    /// Consume `5 · EXP_BITS - 1` gates, `2 · EXP_BITS + 1` of them for the
+    /// decomposition
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_exp<const EXP_BITS: usize>(
+        &mut self,
+        x: PrivateWire,
+        e: PrivateWire,
+    ) -> PrivateWire {
+        let bits = self.component_decomposition::<EXP_BITS>(e);
 
-        result
+        self.component_exp_bits(x, &bits)
     }
 
     /// Conditionally selects a [`PrivateWire`] based on an input bit.
@@ -956,6 +2296,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     ///
     /// `bit` is expected to be constrained by
     /// [`Composer::component_boolean`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn component_select(
         &mut self,
         bit: PrivateWire,
@@ -993,6 +2334,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     ///
     /// `bit` is expected to be constrained by
     /// [`Composer::component_boolean`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn component_select_one(
         &mut self,
         bit: PrivateWire,
@@ -1018,154 +2360,223 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
         f_x
     }
 
-    /// Conditionally selects a [`WitnessPoint`] based on an input bit.
+    /// Selects one of four [`PrivateWire`]s based on two input bits.
     ///
-    /// bit == 1 => a,
-    /// bit == 0 => b,
+    /// (b1, b0) == (0, 0) => x0,
+    /// (b1, b0) == (0, 1) => x1,
+    /// (b1, b0) == (1, 0) => x2,
+    /// (b1, b0) == (1, 1) => x3,
     ///
-    /// `bit` is expected to be constrained by
+    /// `b0` picks within the pairs `(x0, x1)` and `(x2, x3)`, and `b1` between
+    /// the pairs. Every gate holds a single product, so each of the three
+    /// selections takes two gates, with the fourth wire carrying the
+    /// accumulated term.
+    ///
+    /// `b0` and `b1` are expected to be constrained by
     /// [`Composer::component_boolean`]
-    pub fn component_select_point(
+    ///
+    /// Consume `6` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select4(
         &mut self,
-        bit: PrivateWire,
-        a: WitnessPoint,
-        b: WitnessPoint,
-    ) -> WitnessPoint {
-        let x = self.component_select(bit, *a.x(), *b.x());
-        let y = self.component_select(bit, *a.y(), *b.y());
+        b0: PrivateWire,
+        b1: PrivateWire,
+        x0: PrivateWire,
+        x1: PrivateWire,
+        x2: PrivateWire,
+        x3: PrivateWire,
+    ) -> PrivateWire {
+        let lo = self.select_fused(b0, x1, x0);
+        let hi = self.select_fused(b0, x3, x2);
 
-        WitnessPoint::new(x, y)
+        let diff = self.gate_sub(hi, lo);
+
+        // b1 · (hi - lo) + lo
+        let constraint =
+            Constraint::default().mult(1).fourth(1).a(b1).b(diff).d(lo);
+        self.gate_mul(constraint)
     }
 
-    /// Conditionally selects a [`PrivateWire`] based on an input bit.
+    /// Selects the option of `options` at the index of the little endian
+    /// `index_bits`.
     ///
-    /// bit == 1 => value,
-    /// bit == 0 => 0,
+    /// The options are selected pairwise by a balanced tree, the first bit
+    /// picking within the pairs `(x0, x1)`, `(x2, x3)`, ..., as in
+    /// [`Self::component_select4`]. For a number of options that isn't a
+    /// power of two, the index is asserted to be below it from its bits, so
+    /// an index without an option is unsatisfiable instead of picking another
+    /// one. An unpaired option of a level is then only reached with a zero
+    /// bit, and is carried to the next level as is.
     ///
-    /// `bit` is expected to be constrained by
+    /// The bits are expected to be constrained by
     /// [`Composer::component_boolean`]
-    pub fn component_select_zero(
+    ///
+    /// Consume `2 · (K - 1)` gates for `K` options, plus a gate per bit if
+    /// `K` isn't `2^index_bits.len()`
+    ///
+    /// # Panics
+    ///
+    /// If there are no options, or more than `2^index_bits.len()`.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_n(
         &mut self,
-        bit: PrivateWire,
-        value: PrivateWire,
+        index_bits: &[PrivateWire],
+        options: &[PrivateWire],
     ) -> PrivateWire {
-        let constraint = Constraint::default().mult(1).a(bit).b(value);
+        let wide = index_bits.len() >= usize::BITS as usize;
+        assert!(
+            !options.is_empty()
+                && (wide || options.len() <= 1 << index_bits.len()),
+            "the index bits must address every option"
+        );
+        let full = !wide && options.len() == 1 << index_bits.len();
 
-        self.gate_mul(constraint)
-    }
+        if !full {
+            self.assert_reserved_zero_one("component_select_n");
 
-    /// Adds a range-constraint gate that checks and constrains a
-    /// [`PrivateWire`] to be inside of the range \[0,num_bits\].
-    ///
-    /// This function adds `num_bits/4` gates to the circuit description in
-    /// order to add the range constraint.
-    ///
-    ///# Panics
-    /// This function will panic if the num_bits specified is not even, ie.
-    /// `num_bits % 2 != 0`.
-    pub fn component_range(&mut self, witness: PrivateWire, num_bits: usize) {
-        // convert witness to bit representation and reverse
-        let bits = self[witness];
-        let bit_iter = BitIterator8::new(bits.to_raw_bytes());
-        let mut bits: Vec<_> = bit_iter.collect();
-        bits.reverse();
+            let max = options.len() - 1;
+            let max: Vec<bool> = (0..index_bits.len())
+                .map(|i| i < usize::BITS as usize && (max >> i) & 1 == 1)
+                .collect();
+
+            self.assert_bits_at_most(index_bits, &max);
+        }
 
-        // considering this is a width-4 program, one gate will contain 4
-        // accumulators. each accumulator proves that a single quad is a
-        // base-4 digit. accumulators are bijective to quads, and these
-        // are 2-bits each. given that, one gate accumulates 8 bits.
-        let mut num_gates = num_bits >> 3;
+        let mut layer = options.to_vec();
+        for bit in index_bits {
+            if layer.len() == 1 {
+                break;
+            }
 
-        // given each gate accumulates 8 bits, its count must be padded
-        if num_bits % 8 != 0 {
-            num_gates += 1;
+            layer = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [lo, hi] => self.select_fused(*bit, *hi, *lo),
+                    _ => pair[0],
+                })
+                .collect();
         }
 
-        // a gate holds 4 quads
-        let num_quads = num_gates * 4;
+        layer[0]
+    }
 
-        // the wires are left-padded with the difference between the quads count
-        // and the bits argument
-        let pad = 1 + (((num_quads << 1) - num_bits) >> 1);
+    /// `bit · a + b - bit · b` in two gates, the first product carried to the
+    /// second gate by the fourth wire
+    fn select_fused(
+        &mut self,
+        bit: PrivateWire,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> PrivateWire {
+        // bit · a + b
+        let constraint =
+            Constraint::default().mult(1).fourth(1).a(bit).b(a).d(b);
+        let t = self.gate_mul(constraint);
 
-        // last gate is reserved for either the genesis quad or the padding
-        let used_gates = num_gates + 1;
+        // t - bit · b
+        let constraint = Constraint::default()
+            .mult(-C::Range::one())
+            .fourth(1)
+            .a(bit)
+            .b(b)
+            .d(t);
+        self.gate_mul(constraint)
+    }
 
-        let base = Constraint::<C::Range>::default();
-        let base = Constraint::range(base);
-        let mut constraints = vec![base; used_gates];
+    /// Conditionally swaps two [`PrivateWire`] based on an input bit.
+    ///
+    /// bit == 1 => (b, a),
+    /// bit == 0 => (a, b),
+    ///
+    /// Computes `delta = bit · (b - a)` and returns `(a + delta, b - delta)`.
+    ///
+    /// `bit` is expected to be constrained by
+    /// [`Composer::component_boolean`], unless `constrain_bit` is set.
+    ///
+    /// Consume 3 gates, plus one if `constrain_bit` is set
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_swap(
+        &mut self,
+        bit: PrivateWire,
+        a: PrivateWire,
+        b: PrivateWire,
+        constrain_bit: bool,
+    ) -> (PrivateWire, PrivateWire) {
+        if constrain_bit {
+            self.component_boolean(bit);
+        }
 
-        // We collect the set of accumulators to return back to the user
-        // and keep a running count of the current accumulator
-        let mut accumulators: Vec<PrivateWire> = Vec::new();
-        let mut accumulator = C::Range::zero();
-        let four = C::Range::from(4);
+        let b_min_a = self.gate_sub(b, a);
 
-        for i in pad..=num_quads {
-            // convert each pair of bits to quads
-            let bit_index = (num_quads - i) << 1;
-            let q_0 = bits[bit_index] as u64;
-            let q_1 = bits[bit_index + 1] as u64;
-            let quad = q_0 + (2 * q_1);
+        // bit · (b - a) + a
+        let constraint = Constraint::default()
+            .mult(1)
+            .fourth(1)
+            .a(bit)
+            .b(b_min_a)
+            .d(a);
+        let first = self.gate_mul(constraint);
 
-            accumulator = four * accumulator;
-            accumulator += C::Range::from(quad);
+        // a + b - first
+        let constraint = Constraint::default()
+            .left(1)
+            .right(1)
+            .fourth(-C::Range::one())
+            .a(a)
+            .b(b)
+            .d(first);
+        let second = self.gate_add(constraint);
 
-            let accumulator_var = self.append_witness(accumulator);
+        (first, second)
+    }
 
-            accumulators.push(accumulator_var);
+    /// Conditionally selects a [`PrivateWire`] based on an input bit.
+    ///
+    /// bit == 1 => value,
+    /// bit == 0 => 0,
+    ///
+    /// `bit` is expected to be constrained by
+    /// [`Composer::component_boolean`]
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_select_zero(
+        &mut self,
+        bit: PrivateWire,
+        value: PrivateWire,
+    ) -> PrivateWire {
+        let constraint = Constraint::default().mult(1).a(bit).b(value);
 
-            let idx = i / 4;
-            match i % 4 {
-                0 => {
-                    constraints[idx].w_d = accumulator_var;
-                }
-                1 => {
-                    constraints[idx].w_o = accumulator_var;
-                }
-                2 => {
-                    constraints[idx].w_b = accumulator_var;
-                }
-                3 => {
-                    constraints[idx].w_a = accumulator_var;
-                }
-                _ => unreachable!(),
-            };
-        }
+        self.gate_mul(constraint)
+    }
 
-        // last constraint is zeroed as it is reserved for the genesis quad or
-        // padding
-        if let Some(c) = constraints.last_mut() {
-            *c = Constraint::default()
-        }
+    /// Whether the integer representation of `scalar` is below the field
+    /// order
+    fn is_canonical(scalar: &C::Range) -> bool {
+        let raw = scalar.to_raw_bytes();
+        let max = (-C::Range::one()).to_raw_bytes();
 
-        // the accumulators count is a function to the number of quads. hence,
-        // this optional gate will not cause different circuits depending on the
-        // witness because this computation is bound to the constant bits count
-        // alone.
-        if let Some(accumulator) = accumulators.last() {
-            if let Some(c) = constraints.last_mut() {
-                c.w_d = *accumulator
-            }
-        }
+        // little endian, so compared from the most significant byte
+        raw.iter().rev().cmp(max.iter().rev()) != cmp::Ordering::Greater
+    }
 
-        constraints
-            .into_iter()
-            .for_each(|c| self.append_custom_gate(c));
+    /// Canonical reduction of `scalar`, rebuilt from its integer
+    /// representation with the field arithmetic
+    fn canonical(scalar: C::Range) -> C::Range {
+        let radix = C::Range::from(256u64);
 
-        // the accumulators count is a function to the number of quads. hence,
-        // this optional gate will not cause different circuits depending on the
-        // witness because this computation is bound to the constant bits count
-        // alone.
-        if let Some(accumulator) = accumulators.last() {
-            self.assert_equal(*accumulator, witness);
-        }
+        scalar
+            .to_raw_bytes()
+            .iter()
+            .rev()
+            .fold(C::Range::zero(), |acc, byte| {
+                acc * radix + C::Range::from(*byte as u64)
+            })
     }
 
     /// Evaluate and return `o` by appending a new constraint into the circuit.
     ///
     /// Set `q_o = (-1)` and override the output of the constraint with:
     /// `o := q_l · a + q_r · b + q_4 · d + q_c + PI`
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn gate_add(&mut self, s: Constraint<C::Range>) -> PrivateWire {
         let s = Constraint::arithmetic(s).output(-C::Range::one());
 
@@ -1183,6 +2594,7 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
     ///
     /// Set `q_o = (-1)` and override the output of the constraint with:
     /// `o := q_m · a · b + q_4 · d + q_c + PI`
+    #[cfg_attr(feature = "track-callers", track_caller)]
     pub fn gate_mul(&mut self, s: Constraint<C::Range>) -> PrivateWire {
         let s = Constraint::arithmetic(s).output(-C::Range::one());
 
@@ -1195,4 +2607,253 @@ impl<C: TwistedEdwardsAffine> Plonk<C> {
 
         o
     }
+
+    /// Evaluate `a - b`.
+    ///
+    /// Consume `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn gate_sub(&mut self, a: PrivateWire, b: PrivateWire) -> PrivateWire {
+        let constraint = Constraint::default()
+            .left(1)
+            .right(-C::Range::one())
+            .a(a)
+            .b(b);
+
+        self.gate_add(constraint)
+    }
+
+    /// Evaluate `-a`.
+    ///
+    /// Consume `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_negate(&mut self, a: PrivateWire) -> PrivateWire {
+        let constraint = Constraint::default().left(-C::Range::one()).a(a);
+
+        self.gate_add(constraint)
+    }
+
+    /// Evaluate `a · b + c` with a single gate, carrying `c` in the fourth
+    /// wire.
+    ///
+    /// Consume `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn gate_madd(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+        c: PrivateWire,
+    ) -> PrivateWire {
+        let constraint = Constraint::default().mult(1).fourth(1).a(a).b(b).d(c);
+
+        self.gate_mul(constraint)
+    }
+
+    /// Evaluate `a · b + c · d`.
+    ///
+    /// A gate has a single product term, so `c · d` takes a gate of its own
+    /// and its output is added to `a · b` through the fourth wire of
+    /// [`Self::gate_madd`].
+    ///
+    /// Consume `2` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn gate_mul_add(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+        c: PrivateWire,
+        d: PrivateWire,
+    ) -> PrivateWire {
+        let constraint = Constraint::default().mult(1).a(c).b(d);
+        let cd = self.gate_mul(constraint);
+
+        self.gate_madd(a, b, cd)
+    }
+
+    /// Evaluate `Σ coefficient · w + constant` over any number of terms.
+    ///
+    /// A gate has three input wires, so the first gate takes three terms and
+    /// the constant, and every following gate takes two more terms with the
+    /// running sum in the fourth wire. An empty slice appends the constant
+    /// with [`Self::append_constant`].
+    ///
+    /// Consume `1 + ⌈(terms.len() - 3) / 2⌉` gates for more than three terms,
+    /// and `1` otherwise
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn gate_sum(
+        &mut self,
+        terms: &[(C::Range, PrivateWire)],
+        constant: C::Range,
+    ) -> PrivateWire {
+        let (first, rest) = terms.split_at(cmp::min(terms.len(), 3));
+
+        let mut constraint = Constraint::default().constant(constant);
+        match *first {
+            [] => return self.append_constant(constant),
+            [(q_l, a)] => constraint = constraint.left(q_l).a(a),
+            [(q_l, a), (q_r, b)] => {
+                constraint = constraint.left(q_l).a(a).right(q_r).b(b)
+            }
+            [(q_l, a), (q_r, b), (q_4, d)] => {
+                constraint =
+                    constraint.left(q_l).a(a).right(q_r).b(b).fourth(q_4).d(d)
+            }
+            _ => unreachable!("at most three terms"),
+        }
+        let acc = self.gate_add(constraint);
+
+        rest.chunks(2).fold(acc, |acc, chunk| {
+            let constraint = Constraint::default().fourth(1).d(acc);
+            let constraint = match *chunk {
+                [(q_l, a)] => constraint.left(q_l).a(a),
+                [(q_l, a), (q_r, b)] => {
+                    constraint.left(q_l).a(a).right(q_r).b(b)
+                }
+                _ => unreachable!("chunks of at most two terms"),
+            };
+
+            self.gate_add(constraint)
+        })
+    }
+
+    /// Evaluate the inner product `Σ a_i · b_i` of two wire vectors.
+    ///
+    /// Every element is a single gate, with the product in `q_m` and the
+    /// running sum carried in the fourth wire. An empty pair of vectors
+    /// returns [`Self::ZERO`].
+    ///
+    /// Consume `a.len()` gates
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_dot(
+        &mut self,
+        a: &[PrivateWire],
+        b: &[PrivateWire],
+    ) -> Result<PrivateWire, LengthMismatch> {
+        if a.len() != b.len() {
+            return Err(LengthMismatch {
+                left: a.len(),
+                right: b.len(),
+            });
+        }
+
+        let mut terms = a.iter().zip(b.iter());
+        let (a_0, b_0) = match terms.next() {
+            Some(first) => first,
+            None => {
+                self.assert_reserved_zero_one("component_dot");
+                return Ok(Self::ZERO);
+            }
+        };
+
+        let constraint = Constraint::default().mult(1).a(*a_0).b(*b_0);
+        let acc = self.gate_mul(constraint);
+
+        Ok(terms.fold(acc, |acc, (a_i, b_i)| {
+            // a_i · b_i + acc
+            let constraint = Constraint::default()
+                .mult(1)
+                .fourth(1)
+                .a(*a_i)
+                .b(*b_i)
+                .d(acc);
+
+            self.gate_mul(constraint)
+        }))
+    }
+
+    /// Evaluate `o = a / b` and constrain `o · b = a`.
+    ///
+    /// The quotient is a witness, and the gate enforces the relation. It
+    /// doesn't enforce `b ≠ 0` on its own: with `a = b = 0` any quotient
+    /// satisfies it, so a divisor that may be zero must be constrained apart.
+    ///
+    /// Returns [`DivisionByZero`] if the value of `b` is zero, instead of
+    /// appending an unsatisfiable gate.
+    ///
+    /// Consume `1` gate
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_div(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+    ) -> Result<PrivateWire, DivisionByZero> {
+        let inv = self[b].invert().ok_or(DivisionByZero { wire: b.index() })?;
+        let o = self[a] * inv;
+        let o = self.append_witness(o);
+
+        // o · b - a = 0
+        let constraint = Constraint::default()
+            .mult(1)
+            .fourth(-C::Range::one())
+            .a(o)
+            .b(b)
+            .d(a);
+        self.append_gate(constraint);
+
+        Ok(o)
+    }
+
+    /// Evaluate the quotient and the remainder of the division of `a` by the
+    /// constant `divisor`.
+    ///
+    /// The constraint `a = q · divisor + r` holds with `r < divisor`, checked
+    /// by [`Self::component_range_bounded_auto`], and `q` range checked to
+    /// `num_bits` bits. Both bounds keep `q · divisor + r` below the field
+    /// modulus, so the division is unique over the integers.
+    ///
+    /// Returns [`ZeroDivisor`] if `divisor` is zero, before appending any
+    /// gate.
+    ///
+    /// Consume `1` gate plus the range checks of `q` and `r`
+    ///
+    /// # Panics
+    ///
+    /// If `num_bits` and the bits of `divisor` add up to more than
+    /// [`Self::MAX_BOUNDED_BITS`].
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_div_rem(
+        &mut self,
+        a: PrivateWire,
+        divisor: u64,
+        num_bits: usize,
+    ) -> Result<(PrivateWire, PrivateWire), ZeroDivisor> {
+        if divisor == 0 {
+            return Err(ZeroDivisor);
+        }
+
+        let divisor_bits = (u64::BITS - divisor.leading_zeros()) as usize;
+        assert!(
+            num_bits + divisor_bits <= Self::MAX_BOUNDED_BITS,
+            "divisions support quotients and divisors up to {} bits",
+            Self::MAX_BOUNDED_BITS
+        );
+
+        // long division over the bytes, most significant first
+        let (q, r) = Self::canonical(self[a]).to_raw_bytes().iter().rev().fold(
+            (C::Range::zero(), 0u128),
+            |(q, r), byte| {
+                let r = (r << 8) | *byte as u128;
+                let digit = C::Range::from((r / divisor as u128) as u64);
+
+                (q * C::Range::from(256u64) + digit, r % divisor as u128)
+            },
+        );
+
+        let q = self.append_witness(q);
+        let r = self.append_witness(C::Range::from(r as u64));
+
+        // a - divisor · q - r = 0
+        let constraint = Constraint::default()
+            .left(1)
+            .right(-C::Range::from(divisor))
+            .fourth(-C::Range::one())
+            .a(a)
+            .b(q)
+            .d(r);
+        self.append_gate(constraint);
+
+        self.range_any(q, num_bits, "component_div_rem");
+        self.component_range_bounded_auto(r, divisor);
+
+        Ok((q, r))
+    }
 }
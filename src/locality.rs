@@ -0,0 +1,198 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Reordering of the gates so the ones sharing wires are adjacent.
+//!
+//! Every gate equation only reads the wires of its own row, except for the
+//! families that read the wires of the next gate. Such a gate and the gates
+//! following it are kept together in a block, and the blocks are reordered
+//! with a breadth-first traversal of the copy constraint graph: starting
+//! from the first block not yet emitted, every block sharing a witness with
+//! an emitted block is emitted next. The set of gates and their equations
+//! are unchanged, so the circuit accepts exactly the same witnesses.
+//!
+//! The order only depends on the gates, never on the witness values, so the
+//! compiler and the prover reorder the same circuit identically.
+
+use sp_std::collections::vec_deque::VecDeque;
+use sp_std::vec;
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::key::degree::SelectorFamily;
+use crate::permutation::Permutation;
+use crate::Plonk;
+
+/// Witnesses shared by more blocks, such as the `0` and `1` constants, are
+/// ignored by the traversal, since they would link the whole circuit
+const MAX_SHARED_BLOCKS: usize = 8;
+
+/// Whether the equation of the gate reads the wires of the next one
+fn reads_next_gate<F: PrimeField>(constraint: &Constraint<F>) -> bool {
+    SelectorFamily::ALL
+        .iter()
+        .any(|f| f.reads_next_gate() && f.is_active(constraint))
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Reorder the gates so the ones sharing wires are adjacent.
+    ///
+    /// The gates are clustered with a breadth-first traversal of the copy
+    /// constraint graph, skipping the witnesses shared by many gates. Only
+    /// the row order changes, so the circuit accepts the same witnesses, and
+    /// the order only depends on the gates, not on the witness values.
+    ///
+    /// The public inputs keep their relative order, and so do the gates
+    /// reading the next one along with it. The first gate stays first, and
+    /// the last gate stays last if it reads the next row.
+    ///
+    /// The row order is part of the circuit description, so the keys must
+    /// be compiled with
+    /// [`PlonkKey::compile_for_locality`](crate::PlonkKey::compile_for_locality),
+    /// which runs this pass on both the compiler and the prover composers.
+    /// Checkpoints taken before the pass can't be rewound to afterwards.
+    pub fn reorder_for_locality(&mut self) {
+        let m = self.constraints.len();
+        if m == 0 {
+            return;
+        }
+
+        // the custom gates may read the next gate
+        let reads_next = |gate: usize| {
+            reads_next_gate(&self.constraints[gate])
+                || self.custom_gates.contains_key(&gate)
+        };
+
+        // gates of every block, as ranges of their start and end
+        let mut starts: Vec<usize> =
+            (1..m).filter(|i| !reads_next(i - 1)).collect();
+        starts.insert(0, 0);
+        let blocks: Vec<(usize, usize)> = starts
+            .iter()
+            .zip(starts.iter().skip(1).chain([m].iter()))
+            .map(|(start, end)| (*start, *end))
+            .collect();
+
+        // blocks using every witness, in increasing order
+        let mut users: Vec<Vec<usize>> = vec![Vec::new(); self.witness.len()];
+        let mut public = vec![false; blocks.len()];
+        for (b, (start, end)) in blocks.iter().enumerate() {
+            for gate in *start..*end {
+                let c = &self.constraints[gate];
+
+                for w in [c.w_a, c.w_b, c.w_o, c.w_d] {
+                    let users = &mut users[w.index()];
+                    if users.last() != Some(&b) {
+                        users.push(b);
+                    }
+                }
+
+                public[b] |= self.instance.contains_key(&gate);
+            }
+        }
+
+        let pinned = match reads_next(m - 1) {
+            true => Some(blocks.len() - 1),
+            false => None,
+        };
+
+        let mut visited = vec![false; blocks.len()];
+        let mut order = Vec::with_capacity(blocks.len());
+        let mut queue = VecDeque::new();
+
+        for root in 0..blocks.len() {
+            if visited[root] || pinned == Some(root) {
+                continue;
+            }
+
+            visited[root] = true;
+            queue.push_back(root);
+
+            while let Some(b) = queue.pop_front() {
+                order.push(b);
+
+                let (start, end) = blocks[b];
+                for c in &self.constraints[start..end] {
+                    for w in [c.w_a, c.w_b, c.w_o, c.w_d] {
+                        let users = &users[w.index()];
+                        if users.len() > MAX_SHARED_BLOCKS {
+                            continue;
+                        }
+
+                        // the public blocks are only reached as roots, so
+                        // they stay in order
+                        for next in users.iter().copied() {
+                            if !visited[next]
+                                && !public[next]
+                                && pinned != Some(next)
+                            {
+                                visited[next] = true;
+                                queue.push_back(next);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        order.extend(pinned);
+
+        // previous index of the gate at every new index, and the reverse
+        let gates: Vec<usize> = order
+            .iter()
+            .flat_map(|b| blocks[*b].0..blocks[*b].1)
+            .collect();
+        let mut index = vec![0; m];
+        gates
+            .iter()
+            .enumerate()
+            .for_each(|(new, old)| index[*old] = new);
+
+        self.constraints = gates.iter().map(|g| self.constraints[*g]).collect();
+        self.instance = self
+            .instance
+            .iter()
+            .map(|(gate, value)| (index[*gate], *value))
+            .collect();
+        self.public_input_log = self
+            .public_input_log
+            .iter()
+            .map(|gate| index[*gate])
+            .collect();
+        self.digests = self
+            .digests
+            .iter()
+            .map(|(gate, algorithm)| (index[*gate], *algorithm))
+            .collect();
+        self.custom_gates = self
+            .custom_gates
+            .iter()
+            .map(|(gate, custom)| (index[*gate], *custom))
+            .collect();
+        self.lookups = self
+            .lookups
+            .iter()
+            .map(|(gate, table)| (index[*gate], *table))
+            .collect();
+
+        #[cfg(feature = "track-callers")]
+        {
+            self.constraint_locations = gates
+                .iter()
+                .map(|g| self.constraint_locations[*g])
+                .collect();
+        }
+
+        // the cycles of the copy constraints follow the new rows
+        let mut perm = Permutation::with_capacity(self.witness.len());
+        self.witness.iter().for_each(|_| {
+            perm.new_witness();
+        });
+        self.constraints.iter().enumerate().for_each(|(i, c)| {
+            perm.add_witnesses_to_map(c.w_a, c.w_b, c.w_o, c.w_d, i)
+        });
+        self.perm = perm;
+    }
+}
@@ -0,0 +1,411 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Lookup tables of the circuit description.
+//!
+//! A table is a list of rows `(a, b, o)` appended with
+//! [`Plonk::append_lookup_table`], and [`Plonk::component_lookup`]
+//! constrains the wires of a gate to be one of its rows. The tables are laid
+//! out one after the other in the fixed columns `t_a`, `t_b`, `t_c` and
+//! `t_id` over the domain of the circuit, `t_id` holding the index of the
+//! table of every row, and the rows past the last one repeat it. The lookup
+//! gates carry the selector `q_lookup`, and `q_table` holds the index of
+//! the table they read.
+//!
+//! The argument is a multiset equality over logarithmic derivatives. With
+//! the rows compressed by a challenge `ζ` as `f = a + ζ·b + ζ²·c + ζ³·q_table`
+//! and `t = t_a + ζ·t_b + ζ²·t_c + ζ³·t_id`, the prover commits to the
+//! multiplicity `m` of every table row, and proves
+//!
+//! `Σ q_lookup / (γ + f) = Σ m / (γ + t)`
+//!
+//! over the domain for a challenge `γ`. The running sum `φ` starts from
+//! zero and adds the term of a row from one row to the next, so it wraps
+//! around the domain back to zero exactly when the sums are equal. The
+//! quotient enforces, scaled by a separation challenge,
+//!
+//! `(φ(ωX) - φ(X)) · (γ + f) · (γ + t) - q_lookup · (γ + t) + m · (γ + f)`
+//!
+//! whose degree stays below the one of the permutation argument.
+//!
+//! The commitments to the selectors and to the table columns are appended
+//! to the transcript of the circuit. Nothing of the argument runs for a
+//! circuit without tables, so its keys, transcript and proofs are the ones
+//! of a circuit compiled without lookup support.
+
+use codec::{Decode, Encode};
+use poly_commit::{msm_curve_addition, Coefficients, Commitment, PointsValue};
+use sp_std::collections::btree_map::BTreeMap;
+use sp_std::vec;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::plonk::{Transcript, TranscriptProtocol};
+use zksnarks::Constraint;
+use zkstd::common::{Pairing, PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+/// Table appended to a circuit with [`Plonk::append_lookup_table`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TableId(pub(crate) usize);
+
+impl TableId {
+    /// Index of the table in the circuit, as held by the `t_id` column
+    pub const fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Append a table of rows `(a, b, o)` to the circuit description, so the
+    /// wires of a gate can be constrained to one of them with
+    /// [`Self::component_lookup`].
+    ///
+    /// The table takes no gate, but the domain of the circuit grows to hold
+    /// the rows of all the tables.
+    ///
+    /// # Panics
+    ///
+    /// If `rows` is empty.
+    pub fn append_lookup_table(&mut self, rows: &[[C::Range; 3]]) -> TableId {
+        assert!(!rows.is_empty(), "a lookup table needs at least one row");
+
+        self.lookup_tables.push(rows.to_vec());
+
+        TableId(self.lookup_tables.len() - 1)
+    }
+
+    /// Constrain `(a, b, o)` to be a row of `table`.
+    ///
+    /// Consume `1` gate, which sets no other selector.
+    ///
+    /// # Panics
+    ///
+    /// If `table` wasn't appended to the composer.
+    #[cfg_attr(feature = "track-callers", track_caller)]
+    pub fn component_lookup(
+        &mut self,
+        a: PrivateWire,
+        b: PrivateWire,
+        o: PrivateWire,
+        table: TableId,
+    ) {
+        assert!(
+            table.0 < self.lookup_tables.len(),
+            "the lookup table wasn't appended to the composer"
+        );
+
+        self.lookups.insert(self.constraints.len(), table.0);
+
+        let constraint = Constraint::default().a(a).b(b).o(o);
+        self.append_custom_gate(constraint);
+    }
+
+    /// Rows of all the lookup tables
+    pub(crate) fn lookup_rows(&self) -> usize {
+        self.lookup_tables.iter().map(|rows| rows.len()).sum()
+    }
+
+    /// Rows the domain of the circuit must hold: its gates, and the rows of
+    /// its lookup tables
+    pub(crate) fn domain_rows(&self) -> usize {
+        self.m().max(self.lookup_rows())
+    }
+
+    /// Columns `t_a`, `t_b`, `t_c` and `t_id` of the lookup tables over a
+    /// domain of `n` rows
+    pub(crate) fn table_columns(&self, n: usize) -> [Vec<C::Range>; 4] {
+        let mut columns = [
+            Vec::with_capacity(n),
+            Vec::with_capacity(n),
+            Vec::with_capacity(n),
+            Vec::with_capacity(n),
+        ];
+
+        let rows = self.lookup_tables.iter().enumerate().flat_map(|(id, t)| {
+            t.iter().map(move |row| (C::Range::from(id as u64), row))
+        });
+        for (id, row) in rows {
+            columns[0].push(row[0]);
+            columns[1].push(row[1]);
+            columns[2].push(row[2]);
+            columns[3].push(id);
+        }
+
+        // the rows past the last one repeat it, so they are rows of a table
+        // whatever multiplicity the prover gives them
+        columns.iter_mut().for_each(|column| {
+            let last = column.last().copied().unwrap_or_else(C::Range::zero);
+            column.resize(n, last);
+        });
+
+        columns
+    }
+
+    /// Selectors `q_lookup` and `q_table` over a domain of `n` rows
+    pub(crate) fn lookup_selectors(&self, n: usize) -> [Vec<C::Range>; 2] {
+        let mut q_lookup = vec![C::Range::zero(); n];
+        let mut q_table = vec![C::Range::zero(); n];
+
+        self.lookups.iter().for_each(|(gate, table)| {
+            q_lookup[*gate] = C::Range::one();
+            q_table[*gate] = C::Range::from(*table as u64);
+        });
+
+        [q_lookup, q_table]
+    }
+
+    /// Multiplicity of every row of the lookup tables in the lookup gates,
+    /// over a domain of `n` rows.
+    ///
+    /// A gate whose wires aren't a row of its table isn't counted, so the
+    /// running sum of the proof doesn't wrap around to zero.
+    pub(crate) fn lookup_multiplicities(&self, n: usize) -> Vec<C::Range> {
+        let mut m = vec![C::Range::zero(); n];
+
+        // the tables are indexed once rather than searched for every gate
+        let mut index = BTreeMap::new();
+        let mut row = 0;
+        for (table, rows) in self.lookup_tables.iter().enumerate() {
+            for r in rows {
+                index.entry((table, row_key(r))).or_insert(row);
+                row += 1;
+            }
+        }
+
+        self.lookups.iter().for_each(|(gate, table)| {
+            let c = &self.constraints[*gate];
+            let r = [self[c.w_a], self[c.w_b], self[c.w_o]];
+
+            if let Some(row) = index.get(&(*table, row_key(&r))) {
+                m[*row] += C::Range::one();
+            }
+        });
+
+        m
+    }
+}
+
+/// Challenges of the lookup argument
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LookupChallenges<F> {
+    /// Challenge `ζ` compressing the rows
+    pub(crate) zeta: F,
+    /// Challenge `γ` shifting the compressed rows
+    pub(crate) gamma: F,
+    /// Separation challenge of the identity in the quotient
+    pub(crate) separation: F,
+}
+
+impl<F: PrimeField> LookupChallenges<F> {
+    /// Compress a row `(a, b, c, id)` with the powers of `ζ`, shifted by `γ`
+    pub(crate) fn compress(&self, row: [F; 4]) -> F {
+        row.iter()
+            .rev()
+            .fold(F::zero(), |acc, x| acc * self.zeta + *x)
+            + self.gamma
+    }
+}
+
+/// Running sum `φ` of the lookup argument over a domain of `n` rows, given
+/// the compressed and shifted queries `f` and table rows `t`
+pub(crate) fn running_sum<F: PrimeField>(
+    q_lookup: &[F],
+    f: &[F],
+    m: &[F],
+    t: &[F],
+) -> Vec<F> {
+    let invert = |x: &F| x.invert().unwrap_or_else(F::zero);
+
+    let mut phi = Vec::with_capacity(q_lookup.len());
+    let mut acc = F::zero();
+    for i in 0..q_lookup.len() {
+        phi.push(acc);
+        acc += q_lookup[i] * invert(&f[i]) - m[i] * invert(&t[i]);
+    }
+
+    phi
+}
+
+/// Evaluations of the lookup argument opened by a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode)]
+pub(crate) struct LookupEvaluations<F> {
+    /// Evaluation of `q_table` at `z`
+    pub(crate) q_table_eval: F,
+    /// Evaluation of the compressed table `t` at `z`
+    pub(crate) table_eval: F,
+    /// Evaluation of the running sum at `z·ω`
+    pub(crate) phi_next_eval: F,
+}
+
+/// Commitments and evaluations of the lookup argument of a proof
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode)]
+pub(crate) struct LookupProof<P: Pairing> {
+    /// Commitment to the multiplicities of the table rows
+    pub(crate) m_comm: Commitment<P::G1Affine>,
+    /// Commitment to the running sum
+    pub(crate) phi_comm: Commitment<P::G1Affine>,
+    /// Evaluations opened by the proof
+    pub(crate) evaluations: LookupEvaluations<P::ScalarField>,
+}
+
+/// Multiplicities and running sum committed by the prover
+pub(crate) struct LookupWitness<F> {
+    pub(crate) m: Coefficients<F>,
+    pub(crate) phi: Coefficients<F>,
+}
+
+/// Selectors and table columns, as needed by the prover
+#[derive(Clone)]
+pub(crate) struct LookupProvingKey<P: Pairing> {
+    /// Selector of the lookup gates and its evaluations over the `8n` coset
+    pub(crate) q_lookup:
+        (Coefficients<P::ScalarField>, PointsValue<P::ScalarField>),
+    /// Table index of the lookup gates and its evaluations over the `8n`
+    /// coset
+    pub(crate) q_table:
+        (Coefficients<P::ScalarField>, PointsValue<P::ScalarField>),
+    /// Columns `t_a`, `t_b`, `t_c` and `t_id` and their evaluations over the
+    /// `8n` coset
+    pub(crate) table:
+        [(Coefficients<P::ScalarField>, PointsValue<P::ScalarField>); 4],
+}
+
+impl<P: Pairing> LookupProvingKey<P> {
+    /// Contribution of the lookup argument to the quotient at the point `i`
+    /// of the `8n` coset
+    pub(crate) fn compute_quotient_i(
+        &self,
+        i: usize,
+        challenges: &LookupChallenges<P::ScalarField>,
+        (a, b, c): (&P::ScalarField, &P::ScalarField, &P::ScalarField),
+        m: &P::ScalarField,
+        (phi, phi_next): (&P::ScalarField, &P::ScalarField),
+    ) -> P::ScalarField {
+        let f = challenges.compress([*a, *b, *c, self.q_table.1 .0[i]]);
+        let t = challenges.compress([
+            self.table[0].1 .0[i],
+            self.table[1].1 .0[i],
+            self.table[2].1 .0[i],
+            self.table[3].1 .0[i],
+        ]);
+
+        ((*phi_next - *phi) * f * t - self.q_lookup.1 .0[i] * t + *m * f)
+            * challenges.separation
+    }
+
+    /// Table columns compressed with the powers of `ζ`, opened at `z`
+    pub(crate) fn compressed_table(
+        &self,
+        zeta: &P::ScalarField,
+    ) -> Coefficients<P::ScalarField> {
+        let [a, b, c, id] = &self.table;
+
+        let t = &(&id.0 * zeta) + &c.0;
+        let t = &(&t * zeta) + &b.0;
+
+        &(&t * zeta) + &a.0
+    }
+
+    /// Contribution of the lookup argument to the linearization polynomial,
+    /// given the wires opened at `z`.
+    ///
+    /// The term of the running sum at `z·ω` is a constant the verifier adds
+    /// to the evaluation of the quotient.
+    pub(crate) fn linearize(
+        &self,
+        challenges: &LookupChallenges<P::ScalarField>,
+        (a, b, c): (&P::ScalarField, &P::ScalarField, &P::ScalarField),
+        evaluations: &LookupEvaluations<P::ScalarField>,
+        witness: &LookupWitness<P::ScalarField>,
+    ) -> Coefficients<P::ScalarField> {
+        let [phi, m, q_lookup] =
+            linearization_scalars(challenges, (a, b, c), evaluations);
+
+        let r = &witness.phi * &phi;
+        let r = &r + &(&witness.m * &m);
+
+        &r + &(&self.q_lookup.0 * &q_lookup)
+    }
+}
+
+/// Scalars of the running sum, the multiplicities and `q_lookup` in the
+/// linearization polynomial
+pub(crate) fn linearization_scalars<F: PrimeField>(
+    challenges: &LookupChallenges<F>,
+    (a, b, c): (&F, &F, &F),
+    evaluations: &LookupEvaluations<F>,
+) -> [F; 3] {
+    let f = challenges.compress([*a, *b, *c, evaluations.q_table_eval]);
+    let t = evaluations.table_eval + challenges.gamma;
+    let separation = challenges.separation;
+
+    [-(f * t * separation), f * separation, -(t * separation)]
+}
+
+/// Constant term of the lookup argument in the quotient at `z`, the one of
+/// the running sum at `z·ω`
+pub(crate) fn quotient_constant<F: PrimeField>(
+    challenges: &LookupChallenges<F>,
+    (a, b, c): (&F, &F, &F),
+    evaluations: &LookupEvaluations<F>,
+) -> F {
+    let f = challenges.compress([*a, *b, *c, evaluations.q_table_eval]);
+    let t = evaluations.table_eval + challenges.gamma;
+
+    evaluations.phi_next_eval * f * t * challenges.separation
+}
+
+/// Selectors and table columns, as needed by the verifier
+#[derive(Clone)]
+pub(crate) struct LookupVerificationKey<P: Pairing> {
+    pub(crate) q_lookup: Commitment<P::G1Affine>,
+    pub(crate) q_table: Commitment<P::G1Affine>,
+    pub(crate) table: [Commitment<P::G1Affine>; 4],
+}
+
+impl<P: Pairing> LookupVerificationKey<P> {
+    /// Append the commitments to the transcript of a circuit
+    pub(crate) fn append_to(&self, transcript: &mut Transcript) {
+        let labels: [&'static [u8]; 6] =
+            [b"q_lookup", b"q_table", b"t_a", b"t_b", b"t_c", b"t_id"];
+        let commitments = [
+            &self.q_lookup,
+            &self.q_table,
+            &self.table[0],
+            &self.table[1],
+            &self.table[2],
+            &self.table[3],
+        ];
+
+        labels
+            .iter()
+            .zip(commitments.iter())
+            .for_each(|(label, c)| {
+                <Transcript as TranscriptProtocol<P>>::append_commitment(
+                    transcript, label, c,
+                )
+            });
+    }
+
+    /// Commitment to the table columns compressed with the powers of `ζ`
+    pub(crate) fn compressed_table(
+        &self,
+        zeta: &P::ScalarField,
+    ) -> Commitment<P::G1Affine> {
+        let zeta_2 = *zeta * zeta;
+        let zeta_3 = zeta_2 * zeta;
+
+        let points: Vec<_> = self.table.iter().map(|c| c.0).collect();
+        let scalars = [P::ScalarField::one(), *zeta, zeta_2, zeta_3];
+
+        Commitment::new(msm_curve_addition::<P::G1Affine>(&points, &scalars))
+    }
+}
+
+/// Encoding of a row, to index it
+fn row_key<F: Encode>(row: &[F; 3]) -> Vec<u8> {
+    row.iter().flat_map(|x| x.encode()).collect()
+}
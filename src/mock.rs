@@ -0,0 +1,287 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Pairing-free prover and verifier to test the satisfiability of circuits.
+//!
+//! [`MockProver`] and [`MockVerifier`] mirror the API of
+//! [`Prover`](crate::Prover) and [`Verifier`](crate::Verifier), but the
+//! prover only synthesizes the circuit and checks its gates natively,
+//! without FFTs nor commitments. A [`MockProof`] is a distinct type with no
+//! encoding, so it can't be mistaken for a real [`Proof`](crate::Proof).
+//!
+//! The arithmetic, range and lookup gates are checked natively, and the copy
+//! constraints hold by construction since the wires of the composer index a
+//! single witness vector. The logic, curve and custom gates aren't
+//! evaluated, so the mock prover refuses the circuits appending them with
+//! [`MockError::Unsupported`] rather than accepting witnesses the real
+//! prover rejects.
+
+use core::marker::PhantomData;
+
+use rand_core::RngCore;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zkstd::common::{Pairing, PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::{InitConfig, Plonk, PlonkKey, SelectorFamily};
+
+/// Error returned by a [`MockProver`]
+#[derive(Debug)]
+pub enum MockError {
+    /// The circuit failed to synthesize
+    Synthesis(Error),
+    /// The gate isn't satisfied by the witnesses
+    Unsatisfied {
+        /// Index of the gate
+        gate: usize,
+    },
+    /// The gate can't be evaluated natively, so the circuit must be tested
+    /// with the real prover
+    Unsupported {
+        /// Index of the gate
+        gate: usize,
+        /// Family of the gate, or `None` for a custom gate
+        family: Option<SelectorFamily>,
+    },
+}
+
+impl core::fmt::Display for MockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Synthesis(e) => write!(f, "synthesis error: {:?}", e),
+            Self::Unsatisfied { gate } => {
+                write!(f, "gate {} isn't satisfied", gate)
+            }
+            Self::Unsupported { gate, family } => match family {
+                Some(family) => write!(
+                    f,
+                    "gate {} of the {:?} family can't be mocked",
+                    gate, family
+                ),
+                None => write!(f, "the custom gate {} can't be mocked", gate),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MockError {}
+
+impl From<Error> for MockError {
+    fn from(e: Error) -> Self {
+        Self::Synthesis(e)
+    }
+}
+
+/// Dummy proof created by a [`MockProver`].
+///
+/// It holds no commitment and can only be checked by a [`MockVerifier`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockProof<F> {
+    public_input_indexes: Vec<usize>,
+    public_inputs: Vec<F>,
+}
+
+/// Prover that checks the satisfiability of a circuit natively
+#[derive(Debug, Clone)]
+pub struct MockProver<P, A>
+where
+    P: Pairing,
+    A: TwistedEdwardsAffine<Range = P::ScalarField>,
+{
+    config: InitConfig,
+    _mark: PhantomData<(P, A)>,
+}
+
+impl<P, A> MockProver<P, A>
+where
+    P: Pairing,
+    A: TwistedEdwardsAffine<Range = P::ScalarField>,
+{
+    /// Synthesize the circuit and check its gates.
+    ///
+    /// As with the real prover, an unsatisfied circuit fails to prove, with
+    /// [`MockError::Unsatisfied`]. A circuit with gates that can't be
+    /// evaluated natively fails with [`MockError::Unsupported`].
+    pub fn create_proof<R, C>(
+        &self,
+        _rng: &mut R,
+        circuit: &C,
+    ) -> Result<(MockProof<P::ScalarField>, Vec<P::ScalarField>), MockError>
+    where
+        C: Circuit<A, ConstraintSystem = Plonk<A>>,
+        R: RngCore,
+    {
+        let mut prover = Plonk::<A>::with_config(self.config);
+
+        circuit.synthesize(&mut prover)?;
+
+        prover.check_gates()?;
+
+        let public_inputs = prover.instance();
+        let proof = MockProof {
+            public_input_indexes: prover.public_input_indexes(),
+            public_inputs: public_inputs.clone(),
+        };
+
+        Ok((proof, public_inputs))
+    }
+}
+
+/// Verifier of [`MockProof`]s
+#[derive(Debug, Clone)]
+pub struct MockVerifier<P: Pairing> {
+    public_input_indexes: Vec<usize>,
+    _mark: PhantomData<P>,
+}
+
+impl<P: Pairing> MockVerifier<P> {
+    /// Verify a mock proof against the public inputs
+    pub fn verify(
+        &self,
+        proof: &MockProof<P::ScalarField>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<(), Error> {
+        if public_inputs.len() != self.public_input_indexes.len() {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: self.public_input_indexes.len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        match proof.public_input_indexes == self.public_input_indexes
+            && proof.public_inputs == public_inputs
+        {
+            true => Ok(()),
+            false => Err(Error::ProofVerificationError),
+        }
+    }
+}
+
+impl<
+        P: Pairing,
+        A: TwistedEdwardsAffine<Range = P::ScalarField>,
+        C: Circuit<A, ConstraintSystem = Plonk<A>>,
+    > PlonkKey<P, A, C>
+{
+    /// Create a [`MockProver`] and [`MockVerifier`] for the circuit.
+    ///
+    /// The params are unused, and only taken to mirror
+    /// [`Keypair::compile`](zksnarks::keypair::Keypair::compile).
+    pub fn compile_mock(
+        _pp: &PlonkParams<P>,
+    ) -> Result<(MockProver<P, A>, MockVerifier<P>), Error> {
+        let config = InitConfig::default();
+        let mut cs = Plonk::<A>::with_config(config);
+
+        C::default().synthesize(&mut cs)?;
+
+        let prover = MockProver {
+            config,
+            _mark: PhantomData,
+        };
+        let verifier = MockVerifier {
+            public_input_indexes: cs.public_input_indexes(),
+            _mark: PhantomData,
+        };
+
+        Ok((prover, verifier))
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Check every gate against the witnesses, returning the first one that
+    /// isn't satisfied or can't be evaluated natively
+    pub(crate) fn check_gates(&self) -> Result<(), MockError> {
+        let zero = C::Range::zero();
+
+        for (gate, c) in self.constraints.iter().enumerate() {
+            if self.custom_gates.contains_key(&gate) {
+                return Err(MockError::Unsupported { gate, family: None });
+            }
+
+            let unsupported = [
+                SelectorFamily::Logic,
+                SelectorFamily::FixedBase,
+                SelectorFamily::VariableBase,
+            ];
+            if let Some(family) =
+                unsupported.into_iter().find(|f| f.is_active(c))
+            {
+                return Err(MockError::Unsupported {
+                    gate,
+                    family: Some(family),
+                });
+            }
+
+            let a = self[c.w_a];
+            let b = self[c.w_b];
+            let o = self[c.w_o];
+            let d = self[c.w_d];
+            let pi = self.instance.get(&gate).copied().unwrap_or(zero);
+
+            let arithmetic = c.q_m * a * b
+                + c.q_l * a
+                + c.q_r * b
+                + c.q_o * o
+                + c.q_d * d
+                + c.q_c;
+
+            if c.q_arith * arithmetic + pi != zero {
+                return Err(MockError::Unsatisfied { gate });
+            }
+
+            // the wires of a lookup gate are a row of its table
+            if let Some(table) = self.lookups.get(&gate) {
+                if !self.lookup_tables[*table].contains(&[a, b, o]) {
+                    return Err(MockError::Unsatisfied { gate });
+                }
+            }
+
+            // every quad of the gate and the next one is in {0, 1, 2, 3}
+            if SelectorFamily::Range.is_active(c) {
+                let four = C::Range::from(4u64);
+                let d_next = self.next_fourth_wire(gate);
+
+                let quads = [
+                    o - four * d,
+                    b - four * o,
+                    a - four * b,
+                    d_next - four * a,
+                ];
+                if quads.into_iter().any(|q| Self::delta(q) != zero) {
+                    return Err(MockError::Unsatisfied { gate });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Value of the fourth wire of the gate after `gate`, as read by the
+    /// widgets over the padded domain: the rows past the last gate are
+    /// zero, and the domain wraps around to the first gate when the gates
+    /// fill it
+    fn next_fourth_wire(&self, gate: usize) -> C::Range {
+        let m = self.constraints.len();
+
+        match self.constraints.get(gate + 1) {
+            Some(next) => self[next.w_d],
+            None if m.is_power_of_two() => self[self.constraints[0].w_d],
+            None => C::Range::zero(),
+        }
+    }
+
+    /// `x · (x - 1) · (x - 2) · (x - 3)`, zero on the quads
+    fn delta(x: C::Range) -> C::Range {
+        let one = C::Range::one();
+        let two = one + one;
+        let three = two + one;
+
+        x * (x - one) * (x - two) * (x - three)
+    }
+}
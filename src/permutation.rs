@@ -25,9 +25,9 @@ pub(crate) struct Permutation<F: FftField> {
 }
 
 impl<F: FftField> Permutation<F> {
-    const K1: u64 = 7;
-    const K2: u64 = 13;
-    const K3: u64 = 17;
+    pub(crate) const K1: u64 = 7;
+    pub(crate) const K2: u64 = 13;
+    pub(crate) const K3: u64 = 17;
 
     /// Creates a Permutation struct with an expected capacity of zero.
     pub(crate) fn new() -> Permutation<F> {
@@ -42,6 +42,11 @@ impl<F: FftField> Permutation<F> {
         }
     }
 
+    /// Reserves the capacity of at least `additional` more witnesses.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.witness_map.reserve(additional);
+    }
+
     /// Creates a new [`PrivateWire`] by incrementing the index of the
     /// `witness_map`.
     ///
@@ -90,6 +95,20 @@ impl<F: FftField> Permutation<F> {
         self.add_witness_to_map(d, fourth);
     }
 
+    /// Drop the witnesses from index `witnesses` on, and the wires of the
+    /// gates from index `gates` on
+    pub(crate) fn truncate(&mut self, witnesses: usize, gates: usize) {
+        self.witness_map.retain(|w, _| w.index() < witnesses);
+        self.witness_map.values_mut().for_each(|wires| {
+            wires.retain(|wire| match wire {
+                WireType::Left(i)
+                | WireType::Right(i)
+                | WireType::Output(i)
+                | WireType::Fourth(i) => *i < gates,
+            })
+        });
+    }
+
     pub(crate) fn add_witness_to_map(
         &mut self,
         var: PrivateWire,
@@ -109,10 +128,23 @@ impl<F: FftField> Permutation<F> {
         &mut self,
         n: usize,
     ) -> [Vec<WireType>; 4] {
-        let mut sigma_1: Vec<_> = (0..n).map(WireType::Left).collect();
-        let mut sigma_2: Vec<_> = (0..n).map(WireType::Right).collect();
-        let mut sigma_3: Vec<_> = (0..n).map(WireType::Output).collect();
-        let mut sigma_4: Vec<_> = (0..n).map(WireType::Fourth).collect();
+        [0, 1, 2, 3].map(|column| self.compute_sigma_permutation(n, column))
+    }
+
+    /// Computes the sigma permutation of a single wire column, `0` to `3` for
+    /// the left, right, output and fourth wires
+    pub(crate) fn compute_sigma_permutation(
+        &self,
+        n: usize,
+        column: usize,
+    ) -> Vec<WireType> {
+        let wire: fn(usize) -> WireType = match column {
+            0 => WireType::Left,
+            1 => WireType::Right,
+            2 => WireType::Output,
+            _ => WireType::Fourth,
+        };
+        let mut sigma: Vec<_> = (0..n).map(wire).collect();
 
         for (_, wire_data) in self.witness_map.iter() {
             // Gets the data for each wire associated with this witness
@@ -124,20 +156,20 @@ impl<F: FftField> Permutation<F> {
                     false => wire_index + 1,
                 };
 
-                // Fetch the next wire
-                let next_wire = &wire_data[next_index];
-
-                // Map current wire to next wire
-                match current_wire {
-                    WireType::Left(index) => sigma_1[*index] = *next_wire,
-                    WireType::Right(index) => sigma_2[*index] = *next_wire,
-                    WireType::Output(index) => sigma_3[*index] = *next_wire,
-                    WireType::Fourth(index) => sigma_4[*index] = *next_wire,
+                // Map current wire to next wire, if it is in the column
+                let index = match (column, current_wire) {
+                    (0, WireType::Left(index))
+                    | (1, WireType::Right(index))
+                    | (2, WireType::Output(index))
+                    | (3, WireType::Fourth(index)) => *index,
+                    _ => continue,
                 };
+
+                sigma[index] = wire_data[next_index];
             }
         }
 
-        [sigma_1, sigma_2, sigma_3, sigma_4]
+        sigma
     }
 
     fn compute_permutation_lagrange(
@@ -145,7 +177,7 @@ impl<F: FftField> Permutation<F> {
         sigma_mapping: &[WireType],
         fft: &Fft<F>,
     ) -> Vec<F> {
-        let roots = fft.elements.clone();
+        let roots = &fft.elements;
 
         sigma_mapping
             .iter()
@@ -167,36 +199,35 @@ impl<F: FftField> Permutation<F> {
             .collect()
     }
 
+    /// Computes the sigma polynomial of a single wire column.
+    ///
+    /// Only the mapping of the column is held in memory, so the columns can
+    /// be processed one after the other
+    pub(crate) fn compute_sigma_polynomial(
+        &self,
+        n: usize,
+        column: usize,
+        fft: &Fft<F>,
+    ) -> Coefficients<F> {
+        let sigma = self.compute_sigma_permutation(n, column);
+
+        // define the sigma permutation using the non quadratic residues
+        let s_sigma =
+            Evaluations::new(self.compute_permutation_lagrange(&sigma, fft));
+        drop(sigma);
+
+        fft.idft(s_sigma)
+    }
+
     /// Computes the sigma polynomials which are used to build the permutation
     /// polynomial
+    #[cfg(test)]
     pub(crate) fn compute_sigma_polynomials(
         &mut self,
         n: usize,
         fft: &Fft<F>,
     ) -> [Coefficients<F>; 4] {
-        // Compute sigma mappings
-        let sigmas = self.compute_sigma_permutations(n);
-
-        // define the sigma permutations using two non quadratic residues
-        let s_sigma_1 = Evaluations::new(
-            self.compute_permutation_lagrange(&sigmas[0], fft),
-        );
-        let s_sigma_2 = Evaluations::new(
-            self.compute_permutation_lagrange(&sigmas[1], fft),
-        );
-        let s_sigma_3 = Evaluations::new(
-            self.compute_permutation_lagrange(&sigmas[2], fft),
-        );
-        let s_sigma_4 = Evaluations::new(
-            self.compute_permutation_lagrange(&sigmas[3], fft),
-        );
-
-        let s_sigma_1 = fft.idft(s_sigma_1);
-        let s_sigma_2 = fft.idft(s_sigma_2);
-        let s_sigma_3 = fft.idft(s_sigma_3);
-        let s_sigma_4 = fft.idft(s_sigma_4);
-
-        [s_sigma_1, s_sigma_2, s_sigma_3, s_sigma_4]
+        [0, 1, 2, 3].map(|column| self.compute_sigma_polynomial(n, column, fft))
     }
 
     // Uses a rayon multizip to allow more code flexibility while remaining
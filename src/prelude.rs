@@ -9,12 +9,38 @@
 //! Use this as the only import that you need to interact
 //! with the principal data structures of the plonk library.
 
-pub use super::{Plonk, PlonkKey, Prover, Verifier};
+pub use super::{
+    BlindingConfig, BlindingError, Checkpoint, CircuitStats, CompileError,
+    CustomGate, DebugMismatch, Expression, GateWires, InitConfig, Plonk,
+    PlonkKey, Prover, ProverOptions, PublicInput, PublicInputError,
+    QuotientParts, SanityError, SelectorFamily, SizeHint, TableId, Verifier,
+    WirePosition,
+};
+pub use crate::gadget::fixed_base::{FixedBaseTable, WindowedBaseTable};
+pub use crate::gadget::typed::{BoolWire, JubJubScalarWire, RangeWire};
 pub use crate::gadget::WitnessPoint;
-pub use crate::prover::Proof;
+pub use crate::prover::{
+    AggregatedProof, DecodeReason, Proof, ProofDecodeError,
+};
 
 pub use bls_12_381::Fr as BlsScalar;
+pub use ec_pairing::TatePairing;
 pub use jub_jub::{Fp as JubjubScalar, JubjubAffine, JubjubExtended};
 pub use zksnarks::circuit::Circuit;
+pub use zksnarks::constraint_system::ConstraintSystem;
 pub use zksnarks::error::Error;
+pub use zksnarks::keypair::Keypair;
+pub use zksnarks::plonk::wire::PrivateWire;
+pub use zksnarks::plonk::PlonkParams;
+pub use zksnarks::public_params::PublicParameters;
 pub use zksnarks::Constraint;
+pub use zkstd::common::Group;
+
+/// Composer of circuits over the Jubjub curve
+pub type JubjubPlonk = Plonk<JubjubAffine>;
+
+/// Prover of circuits over the Jubjub curve
+pub type JubjubProver = Prover<TatePairing, JubjubAffine>;
+
+/// Verifier of circuits over the Jubjub curve
+pub type JubjubVerifier = Verifier<TatePairing>;
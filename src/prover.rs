@@ -4,15 +4,34 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod aggregate;
+mod blinding;
+#[cfg(feature = "export-keys")]
+mod export;
 mod linearization_poly;
 mod proof;
 mod quotient_poly;
+mod sanity;
 
 use core::marker::PhantomData;
 
-use super::Plonk;
-pub use proof::Proof;
-use zksnarks::constraint_system::ConstraintSystem;
+use super::{InitConfig, Plonk};
+use crate::commitment_scheme::batch_challenge;
+use crate::custom_gate::{self, CustomProvingKey, CustomVerificationKey};
+use crate::key::degree::{self, QuotientParts};
+use crate::lookup::{
+    self, LookupChallenges, LookupProof, LookupProvingKey,
+    LookupVerificationKey, LookupWitness,
+};
+use crate::verifier::Challenges;
+pub use aggregate::AggregatedProof;
+pub use blinding::{BlindingConfig, BlindingError};
+#[cfg(feature = "export-keys")]
+pub use export::{Selector, SelectorView};
+pub(crate) use proof::QUOTIENT_LABELS;
+pub use proof::{DecodeReason, Proof, ProofDecodeError};
+pub(crate) use sanity::CompiledShape;
+pub use sanity::{ProverOptions, SanityError, WirePosition};
 use zksnarks::error::Error;
 
 use poly_commit::{Coefficients, Fft, PointsValue};
@@ -22,7 +41,9 @@ use zksnarks::circuit::Circuit;
 use zksnarks::plonk::{
     PlonkParams, ProvingKey, Transcript, TranscriptProtocol, VerificationKey,
 };
-use zkstd::common::{FftField, Group, Pairing, TwistedEdwardsAffine, Vec};
+use zkstd::common::{
+    FftField, Group, Pairing, Ring, TwistedEdwardsAffine, Vec,
+};
 
 /// Turbo Prover with processed keys
 #[derive(Clone)]
@@ -32,9 +53,17 @@ where
     A: TwistedEdwardsAffine<Range = P::ScalarField>,
 {
     pub(crate) prover_key: ProvingKey<P>,
+    pub(crate) custom: Vec<CustomProvingKey<P>>,
+    pub(crate) lookup: Option<LookupProvingKey<P>>,
+    pub(crate) parts: QuotientParts,
     pub(crate) keypair: PlonkParams<P>,
     pub(crate) transcript: Transcript,
     pub(crate) size: usize,
+    pub(crate) config: InitConfig,
+    pub(crate) blinding: BlindingConfig,
+    pub(crate) shape: CompiledShape,
+    pub(crate) options: ProverOptions,
+    pub(crate) reorder: bool,
     _mark: PhantomData<A>,
 }
 
@@ -48,21 +77,145 @@ where
         keypair: PlonkParams<P>,
         prover_key: ProvingKey<P>,
         verifier_key: VerificationKey<P>,
+        custom: Vec<CustomProvingKey<P>>,
+        custom_verifier: &[CustomVerificationKey<P>],
+        lookup: Option<LookupProvingKey<P>>,
+        lookup_verifier: Option<&LookupVerificationKey<P>>,
+        parts: QuotientParts,
+        config: InitConfig,
         size: usize,
-        constraints: usize,
+        shape: CompiledShape,
+        reorder: bool,
     ) -> Self {
-        let transcript =
+        let constraints = shape.constraints;
+        let mut transcript =
             Transcript::base(label.as_slice(), &verifier_key, constraints);
+        custom_gate::append_selectors(&mut transcript, custom_verifier);
+        if let Some(lookup) = lookup_verifier {
+            lookup.append_to(&mut transcript);
+        }
 
         Self {
             prover_key,
+            custom,
+            lookup,
+            parts,
             keypair,
             transcript,
             size,
+            config,
+            blinding: BlindingConfig::default(),
+            shape,
+            options: ProverOptions::default(),
+            reorder,
             _mark: PhantomData,
         }
     }
 
+    /// Maximum degree of the quotient polynomial for the selector families
+    /// and the lookup argument used by the compiled circuit.
+    ///
+    /// The compiler guarantees it fits the quotient parts of a [`Proof`].
+    pub fn max_quotient_degree(&self) -> usize {
+        degree::max_quotient_degree(
+            self.size,
+            degree::key_families(&self.prover_key),
+            &self.custom_wire_degrees(),
+            self.lookup.is_some(),
+            &self.blinding,
+        )
+    }
+
+    /// Wire degrees of the custom gates of the compiled circuit
+    fn custom_wire_degrees(&self) -> Vec<usize> {
+        self.custom.iter().map(|k| k.gate.wire_degree()).collect()
+    }
+
+    /// Blinding of the committed polynomials
+    pub fn blinding(&self) -> BlindingConfig {
+        self.blinding
+    }
+
+    /// Set the blinding of the committed polynomials.
+    ///
+    /// The verifier is unaffected. Terms below
+    /// [`BlindingConfig::minimum_safe`] are refused unless the config is
+    /// flagged as insecure. Raising the terms increases the degree of the
+    /// committed polynomials, so proving may fail if they exceed the
+    /// committing key.
+    pub fn set_blinding(
+        &mut self,
+        blinding: BlindingConfig,
+    ) -> Result<(), BlindingError> {
+        if !blinding.is_safe() && !blinding.insecure {
+            return Err(BlindingError::Insecure);
+        }
+
+        let degree = degree::max_quotient_degree(
+            self.size,
+            degree::key_families(&self.prover_key),
+            &self.custom_wire_degrees(),
+            self.lookup.is_some(),
+            &blinding,
+        );
+        let max = degree::quotient_domain_budget(self.size);
+
+        if degree > max {
+            return Err(BlindingError::DegreeTooLarge { degree, max });
+        }
+
+        self.blinding = blinding;
+
+        Ok(())
+    }
+
+    /// Options of the prover
+    pub fn options(&self) -> ProverOptions {
+        self.options
+    }
+
+    /// Set the options of the prover
+    pub fn set_options(&mut self, options: ProverOptions) {
+        self.options = options;
+    }
+
+    /// Check that a synthesized composer describes the compiled circuit.
+    ///
+    /// The gates and witnesses must match the compiled circuit, every wire
+    /// must reference an appended witness, and the wires of every copy
+    /// constraint cycle must carry the same value. The first mismatch is
+    /// returned.
+    pub fn check_composer(
+        &self,
+        composer: &Plonk<A>,
+    ) -> Result<(), SanityError<P::ScalarField>> {
+        self.shape.check(composer)
+    }
+
+    /// Empty composer to synthesize the circuit with
+    fn composer(&self) -> Plonk<A> {
+        let mut composer = Plonk::<A>::with_config(self.config);
+        composer.set_strict_witnesses(self.options.strict_witnesses);
+
+        composer
+    }
+
+    /// Synthesize `circuit` as the compiler did
+    fn synthesize<C>(&self, circuit: &C) -> Result<Plonk<A>, Error>
+    where
+        C: Circuit<A, ConstraintSystem = Plonk<A>>,
+    {
+        let mut composer = self.composer();
+
+        circuit.synthesize(&mut composer)?;
+
+        if self.reorder {
+            composer.reorder_for_locality();
+        }
+
+        Ok(composer)
+    }
+
     /// Prove the circuit
     pub fn create_proof<R, C>(
         &self,
@@ -73,16 +226,116 @@ where
         C: Circuit<A, ConstraintSystem = Plonk<A>>,
         R: RngCore,
     {
-        let mut prover = Plonk::<A>::initialize();
+        let prover = self.synthesize(circuit)?;
 
-        circuit.synthesize(&mut prover)?;
+        self.create_proof_with_composer(rng, &prover)
+    }
+
+    /// Prove the circuit `C` with the witnesses of a state produced by
+    /// [`Plonk::serialize_witness_state`].
+    ///
+    /// The gates are synthesized locally from [`Circuit::default`], then the
+    /// state is applied and the composer is checked with
+    /// [`Self::check_composer`], regardless of [`ProverOptions::skip_sanity`].
+    /// [`Error::ProofVerificationError`] is returned if the state doesn't
+    /// apply or the check fails; [`Plonk::apply_witness_state`] reports why.
+    pub fn prove_with_witness_state<R, C>(
+        &self,
+        rng: &mut R,
+        bytes: &[u8],
+    ) -> Result<(Proof<P>, Vec<P::ScalarField>), Error>
+    where
+        C: Circuit<A, ConstraintSystem = Plonk<A>>,
+        R: RngCore,
+    {
+        let mut prover = self.synthesize(&C::default())?;
+
+        prover
+            .apply_witness_state(bytes)
+            .map_err(|_| Error::ProofVerificationError)?;
+
+        if self.check_composer(&prover).is_err() {
+            return Err(Error::ProofVerificationError);
+        }
+
+        self.prove(rng, &prover)
+            .map(|(proof, public_inputs, _)| (proof, public_inputs))
+    }
+
+    /// Prove the circuit described by an already synthesized composer.
+    ///
+    /// The composer must be created with [`Plonk::with_config`] with the
+    /// config of the compiled circuit, and describe the same circuit. This
+    /// allows reusing a composer across proofs with [`Plonk::checkpoint`].
+    /// If the keys were compiled with
+    /// [`PlonkKey::compile_for_locality`](crate::PlonkKey::compile_for_locality),
+    /// the composer must be reordered with [`Plonk::reorder_for_locality`].
+    ///
+    /// Unless [`ProverOptions::skip_sanity`] is set, the composer is checked
+    /// with [`Self::check_composer`] before committing to anything, and
+    /// [`Error::ProofVerificationError`] is returned on mismatch, since the
+    /// proof would fail to verify. [`Self::check_composer`] reports the
+    /// mismatch.
+    pub fn create_proof_with_composer<R>(
+        &self,
+        rng: &mut R,
+        prover: &Plonk<A>,
+    ) -> Result<(Proof<P>, Vec<P::ScalarField>), Error>
+    where
+        R: RngCore,
+    {
+        self.prove(rng, prover)
+            .map(|(proof, public_inputs, _)| (proof, public_inputs))
+    }
+
+    /// Prove the circuit and return the challenges the proof was created
+    /// with, to check them against
+    /// [`Verifier::derive_challenges`](crate::Verifier::derive_challenges)
+    #[cfg(feature = "test-utils")]
+    pub fn create_proof_with_challenges<R, C>(
+        &self,
+        rng: &mut R,
+        circuit: &C,
+    ) -> Result<
+        (Proof<P>, Vec<P::ScalarField>, Challenges<P::ScalarField>),
+        Error,
+    >
+    where
+        C: Circuit<A, ConstraintSystem = Plonk<A>>,
+        R: RngCore,
+    {
+        let prover = self.synthesize(circuit)?;
+
+        self.prove(rng, &prover)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn prove<R>(
+        &self,
+        rng: &mut R,
+        prover: &Plonk<A>,
+    ) -> Result<
+        (Proof<P>, Vec<P::ScalarField>, Challenges<P::ScalarField>),
+        Error,
+    >
+    where
+        R: RngCore,
+    {
+        if !self.options.skip_sanity && self.check_composer(prover).is_err() {
+            return Err(Error::ProofVerificationError);
+        }
 
         let Self {
             prover_key,
+            custom,
+            lookup: lookup_key,
+            parts,
             keypair,
             mut transcript,
             size,
+            blinding,
             _mark,
+            ..
         } = self.clone();
         let k = size.trailing_zeros();
         let fft = Fft::<P::ScalarField>::new(k as usize);
@@ -123,10 +376,14 @@ where
         let mut o_w_poly = fft.idft(o_w_scalar.clone());
         let mut d_w_poly = fft.idft(d_w_scalar.clone());
 
-        a_w_poly.blind(1, rng);
-        b_w_poly.blind(1, rng);
-        o_w_poly.blind(1, rng);
-        d_w_poly.blind(1, rng);
+        if blinding.wire_poly_terms > 0 {
+            let degree = blinding.wire_poly_terms - 1;
+
+            a_w_poly.blind(degree, rng);
+            b_w_poly.blind(degree, rng);
+            o_w_poly.blind(degree, rng);
+            d_w_poly.blind(degree, rng);
+        }
 
         // commit to wire polynomials
         // ([a(x)]_1, [b(x)]_1, [c(x)]_1, [d(x)]_1)
@@ -157,6 +414,84 @@ where
             &d_w_poly_commit,
         );
 
+        // lookup round
+        // commit to the multiplicities of the table rows, then to the running
+        // sum of the compressed lookups and table rows
+        let lookup_round = match &lookup_key {
+            Some(_) => {
+                let m = prover.lookup_multiplicities(size);
+                let mut m_poly = fft.idft(PointsValue(m.clone()));
+                if blinding.wire_poly_terms > 0 {
+                    m_poly.blind(blinding.wire_poly_terms - 1, rng);
+                }
+                let m_poly_commit = keypair.commit(&m_poly)?;
+                <Transcript as TranscriptProtocol<P>>::append_commitment(
+                    &mut transcript,
+                    b"lookup_m",
+                    &m_poly_commit,
+                );
+
+                let zeta =
+                    <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                        &mut transcript,
+                        b"lookup compression",
+                    );
+                let gamma =
+                    <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                        &mut transcript,
+                        b"lookup gamma",
+                    );
+                let challenges = LookupChallenges {
+                    zeta,
+                    gamma,
+                    separation: P::ScalarField::zero(),
+                };
+
+                let [q_lookup, q_table] = prover.lookup_selectors(size);
+                let table = prover.table_columns(size);
+                let f: Vec<_> = (0..size)
+                    .map(|i| {
+                        challenges.compress([
+                            a_w_scalar.0[i],
+                            b_w_scalar.0[i],
+                            o_w_scalar.0[i],
+                            q_table[i],
+                        ])
+                    })
+                    .collect();
+                let t: Vec<_> = (0..size)
+                    .map(|i| {
+                        challenges.compress([
+                            table[0][i],
+                            table[1][i],
+                            table[2][i],
+                            table[3][i],
+                        ])
+                    })
+                    .collect();
+                let phi = lookup::running_sum(&q_lookup, &f, &m, &t);
+
+                let mut phi_poly = fft.idft(PointsValue(phi));
+                if blinding.perm_poly_terms > 0 {
+                    phi_poly.blind(blinding.perm_poly_terms - 1, rng);
+                }
+                let phi_poly_commit = keypair.commit(&phi_poly)?;
+                <Transcript as TranscriptProtocol<P>>::append_commitment(
+                    &mut transcript,
+                    b"lookup_phi",
+                    &phi_poly_commit,
+                );
+
+                let witness = LookupWitness {
+                    m: m_poly,
+                    phi: phi_poly,
+                };
+
+                Some((witness, m_poly_commit, phi_poly_commit, challenges))
+            }
+            None => None,
+        };
+
         // round 2
         // permutation challenges
         let beta = <Transcript as TranscriptProtocol<P>>::challenge_scalar(
@@ -190,7 +525,9 @@ where
             .compute_permutation_vec(&fft, wires, &beta, &gamma, sigma);
 
         let mut z_poly = fft.idft(PointsValue(permutation));
-        z_poly.blind(2, rng);
+        if blinding.perm_poly_terms > 0 {
+            z_poly.blind(blinding.perm_poly_terms - 1, rng);
+        }
         let z_poly_commit = keypair.commit(&z_poly)?;
         <Transcript as TranscriptProtocol<P>>::append_commitment(
             &mut transcript,
@@ -224,6 +561,27 @@ where
                 &mut transcript,
                 b"variable base separation challenge",
             );
+        let custom_sep_challenges = custom_gate::separation_challenges::<P, _>(
+            &mut transcript,
+            custom.iter().map(|k| k.gate.label()),
+        );
+        let lookup_round = lookup_round.map(|(witness, m, phi, challenges)| {
+            let separation =
+                <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                    &mut transcript,
+                    b"lookup separation challenge",
+                );
+
+            let challenges = LookupChallenges {
+                separation,
+                ..challenges
+            };
+
+            (witness, m, phi, challenges)
+        });
+        let lookup_args = lookup_key.as_ref().zip(lookup_round.as_ref()).map(
+            |(key, (witness, _, _, challenges))| (key, challenges, witness),
+        );
 
         // compute public inputs polynomial
         let pi_poly = fft.idft(dense_public_inputs);
@@ -246,45 +604,42 @@ where
             wires,
             &pi_poly,
             args,
+            (custom.as_slice(), custom_sep_challenges.as_slice()),
+            lookup_args,
         )?;
 
-        // split quotient polynomial into 4 degree `n` polynomials
+        // split quotient polynomial into degree `n` polynomials, the last
+        // one holding the remainder
         let domain_size = fft.size();
-        let t_low_poly = Coefficients::new(t_poly[0..domain_size].to_vec());
-        let t_mid_poly =
-            Coefficients::new(t_poly[domain_size..2 * domain_size].to_vec());
-        let t_high_poly = Coefficients::new(
-            t_poly[2 * domain_size..3 * domain_size].to_vec(),
-        );
-        let t_4_poly = Coefficients::new(t_poly[3 * domain_size..].to_vec());
-
-        // commit to split quotient polynomial
-        let t_low_commit = keypair.commit(&t_low_poly)?;
-        let t_mid_commit = keypair.commit(&t_mid_poly)?;
-        let t_high_commit = keypair.commit(&t_high_poly)?;
-        let t_4_commit = keypair.commit(&t_4_poly)?;
-
-        // add quotient polynomial commitments to transcript
-        <Transcript as TranscriptProtocol<P>>::append_commitment(
-            &mut transcript,
-            b"t_low",
-            &t_low_commit,
-        );
-        <Transcript as TranscriptProtocol<P>>::append_commitment(
-            &mut transcript,
-            b"t_mid",
-            &t_mid_commit,
-        );
-        <Transcript as TranscriptProtocol<P>>::append_commitment(
-            &mut transcript,
-            b"t_high",
-            &t_high_commit,
-        );
-        <Transcript as TranscriptProtocol<P>>::append_commitment(
-            &mut transcript,
-            b"t_4",
-            &t_4_commit,
-        );
+        let len = t_poly.0.len();
+        let t_parts: Vec<_> = (0..parts.count())
+            .map(|i| {
+                let start = (i * domain_size).min(len);
+                let end = match i + 1 == parts.count() {
+                    true => len,
+                    false => ((i + 1) * domain_size).min(len),
+                };
+
+                // the top parts of six may be empty for a lower quotient
+                match start < end {
+                    true => Coefficients::new(t_poly.0[start..end].to_vec()),
+                    false => Coefficients::new(vec![P::ScalarField::zero()]),
+                }
+            })
+            .collect();
+
+        // commit to split quotient polynomial and add the commitments to
+        // transcript
+        let mut t_commits = Vec::with_capacity(t_parts.len());
+        for (part, label) in t_parts.iter().zip(QUOTIENT_LABELS) {
+            let commit = keypair.commit(part)?;
+            <Transcript as TranscriptProtocol<P>>::append_commitment(
+                &mut transcript,
+                label,
+                &commit,
+            );
+            t_commits.push(commit);
+        }
 
         // round 4
         // compute evaluation challenge 'z'
@@ -315,6 +670,8 @@ where
             &d_w_poly,
             &t_poly,
             &z_poly,
+            (custom.as_slice(), custom_sep_challenges.as_slice()),
+            lookup_args,
         );
 
         // add evaluations to transcript.
@@ -403,54 +760,109 @@ where
             b"r_eval",
             &evaluations.proof.r_poly_eval,
         );
+        if let Some(lookup_evals) = &evaluations.lookup {
+            <Transcript as TranscriptProtocol<P>>::append_scalar(
+                &mut transcript,
+                b"q_table_eval",
+                &lookup_evals.q_table_eval,
+            );
+            <Transcript as TranscriptProtocol<P>>::append_scalar(
+                &mut transcript,
+                b"table_eval",
+                &lookup_evals.table_eval,
+            );
+            <Transcript as TranscriptProtocol<P>>::append_scalar(
+                &mut transcript,
+                b"phi_next_eval",
+                &lookup_evals.phi_next_eval,
+            );
+        }
 
         // compute Openings using KZG10
         let z_n = z_challenge.pow(domain_size as u64);
-        let z_two_n = z_challenge.pow(2 * domain_size as u64);
-        let z_three_n = z_challenge.pow(3 * domain_size as u64);
-
-        let a = &t_low_poly;
-        let b = &t_mid_poly * &z_n;
-        let c = &t_high_poly * &z_two_n;
-        let d = &t_4_poly * &z_three_n;
-        let abc = (a + &b) + c;
-
-        let quot = &abc + &d;
+        let mut z_in = P::ScalarField::one();
+        let mut quot = t_parts[0].clone();
+        for part in &t_parts[1..] {
+            z_in *= z_n;
+            quot = &quot + &(part * &z_in);
+        }
 
         // compute aggregate witness to polynomials evaluated at the evaluation
-        // challenge z. The challenge v is selected inside
-        let aggregate_witness = keypair.compute_aggregate_witness(
-            &[
-                quot,
-                r_poly,
-                a_w_poly.clone(),
-                b_w_poly.clone(),
-                o_w_poly,
-                d_w_poly.clone(),
-                prover_key.permutation.s_sigma_1.0.clone(),
-                prover_key.permutation.s_sigma_2.0.clone(),
-                prover_key.permutation.s_sigma_3.0,
-            ],
-            &z_challenge,
-            &<Transcript as TranscriptProtocol<P>>::challenge_scalar(
+        // challenge z
+        let v_challenge =
+            <Transcript as TranscriptProtocol<P>>::challenge_scalar(
                 &mut transcript,
                 b"v_challenge",
-            ),
+            );
+        let mut polynomials = vec![
+            quot,
+            r_poly,
+            a_w_poly.clone(),
+            b_w_poly.clone(),
+            o_w_poly,
+            d_w_poly.clone(),
+            prover_key.permutation.s_sigma_1.0.clone(),
+            prover_key.permutation.s_sigma_2.0.clone(),
+            prover_key.permutation.s_sigma_3.0,
+        ];
+        if let Some((key, challenges, _)) = lookup_args {
+            polynomials.push(key.q_table.0.clone());
+            polynomials.push(key.compressed_table(&challenges.zeta));
+        }
+        let aggregate_witness = keypair.compute_aggregate_witness(
+            &polynomials,
+            &z_challenge,
+            &v_challenge,
         );
         let w_z_chall_comm = keypair.commit(&aggregate_witness)?;
 
         // compute aggregate witness to polynomials evaluated at the shifted
         // evaluation challenge
-        let shifted_aggregate_witness = keypair.compute_aggregate_witness(
-            &[z_poly, a_w_poly, b_w_poly, d_w_poly],
-            &(z_challenge * fft.generator()),
-            &<Transcript as TranscriptProtocol<P>>::challenge_scalar(
+        let shifted_v_challenge =
+            <Transcript as TranscriptProtocol<P>>::challenge_scalar(
                 &mut transcript,
                 b"v_challenge",
-            ),
+            );
+        let mut shifted_polynomials =
+            vec![z_poly, a_w_poly, b_w_poly, d_w_poly];
+        if let Some((_, _, witness)) = lookup_args {
+            shifted_polynomials.push(witness.phi.clone());
+        }
+        let shifted_aggregate_witness = keypair.compute_aggregate_witness(
+            &shifted_polynomials,
+            &(z_challenge * fft.generator()),
+            &shifted_v_challenge,
         );
         let w_z_chall_w_comm = keypair.commit(&shifted_aggregate_witness)?;
 
+        // the batching challenge is derived by the verifier once the
+        // openings are in the transcript
+        <Transcript as TranscriptProtocol<P>>::append_commitment(
+            &mut transcript,
+            b"w_z",
+            &w_z_chall_comm,
+        );
+        <Transcript as TranscriptProtocol<P>>::append_commitment(
+            &mut transcript,
+            b"w_z_w",
+            &w_z_chall_w_comm,
+        );
+        let batch = batch_challenge::<P>(&mut transcript);
+
+        let challenges = Challenges {
+            beta,
+            gamma,
+            alpha,
+            range_separation: range_sep_challenge,
+            logic_separation: logic_sep_challenge,
+            fixed_base_separation: curve_scalar_sep_challenge,
+            variable_base_separation: var_base_sep_challenge,
+            z_challenge,
+            v_challenge,
+            shifted_v_challenge,
+            batch,
+        };
+
         let proof = Proof {
             a_comm: a_w_poly_commit,
             b_comm: b_w_poly_commit,
@@ -459,17 +871,51 @@ where
 
             z_comm: z_poly_commit,
 
-            t_low_comm: t_low_commit,
-            t_mid_comm: t_mid_commit,
-            t_high_comm: t_high_commit,
-            t_4_comm: t_4_commit,
+            t_low_comm: t_commits[0],
+            t_mid_comm: t_commits[1],
+            t_high_comm: t_commits[2],
+            t_4_comm: t_commits[3],
+            t_extra_comms: match parts {
+                QuotientParts::Four => None,
+                QuotientParts::Six => Some([t_commits[4], t_commits[5]]),
+            },
 
             w_z_chall_comm,
             w_z_chall_w_comm,
 
             evaluations: evaluations.proof,
+            lookup: lookup_round.zip(evaluations.lookup).map(
+                |((_, m_comm, phi_comm, _), evaluations)| LookupProof {
+                    m_comm,
+                    phi_comm,
+                    evaluations,
+                },
+            ),
         };
 
-        Ok((proof, public_inputs))
+        Ok((proof, public_inputs, challenges))
+    }
+    /// Aggregate proofs of this circuit so they are verified with a single
+    /// pairing check.
+    ///
+    /// The public inputs are handed to
+    /// [`Verifier::verify_aggregated`](crate::Verifier::verify_aggregated)
+    /// in the same order. Every proof must have the same number of public
+    /// inputs.
+    pub fn aggregate(
+        &self,
+        proofs: &[(Proof<P>, Vec<P::ScalarField>)],
+    ) -> Result<AggregatedProof<P>, Error> {
+        let expected = proofs.first().map(|(_, pi)| pi.len()).unwrap_or(0);
+
+        match proofs.iter().find(|(_, pi)| pi.len() != expected) {
+            Some((_, pi)) => Err(Error::InconsistentPublicInputsLen {
+                expected,
+                provided: pi.len(),
+            }),
+            None => Ok(AggregatedProof {
+                proofs: proofs.iter().map(|(proof, _)| proof.clone()).collect(),
+            }),
+        }
     }
 }
@@ -0,0 +1,36 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Aggregation of many proofs of the same circuit.
+
+use codec::{Decode, Encode};
+use zkstd::common::{Pairing, Vec};
+
+use super::Proof;
+
+/// Proofs of the same circuit verified with a single pairing check.
+///
+/// The opening claims of every inner [`Proof`] are folded with a random
+/// linear combination derived from a transcript that binds all of them, so
+/// the [`Verifier`](crate::Verifier) computes one pairing for the whole
+/// aggregate instead of one per proof.
+#[derive(Debug, Eq, PartialEq, Clone, Decode, Encode)]
+pub struct AggregatedProof<P: Pairing> {
+    /// Aggregated proofs
+    pub(crate) proofs: Vec<Proof<P>>,
+}
+
+impl<P: Pairing> AggregatedProof<P> {
+    /// Number of aggregated proofs
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    /// Check if the aggregate contains no proof
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+}
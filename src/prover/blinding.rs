@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Blinding of the polynomials committed by the prover.
+//!
+//! A polynomial `p(X)` interpolated over the domain `H` is blinded as
+//! `p(X) + b(X) · Z_H(X)`, where `b(X)` has `terms` random coefficients.
+//! The vanishing polynomial `Z_H` is zero over `H`, so the verifier is
+//! unaffected. Outside of `H` the evaluations at `terms` distinct points
+//! are independent uniform values, so a polynomial needs at least as many
+//! terms as the evaluations of it revealed by a proof.
+
+/// Evaluations revealed by a proof for each wire polynomial
+const WIRE_OPENINGS: [(&str, usize); 4] = [
+    // opened at `z` and `z·ω`
+    ("a", 2),
+    ("b", 2),
+    // opened at `z`
+    ("c", 1),
+    // opened at `z` and `z·ω`
+    ("d", 2),
+];
+
+/// Evaluations revealed by a proof for the permutation polynomial.
+///
+/// It is opened at `z·ω`, and evaluated at `z` inside both the
+/// linearization and the quotient, which are opened separately.
+const PERMUTATION_OPENINGS: usize = 3;
+
+/// Number of random multiples of the vanishing polynomial added to the
+/// polynomials committed by the prover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindingConfig {
+    /// Random terms added to every wire polynomial
+    pub wire_poly_terms: usize,
+    /// Random terms added to the permutation polynomial
+    pub perm_poly_terms: usize,
+    /// Allow fewer terms than [`BlindingConfig::minimum_safe`], losing
+    /// zero knowledge. Only meant for testing and benchmarks.
+    pub insecure: bool,
+}
+
+impl BlindingConfig {
+    /// Blinding matching the number of evaluations of every polynomial
+    pub const DEFAULT: Self = Self::minimum_safe();
+
+    /// Minimum terms per polynomial that keep the revealed evaluations
+    /// independent from the witnesses
+    pub const fn minimum_safe() -> Self {
+        let mut wire_poly_terms = 0;
+        let mut i = 0;
+
+        while i < WIRE_OPENINGS.len() {
+            if WIRE_OPENINGS[i].1 > wire_poly_terms {
+                wire_poly_terms = WIRE_OPENINGS[i].1;
+            }
+            i += 1;
+        }
+
+        Self {
+            wire_poly_terms,
+            perm_poly_terms: PERMUTATION_OPENINGS,
+            insecure: false,
+        }
+    }
+
+    /// Check if the terms are at least [`BlindingConfig::minimum_safe`]
+    pub const fn is_safe(&self) -> bool {
+        let minimum = Self::minimum_safe();
+
+        self.wire_poly_terms >= minimum.wire_poly_terms
+            && self.perm_poly_terms >= minimum.perm_poly_terms
+    }
+}
+
+impl Default for BlindingConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Error returned when a [`BlindingConfig`] can't be used by a
+/// [`Prover`](crate::Prover)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindingError {
+    /// The terms are below [`BlindingConfig::minimum_safe`] and the config
+    /// isn't flagged as insecure
+    Insecure,
+    /// The blinded quotient polynomial exceeds the evaluation domain of the
+    /// prover
+    DegreeTooLarge {
+        /// Degree of the blinded quotient
+        degree: usize,
+        /// Maximum supported degree
+        max: usize,
+    },
+}
+
+impl core::fmt::Display for BlindingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Insecure => {
+                write!(f, "blinding terms are below the safe minimum")
+            }
+            Self::DegreeTooLarge { degree, max } => write!(
+                f,
+                "blinded quotient degree {} exceeds the maximum {}",
+                degree, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlindingError {}
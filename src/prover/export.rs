@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Read-only export of the preprocessed circuit for external tooling.
+
+use poly_commit::{Coefficients, Fft};
+use zkstd::common::{FftField, Pairing, TwistedEdwardsAffine, Vec};
+
+use super::Prover;
+
+/// Preprocessed selector polynomial of a compiled circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector<'a, F> {
+    /// Name of the selector, such as `q_m` or `s_sigma_1`
+    pub name: &'static str,
+    /// Coefficients of the selector polynomial
+    pub coefficients: &'a [F],
+    /// Evaluations of the selector over the domain of size `n`, one per gate
+    pub evaluations: Vec<F>,
+}
+
+/// Read-only view over the selector polynomials of a compiled circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorView<'a, F> {
+    /// Size of the evaluation domain
+    pub n: usize,
+    /// Selectors in the order of the proving key
+    pub selectors: Vec<Selector<'a, F>>,
+}
+
+impl<'a, F> SelectorView<'a, F> {
+    /// Return the selector with the given name
+    pub fn get(&self, name: &str) -> Option<&Selector<'a, F>> {
+        self.selectors.iter().find(|s| s.name == name)
+    }
+}
+
+impl<P, A> Prover<P, A>
+where
+    P: Pairing,
+    A: TwistedEdwardsAffine<Range = P::ScalarField>,
+{
+    /// Export the selector and sigma polynomials of the compiled circuit
+    pub fn selectors(&self) -> SelectorView<'_, P::ScalarField> {
+        let key = &self.prover_key;
+        let fft =
+            Fft::<P::ScalarField>::new(self.size.trailing_zeros() as usize);
+
+        let selectors = [
+            ("q_m", &key.arithmetic.q_m.0),
+            ("q_l", &key.arithmetic.q_l.0),
+            ("q_r", &key.arithmetic.q_r.0),
+            ("q_o", &key.arithmetic.q_o.0),
+            ("q_c", &key.arithmetic.q_c.0),
+            ("q_4", &key.arithmetic.q_4.0),
+            ("q_arith", &key.arithmetic.q_arith.0),
+            ("q_range", &key.range.q_range.0),
+            ("q_logic", &key.logic.q_logic.0),
+            ("q_fixed_group_add", &key.curve_scalar.q_fixed_group_add.0),
+            (
+                "q_variable_group_add",
+                &key.curve_addtion.q_variable_group_add.0,
+            ),
+            ("s_sigma_1", &key.permutation.s_sigma_1.0),
+            ("s_sigma_2", &key.permutation.s_sigma_2.0),
+            ("s_sigma_3", &key.permutation.s_sigma_3.0),
+            ("s_sigma_4", &key.permutation.s_sigma_4.0),
+        ]
+        .into_iter()
+        .map(|(name, poly)| Selector {
+            name,
+            coefficients: poly.0.as_slice(),
+            evaluations: evaluate(&fft, poly),
+        })
+        .collect();
+
+        SelectorView {
+            n: self.size,
+            selectors,
+        }
+    }
+
+    /// Export the evaluations of the four sigma polynomials over the domain.
+    ///
+    /// The wire `j` of the gate `i` is mapped to `k_j · ω^i`, with `k_j` the
+    /// coset generator of the wire, and the entry `[j][i]` holds the position
+    /// the permutation sends it to.
+    pub fn sigma_mappings(&self) -> [Vec<P::ScalarField>; 4] {
+        let key = &self.prover_key.permutation;
+        let fft =
+            Fft::<P::ScalarField>::new(self.size.trailing_zeros() as usize);
+
+        [
+            evaluate(&fft, &key.s_sigma_1.0),
+            evaluate(&fft, &key.s_sigma_2.0),
+            evaluate(&fft, &key.s_sigma_3.0),
+            evaluate(&fft, &key.s_sigma_4.0),
+        ]
+    }
+}
+
+fn evaluate<F: FftField>(fft: &Fft<F>, poly: &Coefficients<F>) -> Vec<F> {
+    fft.dft(poly.clone()).0
+}
@@ -8,12 +8,19 @@ use poly_commit::Coefficients;
 use zksnarks::plonk::{Evaluations as ProofEvaluations, ProvingKey};
 use zkstd::common::Pairing;
 
+use crate::custom_gate::{CustomProvingKey, GateWires};
+use crate::lookup::{
+    LookupChallenges, LookupEvaluations, LookupProvingKey, LookupWitness,
+};
+
 /// Evaluations at points `z` or and `z * root of unity`
 #[allow(dead_code)]
 pub(crate) struct Evaluations<P: Pairing> {
     pub(crate) proof: ProofEvaluations<P::ScalarField>,
     // Evaluation of the linearization sigma polynomial at `z`
     pub(crate) t_eval: P::ScalarField,
+    // Evaluations of the lookup argument, if the circuit has tables
+    pub(crate) lookup: Option<LookupEvaluations<P::ScalarField>>,
 }
 
 /// Compute the linearization polynomial.
@@ -47,6 +54,12 @@ pub(crate) fn compute<P: Pairing>(
     d_w_poly: &Coefficients<P::ScalarField>,
     t_x_poly: &Coefficients<P::ScalarField>,
     z_poly: &Coefficients<P::ScalarField>,
+    (custom, custom_challenges): (&[CustomProvingKey<P>], &[P::ScalarField]),
+    lookup: Option<(
+        &LookupProvingKey<P>,
+        &LookupChallenges<P::ScalarField>,
+        &LookupWitness<P::ScalarField>,
+    )>,
 ) -> (Coefficients<P::ScalarField>, Evaluations<P>) {
     // Compute evaluations
     let t_eval = t_x_poly.evaluate(z_challenge);
@@ -102,7 +115,45 @@ pub(crate) fn compute<P: Pairing>(
         z_poly,
     );
 
-    let r_poly = f_1 + f_2;
+    let mut r_poly = f_1 + f_2;
+
+    // the custom gates only read the opened wires
+    let wires = GateWires {
+        a: a_eval,
+        b: b_eval,
+        c: c_eval,
+        d: d_eval,
+        a_next: a_next_eval,
+        b_next: b_next_eval,
+        d_next: d_next_eval,
+    };
+    for (key, challenge) in custom.iter().zip(custom_challenges.iter()) {
+        r_poly = r_poly + key.linearize(challenge, &wires);
+    }
+
+    // the lookup argument is linear in the running sum, the multiplicities
+    // and `q_lookup` once the rest is opened
+    let mut lookup_evals = None;
+    if let Some((key, challenges, witness)) = lookup {
+        let evaluations = LookupEvaluations {
+            q_table_eval: key.q_table.0.evaluate(z_challenge),
+            table_eval: key
+                .compressed_table(&challenges.zeta)
+                .evaluate(z_challenge),
+            phi_next_eval: witness
+                .phi
+                .evaluate(&(*z_challenge * group_generator)),
+        };
+        let r = key.linearize(
+            challenges,
+            (&a_eval, &b_eval, &c_eval),
+            &evaluations,
+            witness,
+        );
+
+        r_poly = r_poly + r;
+        lookup_evals = Some(evaluations);
+    }
 
     // Evaluate linearization polynomial at challenge `z`
     let r_poly_eval = r_poly.evaluate(z_challenge);
@@ -129,6 +180,7 @@ pub(crate) fn compute<P: Pairing>(
                 perm_eval,
             },
             t_eval,
+            lookup: lookup_evals,
         },
     )
 }
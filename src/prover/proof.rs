@@ -7,21 +7,28 @@
 //! A Proof stores the commitments to all of the elements that
 //! are needed to univocally identify a prove of some statement.
 
-use crate::commitment_scheme::{batch_check, AggregateProof};
+use crate::commitment_scheme::{AggregateProof, OpeningClaim};
+use crate::custom_gate::{self, CustomVerificationKey, GateWires};
+use crate::key::degree::QuotientParts;
+use crate::lookup::{
+    self, LookupChallenges, LookupEvaluations, LookupProof,
+    LookupVerificationKey,
+};
+use crate::verifier::{Challenges, LinearizationChallenges, VerifierCache};
 use codec::{Decode, Encode};
 use poly_commit::{
     batch_inversion, msm_curve_addition, Coefficients, Commitment,
-    EvaluationKey,
 };
 #[cfg(feature = "std")]
 use rayon::prelude::*;
+use sp_std::vec;
 use zksnarks::error::Error;
 use zksnarks::plonk::{
     Evaluations as ProofEvaluations, Transcript, TranscriptProtocol,
     VerificationKey,
 };
 use zkstd::common::Vec;
-use zkstd::common::{FftField, Group, Pairing, PrimeField, Ring};
+use zkstd::common::{Curve, FftField, Group, Pairing, PrimeField, Ring};
 
 /// A Proof is a composition of `Commitment`s to the Witness, Permutation,
 /// Quotient, Shifted and Opening polynomials as well as the
@@ -33,8 +40,13 @@ use zkstd::common::{FftField, Group, Pairing, PrimeField, Ring};
 /// [`Verifier`](crate::prelude::Verifier) have in common succintly
 /// and without any capabilities of adquiring any kind of knowledge about the
 /// witness used to construct the Proof.
-#[derive(Debug, Eq, PartialEq, Clone, Decode, Encode)]
-
+///
+/// The fields of every proof are followed by a byte tagging the optional
+/// parts encoded after it: the commitments and evaluations of the lookup
+/// argument are only carried by the proofs of circuits with lookup tables,
+/// and the commitments to `t_5` and `t_6` by the proofs of circuits
+/// compiled with [`QuotientParts::Six`].
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Proof<P: Pairing> {
     /// Commitment to the witness polynomial for the left wires.
     pub(crate) a_comm: Commitment<P::G1Affine>,
@@ -56,6 +68,9 @@ pub struct Proof<P: Pairing> {
     pub(crate) t_high_comm: Commitment<P::G1Affine>,
     /// Commitment to the quotient polynomial.
     pub(crate) t_4_comm: Commitment<P::G1Affine>,
+    /// Commitments to the fifth and sixth parts of the quotient polynomial,
+    /// if it is split in six.
+    pub(crate) t_extra_comms: Option<[Commitment<P::G1Affine>; 2]>,
 
     /// Commitment to the opening polynomial.
     pub(crate) w_z_chall_comm: Commitment<P::G1Affine>,
@@ -63,19 +78,115 @@ pub struct Proof<P: Pairing> {
     pub(crate) w_z_chall_w_comm: Commitment<P::G1Affine>,
     /// Subset of all of the evaluations added to the proof.
     pub(crate) evaluations: ProofEvaluations<P::ScalarField>,
+    /// Lookup argument, if the circuit has lookup tables.
+    pub(crate) lookup: Option<LookupProof<P>>,
+}
+
+impl<P: Pairing> Encode for Proof<P> {
+    fn size_hint(&self) -> usize {
+        Self::encoded_size() + Self::parts_size(self.parts())
+    }
+
+    fn encode_to<T: codec::Output + ?Sized>(&self, dest: &mut T) {
+        self.a_comm.encode_to(dest);
+        self.b_comm.encode_to(dest);
+        self.c_comm.encode_to(dest);
+        self.d_comm.encode_to(dest);
+        self.z_comm.encode_to(dest);
+        self.t_low_comm.encode_to(dest);
+        self.t_mid_comm.encode_to(dest);
+        self.t_high_comm.encode_to(dest);
+        self.t_4_comm.encode_to(dest);
+        self.w_z_chall_comm.encode_to(dest);
+        self.w_z_chall_w_comm.encode_to(dest);
+        self.evaluations.encode_to(dest);
+        self.parts().encode_to(dest);
+
+        if let Some(lookup) = &self.lookup {
+            lookup.encode_to(dest);
+        }
+        if let Some(t_extra_comms) = &self.t_extra_comms {
+            t_extra_comms.encode_to(dest);
+        }
+    }
+}
+
+impl<P: Pairing> Decode for Proof<P> {
+    fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let proof = Self {
+            a_comm: Decode::decode(input)?,
+            b_comm: Decode::decode(input)?,
+            c_comm: Decode::decode(input)?,
+            d_comm: Decode::decode(input)?,
+            z_comm: Decode::decode(input)?,
+            t_low_comm: Decode::decode(input)?,
+            t_mid_comm: Decode::decode(input)?,
+            t_high_comm: Decode::decode(input)?,
+            t_4_comm: Decode::decode(input)?,
+            w_z_chall_comm: Decode::decode(input)?,
+            w_z_chall_w_comm: Decode::decode(input)?,
+            evaluations: Decode::decode(input)?,
+            lookup: None,
+            t_extra_comms: None,
+        };
+
+        let parts = u8::decode(input)?;
+        if parts & !(LOOKUP_PART | QUOTIENT_PART) != 0 {
+            return Err("unknown parts of the proof".into());
+        }
+
+        let lookup = match parts & LOOKUP_PART != 0 {
+            true => Some(Decode::decode(input)?),
+            false => None,
+        };
+        let t_extra_comms = match parts & QUOTIENT_PART != 0 {
+            true => Some(Decode::decode(input)?),
+            false => None,
+        };
+
+        Ok(Self {
+            lookup,
+            t_extra_comms,
+            ..proof
+        })
+    }
 }
 
 impl<P: Pairing> Proof<P> {
-    /// Performs the verification of a [`Proof`] returning a boolean result.
-    pub(crate) fn verify(
+    /// Replay the transcript of the [`Proof`] and compute the opening claims
+    /// at the evaluation challenge `z` and at its shift `z·ω`, along with the
+    /// challenges derived on the way.
+    ///
+    /// The transcript is left ready for the batching challenge, which binds
+    /// every claim of a batch, so the returned batching challenge is zero.
+    pub(crate) fn opening_claims(
         &self,
         verifier_key: &VerificationKey<P>,
+        custom: &[CustomVerificationKey<P>],
+        lookup_key: Option<&LookupVerificationKey<P>>,
+        parts: QuotientParts,
+        cache: &VerifierCache<P>,
         transcript: &mut Transcript,
-        opening_key: &EvaluationKey<P>,
         pub_inputs: &[P::ScalarField],
-    ) -> Result<(), Error> {
+    ) -> Result<([OpeningClaim<P>; 2], Challenges<P::ScalarField>), Error> {
         // Subgroup checks are done when the proof is deserialized.
 
+        // the lookup argument is carried by the proofs of the circuits with
+        // lookup tables only
+        let lookup = match (lookup_key, &self.lookup) {
+            (Some(key), Some(proof)) => Some((key, proof)),
+            (None, None) => None,
+            _ => return Err(Error::ProofVerificationError),
+        };
+
+        // and the fifth and sixth quotient parts by the proofs of the
+        // circuits compiled with six parts only
+        let t_extra_comms = match (parts, &self.t_extra_comms) {
+            (QuotientParts::Four, None) => [].as_slice(),
+            (QuotientParts::Six, Some(comms)) => comms.as_slice(),
+            _ => return Err(Error::ProofVerificationError),
+        };
+
         // In order for the Verifier and Prover to have the same view in the
         // non-interactive setting Both parties must commit the same
         // elements into the transcript Below the verifier will simulate
@@ -105,6 +216,31 @@ impl<P: Pairing> Proof<P> {
             &self.d_comm,
         );
 
+        // Add commitment to the multiplicities and compute the lookup
+        // challenges
+        let lookup_challenges = lookup.map(|(_, proof)| {
+            <Transcript as TranscriptProtocol<P>>::append_commitment(
+                transcript,
+                b"lookup_m",
+                &proof.m_comm,
+            );
+            let zeta = <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                transcript,
+                b"lookup compression",
+            );
+            let gamma = <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                transcript,
+                b"lookup gamma",
+            );
+            <Transcript as TranscriptProtocol<P>>::append_commitment(
+                transcript,
+                b"lookup_phi",
+                &proof.phi_comm,
+            );
+
+            (zeta, gamma)
+        });
+
         // Compute beta and gamma challenges
         let beta = <Transcript as TranscriptProtocol<P>>::challenge_scalar(
             transcript, b"beta",
@@ -147,6 +283,28 @@ impl<P: Pairing> Proof<P> {
                 transcript,
                 b"variable base separation challenge",
             );
+        let custom_sep_challenges = custom_gate::separation_challenges::<P, _>(
+            transcript,
+            custom.iter().map(|key| key.gate.label()),
+        );
+        let lookup_challenges = lookup_challenges.map(|(zeta, gamma)| {
+            let separation =
+                <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                    transcript,
+                    b"lookup separation challenge",
+                );
+
+            LookupChallenges {
+                zeta,
+                gamma,
+                separation,
+            }
+        });
+        let opened = (
+            &self.evaluations.a_eval,
+            &self.evaluations.b_eval,
+            &self.evaluations.c_eval,
+        );
 
         // Add commitment to quotient polynomial to transcript
         <Transcript as TranscriptProtocol<P>>::append_commitment(
@@ -169,6 +327,12 @@ impl<P: Pairing> Proof<P> {
             b"t_4",
             &self.t_4_comm,
         );
+        let labels = QUOTIENT_LABELS.into_iter().skip(4);
+        for (comm, label) in t_extra_comms.iter().zip(labels) {
+            <Transcript as TranscriptProtocol<P>>::append_commitment(
+                transcript, label, comm,
+            );
+        }
 
         // Compute evaluation challenge z
         let z_challenge =
@@ -189,7 +353,16 @@ impl<P: Pairing> Proof<P> {
         let l1_eval =
             compute_first_lagrange_evaluation(n, &z_h_eval, &z_challenge);
 
-        // Compute quotient polynomial evaluated at challenge `z`
+        // Compute quotient polynomial evaluated at challenge `z`, with the
+        // term of the running sum at `z·ω` of the lookup argument
+        let lookup_constant = match (&lookup, &lookup_challenges) {
+            (Some((_, proof)), Some(challenges)) => lookup::quotient_constant(
+                challenges,
+                opened,
+                &proof.evaluations,
+            ),
+            _ => P::ScalarField::zero(),
+        };
         let t_eval = self.compute_quotient_evaluation(
             n,
             &n_inv,
@@ -202,6 +375,7 @@ impl<P: Pairing> Proof<P> {
             &z_h_eval,
             &l1_eval,
             &self.evaluations.perm_eval,
+            &lookup_constant,
         );
 
         // Compute commitment to quotient polynomial
@@ -293,22 +467,74 @@ impl<P: Pairing> Proof<P> {
             b"r_eval",
             &self.evaluations.r_poly_eval,
         );
+        if let Some((_, proof)) = &lookup {
+            <Transcript as TranscriptProtocol<P>>::append_scalar(
+                transcript,
+                b"q_table_eval",
+                &proof.evaluations.q_table_eval,
+            );
+            <Transcript as TranscriptProtocol<P>>::append_scalar(
+                transcript,
+                b"table_eval",
+                &proof.evaluations.table_eval,
+            );
+            <Transcript as TranscriptProtocol<P>>::append_scalar(
+                transcript,
+                b"phi_next_eval",
+                &proof.evaluations.phi_next_eval,
+            );
+        }
 
         // Compute linearization commitment
-        let r_comm = self.compute_linearization_commitment(
-            &alpha,
-            &beta,
-            &gamma,
-            (
-                &range_sep_challenge,
-                &logic_sep_challenge,
-                &curve_scalar_sep_challenge,
-                &var_base_sep_challenge,
-            ),
-            &z_challenge,
+        let challenges = LinearizationChallenges {
+            alpha,
+            beta,
+            gamma,
+            z_challenge,
             l1_eval,
+            separation: [
+                range_sep_challenge,
+                logic_sep_challenge,
+                curve_scalar_sep_challenge,
+                var_base_sep_challenge,
+            ],
+        };
+        let wires = GateWires::opened(&self.evaluations);
+        let custom_scalars: Vec<_> = custom
+            .iter()
+            .zip(custom_sep_challenges.iter())
+            .map(|(key, challenge)| key.gate.evaluate(challenge, &wires))
+            .collect();
+        let r_comm = cache.linearization_commitment(
             verifier_key,
+            &self.evaluations,
+            &challenges,
+            self.z_comm,
+            &custom_scalars,
         );
+        // the lookup argument is linear in the running sum, the
+        // multiplicities and `q_lookup`
+        let r_comm = match (&lookup, &lookup_challenges) {
+            (Some((key, proof)), Some(challenges)) => {
+                let [phi, m, q_lookup] = lookup::linearization_scalars(
+                    challenges,
+                    opened,
+                    &proof.evaluations,
+                );
+                let points = [
+                    r_comm.0,
+                    proof.phi_comm.0,
+                    proof.m_comm.0,
+                    key.q_lookup.0,
+                ];
+                let scalars = [P::ScalarField::one(), phi, m, q_lookup];
+
+                Commitment::new(msm_curve_addition::<P::G1Affine>(
+                    &points, &scalars,
+                ))
+            }
+            _ => r_comm,
+        };
 
         // Commitment Scheme
         // Now we delegate computation to the commitment scheme by batch
@@ -340,8 +566,23 @@ impl<P: Pairing> Proof<P> {
             self.evaluations.s_sigma_3_eval,
             verifier_key.permutation.s_sigma_3,
         ));
+        if let (Some((key, proof)), Some(challenges)) =
+            (&lookup, &lookup_challenges)
+        {
+            aggregate_proof
+                .add_part((proof.evaluations.q_table_eval, key.q_table));
+            aggregate_proof.add_part((
+                proof.evaluations.table_eval,
+                key.compressed_table(&challenges.zeta),
+            ));
+        }
         // Flatten proof with opening challenge
-        let flattened_proof_a = aggregate_proof.flatten(transcript);
+        let v_challenge =
+            <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                transcript,
+                b"v_challenge",
+            );
+        let flattened_proof_a = aggregate_proof.flatten(&v_challenge);
 
         // Compose the shifted aggregate proof
         let mut shifted_aggregate_proof =
@@ -354,8 +595,18 @@ impl<P: Pairing> Proof<P> {
             .add_part((self.evaluations.b_next_eval, self.b_comm));
         shifted_aggregate_proof
             .add_part((self.evaluations.d_next_eval, self.d_comm));
+        if let Some((_, proof)) = &lookup {
+            shifted_aggregate_proof
+                .add_part((proof.evaluations.phi_next_eval, proof.phi_comm));
+        }
 
-        let flattened_proof_b = shifted_aggregate_proof.flatten(transcript);
+        let shifted_v_challenge =
+            <Transcript as TranscriptProtocol<P>>::challenge_scalar(
+                transcript,
+                b"v_challenge",
+            );
+        let flattened_proof_b =
+            shifted_aggregate_proof.flatten(&shifted_v_challenge);
         // Add commitment to openings to transcript
         <Transcript as TranscriptProtocol<P>>::append_commitment(
             transcript,
@@ -367,19 +618,36 @@ impl<P: Pairing> Proof<P> {
             b"w_z_w",
             &self.w_z_chall_w_comm,
         );
-        // Batch check
-        if batch_check(
-            opening_key,
-            &[z_challenge, (z_challenge * generator)],
-            &[flattened_proof_a, flattened_proof_b],
-            transcript,
-        )
-        .is_err()
-        {
-            return Err(Error::ProofVerificationError);
-        }
+        let claims = [
+            OpeningClaim {
+                point: z_challenge,
+                commitment: flattened_proof_a.commitment_to_polynomial,
+                evaluation: flattened_proof_a.evaluated_point,
+                witness: flattened_proof_a.commitment_to_witness,
+            },
+            OpeningClaim {
+                point: z_challenge * generator,
+                commitment: flattened_proof_b.commitment_to_polynomial,
+                evaluation: flattened_proof_b.evaluated_point,
+                witness: flattened_proof_b.commitment_to_witness,
+            },
+        ];
+
+        let challenges = Challenges {
+            beta,
+            gamma,
+            alpha,
+            range_separation: range_sep_challenge,
+            logic_separation: logic_sep_challenge,
+            fixed_base_separation: curve_scalar_sep_challenge,
+            variable_base_separation: var_base_sep_challenge,
+            z_challenge,
+            v_challenge,
+            shifted_v_challenge,
+            batch: P::ScalarField::zero(),
+        };
 
-        Ok(())
+        Ok((claims, challenges))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -396,6 +664,7 @@ impl<P: Pairing> Proof<P> {
         z_h_eval: &P::ScalarField,
         l1_eval: &P::ScalarField,
         z_hat_eval: &P::ScalarField,
+        lookup_constant: &P::ScalarField,
     ) -> P::ScalarField {
         // Compute the public input polynomial evaluated at challenge `z`
         let pi_eval = compute_barycentric_eval::<P>(
@@ -433,10 +702,7 @@ impl<P: Pairing> Proof<P> {
         let c = *l1_eval * alpha_sq;
 
         // Return t_eval
-        (
-            a - b - c
-            //+ d
-        ) * z_h_eval.invert().unwrap()
+        (a - b - c + lookup_constant) * z_h_eval.invert().unwrap()
     }
 
     fn compute_quotient_commitment(
@@ -447,83 +713,378 @@ impl<P: Pairing> Proof<P> {
         let z_n = z_challenge.pow(n);
         let z_two_n = z_challenge.pow(2 * n);
         let z_three_n = z_challenge.pow(3 * n);
-        let t_comm = self.t_low_comm.0
+        let mut t_comm = self.t_low_comm.0
             + self.t_mid_comm.0 * z_n
             + self.t_high_comm.0 * z_two_n
             + self.t_4_comm.0 * z_three_n;
+
+        // the fifth and sixth parts are scaled by `z^4n` and `z^5n`
+        let mut z_in = z_three_n;
+        for comm in self.t_extra_comms.iter().flatten() {
+            z_in *= z_n;
+            t_comm = t_comm + comm.0 * z_in;
+        }
+
         Commitment::new(t_comm)
     }
+}
 
-    // Commitment to [r]_1
-    #[allow(clippy::too_many_arguments)]
-    fn compute_linearization_commitment(
-        &self,
-        alpha: &P::ScalarField,
-        beta: &P::ScalarField,
-        gamma: &P::ScalarField,
-        (
-            range_sep_challenge,
-            logic_sep_challenge,
-            curve_scalar_sep_challenge,
-            var_base_sep_challenge,
-        ): (
-            &P::ScalarField,
-            &P::ScalarField,
-            &P::ScalarField,
-            &P::ScalarField,
-        ),
-        z_challenge: &P::ScalarField,
-        l1_eval: P::ScalarField,
-        verifier_key: &VerificationKey<P>,
-    ) -> Commitment<P::G1Affine> {
-        let (arithmetic_scalars, arithmetic_points) =
-            verifier_key.arithmetic.linearize(&self.evaluations);
-
-        let (range_scalars, range_points) = verifier_key
-            .range
-            .linearize(range_sep_challenge, &self.evaluations);
-
-        let (logic_scalars, logic_points) = verifier_key
-            .logic
-            .linearize(logic_sep_challenge, &self.evaluations);
-
-        let (scalar_scalars, scalar_points) = verifier_key
-            .curve_scalar
-            .linearize(curve_scalar_sep_challenge, &self.evaluations);
-
-        let (addition_scalars, addition_points) = verifier_key
-            .curve_addtion
-            .linearize(var_base_sep_challenge, &self.evaluations);
-
-        let (permutation_scalars, permutation_points) =
-            verifier_key.permutation.linearize(
-                z_challenge,
-                (alpha, beta, gamma),
-                &l1_eval,
-                self.z_comm.0,
-                &self.evaluations,
-            );
+/// Bit of the tag of a [`Proof`] set if the lookup argument follows it
+const LOOKUP_PART: u8 = 1;
 
-        Commitment::new(msm_curve_addition::<P::G1Affine>(
-            &[
-                arithmetic_points,
-                range_points,
-                logic_points,
-                scalar_points,
-                addition_points,
-                permutation_points,
-            ]
-            .concat(),
-            &[
-                arithmetic_scalars,
-                range_scalars,
-                logic_scalars,
-                scalar_scalars,
-                addition_scalars,
-                permutation_scalars,
+/// Bit of the tag of a [`Proof`] set if the commitments to `t_5` and `t_6`
+/// follow it
+const QUOTIENT_PART: u8 = 2;
+
+/// Transcript labels of the commitments to the quotient parts
+pub(crate) const QUOTIENT_LABELS: [&[u8]; 6] =
+    [b"t_low", b"t_mid", b"t_high", b"t_4", b"t_5", b"t_6"];
+
+/// Names of the commitments of a [`Proof`] in encoding order
+const COMMITMENT_FIELDS: [&str; 11] = [
+    "a_comm",
+    "b_comm",
+    "c_comm",
+    "d_comm",
+    "z_comm",
+    "t_low_comm",
+    "t_mid_comm",
+    "t_high_comm",
+    "t_4_comm",
+    "w_z_chall_comm",
+    "w_z_chall_w_comm",
+];
+
+/// Reason why a field of an encoded [`Proof`] couldn't be decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeReason {
+    /// The input ended before the field was complete
+    Truncated,
+    /// The bytes of the field don't represent a valid value
+    Malformed,
+    /// The commitment isn't a point of the curve
+    NotOnCurve,
+    /// The input has bytes left after the last field
+    TrailingBytes,
+    /// The reader failed with the given kind of error
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+/// Error returned when decoding a [`Proof`] from bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofDecodeError {
+    /// Name of the field that failed to decode
+    pub field: &'static str,
+    /// Why the field failed to decode
+    pub reason: DecodeReason,
+}
+
+impl ProofDecodeError {
+    const fn new(field: &'static str, reason: DecodeReason) -> Self {
+        Self { field, reason }
+    }
+}
+
+impl core::fmt::Display for ProofDecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "failed to decode proof field `{}`: ", self.field)?;
+
+        match self.reason {
+            DecodeReason::Truncated => write!(f, "not enough bytes"),
+            DecodeReason::Malformed => write!(f, "malformed bytes"),
+            DecodeReason::NotOnCurve => write!(f, "point is not on curve"),
+            DecodeReason::TrailingBytes => {
+                write!(f, "unexpected trailing bytes")
+            }
+            #[cfg(feature = "std")]
+            DecodeReason::Io(kind) => write!(f, "io error: {:?}", kind),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProofDecodeError {}
+
+impl<P: Pairing> Proof<P> {
+    /// Size in bytes of an encoded [`Proof`] of a circuit without lookup
+    /// tables, tag of the optional parts included.
+    ///
+    /// All the fields of a proof have a fixed size, so this can be used to
+    /// split a stream of framed proofs without decoding them. The proof of a
+    /// circuit with lookup tables is [`Self::lookup_size`] bytes longer.
+    pub fn encoded_size() -> usize {
+        COMMITMENT_FIELDS.len() * Self::commitment_size()
+            + Self::evaluations_size()
+            + 1
+    }
+
+    /// Tag of the optional parts encoded after the fields of the proof
+    fn parts(&self) -> u8 {
+        self.lookup.as_ref().map_or(0, |_| LOOKUP_PART)
+            | self.t_extra_comms.as_ref().map_or(0, |_| QUOTIENT_PART)
+    }
+
+    /// Size in bytes of the optional parts announced by the tag `parts`
+    fn parts_size(parts: u8) -> usize {
+        let lookup = match parts & LOOKUP_PART != 0 {
+            true => Self::lookup_size(),
+            false => 0,
+        };
+        let quotient = match parts & QUOTIENT_PART != 0 {
+            true => Self::quotient_parts_size(),
+            false => 0,
+        };
+
+        lookup + quotient
+    }
+
+    /// Size in bytes of the lookup argument of an encoded [`Proof`] of a
+    /// circuit with lookup tables
+    pub fn lookup_size() -> usize {
+        let zero = P::ScalarField::zero();
+
+        2 * Self::commitment_size()
+            + LookupEvaluations {
+                q_table_eval: zero,
+                table_eval: zero,
+                phi_next_eval: zero,
+            }
+            .encoded_size()
+    }
+
+    /// Size in bytes of the commitments to `t_5` and `t_6` of an encoded
+    /// [`Proof`] of a circuit compiled with [`QuotientParts::Six`]
+    pub fn quotient_parts_size() -> usize {
+        2 * Self::commitment_size()
+    }
+
+    fn commitment_size() -> usize {
+        Commitment::<P::G1Affine>::default().encoded_size()
+    }
+
+    fn evaluations_size() -> usize {
+        let zero = P::ScalarField::zero();
+
+        ProofEvaluations {
+            a_eval: zero,
+            b_eval: zero,
+            c_eval: zero,
+            d_eval: zero,
+            a_next_eval: zero,
+            b_next_eval: zero,
+            d_next_eval: zero,
+            q_arith_eval: zero,
+            q_c_eval: zero,
+            q_l_eval: zero,
+            q_r_eval: zero,
+            s_sigma_1_eval: zero,
+            s_sigma_2_eval: zero,
+            s_sigma_3_eval: zero,
+            r_poly_eval: zero,
+            perm_eval: zero,
+        }
+        .encoded_size()
+    }
+
+    /// Name of the field encoded at byte `offset` of a proof tagged with
+    /// `parts`
+    fn field_at(offset: usize, parts: u8) -> &'static str {
+        let commitment = Self::commitment_size();
+        let lookup = (parts & LOOKUP_PART != 0).then(|| {
+            [
+                ("lookup_m_comm", commitment),
+                ("lookup_phi_comm", commitment),
+                ("lookup_evaluations", Self::lookup_size() - 2 * commitment),
             ]
-            .concat(),
-        ))
+        });
+        let quotient = (parts & QUOTIENT_PART != 0)
+            .then(|| [("t_5_comm", commitment), ("t_6_comm", commitment)]);
+
+        let fields = COMMITMENT_FIELDS
+            .iter()
+            .map(|field| (*field, commitment))
+            .chain([("evaluations", Self::evaluations_size()), ("parts", 1)])
+            .chain(lookup.into_iter().flatten())
+            .chain(quotient.into_iter().flatten());
+
+        let mut end = 0;
+        let mut name = "parts";
+        for (field, size) in fields {
+            name = field;
+            end += size;
+            if offset < end {
+                break;
+            }
+        }
+
+        name
+    }
+
+    /// Decode a [`Proof`] from `r`, reading exactly the bytes of its
+    /// encoding.
+    ///
+    /// [`Proof::encoded_size`] bytes are read first, and the optional parts
+    /// their tag announces after them, so consecutive proofs can be decoded
+    /// from the same reader.
+    #[cfg(feature = "std")]
+    pub fn decode_from_reader<R: std::io::Read>(
+        r: &mut R,
+    ) -> Result<Self, ProofDecodeError> {
+        let mut bytes = vec![0u8; Self::encoded_size()];
+        let mut filled = Self::read_into(r, &mut bytes, 0, 0)?;
+
+        // the tag is the last byte of the fields of every proof
+        if filled == bytes.len() {
+            let parts = bytes[filled - 1];
+
+            bytes.resize(filled + Self::parts_size(parts), 0);
+            filled = Self::read_into(r, &mut bytes, filled, parts)?;
+        }
+
+        Self::try_from(&bytes[..filled])
+    }
+
+    /// Fill `bytes` of a proof tagged with `parts` from `r` past the
+    /// `filled` bytes already read, until the reader ends, and return the
+    /// number of bytes filled
+    #[cfg(feature = "std")]
+    fn read_into<R: std::io::Read>(
+        r: &mut R,
+        bytes: &mut [u8],
+        mut filled: usize,
+        parts: u8,
+    ) -> Result<usize, ProofDecodeError> {
+        while filled < bytes.len() {
+            match r.read(&mut bytes[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                    continue
+                }
+                Err(e) => {
+                    return Err(ProofDecodeError::new(
+                        Self::field_at(filled, parts),
+                        DecodeReason::Io(e.kind()),
+                    ))
+                }
+            }
+        }
+
+        Ok(filled)
+    }
+}
+
+/// Decode a field of `size` bytes from the head of `bytes`
+fn decode_field<T: Decode>(
+    bytes: &mut &[u8],
+    field: &'static str,
+    size: usize,
+) -> Result<T, ProofDecodeError> {
+    if bytes.len() < size {
+        return Err(ProofDecodeError::new(field, DecodeReason::Truncated));
+    }
+
+    let (mut head, tail) = bytes.split_at(size);
+    let value = T::decode(&mut head)
+        .map_err(|_| ProofDecodeError::new(field, DecodeReason::Malformed))?;
+
+    if !head.is_empty() {
+        return Err(ProofDecodeError::new(field, DecodeReason::Malformed));
+    }
+
+    *bytes = tail;
+
+    Ok(value)
+}
+
+/// Decode a commitment from the head of `bytes`, checking it is on the curve
+fn decode_commitment<P: Pairing>(
+    bytes: &mut &[u8],
+    field: &'static str,
+) -> Result<Commitment<P::G1Affine>, ProofDecodeError> {
+    let commitment: Commitment<P::G1Affine> =
+        decode_field(bytes, field, Proof::<P>::commitment_size())?;
+
+    if !commitment.0.is_on_curve() {
+        return Err(ProofDecodeError::new(field, DecodeReason::NotOnCurve));
+    }
+
+    Ok(commitment)
+}
+
+impl<P: Pairing> TryFrom<&[u8]> for Proof<P> {
+    type Error = ProofDecodeError;
+
+    fn try_from(mut bytes: &[u8]) -> Result<Self, Self::Error> {
+        let b = &mut bytes;
+        let proof = Self {
+            a_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[0])?,
+            b_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[1])?,
+            c_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[2])?,
+            d_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[3])?,
+            z_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[4])?,
+            t_low_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[5])?,
+            t_mid_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[6])?,
+            t_high_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[7])?,
+            t_4_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[8])?,
+            w_z_chall_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[9])?,
+            w_z_chall_w_comm: decode_commitment::<P>(b, COMMITMENT_FIELDS[10])?,
+            evaluations: decode_field(
+                b,
+                "evaluations",
+                Self::evaluations_size(),
+            )?,
+            lookup: None,
+            t_extra_comms: None,
+        };
+
+        let parts: u8 = decode_field(b, "parts", 1)?;
+        if parts & !(LOOKUP_PART | QUOTIENT_PART) != 0 {
+            return Err(ProofDecodeError::new(
+                "parts",
+                DecodeReason::Malformed,
+            ));
+        }
+
+        let lookup = match parts & LOOKUP_PART != 0 {
+            true => Some(LookupProof {
+                m_comm: decode_commitment::<P>(b, "lookup_m_comm")?,
+                phi_comm: decode_commitment::<P>(b, "lookup_phi_comm")?,
+                evaluations: decode_field(
+                    b,
+                    "lookup_evaluations",
+                    Self::lookup_size() - 2 * Self::commitment_size(),
+                )?,
+            }),
+            false => None,
+        };
+        let t_extra_comms = match parts & QUOTIENT_PART != 0 {
+            true => Some([
+                decode_commitment::<P>(b, "t_5_comm")?,
+                decode_commitment::<P>(b, "t_6_comm")?,
+            ]),
+            false => None,
+        };
+
+        if !bytes.is_empty() {
+            let field = match (&lookup, &t_extra_comms) {
+                (_, Some(_)) => "t_6_comm",
+                (Some(_), None) => "lookup_evaluations",
+                (None, None) => "parts",
+            };
+
+            return Err(ProofDecodeError::new(
+                field,
+                DecodeReason::TrailingBytes,
+            ));
+        }
+
+        Ok(Self {
+            lookup,
+            t_extra_comms,
+            ..proof
+        })
     }
 }
 
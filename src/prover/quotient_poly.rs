@@ -14,6 +14,9 @@ use zksnarks::error::Error;
 use zksnarks::plonk::ProvingKey;
 use zkstd::common::*;
 
+use crate::custom_gate::{CustomProvingKey, GateWires};
+use crate::lookup::{LookupChallenges, LookupProvingKey, LookupWitness};
+
 #[allow(clippy::type_complexity)]
 /// Computes the Quotient [`Coefficients`] given the [`EvaluationDomain`], a
 /// [`ProvingKey`] and some other info.
@@ -45,6 +48,12 @@ pub(crate) fn compute<P: Pairing>(
         P::ScalarField,
         P::ScalarField,
     ),
+    custom: (&[CustomProvingKey<P>], &[P::ScalarField]),
+    lookup: Option<(
+        &LookupProvingKey<P>,
+        &LookupChallenges<P::ScalarField>,
+        &LookupWitness<P::ScalarField>,
+    )>,
 ) -> Result<Coefficients<P::ScalarField>, Error> {
     // Compute 8n evals
     let n = (8 * fft.size()).next_power_of_two();
@@ -87,6 +96,7 @@ pub(crate) fn compute<P: Pairing>(
             &d_w_eval_8n.0,
         ),
         public_inputs_poly,
+        custom,
     );
 
     let t_2 = compute_permutation_checks(
@@ -103,10 +113,19 @@ pub(crate) fn compute<P: Pairing>(
         (alpha, beta, gamma),
     );
 
+    let t_3 = lookup.map(|lookup| {
+        compute_lookup_checks(
+            &fft_8n,
+            lookup,
+            (&a_w_eval_8n.0, &b_w_eval_8n.0, &c_w_eval_8n.0),
+        )
+    });
+
     let quotient = PointsValue::new(
         (0..fft_8n.size())
             .map(|i| {
-                let numerator = t_1[i] + t_2[i];
+                let t_3 = t_3.as_ref().map_or(P::ScalarField::zero(), |t| t[i]);
+                let numerator = t_1[i] + t_2[i] + t_3;
                 let denominator = prover_key.v_h_coset_8n().0[i];
                 numerator * denominator.invert().unwrap()
             })
@@ -140,6 +159,7 @@ fn compute_circuit_satisfiability_equation<P: Pairing>(
         &[P::ScalarField],
     ),
     pi_poly: &Coefficients<P::ScalarField>,
+    (custom, custom_challenges): (&[CustomProvingKey<P>], &[P::ScalarField]),
 ) -> Vec<P::ScalarField> {
     let pi_poly = Coefficients::new(pi_poly.0.clone());
     let pi_evals = fft.coset_dft(pi_poly);
@@ -212,7 +232,23 @@ fn compute_circuit_satisfiability_equation<P: Pairing>(
                 d_w_next,
             );
 
-            (a + pi) + b + c + d + e
+            let wires = GateWires {
+                a: *a_w,
+                b: *b_w,
+                c: *c_w,
+                d: *d_w,
+                a_next: *a_w_next,
+                b_next: *b_w_next,
+                d_next: *d_w_next,
+            };
+            let f = custom.iter().zip(custom_challenges.iter()).fold(
+                P::ScalarField::zero(),
+                |acc, (key, challenge)| {
+                    acc + key.compute_quotient_i(i, challenge, &wires)
+                },
+            );
+
+            (a + pi) + b + c + d + e + f
         })
         .collect();
     t
@@ -261,6 +297,39 @@ fn compute_permutation_checks<P: Pairing>(
         .collect()
 }
 
+#[allow(clippy::type_complexity)]
+fn compute_lookup_checks<P: Pairing>(
+    fft_n8: &Fft<P::ScalarField>,
+    (key, challenges, witness): (
+        &LookupProvingKey<P>,
+        &LookupChallenges<P::ScalarField>,
+        &LookupWitness<P::ScalarField>,
+    ),
+    (a_w_eval_8n, b_w_eval_8n, c_w_eval_8n): (
+        &[P::ScalarField],
+        &[P::ScalarField],
+        &[P::ScalarField],
+    ),
+) -> Vec<P::ScalarField> {
+    let m_eval_8n = fft_n8.coset_dft(witness.m.clone());
+    let mut phi_eval_8n = fft_n8.coset_dft(witness.phi.clone());
+    for i in 0..8 {
+        phi_eval_8n.0.push(phi_eval_8n.0[i]);
+    }
+
+    (0..fft_n8.size())
+        .map(|i| {
+            key.compute_quotient_i(
+                i,
+                challenges,
+                (&a_w_eval_8n[i], &b_w_eval_8n[i], &c_w_eval_8n[i]),
+                &m_eval_8n.0[i],
+                (&phi_eval_8n.0[i], &phi_eval_8n.0[i + 8]),
+            )
+        })
+        .collect()
+}
+
 fn compute_first_lagrange_poly_scaled<P: Pairing>(
     fft: &Fft<P::ScalarField>,
     scale: P::ScalarField,
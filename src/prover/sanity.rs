@@ -0,0 +1,233 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Checks of a synthesized composer against the compiled circuit, run by
+//! the prover before committing to anything.
+//!
+//! A composer that doesn't describe the compiled circuit, such as one whose
+//! gates depend on the witnesses, otherwise produces a proof that only fails
+//! to verify. The checks are linear in the number of gates and don't need
+//! any FFT.
+
+use core::fmt::Debug;
+
+use zkstd::common::{TwistedEdwardsAffine, Vec};
+
+use crate::gadget::WireType;
+use crate::Plonk;
+
+/// Options of a [`Prover`](crate::Prover)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProverOptions {
+    /// Skip the checks of the composer against the compiled circuit.
+    ///
+    /// The checks are linear in the number of gates. Skipping them is only
+    /// meant for hot paths proving circuits that are known to be correct.
+    pub skip_sanity: bool,
+
+    /// Panic on non-canonical witnesses appended to the composers the
+    /// prover synthesizes, see [`Plonk::set_strict_witnesses`].
+    ///
+    /// Enabled by default in debug builds.
+    pub strict_witnesses: bool,
+}
+
+impl Default for ProverOptions {
+    fn default() -> Self {
+        Self {
+            skip_sanity: false,
+            strict_witnesses: cfg!(debug_assertions),
+        }
+    }
+}
+
+/// Position of a wire in the gates of a circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WirePosition {
+    /// Index of the gate
+    pub gate: usize,
+    /// Column of the wire, `0` to `3` for the `a`, `b`, `c` and `d` wires
+    pub column: usize,
+}
+
+impl From<WireType> for WirePosition {
+    fn from(wire: WireType) -> Self {
+        let (gate, column) = match wire {
+            WireType::Left(gate) => (gate, 0),
+            WireType::Right(gate) => (gate, 1),
+            WireType::Output(gate) => (gate, 2),
+            WireType::Fourth(gate) => (gate, 3),
+        };
+
+        Self { gate, column }
+    }
+}
+
+/// Mismatch between a synthesized composer and the compiled circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanityError<F> {
+    /// The composer doesn't have the gates of the compiled circuit
+    ConstraintCount {
+        /// Gates of the compiled circuit
+        expected: usize,
+        /// Gates of the composer
+        found: usize,
+    },
+    /// The composer doesn't have the witnesses of the compiled circuit
+    WitnessCount {
+        /// Witnesses of the compiled circuit
+        expected: usize,
+        /// Witnesses of the composer
+        found: usize,
+    },
+    /// A gate references a witness that wasn't appended
+    WireOutOfBounds {
+        /// Position of the wire
+        position: WirePosition,
+        /// Index of the witness
+        witness: usize,
+    },
+    /// Two wires of a copy constraint cycle of the compiled circuit carry
+    /// different values
+    CopyConstraintViolation {
+        /// Position of the first wire of the cycle with a different value
+        /// than the next one
+        from: WirePosition,
+        /// Position of the next wire in the cycle
+        to: WirePosition,
+        /// Value of the wire at `from`
+        from_value: F,
+        /// Value of the wire at `to`
+        to_value: F,
+    },
+}
+
+impl<F: Debug> core::fmt::Display for SanityError<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ConstraintCount { expected, found } => write!(
+                f,
+                "the circuit has {} gates, {} were compiled",
+                found, expected
+            ),
+            Self::WitnessCount { expected, found } => write!(
+                f,
+                "the circuit has {} witnesses, {} were compiled",
+                found, expected
+            ),
+            Self::WireOutOfBounds { position, witness } => write!(
+                f,
+                "wire {} of gate {} references the missing witness {}",
+                position.column, position.gate, witness
+            ),
+            Self::CopyConstraintViolation {
+                from,
+                to,
+                from_value,
+                to_value,
+            } => write!(
+                f,
+                "copy constraint from wire {} of gate {} ({:?}) to wire {} \
+                 of gate {} ({:?}) isn't satisfied",
+                from.column,
+                from.gate,
+                from_value,
+                to.column,
+                to.gate,
+                to_value
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Debug> std::error::Error for SanityError<F> {}
+
+/// Shape and copy constraints of the compiled circuit
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledShape {
+    pub(crate) constraints: usize,
+    pub(crate) witnesses: usize,
+    /// Next wire in the copy constraint cycle of every wire of the gates, by
+    /// column
+    pub(crate) sigmas: [Vec<WireType>; 4],
+}
+
+impl CompiledShape {
+    pub(crate) fn new<C: TwistedEdwardsAffine>(
+        cs: &Plonk<C>,
+        n: usize,
+    ) -> Self {
+        let mut sigmas = cs.perm.clone().compute_sigma_permutations(n);
+
+        // the padding gates have no wires
+        let constraints = cs.constraints.len();
+        sigmas.iter_mut().for_each(|s| s.truncate(constraints));
+
+        Self {
+            constraints,
+            witnesses: cs.witness.len(),
+            sigmas,
+        }
+    }
+
+    /// Check the composer against the compiled circuit
+    pub(crate) fn check<C: TwistedEdwardsAffine>(
+        &self,
+        cs: &Plonk<C>,
+    ) -> Result<(), SanityError<C::Range>> {
+        if cs.constraints.len() != self.constraints {
+            return Err(SanityError::ConstraintCount {
+                expected: self.constraints,
+                found: cs.constraints.len(),
+            });
+        }
+
+        if cs.witness.len() != self.witnesses {
+            return Err(SanityError::WitnessCount {
+                expected: self.witnesses,
+                found: cs.witness.len(),
+            });
+        }
+
+        let wires = |gate: usize| {
+            let c = &cs.constraints[gate];
+            [c.w_a, c.w_b, c.w_o, c.w_d]
+        };
+
+        for gate in 0..cs.constraints.len() {
+            for (column, w) in wires(gate).iter().enumerate() {
+                if w.index() >= cs.witness.len() {
+                    return Err(SanityError::WireOutOfBounds {
+                        position: WirePosition { gate, column },
+                        witness: w.index(),
+                    });
+                }
+            }
+        }
+
+        // every wire carries the value of the next one in its cycle iff all
+        // the wires of the cycle carry the same value
+        for gate in 0..cs.constraints.len() {
+            for (column, w) in wires(gate).iter().enumerate() {
+                let to = WirePosition::from(self.sigmas[column][gate]);
+                let from_value = cs[*w];
+                let to_value = cs[wires(to.gate)[to.column]];
+
+                if from_value != to_value {
+                    return Err(SanityError::CopyConstraintViolation {
+                        from: WirePosition { gate, column },
+                        to,
+                        from_value,
+                        to_value,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
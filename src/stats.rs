@@ -0,0 +1,154 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Statistics of the gates of a circuit, for capacity planning.
+
+use zksnarks::Constraint;
+use zkstd::common::{PrimeField, TwistedEdwardsAffine};
+
+use crate::key::degree::SelectorFamily;
+use crate::Plonk;
+
+/// Gates, witnesses and public inputs of a circuit.
+///
+/// Every gate is counted once in the family of its selector. The gate
+/// following a family that reads the wires of the next gate often carries
+/// no selector, and is counted in that family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// Total number of gates
+    pub gates: usize,
+    /// Arithmetic gates
+    pub arithmetic: usize,
+    /// Range gates
+    pub range: usize,
+    /// Logic gates
+    pub logic: usize,
+    /// Fixed-base curve gates
+    pub fixed_base: usize,
+    /// Variable-base curve gates
+    pub variable_base: usize,
+    /// Gates without selector that don't belong to a family
+    pub empty: usize,
+    /// Arithmetic gates asserting two witnesses are equal
+    pub copy: usize,
+    /// Number of witnesses
+    pub witnesses: usize,
+    /// Number of public inputs
+    pub public_inputs: usize,
+    /// Size of the domain the gates and the rows of the lookup tables are
+    /// padded to
+    pub domain_size: usize,
+}
+
+impl CircuitStats {
+    /// Number of gates of the selector family
+    pub const fn family(&self, family: SelectorFamily) -> usize {
+        match family {
+            SelectorFamily::Arithmetic => self.arithmetic,
+            SelectorFamily::Range => self.range,
+            SelectorFamily::Logic => self.logic,
+            SelectorFamily::FixedBase => self.fixed_base,
+            SelectorFamily::VariableBase => self.variable_base,
+        }
+    }
+
+    /// Fraction of the gates that only assert two witnesses are equal
+    pub fn copy_fraction(&self) -> f64 {
+        match self.gates {
+            0 => 0.0,
+            gates => self.copy as f64 / gates as f64,
+        }
+    }
+
+    /// Serialize the statistics as a JSON object
+    #[cfg(feature = "std")]
+    pub fn to_json(&self) -> std::string::String {
+        format!(
+            concat!(
+                "{{\"gates\":{},\"arithmetic\":{},\"range\":{},",
+                "\"logic\":{},\"fixed_base\":{},\"variable_base\":{},",
+                "\"empty\":{},\"copy\":{},\"copy_fraction\":{},",
+                "\"witnesses\":{},\"public_inputs\":{},\"domain_size\":{}}}",
+            ),
+            self.gates,
+            self.arithmetic,
+            self.range,
+            self.logic,
+            self.fixed_base,
+            self.variable_base,
+            self.empty,
+            self.copy,
+            self.copy_fraction(),
+            self.witnesses,
+            self.public_inputs,
+            self.domain_size,
+        )
+    }
+}
+
+/// Check if the constraint is an arithmetic gate asserting `a = b`, as
+/// appended by [`Plonk::assert_equal`]
+pub fn is_copy_gate<F: PrimeField>(constraint: &Constraint<F>) -> bool {
+    let zero = F::zero();
+    let one = F::one();
+
+    constraint.q_arith == one
+        && constraint.q_l == one
+        && constraint.q_r == -one
+        && [
+            constraint.q_m,
+            constraint.q_o,
+            constraint.q_c,
+            constraint.q_d,
+            constraint.q_range,
+            constraint.q_logic,
+            constraint.q_fixed_group_add,
+            constraint.q_variable_group_add,
+        ]
+        .iter()
+        .all(|q| q == &zero)
+        && constraint.public_input.is_none()
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Statistics of the gates appended so far
+    pub fn statistics(&self) -> CircuitStats {
+        let mut stats = CircuitStats {
+            gates: self.constraints.len(),
+            witnesses: self.witness.len(),
+            public_inputs: self.instance.len(),
+            domain_size: self.domain_rows().next_power_of_two(),
+            ..Default::default()
+        };
+
+        let mut previous = None;
+
+        for constraint in self.constraints.iter() {
+            let family = SelectorFamily::of(constraint).or_else(|| {
+                previous.filter(|f: &SelectorFamily| f.reads_next_gate())
+            });
+
+            match family {
+                Some(SelectorFamily::Arithmetic) => stats.arithmetic += 1,
+                Some(SelectorFamily::Range) => stats.range += 1,
+                Some(SelectorFamily::Logic) => stats.logic += 1,
+                Some(SelectorFamily::FixedBase) => stats.fixed_base += 1,
+                Some(SelectorFamily::VariableBase) => stats.variable_base += 1,
+                None => stats.empty += 1,
+            }
+
+            if is_copy_gate(constraint) {
+                stats.copy += 1;
+            }
+
+            // a trailing gate doesn't extend the family to the next one
+            previous = SelectorFamily::of(constraint);
+        }
+
+        stats
+    }
+}
@@ -4,50 +4,321 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use crate::prover::Proof;
+mod cache;
+
+use crate::commitment_scheme::{
+    batch_challenge, batch_check, batch_check_with, OpeningClaim,
+};
+use crate::custom_gate::{self, CustomVerificationKey};
+use crate::digest::digest_to_scalar;
+use crate::key::degree::QuotientParts;
+use crate::lookup::LookupVerificationKey;
+use crate::prover::{AggregatedProof, Proof};
 
 use poly_commit::EvaluationKey;
 use sp_std::vec;
 use zksnarks::error::Error;
 use zksnarks::plonk::{Transcript, TranscriptProtocol, VerificationKey};
-use zkstd::common::{Group, Pairing, Vec};
+use zkstd::common::{Group, Pairing, TwistedEdwardsAffine, Vec};
+
+pub(crate) use cache::{LinearizationChallenges, VerifierCache};
+
+/// Public input of a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicInput<A: TwistedEdwardsAffine> {
+    /// Scalar public input, as returned by the prover
+    Scalar(A::Range),
+    /// Commitment appended with
+    /// [`Plonk::append_committed_public`](crate::Plonk::append_committed_public)
+    Commitment(A),
+}
+
+/// Kind of a public input in the layout of a circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicInputKind {
+    /// Scalar supplied as is
+    Scalar,
+    /// Digest appended with
+    /// [`Plonk::append_public_digest`](crate::Plonk::append_public_digest)
+    Digest {
+        /// Name of the hash algorithm of the digest
+        algorithm: &'static str,
+    },
+}
+
+/// Fiat-Shamir challenges of a proof, in the order they are derived from
+/// the transcript
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenges<F> {
+    /// Permutation challenge `β`
+    pub beta: F,
+    /// Permutation challenge `γ`
+    pub gamma: F,
+    /// Quotient challenge `α`
+    pub alpha: F,
+    /// Separation challenge of the range gates
+    pub range_separation: F,
+    /// Separation challenge of the logic gates
+    pub logic_separation: F,
+    /// Separation challenge of the fixed base gates
+    pub fixed_base_separation: F,
+    /// Separation challenge of the variable base gates
+    pub variable_base_separation: F,
+    /// Evaluation challenge `z`
+    pub z_challenge: F,
+    /// Challenge aggregating the openings at `z`
+    pub v_challenge: F,
+    /// Challenge aggregating the openings at `z·ω`
+    pub shifted_v_challenge: F,
+    /// Challenge batching the two opening claims
+    pub batch: F,
+}
 
 /// Verify proofs of a given circuit
 pub struct Verifier<P: Pairing> {
     verifier_key: VerificationKey<P>,
+    custom: Vec<CustomVerificationKey<P>>,
+    lookup: Option<LookupVerificationKey<P>>,
+    parts: QuotientParts,
     opening_key: EvaluationKey<P>,
     public_input_indexes: Vec<usize>,
+    public_input_layout: Vec<PublicInputKind>,
     transcript: Transcript,
     size: usize,
+    cache: VerifierCache<P>,
 }
 
 impl<P: Pairing> Verifier<P> {
     pub(crate) fn new(
         label: Vec<u8>,
         verifier_key: VerificationKey<P>,
+        custom: Vec<CustomVerificationKey<P>>,
+        lookup: Option<LookupVerificationKey<P>>,
+        parts: QuotientParts,
         opening_key: EvaluationKey<P>,
         public_input_indexes: Vec<usize>,
+        public_input_layout: Vec<PublicInputKind>,
         size: usize,
         constraints: usize,
     ) -> Self {
-        let transcript =
+        let mut transcript =
             Transcript::base(label.as_slice(), &verifier_key, constraints);
+        custom_gate::append_selectors(&mut transcript, &custom);
+        if let Some(lookup) = &lookup {
+            lookup.append_to(&mut transcript);
+        }
+
+        let cache = VerifierCache::new(&verifier_key, &custom);
 
         Self {
             verifier_key,
+            custom,
+            lookup,
+            parts,
             opening_key,
             public_input_indexes,
+            public_input_layout,
             transcript,
             size,
+            cache,
         }
     }
 
+    /// Kind of every public input of the circuit, in the order of the public
+    /// inputs
+    pub fn public_input_layout(&self) -> &[PublicInputKind] {
+        &self.public_input_layout
+    }
+
     /// Verify a generated proof
     pub fn verify(
         &self,
         proof: &Proof<P>,
         public_inputs: &[P::ScalarField],
     ) -> Result<(), Error> {
+        self.verify_returning_challenges(proof, public_inputs)
+            .map(|_| ())
+    }
+
+    /// Verify a generated proof and return the challenges it was verified
+    /// with
+    pub fn verify_returning_challenges(
+        &self,
+        proof: &Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<Challenges<P::ScalarField>, Error> {
+        let (claims, challenges) = self.replay(proof, public_inputs)?;
+
+        batch_check_with(&self.opening_key, &claims, &challenges.batch)
+            .map_err(|_| Error::ProofVerificationError)?;
+
+        Ok(challenges)
+    }
+
+    /// Derive the Fiat-Shamir challenges of a proof, without checking it.
+    ///
+    /// The transcript operations are the ones of [`Self::verify`], so the
+    /// challenges can be used to audit external verifiers.
+    pub fn derive_challenges(
+        &self,
+        proof: &Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<Challenges<P::ScalarField>, Error> {
+        self.replay(proof, public_inputs)
+            .map(|(_, challenges)| challenges)
+    }
+
+    /// Replay the transcript of a proof, returning its opening claims and
+    /// all of its challenges
+    fn replay(
+        &self,
+        proof: &Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<([OpeningClaim<P>; 2], Challenges<P::ScalarField>), Error> {
+        let (mut transcript, dense_public_inputs) =
+            self.transcript_with(public_inputs)?;
+
+        let (claims, mut challenges) = proof.opening_claims(
+            &self.verifier_key,
+            &self.custom,
+            self.lookup.as_ref(),
+            self.parts,
+            &self.cache,
+            &mut transcript,
+            &dense_public_inputs,
+        )?;
+        challenges.batch = batch_challenge::<P>(&mut transcript);
+
+        Ok((claims, challenges))
+    }
+
+    /// Verify a generated proof whose public inputs mix scalars and committed
+    /// values
+    pub fn verify_with_commitments<A>(
+        &self,
+        proof: &Proof<P>,
+        public_inputs: &[PublicInput<A>],
+    ) -> Result<(), Error>
+    where
+        A: TwistedEdwardsAffine<Range = P::ScalarField>,
+    {
+        // the commitment coordinates are public inputs of the gates
+        // asserting them, as in `Plonk::assert_equal_public_point`
+        let public_inputs: Vec<_> = public_inputs
+            .iter()
+            .flat_map(|pi| match pi {
+                PublicInput::Scalar(s) => vec![*s],
+                PublicInput::Commitment(c) => vec![-c.get_x(), -c.get_y()],
+            })
+            .collect();
+
+        self.verify(proof, &public_inputs)
+    }
+
+    /// Verify a generated proof whose digest public inputs are given by
+    /// their preimages.
+    ///
+    /// The preimages are hashed with [`public_digest`](crate::public_digest)
+    /// and, along with `other_inputs`, placed in the positions of the
+    /// [`Self::public_input_layout`]: both slices follow the order of the
+    /// public inputs of their kind.
+    pub fn verify_with_preimage(
+        &self,
+        proof: &Proof<P>,
+        preimages: &[&[u8]],
+        other_inputs: &[P::ScalarField],
+    ) -> Result<(), Error> {
+        let digests = self
+            .public_input_layout
+            .iter()
+            .filter(|kind| matches!(kind, PublicInputKind::Digest { .. }))
+            .count();
+        let others = self.public_input_layout.len() - digests;
+
+        if preimages.len() != digests {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: digests,
+                provided: preimages.len(),
+            });
+        }
+        if other_inputs.len() != others {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: others,
+                provided: other_inputs.len(),
+            });
+        }
+
+        let mut preimages = preimages.iter();
+        let mut other_inputs = other_inputs.iter();
+        let public_inputs: Vec<_> = self
+            .public_input_layout
+            .iter()
+            .filter_map(|kind| match kind {
+                PublicInputKind::Scalar => other_inputs.next().copied(),
+                PublicInputKind::Digest { .. } => {
+                    preimages.next().map(|data| digest_to_scalar(data))
+                }
+            })
+            .collect();
+
+        self.verify(proof, &public_inputs)
+    }
+
+    /// Verify an [`AggregatedProof`] with a single pairing check
+    ///
+    /// `public_inputs` holds the public inputs of every aggregated proof, in
+    /// order.
+    pub fn verify_aggregated(
+        &self,
+        aggregated: &AggregatedProof<P>,
+        public_inputs: &[Vec<P::ScalarField>],
+    ) -> Result<(), Error> {
+        if public_inputs.len() != aggregated.proofs.len() {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: aggregated.proofs.len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        let mut claims = Vec::with_capacity(2 * aggregated.proofs.len());
+
+        for (proof, public_inputs) in
+            aggregated.proofs.iter().zip(public_inputs.iter())
+        {
+            let (mut transcript, dense_public_inputs) =
+                self.transcript_with(public_inputs)?;
+
+            let (proof_claims, _) = proof.opening_claims(
+                &self.verifier_key,
+                &self.custom,
+                self.lookup.as_ref(),
+                self.parts,
+                &self.cache,
+                &mut transcript,
+                &dense_public_inputs,
+            )?;
+            claims.extend(proof_claims);
+        }
+
+        // the batching challenge binds the claims of all the proofs
+        let mut transcript = self.transcript.clone();
+        claims
+            .iter()
+            .for_each(|claim| claim.append_to(&mut transcript));
+
+        match claims.is_empty() {
+            true => Ok(()),
+            false => batch_check(&self.opening_key, &claims, &mut transcript)
+                .map_err(|_| Error::ProofVerificationError),
+        }
+    }
+
+    /// Append the public inputs to the circuit transcript and return it
+    /// along with their dense representation
+    fn transcript_with(
+        &self,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<(Transcript, Vec<P::ScalarField>), Error> {
         if public_inputs.len() != self.public_input_indexes.len() {
             return Err(Error::InconsistentPublicInputsLen {
                 expected: self.public_input_indexes.len(),
@@ -72,11 +343,6 @@ impl<P: Pairing> Verifier<P> {
             .zip(public_inputs.iter())
             .for_each(|(idx, pi)| dense_public_inputs[*idx] = *pi);
 
-        proof.verify(
-            &self.verifier_key,
-            &mut transcript,
-            &self.opening_key,
-            &dense_public_inputs,
-        )
+        Ok((transcript, dense_public_inputs))
     }
 }
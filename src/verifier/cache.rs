@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Data of the linearization commitment that is fixed per circuit, computed
+//! once when the [`Verifier`](crate::Verifier) is created.
+
+use poly_commit::{msm_curve_addition, Commitment};
+use zksnarks::plonk::{Evaluations as ProofEvaluations, VerificationKey};
+use zkstd::common::{Pairing, PrimeField, Vec};
+
+use crate::custom_gate::CustomVerificationKey;
+use crate::permutation::Permutation;
+
+/// Challenges of a proof the linearization commitment depends on
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LinearizationChallenges<F> {
+    pub(crate) alpha: F,
+    pub(crate) beta: F,
+    pub(crate) gamma: F,
+    pub(crate) z_challenge: F,
+    pub(crate) l1_eval: F,
+    /// Separation challenges of the range, logic, fixed base and variable
+    /// base gates
+    pub(crate) separation: [F; 4],
+}
+
+/// Circuit-fixed part of the linearization commitment
+#[derive(Debug, Clone)]
+pub(crate) struct VerifierCache<P: Pairing> {
+    /// Coset shifts of the `b`, `c` and `d` wires
+    k: [P::ScalarField; 3],
+    /// Points of the linearization commitment, in the order of its scalars
+    points: Vec<P::G1Affine>,
+    /// Index of the commitment to the permutation polynomial in `points`,
+    /// which is set per proof
+    z_slot: usize,
+}
+
+impl<P: Pairing> VerifierCache<P> {
+    pub(crate) fn new(
+        verifier_key: &VerificationKey<P>,
+        custom: &[CustomVerificationKey<P>],
+    ) -> Self {
+        let zero = P::ScalarField::zero();
+        let one = P::ScalarField::one();
+        let evaluations = ProofEvaluations {
+            a_eval: zero,
+            b_eval: zero,
+            c_eval: zero,
+            d_eval: zero,
+            a_next_eval: zero,
+            b_next_eval: zero,
+            d_next_eval: zero,
+            q_arith_eval: zero,
+            q_c_eval: zero,
+            q_l_eval: zero,
+            q_r_eval: zero,
+            s_sigma_1_eval: zero,
+            s_sigma_2_eval: zero,
+            s_sigma_3_eval: zero,
+            r_poly_eval: zero,
+            perm_eval: zero,
+        };
+
+        // the points of the gate families are commitments of the key, so
+        // they don't depend on the evaluations nor the challenges
+        let mut points = Vec::new();
+        points.extend(verifier_key.arithmetic.linearize(&evaluations).1);
+        points.extend(verifier_key.range.linearize(&one, &evaluations).1);
+        points.extend(verifier_key.logic.linearize(&one, &evaluations).1);
+        points
+            .extend(verifier_key.curve_scalar.linearize(&one, &evaluations).1);
+        points
+            .extend(verifier_key.curve_addtion.linearize(&one, &evaluations).1);
+
+        let z_slot = points.len();
+        points.push(verifier_key.permutation.s_sigma_4.0);
+        points.push(verifier_key.permutation.s_sigma_4.0);
+        points.extend(custom.iter().map(|key| key.q.0));
+
+        let k = [
+            P::ScalarField::from(Permutation::<P::ScalarField>::K1),
+            P::ScalarField::from(Permutation::<P::ScalarField>::K2),
+            P::ScalarField::from(Permutation::<P::ScalarField>::K3),
+        ];
+
+        Self { k, points, z_slot }
+    }
+
+    /// Commitment to the linearization polynomial `[r]_1`, given the
+    /// scalars of the selectors of the custom gates
+    pub(crate) fn linearization_commitment(
+        &self,
+        verifier_key: &VerificationKey<P>,
+        evaluations: &ProofEvaluations<P::ScalarField>,
+        challenges: &LinearizationChallenges<P::ScalarField>,
+        z_comm: Commitment<P::G1Affine>,
+        custom: &[P::ScalarField],
+    ) -> Commitment<P::G1Affine> {
+        let [range_sep, logic_sep, curve_scalar_sep, var_base_sep] =
+            challenges.separation;
+
+        let mut scalars = Vec::with_capacity(self.points.len());
+        scalars.extend(verifier_key.arithmetic.linearize(evaluations).0);
+        scalars.extend(verifier_key.range.linearize(&range_sep, evaluations).0);
+        scalars.extend(verifier_key.logic.linearize(&logic_sep, evaluations).0);
+        scalars.extend(
+            verifier_key
+                .curve_scalar
+                .linearize(&curve_scalar_sep, evaluations)
+                .0,
+        );
+        scalars.extend(
+            verifier_key
+                .curve_addtion
+                .linearize(&var_base_sep, evaluations)
+                .0,
+        );
+        scalars.extend(self.permutation_scalars(evaluations, challenges));
+        scalars.extend_from_slice(custom);
+
+        let mut points = self.points.clone();
+        points[self.z_slot] = z_comm.0;
+
+        debug_assert_eq!(scalars.len(), points.len());
+
+        Commitment::new(msm_curve_addition::<P::G1Affine>(&points, &scalars))
+    }
+
+    /// Scalars of the commitments to the permutation polynomial and to the
+    /// fourth sigma polynomial
+    fn permutation_scalars(
+        &self,
+        e: &ProofEvaluations<P::ScalarField>,
+        challenges: &LinearizationChallenges<P::ScalarField>,
+    ) -> [P::ScalarField; 2] {
+        let LinearizationChallenges {
+            alpha,
+            beta,
+            gamma,
+            z_challenge,
+            l1_eval,
+            ..
+        } = *challenges;
+        let [k1, k2, k3] = self.k;
+
+        // (a + β·z + γ)(b + β·k1·z + γ)(c + β·k2·z + γ)(d + β·k3·z + γ)·α
+        // + l1(z)·α²
+        let beta_z = beta * z_challenge;
+        let x = (e.a_eval + beta_z + gamma)
+            * (e.b_eval + beta_z * k1 + gamma)
+            * (e.c_eval + beta_z * k2 + gamma)
+            * (e.d_eval + beta_z * k3 + gamma)
+            * alpha
+            + l1_eval * alpha.square();
+
+        // -(a + β·σ1 + γ)(b + β·σ2 + γ)(c + β·σ3 + γ)·β·z(z·ω)·α
+        let y = -((e.a_eval + beta * e.s_sigma_1_eval + gamma)
+            * (e.b_eval + beta * e.s_sigma_2_eval + gamma)
+            * (e.c_eval + beta * e.s_sigma_3_eval + gamma)
+            * beta
+            * e.perm_eval
+            * alpha);
+
+        [x, y]
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Plonk, PlonkKey};
+    use bls_12_381::Fr as BlsScalar;
+    use ec_pairing::TatePairing;
+    use jub_jub::JubjubAffine;
+    use rand_core::OsRng;
+    use zksnarks::circuit::Circuit;
+    use zksnarks::error::Error;
+    use zksnarks::plonk::PlonkParams;
+    use zksnarks::public_params::PublicParameters;
+    use zkstd::common::Group;
+
+    #[derive(Debug, Default)]
+    struct DummyCircuit;
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let a = composer.append_witness(BlsScalar::from(3u64));
+            let b = composer.append_witness(BlsScalar::from(4u64));
+            let c = composer
+                .gate_mul(zksnarks::Constraint::default().mult(1).a(a).b(b));
+
+            composer.assert_equal_constant(c, BlsScalar::from(12u64), None);
+            composer.component_range(a, 8);
+
+            Ok(())
+        }
+    }
+
+    /// Linearization commitment as computed before the cache
+    fn uncached(
+        verifier_key: &VerificationKey<TatePairing>,
+        evaluations: &ProofEvaluations<BlsScalar>,
+        c: &LinearizationChallenges<BlsScalar>,
+        z_comm: Commitment<<TatePairing as Pairing>::G1Affine>,
+    ) -> Commitment<<TatePairing as Pairing>::G1Affine> {
+        let [range_sep, logic_sep, curve_scalar_sep, var_base_sep] =
+            c.separation;
+
+        let (s0, p0) = verifier_key.arithmetic.linearize(evaluations);
+        let (s1, p1) = verifier_key.range.linearize(&range_sep, evaluations);
+        let (s2, p2) = verifier_key.logic.linearize(&logic_sep, evaluations);
+        let (s3, p3) = verifier_key
+            .curve_scalar
+            .linearize(&curve_scalar_sep, evaluations);
+        let (s4, p4) = verifier_key
+            .curve_addtion
+            .linearize(&var_base_sep, evaluations);
+        let (s5, p5) = verifier_key.permutation.linearize(
+            &c.z_challenge,
+            (&c.alpha, &c.beta, &c.gamma),
+            &c.l1_eval,
+            z_comm.0,
+            evaluations,
+        );
+
+        Commitment::new(
+            msm_curve_addition::<<TatePairing as Pairing>::G1Affine>(
+                &[p0, p1, p2, p3, p4, p5].concat(),
+                &[s0, s1, s2, s3, s4, s5].concat(),
+            ),
+        )
+    }
+
+    #[test]
+    fn cached_linearization_commitment() {
+        let mut rng = OsRng;
+        let pp = PlonkParams::<TatePairing>::setup(6, &mut rng);
+
+        let (_, verifier) =
+            PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile_with_circuit(
+                &pp,
+                b"cache",
+                &DummyCircuit,
+            )
+            .expect("failed to compile circuit");
+
+        let verifier_key = &verifier.verifier_key;
+        let cache = VerifierCache::new(verifier_key, &[]);
+
+        for _ in 0..8 {
+            let mut random = || BlsScalar::random(&mut rng);
+            let evaluations = ProofEvaluations {
+                a_eval: random(),
+                b_eval: random(),
+                c_eval: random(),
+                d_eval: random(),
+                a_next_eval: random(),
+                b_next_eval: random(),
+                d_next_eval: random(),
+                q_arith_eval: random(),
+                q_c_eval: random(),
+                q_l_eval: random(),
+                q_r_eval: random(),
+                s_sigma_1_eval: random(),
+                s_sigma_2_eval: random(),
+                s_sigma_3_eval: random(),
+                r_poly_eval: random(),
+                perm_eval: random(),
+            };
+            let challenges = LinearizationChallenges {
+                alpha: random(),
+                beta: random(),
+                gamma: random(),
+                z_challenge: random(),
+                l1_eval: random(),
+                separation: [random(), random(), random(), random()],
+            };
+            let z_comm = verifier_key.permutation.s_sigma_1;
+
+            assert_eq!(
+                cache.linearization_commitment(
+                    verifier_key,
+                    &evaluations,
+                    &challenges,
+                    z_comm,
+                    &[],
+                ),
+                uncached(verifier_key, &evaluations, &challenges, z_comm)
+            );
+        }
+    }
+}
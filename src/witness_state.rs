@@ -0,0 +1,342 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Transport of the witnesses of a synthesized composer.
+//!
+//! The gates are described by the circuit code, so only the witness values
+//! and the public inputs are serialized. The receiver synthesizes the
+//! circuit with any witnesses and applies the state on top of it.
+//!
+//! The state is a fixed header, all integers little endian:
+//!
+//! - 4 bytes magic, `PLWS`
+//! - 1 byte format version
+//! - 1 byte flags, the lowest bit set if the body is zstd compressed
+//! - 8 bytes [`Plonk::circuit_id`] of the sender
+//! - 8 bytes number of witnesses
+//! - 8 bytes number of public inputs
+//!
+//! followed by the body: the witnesses, then the public inputs as pairs of
+//! 8 bytes gate index and value. Every scalar has the fixed width of its
+//! encoding.
+
+use codec::{Decode, Encode};
+use zkstd::common::{PrimeField, TwistedEdwardsAffine, Vec};
+
+use crate::Plonk;
+
+const MAGIC: [u8; 4] = *b"PLWS";
+const VERSION: u8 = 1;
+const FLAG_ZSTD: u8 = 1;
+const HEADER_SIZE: usize = 4 + 1 + 1 + 8 + 8 + 8;
+
+/// Error returned when a witness state can't be applied to a composer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessStateError {
+    /// The state ended before the expected data
+    Truncated,
+    /// The state doesn't start with the witness state magic
+    BadMagic,
+    /// The state was written by an unsupported version of the format
+    UnsupportedVersion(u8),
+    /// The state is compressed, but compression support isn't enabled
+    UnsupportedCompression,
+    /// The state was produced by a different circuit
+    CircuitMismatch {
+        /// Id of the circuit of the composer
+        expected: u64,
+        /// Id of the circuit of the state
+        found: u64,
+    },
+    /// The number of witnesses doesn't match the composer
+    WitnessCount {
+        /// Witnesses of the composer
+        expected: usize,
+        /// Witnesses of the state
+        found: usize,
+    },
+    /// The public inputs don't belong to the same gates as the ones of the
+    /// composer
+    PublicInputs,
+    /// A scalar or the compressed body can't be decoded, or there are
+    /// trailing bytes
+    Malformed,
+}
+
+impl core::fmt::Display for WitnessStateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "the witness state is truncated"),
+            Self::BadMagic => write!(f, "not a witness state"),
+            Self::UnsupportedVersion(v) => {
+                write!(f, "unsupported witness state version {}", v)
+            }
+            Self::UnsupportedCompression => {
+                write!(f, "the witness state is compressed")
+            }
+            Self::CircuitMismatch { expected, found } => write!(
+                f,
+                "the witness state belongs to circuit {:016x}, expected \
+                 {:016x}",
+                found, expected
+            ),
+            Self::WitnessCount { expected, found } => write!(
+                f,
+                "the witness state has {} witnesses, expected {}",
+                found, expected
+            ),
+            Self::PublicInputs => {
+                write!(f, "the public inputs belong to other gates")
+            }
+            Self::Malformed => write!(f, "the witness state is malformed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WitnessStateError {}
+
+/// 64-bit FNV-1a, to fingerprint the gates
+struct Fnv(u64);
+
+impl Fnv {
+    const fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl<C: TwistedEdwardsAffine> Plonk<C> {
+    /// Fingerprint of the gates of the circuit, regardless of the values of
+    /// its witnesses and public inputs.
+    ///
+    /// It isn't a cryptographic hash: it catches mismatched circuit code, not
+    /// a forged circuit.
+    pub fn circuit_id(&self) -> u64 {
+        let mut hash = Fnv::new();
+
+        hash.write(&[
+            self.config.dummy_gate_sets,
+            self.config.reserve_zero_one as u8,
+        ]);
+        hash.write(&(self.constraints.len() as u64).to_le_bytes());
+
+        for c in self.constraints.iter() {
+            [
+                c.q_m,
+                c.q_l,
+                c.q_r,
+                c.q_o,
+                c.q_c,
+                c.q_d,
+                c.q_arith,
+                c.q_range,
+                c.q_logic,
+                c.q_fixed_group_add,
+                c.q_variable_group_add,
+            ]
+            .iter()
+            .for_each(|q| hash.write(&q.encode()));
+
+            [c.w_a, c.w_b, c.w_o, c.w_d]
+                .iter()
+                .for_each(|w| hash.write(&(w.index() as u64).to_le_bytes()));
+
+            hash.write(&[c.public_input.is_some() as u8]);
+        }
+
+        for (gate, custom) in self.custom_gates.iter() {
+            hash.write(&(*gate as u64).to_le_bytes());
+            hash.write(&(*custom as u64).to_le_bytes());
+        }
+
+        for (gate, table) in self.lookups.iter() {
+            hash.write(&(*gate as u64).to_le_bytes());
+            hash.write(&(*table as u64).to_le_bytes());
+        }
+
+        for rows in self.lookup_tables.iter() {
+            hash.write(&(rows.len() as u64).to_le_bytes());
+            rows.iter().flatten().for_each(|x| hash.write(&x.encode()));
+        }
+
+        hash.0
+    }
+
+    /// Serialize the witnesses and public inputs of the composer.
+    ///
+    /// The gates aren't included: [`Self::apply_witness_state`] expects a
+    /// composer synthesized from the same circuit code.
+    pub fn serialize_witness_state(&self) -> Vec<u8> {
+        let mut bytes = self.witness_state_header(0);
+        bytes.extend(self.witness_state_body());
+
+        bytes
+    }
+
+    /// Serialize the witnesses and public inputs of the composer, with the
+    /// body compressed with zstd at `level`
+    #[cfg(feature = "witness-compression")]
+    pub fn serialize_witness_state_compressed(&self, level: i32) -> Vec<u8> {
+        let body = zstd::bulk::compress(&self.witness_state_body(), level)
+            .expect("compressing in memory doesn't fail");
+
+        let mut bytes = self.witness_state_header(FLAG_ZSTD);
+        bytes.extend(body);
+
+        bytes
+    }
+
+    /// Replace the witnesses and public inputs of the composer with the ones
+    /// of a state produced by [`Self::serialize_witness_state`].
+    ///
+    /// The composer must be synthesized from the same circuit code as the
+    /// composer the state was serialized from, with any witness values. The
+    /// composer is left untouched on error.
+    pub fn apply_witness_state(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), WitnessStateError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(WitnessStateError::Truncated);
+        }
+
+        let (header, body) = bytes.split_at(HEADER_SIZE);
+        let u64_at = |offset: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&header[offset..offset + 8]);
+            u64::from_le_bytes(word)
+        };
+
+        if header[..4] != MAGIC {
+            return Err(WitnessStateError::BadMagic);
+        }
+        if header[4] != VERSION {
+            return Err(WitnessStateError::UnsupportedVersion(header[4]));
+        }
+
+        let flags = header[5];
+        let circuit_id = u64_at(6);
+        let witnesses = u64_at(14) as usize;
+        let public_inputs = u64_at(22) as usize;
+
+        let expected = self.circuit_id();
+        if circuit_id != expected {
+            return Err(WitnessStateError::CircuitMismatch {
+                expected,
+                found: circuit_id,
+            });
+        }
+        if witnesses != self.witness.len() {
+            return Err(WitnessStateError::WitnessCount {
+                expected: self.witness.len(),
+                found: witnesses,
+            });
+        }
+        if public_inputs != self.instance.len() {
+            return Err(WitnessStateError::PublicInputs);
+        }
+
+        let width = C::Range::zero().encoded_size();
+        let body_size = witnesses * width + public_inputs * (8 + width);
+
+        #[cfg(feature = "witness-compression")]
+        let decompressed;
+        let mut body = match flags {
+            0 => body,
+            #[cfg(feature = "witness-compression")]
+            FLAG_ZSTD => {
+                // the size is bounded by the composer, not by the state
+                decompressed = zstd::bulk::decompress(body, body_size)
+                    .map_err(|_| WitnessStateError::Malformed)?;
+                decompressed.as_slice()
+            }
+            #[cfg(not(feature = "witness-compression"))]
+            FLAG_ZSTD => return Err(WitnessStateError::UnsupportedCompression),
+            _ => return Err(WitnessStateError::Malformed),
+        };
+
+        if body.len() < body_size {
+            return Err(WitnessStateError::Truncated);
+        }
+        if body.len() > body_size {
+            return Err(WitnessStateError::Malformed);
+        }
+
+        let scalar = |body: &mut &[u8]| {
+            let (mut head, tail) = body.split_at(width);
+            let value = C::Range::decode(&mut head)
+                .map_err(|_| WitnessStateError::Malformed)?;
+            *body = tail;
+
+            Ok(value)
+        };
+
+        let witness = (0..witnesses)
+            .map(|_| scalar(&mut body))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // the gates are serialized in increasing order, so the state covers
+        // every public input of the composer exactly once
+        let mut instance: Vec<(usize, C::Range)> =
+            Vec::with_capacity(public_inputs);
+        for _ in 0..public_inputs {
+            let (gate, tail) = body.split_at(8);
+            let mut word = [0u8; 8];
+            word.copy_from_slice(gate);
+            body = tail;
+
+            let gate = u64::from_le_bytes(word) as usize;
+            let increasing = instance.last().map_or(true, |(g, _)| *g < gate);
+            if !increasing || !self.instance.contains_key(&gate) {
+                return Err(WitnessStateError::PublicInputs);
+            }
+
+            instance.push((gate, scalar(&mut body)?));
+        }
+
+        self.witness = witness;
+        for (gate, value) in instance {
+            self.constraints[gate].public_input = Some(value);
+            self.instance.insert(gate, value);
+        }
+
+        Ok(())
+    }
+
+    fn witness_state_header(&self, flags: u8) -> Vec<u8> {
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+
+        header.extend_from_slice(&MAGIC);
+        header.extend_from_slice(&[VERSION, flags]);
+        header.extend_from_slice(&self.circuit_id().to_le_bytes());
+        header.extend_from_slice(&(self.witness.len() as u64).to_le_bytes());
+        header.extend_from_slice(&(self.instance.len() as u64).to_le_bytes());
+
+        header
+    }
+
+    fn witness_state_body(&self) -> Vec<u8> {
+        let width = C::Range::zero().encoded_size();
+        let mut body = Vec::with_capacity(
+            self.witness.len() * width + self.instance.len() * (8 + width),
+        );
+
+        self.witness.iter().for_each(|w| body.extend(w.encode()));
+        self.instance.iter().for_each(|(gate, value)| {
+            body.extend_from_slice(&(*gate as u64).to_le_bytes());
+            body.extend(value.encode());
+        });
+
+        body
+    }
+}
@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+const BITS: usize = 64;
+
+/// Asserts the public value is the magnitude of `a`
+#[derive(Debug, Default)]
+pub struct AbsCircuit {
+    a: BlsScalar,
+    magnitude: BlsScalar,
+}
+
+impl AbsCircuit {
+    pub fn new(a: i128) -> Self {
+        let magnitude = BlsScalar::from(a.unsigned_abs() as u64);
+        let a = match a < 0 {
+            true => -magnitude,
+            false => magnitude,
+        };
+
+        Self { a, magnitude }
+    }
+}
+
+impl Circuit<JubjubAffine> for AbsCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let magnitude = composer.append_public(self.magnitude);
+
+        let x = composer.component_abs(a, BITS);
+        composer.assert_equal(x, magnitude);
+
+        Ok(())
+    }
+}
+
+type JubjubKey = PlonkKey<TatePairing, JubjubAffine, AbsCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let circuit = AbsCircuit::default();
+    let pp = JubjubKey::setup_for_circuit(&circuit, rng)
+        .expect("failed to synthesize");
+
+    JubjubKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn abs_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let max = u64::MAX as i128;
+    for a in [0, 1, -1, 5, -5, max, -max] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &AbsCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn abs_refuses_wrong_sign() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for a in [5, -5, u64::MAX as i128] {
+        // the magnitude of the other sign
+        let mut circuit = AbsCircuit::new(a);
+        circuit.magnitude = -circuit.magnitude;
+
+        if let Ok((proof, public_inputs)) =
+            prover.create_proof(&mut rng, &circuit)
+        {
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect_err("the proof must not verify");
+        }
+    }
+}
+
+#[test]
+fn abs_out_of_range() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, _) = keys(&mut rng);
+
+    // (p - 1) / 2 and its negation have no magnitude of the supported bits
+    let half = -BlsScalar::one() * BlsScalar::from(2u64).invert().unwrap();
+    let wide = BlsScalar::pow_of_2(BITS as u64);
+
+    for a in [half, -half, wide, -wide] {
+        let circuit = AbsCircuit {
+            a,
+            magnitude: BlsScalar::zero(),
+        };
+
+        prover
+            .create_proof(&mut rng, &circuit)
+            .expect_err("the magnitude doesn't fit the bits");
+    }
+}
+
+#[test]
+fn abs_of_zero_is_deterministic() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let zero = composer.append_witness(BlsScalar::zero());
+    let magnitude = composer.component_abs(zero, BITS);
+
+    assert_eq!(composer[magnitude], BlsScalar::zero());
+}
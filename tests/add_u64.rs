@@ -0,0 +1,143 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+
+/// Asserts the public values are the sum modulo `2^64` and the carry of the
+/// secret operands
+#[derive(Debug, Default)]
+pub struct AddCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    sum: BlsScalar,
+    carry: BlsScalar,
+}
+
+impl AddCircuit {
+    pub fn new(a: u64, b: u64) -> Self {
+        let (sum, carry) = a.overflowing_add(b);
+
+        Self {
+            a: BlsScalar::from(a),
+            b: BlsScalar::from(b),
+            sum: BlsScalar::from(sum),
+            carry: BlsScalar::from(carry as u64),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for AddCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let (sum, carry) = composer.component_add_u64(a, b);
+
+        composer.component_range(a, 64);
+        composer.component_range(b, 64);
+
+        let public_sum = composer.append_public(self.sum);
+        let public_carry = composer.append_public(self.carry);
+        composer.assert_equal(sum, public_sum);
+        composer.assert_equal(carry, public_carry);
+
+        Ok(())
+    }
+}
+
+type AddKey = PlonkKey<TatePairing, JubjubAffine, AddCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let circuit = AddCircuit::default();
+    let pp =
+        AddKey::setup_for_circuit(&circuit, rng).expect("failed to synthesize");
+
+    AddKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn add_u64_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for (a, b) in [
+        (0, 0),
+        (0, u64::MAX),
+        (u64::MAX, 1),
+        (u64::MAX, u64::MAX),
+        (rng.next_u64(), rng.next_u64()),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &AddCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn add_u64_values() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    for (a, b) in [(0, 0), (u64::MAX, 1), (u64::MAX, u64::MAX), (3, 4)] {
+        let x = composer.append_witness(BlsScalar::from(a));
+        let y = composer.append_witness(BlsScalar::from(b));
+
+        let gates = composer.statistics().gates;
+        let (sum, carry) = composer.component_add_u64(x, y);
+        assert_eq!(composer.statistics().gates, gates + 12);
+
+        let (expected, overflow) = a.overflowing_add(b);
+        assert_eq!(composer[sum], BlsScalar::from(expected));
+        assert_eq!(composer[carry], BlsScalar::from(overflow as u64));
+    }
+}
+
+#[test]
+fn add_u64_refuses_hidden_carry() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // claim `(2^64 - 1) + 1` doesn't overflow, with the whole sum as the
+    // low part
+    let unreduced = BlsScalar::from(u64::MAX) + BlsScalar::one();
+    let circuit = AddCircuit {
+        a: BlsScalar::from(u64::MAX),
+        b: BlsScalar::one(),
+        sum: unreduced,
+        carry: BlsScalar::zero(),
+    };
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and the operands, the gadget
+    // witnesses the sum and then the carry
+    let mut state = composer.serialize_witness_state();
+    for (witness, value) in [(4, unreduced), (5, BlsScalar::zero())] {
+        let offset = 30 + witness * 32;
+        state[offset..offset + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a hidden carry must not verify");
+}
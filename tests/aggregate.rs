@@ -0,0 +1,103 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use ec_pairing::TatePairing;
+use jub_jub::JubjubAffine;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_ab = composer.append_public(self.a * self.b);
+
+        let constraint = Constraint::default().mult(1).a(w_a).b(w_b);
+        let product = composer.gate_mul(constraint);
+        composer.assert_equal(product, w_ab);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn aggregate_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut pp = PlonkParams::<TatePairing>::setup(5, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    for size in [2, 20] {
+        let proofs: Vec<_> = (0..size)
+            .map(|_| {
+                let circuit = DummyCircuit {
+                    a: BlsScalar::random(&mut rng),
+                    b: BlsScalar::random(&mut rng),
+                };
+
+                prover
+                    .create_proof(&mut rng, &circuit)
+                    .expect("failed to prove")
+            })
+            .collect();
+        let public_inputs: Vec<_> =
+            proofs.iter().map(|(_, pi)| pi.clone()).collect();
+
+        let aggregated =
+            prover.aggregate(&proofs).expect("failed to aggregate");
+        assert_eq!(aggregated.len(), size);
+
+        verifier
+            .verify_aggregated(&aggregated, &public_inputs)
+            .expect("failed to verify aggregated proof");
+
+        // public inputs of a different statement
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[size - 1][0] += BlsScalar::one();
+        verifier
+            .verify_aggregated(&aggregated, &wrong_inputs)
+            .expect_err("wrong public inputs must not verify");
+
+        // missing public inputs
+        verifier
+            .verify_aggregated(&aggregated, &public_inputs[1..])
+            .expect_err("missing public inputs must not verify");
+
+        // tamper with the last evaluation of an inner proof
+        let mut tampered = proofs.clone();
+        let mut bytes = tampered[size / 2].0.encode();
+        let len = bytes.len();
+        bytes[len - 32..].copy_from_slice(&BlsScalar::one().encode());
+        tampered[size / 2].0 = Proof::try_from(bytes.as_slice())
+            .expect("tampered proof is well formed");
+
+        let aggregated =
+            prover.aggregate(&tampered).expect("failed to aggregate");
+        verifier
+            .verify_aggregated(&aggregated, &public_inputs)
+            .expect_err("tampered proof must not verify");
+    }
+}
@@ -0,0 +1,149 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts `a == b` and `a · b + c = d` only if the flag is set
+#[derive(Debug, Default)]
+pub struct AssertIfCircuit {
+    flag: bool,
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+    d: BlsScalar,
+}
+
+impl AssertIfCircuit {
+    pub fn new(
+        flag: bool,
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+        d: BlsScalar,
+    ) -> Self {
+        Self { flag, a, b, c, d }
+    }
+}
+
+impl Circuit<JubjubAffine> for AssertIfCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let flag = composer.append_witness(BlsScalar::from(self.flag as u64));
+        composer.component_boolean(flag);
+
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let c = composer.append_witness(self.c);
+        let d = composer.append_witness(self.d);
+
+        composer.assert_equal_if(flag, a, b);
+
+        let constraint = Constraint::default()
+            .mult(1)
+            .fourth(1)
+            .output(-BlsScalar::one())
+            .a(a)
+            .b(b)
+            .d(c)
+            .o(d);
+        composer.append_gate_if(flag, constraint);
+
+        Ok(())
+    }
+}
+
+type AssertIfKey = PlonkKey<TatePairing, JubjubAffine, AssertIfCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = AssertIfKey::setup_for_circuit(&AssertIfCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    AssertIfKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn assert_if_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let a = BlsScalar::random(&mut rng);
+    let c = BlsScalar::random(&mut rng);
+    let d = a * a + c;
+
+    // the constraints hold, whatever the flag
+    for flag in [false, true] {
+        let circuit = AssertIfCircuit::new(flag, a, a, c, d);
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // the constraints are violated, but disabled
+    let b = BlsScalar::random(&mut rng);
+    let d = BlsScalar::random(&mut rng);
+    let circuit = AssertIfCircuit::new(false, a, b, c, d);
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn assert_if_enforces_when_set() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let a = BlsScalar::random(&mut rng);
+    let b = a + BlsScalar::one();
+    let c = BlsScalar::random(&mut rng);
+
+    // `a != b`, with a satisfied gate
+    let circuit = AssertIfCircuit::new(true, a, b, c, a * b + c);
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("an enabled equality must hold");
+    }
+
+    // `a == b`, with a violated gate
+    let circuit = AssertIfCircuit::new(true, a, a, c, a * a);
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("an enabled gate must hold");
+    }
+}
+
+#[test]
+fn assert_if_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let flag = composer.append_witness(BlsScalar::one());
+    let a = composer.append_witness(BlsScalar::from(2u64));
+    let b = composer.append_witness(BlsScalar::from(3u64));
+
+    let gates = composer.statistics().gates;
+    composer.assert_equal_if(flag, a, b);
+    assert_eq!(composer.statistics().gates, gates + 2);
+
+    let constraint = Constraint::default().mult(1).a(a).b(b);
+    composer.append_gate_if(flag, constraint);
+    assert_eq!(composer.statistics().gates, gates + 5);
+}
@@ -0,0 +1,116 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+
+const LEN: usize = 5;
+
+/// Asserts the secret values are sorted in ascending order
+#[derive(Debug, Default)]
+pub struct SortedCircuit {
+    values: [u64; LEN],
+}
+
+impl SortedCircuit {
+    pub fn new(values: [u64; LEN]) -> Self {
+        Self { values }
+    }
+}
+
+impl Circuit<JubjubAffine> for SortedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let values: Vec<_> = self
+            .values
+            .iter()
+            .map(|v| composer.append_witness(BlsScalar::from(*v)))
+            .collect();
+
+        composer.component_assert_sorted::<64>(&values);
+
+        Ok(())
+    }
+}
+
+type SortedKey = PlonkKey<TatePairing, JubjubAffine, SortedCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = SortedKey::setup_for_circuit(&SortedCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    SortedKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn assert_sorted_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let mut random = [(); LEN].map(|_| rng.next_u64());
+    random.sort_unstable();
+
+    for values in [
+        [0; LEN],
+        [u64::MAX; LEN],
+        [0, 0, 1, u64::MAX - 1, u64::MAX],
+        [3, 5, 5, 5, 8],
+        random,
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &SortedCircuit::new(values))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn assert_sorted_refuses_inversion() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for values in [
+        [3, 5, 4, 6, 8],
+        [1, 2, 3, 4, 0],
+        [u64::MAX, 0, 0, 0, 0],
+        [0, 0, 0, u64::MAX, u64::MAX - 1],
+    ] {
+        if let Ok((proof, public_inputs)) =
+            prover.create_proof(&mut rng, &SortedCircuit::new(values))
+        {
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect_err("an unsorted list shouldn't verify");
+        }
+    }
+}
+
+#[test]
+fn assert_sorted_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::from(7u64));
+    let b = composer.append_witness(BlsScalar::from(9u64));
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_sorted::<64>(&[]);
+    assert_eq!(composer.statistics().gates, gates);
+
+    // a single value is only range checked
+    composer.component_assert_sorted::<64>(&[a]);
+    assert_eq!(composer.statistics().gates, gates + 10);
+
+    // every value is range checked once, and every pair adds a difference
+    let gates = composer.statistics().gates;
+    composer.component_assert_sorted::<64>(&[a, b, b]);
+    assert_eq!(composer.statistics().gates, gates + 3 * 10 + 2 * 11);
+}
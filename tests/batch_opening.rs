@@ -0,0 +1,61 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use poly_commit::Coefficients;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::commitment_scheme::{
+    batch_check_independent, find_invalid_claim, OpeningClaim,
+};
+use zkplonk::prelude::*;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+/// Open a random polynomial at a random point
+fn claim(
+    pp: &PlonkParams<TatePairing>,
+    rng: &mut StdRng,
+) -> OpeningClaim<TatePairing> {
+    let coefficients = (0..32).map(|_| BlsScalar::random(&mut *rng)).collect();
+    let poly = Coefficients::new(coefficients);
+    let point = BlsScalar::random(&mut *rng);
+
+    let witness = pp.compute_aggregate_witness(
+        &[poly.clone()],
+        &point,
+        &BlsScalar::one(),
+    );
+
+    OpeningClaim {
+        point,
+        commitment: pp.commit(&poly).expect("failed to commit"),
+        evaluation: poly.evaluate(&point),
+        witness: pp.commit(&witness).expect("failed to commit"),
+    }
+}
+
+#[test]
+fn batch_check_independent_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = PlonkParams::<TatePairing>::setup(6, &mut rng);
+    let evaluation_key = pp.verification_key();
+
+    let mut claims: Vec<_> = (0..50).map(|_| claim(&pp, &mut rng)).collect();
+
+    batch_check_independent(&evaluation_key, &claims, &mut rng)
+        .expect("valid claims must verify");
+    assert_eq!(find_invalid_claim(&evaluation_key, &claims), None);
+
+    // corrupt a single evaluation
+    claims[17].evaluation += BlsScalar::one();
+
+    batch_check_independent(&evaluation_key, &claims, &mut rng)
+        .expect_err("corrupted claim must not verify");
+    assert_eq!(find_invalid_claim(&evaluation_key, &claims), Some(17));
+}
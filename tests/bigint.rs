@@ -0,0 +1,270 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::gadget::bigint::BigIntWitness;
+use zkplonk::prelude::*;
+use zksnarks::plonk::wire::PrivateWire;
+
+const LIMBS: usize = 4;
+
+type Limbs = [u64; LIMBS];
+
+fn add(a: Limbs, b: Limbs) -> (Limbs, bool) {
+    let mut carry = 0u128;
+    let sum = core::array::from_fn(|i| {
+        let total = a[i] as u128 + b[i] as u128 + carry;
+        carry = total >> 64;
+
+        total as u64
+    });
+
+    (sum, carry == 1)
+}
+
+fn sub(a: Limbs, b: Limbs) -> (Limbs, bool) {
+    let mut borrow = false;
+    let diff = core::array::from_fn(|i| {
+        let (d, x) = a[i].overflowing_sub(b[i]);
+        let (d, y) = d.overflowing_sub(borrow as u64);
+        borrow = x || y;
+
+        d
+    });
+
+    (diff, borrow)
+}
+
+fn mul(a: Limbs, b: Limbs) -> [u64; 2 * LIMBS] {
+    let mut product = [0u64; 2 * LIMBS];
+    for i in 0..LIMBS {
+        let mut carry = 0u128;
+        for j in 0..LIMBS {
+            let t =
+                a[i] as u128 * b[j] as u128 + product[i + j] as u128 + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        product[i + LIMBS] = carry as u64;
+    }
+
+    product
+}
+
+fn assert_public(
+    composer: &mut Plonk<JubjubAffine>,
+    wires: &[PrivateWire],
+    values: &[u64],
+) {
+    for (w, v) in wires.iter().zip(values) {
+        let public = composer.append_public(BlsScalar::from(*v));
+        composer.assert_equal(*w, public);
+    }
+}
+
+/// Asserts the public values are the sum, difference, product and
+/// comparison of the secret operands
+#[derive(Debug, Default)]
+pub struct BigIntCircuit {
+    a: Limbs,
+    b: Limbs,
+    sum: Limbs,
+    carry: bool,
+    diff: Limbs,
+    borrow: bool,
+    product: [u64; 2 * LIMBS],
+}
+
+impl BigIntCircuit {
+    pub fn new(a: Limbs, b: Limbs) -> Self {
+        let (sum, carry) = add(a, b);
+        let (diff, borrow) = sub(a, b);
+        let product = mul(a, b);
+
+        Self {
+            a,
+            b,
+            sum,
+            carry,
+            diff,
+            borrow,
+            product,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for BigIntCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = BigIntWitness::witness(composer, self.a);
+        let b = BigIntWitness::witness(composer, self.b);
+
+        let (sum, carry) = a.add(composer, &b);
+        let (diff, borrow) = a.sub(composer, &b);
+        let lt = a.less_than(composer, &b);
+        let product: BigIntWitness<{ 2 * LIMBS }> = a.mul(composer, &b);
+
+        // the difference added back yields the minuend
+        let (back, _) = diff.add(composer, &b);
+        back.assert_eq(composer, &a);
+
+        assert_public(composer, &sum.limbs(), &self.sum);
+        assert_public(composer, &[carry], &[self.carry as u64]);
+        assert_public(composer, &diff.limbs(), &self.diff);
+        assert_public(composer, &[borrow, lt], &[self.borrow as u64; 2]);
+        assert_public(composer, &product.limbs(), &self.product);
+
+        Ok(())
+    }
+}
+
+type BigIntKey = PlonkKey<TatePairing, JubjubAffine, BigIntCircuit>;
+
+#[test]
+fn bigint_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = BigIntKey::setup_for_circuit(&BigIntCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        BigIntKey::compile(&pp).expect("failed to compile circuit");
+
+    let max = [u64::MAX; LIMBS];
+    let one = [1, 0, 0, 0];
+    let random = [(); LIMBS].map(|_| rng.next_u64());
+
+    for (a, b) in [
+        ([0; LIMBS], [0; LIMBS]),
+        (max, max),
+        (max, one),
+        (one, max),
+        ([0; LIMBS], one),
+        (random, max),
+        (random, [(); LIMBS].map(|_| rng.next_u64())),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &BigIntCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    let mut circuit = BigIntCircuit::new(random, max);
+    circuit.product[2 * LIMBS - 1] ^= 1;
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("a wrong product shouldn't verify");
+    }
+}
+
+#[test]
+fn bigint_ripples_carries() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let max = BigIntWitness::witness(&mut composer, [u64::MAX; LIMBS]);
+    let one = BigIntWitness::witness(&mut composer, [1, 0, 0, 0]);
+
+    let (sum, carry) = max.add(&mut composer, &one);
+    assert_eq!(sum.value(&composer), [0; LIMBS]);
+    assert_eq!(composer[carry], BlsScalar::one());
+
+    let (diff, borrow) = sum.sub(&mut composer, &one);
+    assert_eq!(diff.value(&composer), [u64::MAX; LIMBS]);
+    assert_eq!(composer[borrow], BlsScalar::one());
+
+    let product: BigIntWitness<{ 2 * LIMBS }> = max.mul(&mut composer, &max);
+    assert_eq!(
+        product.value(&composer),
+        mul([u64::MAX; LIMBS], [u64::MAX; LIMBS])
+    );
+}
+
+/// Asserts the public values are the sum modulo `2^64` and the carry of a
+/// single limb
+#[derive(Debug, Default)]
+pub struct LimbCircuit {
+    a: u64,
+    b: u64,
+    sum: BlsScalar,
+    carry: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for LimbCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = BigIntWitness::witness(composer, [self.a]);
+        let b = BigIntWitness::witness(composer, [self.b]);
+
+        let (sum, carry) = a.add(composer, &b);
+
+        let public_sum = composer.append_public(self.sum);
+        let public_carry = composer.append_public(self.carry);
+        composer.assert_equal(sum.limbs()[0], public_sum);
+        composer.assert_equal(carry, public_carry);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn bigint_refuses_hidden_carry() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &LimbCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, LimbCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    // claim `(2^64 - 1) + 1` has no carry, keeping the whole sum in the limb
+    let unreduced = BlsScalar::from(u64::MAX) + BlsScalar::one();
+    let circuit = LimbCircuit {
+        a: u64::MAX,
+        b: 1,
+        sum: unreduced,
+        carry: BlsScalar::zero(),
+    };
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // the addition witnesses the carry, then the limb, after the operands
+    let mut operands = Plonk::<JubjubAffine>::initialize();
+    BigIntWitness::witness(&mut operands, [0]);
+    BigIntWitness::witness(&mut operands, [0]);
+    let carry = operands.statistics().witnesses;
+
+    let mut state = composer.serialize_witness_state();
+    for (witness, value) in [(carry, BlsScalar::zero()), (carry + 1, unreduced)]
+    {
+        let offset = 30 + witness * 32;
+        state[offset..offset + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a hidden carry must not verify");
+}
@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Message `abc` of the example of RFC 7693, appendix B
+const ABC: &[u8] = b"abc";
+
+/// Digests of RFC 7693, and of messages of bytes `0, 1, ..., n - 1` spanning
+/// one, two and three blocks
+const VECTORS: [(usize, &str); 5] = [
+    (
+        0,
+        "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9",
+    ),
+    (
+        3,
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+    ),
+    (
+        64,
+        "56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e",
+    ),
+    (
+        100,
+        "81dcc3a505eace3f879d8f702776770f9df50e521d1428a85daf04f9ad2150e0",
+    ),
+    (
+        130,
+        "c80abeebb669ad5deeb5f5ec8ea6b7a05ddf7d31ec4c0a2ee20b0b98caec6746",
+    ),
+];
+
+fn message(len: usize) -> Vec<u8> {
+    match len {
+        3 => ABC.to_vec(),
+        _ => (0..len as u8).collect(),
+    }
+}
+
+/// Little-endian words of `bytes`, zero padded
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect()
+}
+
+fn digest_words(hex: &str) -> [u32; 8] {
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+
+    let mut digest = [0u32; 8];
+    digest.copy_from_slice(&words(&bytes));
+    digest
+}
+
+/// Asserts the public digest is the BLAKE2s-256 of the secret `abc`
+#[derive(Debug)]
+pub struct Blake2sCircuit {
+    message: [u8; 3],
+    digest: [u32; 8],
+}
+
+impl Default for Blake2sCircuit {
+    fn default() -> Self {
+        Self {
+            message: [b'a', b'b', b'c'],
+            digest: digest_words(VECTORS[1].1),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for Blake2sCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let input: Vec<_> = words(&self.message)
+            .iter()
+            .map(|w| composer.append_witness(BlsScalar::from(*w as u64)))
+            .collect();
+
+        let digest = composer.component_blake2s(&input, self.message.len());
+
+        for (w, expected) in digest.iter().zip(self.digest) {
+            let expected = composer.append_public(BlsScalar::from(expected));
+            composer.assert_equal(*w, expected);
+        }
+
+        Ok(())
+    }
+}
+
+type Blake2sKey = PlonkKey<TatePairing, JubjubAffine, Blake2sCircuit>;
+
+#[test]
+fn blake2s_vectors() {
+    for (len, hex) in VECTORS {
+        let bytes = message(len);
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let input: Vec<_> = words(&bytes)
+            .iter()
+            .map(|w| composer.append_witness(BlsScalar::from(*w as u64)))
+            .collect();
+
+        let digest = composer.component_blake2s(&input, len);
+        digest
+            .iter()
+            .zip(digest_words(hex))
+            .for_each(|(w, x)| assert_eq!(composer[*w], BlsScalar::from(x)));
+    }
+}
+
+#[test]
+fn blake2s_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        Blake2sKey::setup_for_circuit(&Blake2sCircuit::default(), &mut rng)
+            .expect("failed to synthesize");
+    let (prover, verifier) =
+        Blake2sKey::compile(&pp).expect("failed to compile circuit");
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &Blake2sCircuit::default())
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // a wrong digest is refused
+    let mut circuit = Blake2sCircuit::default();
+    circuit.digest[7] ^= 1;
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrong digest must not verify");
+}
+
+#[test]
+#[should_panic(expected = "a word per 4 bytes")]
+fn blake2s_rejects_wrong_length() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::from(7u64));
+
+    composer.component_blake2s(&[w], 5);
+}
@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_ab = composer.append_public(self.a * self.b);
+
+        let constraint = Constraint::default().mult(1).a(w_a).b(w_b);
+        let product = composer.gate_mul(constraint);
+
+        composer.assert_equal(product, w_ab);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn blinding_defaults_are_safe() {
+    let default = BlindingConfig::default();
+
+    assert_eq!(default, BlindingConfig::minimum_safe());
+    assert!(default.is_safe());
+    assert!(!default.insecure);
+
+    // wires are opened at two points, the permutation polynomial at three
+    assert_eq!(default.wire_poly_terms, 2);
+    assert_eq!(default.perm_poly_terms, 3);
+}
+
+#[test]
+fn blinding_below_minimum_is_rejected() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut pp = PlonkParams::<TatePairing>::setup(5, &mut rng);
+    let (mut prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    assert_eq!(prover.blinding(), BlindingConfig::default());
+
+    let unblinded = BlindingConfig {
+        wire_poly_terms: 0,
+        perm_poly_terms: 0,
+        insecure: false,
+    };
+
+    assert_eq!(prover.set_blinding(unblinded), Err(BlindingError::Insecure));
+    assert_eq!(prover.blinding(), BlindingConfig::default());
+
+    // the escape hatch allows it, and the verifier is unaffected
+    let unblinded = BlindingConfig {
+        insecure: true,
+        ..unblinded
+    };
+    prover
+        .set_blinding(unblinded)
+        .expect("insecure blinding must be accepted");
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let circuit = DummyCircuit { a, b };
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
@@ -0,0 +1,155 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the boolean wires of the bits encode an integer below the modulus
+#[derive(Debug, Clone)]
+pub struct CanonicalCircuit {
+    bits: [bool; 255],
+}
+
+impl CanonicalCircuit {
+    pub fn new(bits: [bool; 255]) -> Self {
+        Self { bits }
+    }
+}
+
+impl Default for CanonicalCircuit {
+    fn default() -> Self {
+        Self::new([false; 255])
+    }
+}
+
+impl Circuit<JubjubAffine> for CanonicalCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let mut bits = [Plonk::<JubjubAffine>::ZERO; 255];
+        bits.iter_mut().zip(self.bits.iter()).for_each(|(w, bit)| {
+            *w = composer.append_witness(BlsScalar::from(*bit as u64));
+            composer.component_boolean(*w);
+        });
+
+        composer.component_assert_canonical(&bits);
+
+        Ok(())
+    }
+}
+
+/// Asserts the bits of the decomposition of the scalar are canonical
+#[derive(Debug, Default)]
+pub struct DecompositionCircuit {
+    a: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DecompositionCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let bits = composer.component_decomposition::<255>(a);
+
+        composer.component_assert_canonical(&bits);
+
+        Ok(())
+    }
+}
+
+/// Little endian bits of `p - 1`, plus `carry`
+fn modulus_bits(carry: bool) -> [bool; 255] {
+    let max = (-BlsScalar::one()).to_bits();
+    let mut bits = [false; 255];
+    bits.iter_mut()
+        .zip(max.iter().rev())
+        .for_each(|(b, m)| *b = *m);
+
+    // add one, propagating the carry
+    let mut carry = carry;
+    for b in bits.iter_mut() {
+        let sum = *b as u8 + carry as u8;
+        *b = sum & 1 == 1;
+        carry = sum > 1;
+    }
+
+    bits
+}
+
+type CanonicalKey = PlonkKey<TatePairing, JubjubAffine, CanonicalCircuit>;
+
+#[test]
+fn assert_canonical_bits() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        CanonicalKey::setup_for_circuit(&CanonicalCircuit::default(), &mut rng)
+            .expect("failed to synthesize");
+    let (prover, verifier) =
+        CanonicalKey::compile(&pp).expect("failed to compile circuit");
+
+    let mut small = [false; 255];
+    small[..4].copy_from_slice(&[true, false, true, true]);
+
+    let cases = [
+        (small, true),
+        (modulus_bits(false), true),
+        (modulus_bits(true), false),
+        ([true; 255], false),
+    ];
+
+    for (bits, canonical) in cases {
+        let result = prover
+            .create_proof(&mut rng, &CanonicalCircuit::new(bits))
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+
+        assert_eq!(result.is_ok(), canonical);
+    }
+}
+
+#[test]
+fn assert_canonical_decomposition() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &DecompositionCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DecompositionCircuit>::compile(
+            &pp,
+        )
+        .expect("failed to compile circuit");
+
+    for a in [
+        BlsScalar::zero(),
+        -BlsScalar::one(),
+        BlsScalar::random(&mut rng),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DecompositionCircuit { a })
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn assert_canonical_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let bits = [Plonk::<JubjubAffine>::ZERO; 255];
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_canonical(&bits);
+    assert_eq!(composer.statistics().gates - gates, 255);
+}
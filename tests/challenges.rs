@@ -0,0 +1,116 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "test-utils")]
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::Challenges;
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Default for DummyCircuit {
+    fn default() -> Self {
+        Self {
+            a: BlsScalar::from(3u64),
+            b: BlsScalar::from(4u64),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_public(self.a);
+        let b = composer.append_public(self.b);
+
+        let constraint = Constraint::default().mult(1).a(a).b(b);
+        let c = composer.gate_mul(constraint);
+        composer.component_range(c, 16);
+
+        Ok(())
+    }
+}
+
+fn challenges_list(c: &Challenges<BlsScalar>) -> [BlsScalar; 11] {
+    [
+        c.beta,
+        c.gamma,
+        c.alpha,
+        c.range_separation,
+        c.logic_separation,
+        c.fixed_base_separation,
+        c.variable_base_separation,
+        c.z_challenge,
+        c.v_challenge,
+        c.shifted_v_challenge,
+        c.batch,
+    ]
+}
+
+#[test]
+fn derived_challenges_match_prover() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(6, &mut rng);
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = DummyCircuit {
+        a: BlsScalar::from(21u64),
+        b: BlsScalar::from(2u64),
+    };
+    let (proof, public_inputs, challenges) = prover
+        .create_proof_with_challenges(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    let derived = verifier
+        .derive_challenges(&proof, &public_inputs)
+        .expect("the public inputs have the right length");
+    assert_eq!(derived, challenges);
+
+    let verified = verifier
+        .verify_returning_challenges(&proof, &public_inputs)
+        .expect("failed to verify proof");
+    assert_eq!(verified, challenges);
+
+    // the challenges are all derived after the public inputs, so every one
+    // of them changes with a public input
+    let mut tampered = public_inputs.clone();
+    tampered[1] += BlsScalar::one();
+
+    let derived = verifier
+        .derive_challenges(&proof, &tampered)
+        .expect("the public inputs have the right length");
+    for (i, (a, b)) in challenges_list(&derived)
+        .iter()
+        .zip(challenges_list(&challenges).iter())
+        .enumerate()
+    {
+        assert_ne!(a, b, "challenge {} didn't change", i);
+    }
+
+    verifier
+        .verify_returning_challenges(&proof, &tampered)
+        .expect_err("tampered public inputs must not verify");
+
+    assert!(matches!(
+        verifier.derive_challenges(&proof, &public_inputs[..1]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 2,
+            provided: 1,
+        })
+    ));
+}
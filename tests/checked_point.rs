@@ -0,0 +1,159 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Appends a point and a public point with the checked APIs, and asserts
+/// their checked sum is the public result
+#[derive(Debug)]
+pub struct CheckedCircuit {
+    a: JubjubAffine,
+    b: JubjubAffine,
+    sum: JubjubAffine,
+}
+
+impl CheckedCircuit {
+    pub fn new(a: JubjubAffine, b: JubjubAffine) -> Self {
+        let sum = JubjubExtended::from(a) + JubjubExtended::from(b);
+
+        Self {
+            a,
+            b,
+            sum: sum.into(),
+        }
+    }
+}
+
+impl Default for CheckedCircuit {
+    fn default() -> Self {
+        let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+        Self::new(g, g)
+    }
+}
+
+impl Circuit<JubjubAffine> for CheckedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_point_checked(self.a);
+        let b = composer.append_public_point_checked(self.b);
+
+        let sum = composer.component_add_point_checked(a, b);
+        composer.assert_equal_public_point(sum, self.sum);
+
+        Ok(())
+    }
+}
+
+type CheckedKey = PlonkKey<TatePairing, JubjubAffine, CheckedCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = CheckedKey::setup_for_circuit(&CheckedCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    CheckedKey::compile(&pp).expect("failed to compile circuit")
+}
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+/// `point` with its `y` coordinate moved off the curve
+fn off_curve(point: JubjubAffine) -> JubjubAffine {
+    JubjubAffine::from_raw_unchecked(
+        point.get_x(),
+        point.get_y() + BlsScalar::one(),
+    )
+}
+
+#[test]
+fn checked_points_work() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let identity = JubjubAffine::ADDITIVE_IDENTITY;
+    for (a, b) in [
+        (identity, identity),
+        (random_point(&mut rng), random_point(&mut rng)),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &CheckedCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn checked_points_refuse_off_curve() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let a = random_point(&mut rng);
+    let b = random_point(&mut rng);
+
+    // the sum is whatever the native addition computes, so only the curve
+    // equation can refuse the circuits
+    for (a, b) in [(off_curve(a), b), (a, off_curve(b))] {
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let w_a = composer.append_point(a);
+        let w_b = composer.append_point(b);
+        let sum = composer.component_add_point(w_a, w_b);
+        let sum = JubjubAffine::from_raw_unchecked(
+            composer[*sum.x()],
+            composer[*sum.y()],
+        );
+
+        let circuit = CheckedCircuit { a, b, sum };
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a point off the curve must not verify");
+    }
+}
+
+#[test]
+fn checked_points_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+    let gates = composer.statistics().gates;
+    let a = composer.append_point_checked(g);
+    assert_eq!(composer.statistics().gates, gates + 3);
+
+    let gates = composer.statistics().gates;
+    let b = composer.append_public_point_checked(g);
+    assert_eq!(composer.statistics().gates, gates + 5);
+
+    let gates = composer.statistics().gates;
+    composer.component_add_point_checked(a, b);
+    assert_eq!(composer.statistics().gates, gates + 8);
+}
+
+#[cfg(feature = "debug-assertions")]
+#[test]
+fn add_point_reports_off_curve_operand() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let point = random_point(&mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_point(point);
+    let b = composer.append_point(off_curve(point));
+    composer.component_add_point(a, b);
+
+    let mismatches = composer.check_debug_assertions();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].wire, b.y().index());
+    assert_eq!(mismatches[0].expected, point.get_y());
+}
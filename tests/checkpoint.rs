@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ec_pairing::TatePairing;
+use jub_jub::JubjubAffine;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+static PREFIX_RUNS: AtomicUsize = AtomicUsize::new(0);
+static SUFFIX_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn pow_8(a: BlsScalar) -> BlsScalar {
+    (0..3).fold(a, |acc, _| acc * acc)
+}
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl DummyCircuit {
+    /// Fixed part of the circuit: `a^8` as a public input
+    fn prefix(&self, composer: &mut Plonk<JubjubAffine>) {
+        PREFIX_RUNS.fetch_add(1, Ordering::SeqCst);
+
+        let w_a = composer.append_witness(self.a);
+        let pow = (0..3).fold(w_a, |acc, _| {
+            let constraint = Constraint::default().mult(1).a(acc).b(acc);
+            composer.gate_mul(constraint)
+        });
+
+        let expected = composer.append_public(pow_8(self.a));
+        composer.assert_equal(pow, expected);
+    }
+
+    /// Variable part of the circuit: `a + b` as a public input
+    fn suffix(&self, composer: &mut Plonk<JubjubAffine>) {
+        SUFFIX_RUNS.fetch_add(1, Ordering::SeqCst);
+
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let constraint = Constraint::default().left(1).right(1).a(w_a).b(w_b);
+        let sum = composer.gate_add(constraint);
+
+        let expected = composer.append_public(self.a + self.b);
+        composer.assert_equal(sum, expected);
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        self.prefix(composer);
+        self.suffix(composer);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn checkpoint_rewind_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut pp = PlonkParams::<TatePairing>::setup(5, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    let a = BlsScalar::random(&mut rng);
+    let circuits = [
+        DummyCircuit {
+            a,
+            b: BlsScalar::random(&mut rng),
+        },
+        DummyCircuit {
+            a,
+            b: BlsScalar::random(&mut rng),
+        },
+    ];
+
+    PREFIX_RUNS.store(0, Ordering::SeqCst);
+    SUFFIX_RUNS.store(0, Ordering::SeqCst);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuits[0].prefix(&mut composer);
+
+    let checkpoint = composer.checkpoint();
+    let m = ConstraintSystem::m(&composer);
+
+    for circuit in circuits.iter() {
+        composer.rewind(checkpoint);
+        assert_eq!(ConstraintSystem::m(&composer), m);
+
+        circuit.suffix(&mut composer);
+
+        let (proof, public_inputs) = prover
+            .create_proof_with_composer(&mut rng, &composer)
+            .expect("failed to prove");
+
+        assert_eq!(public_inputs.len(), 2);
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    assert_eq!(PREFIX_RUNS.load(Ordering::SeqCst), 1);
+    assert_eq!(SUFFIX_RUNS.load(Ordering::SeqCst), 2);
+
+    // a rewound composer matches a freshly synthesized one
+    let mut fresh = Plonk::<JubjubAffine>::initialize();
+    circuits[1]
+        .synthesize(&mut fresh)
+        .expect("failed to synthesize");
+    assert_eq!(ConstraintSystem::m(&fresh), ConstraintSystem::m(&composer));
+}
+
+#[test]
+fn rewind_takes_back_public_inputs_set_on_prefix() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let constraint = Constraint::default().left(1).a(a);
+    composer.gate_add(constraint);
+    let gate = ConstraintSystem::m(&composer) - 1;
+
+    let checkpoint = composer.checkpoint();
+    let public_inputs = composer.statistics().public_inputs;
+
+    // a gate of the prefix is given a public input after the checkpoint
+    composer
+        .set_public_input(gate, BlsScalar::from(7u64))
+        .expect("the gate has no public input");
+    assert_eq!(composer.statistics().public_inputs, public_inputs + 1);
+
+    composer.rewind(checkpoint);
+    assert_eq!(composer.statistics().public_inputs, public_inputs);
+
+    // the gate is free again
+    composer
+        .set_public_input(gate, BlsScalar::from(9u64))
+        .expect("the public input was taken back");
+}
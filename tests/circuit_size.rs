@@ -0,0 +1,134 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::CircuitTooLarge;
+
+/// Chain of `GATES` multiplications of `a` with itself
+#[derive(Debug, Default)]
+pub struct ChainCircuit<const GATES: usize> {
+    a: BlsScalar,
+    result: BlsScalar,
+}
+
+impl<const GATES: usize> ChainCircuit<GATES> {
+    fn new(a: BlsScalar) -> Self {
+        let result = (0..GATES).fold(a, |acc, _| acc * a);
+
+        Self { a, result }
+    }
+}
+
+impl<const GATES: usize> Circuit<JubjubAffine> for ChainCircuit<GATES> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let mut acc = a;
+
+        for _ in 0..GATES {
+            let constraint = Constraint::default().mult(1).a(acc).b(a);
+            acc = composer.gate_mul(constraint);
+        }
+
+        composer.assert_equal_constant(
+            acc,
+            BlsScalar::zero(),
+            Some(self.result),
+        );
+
+        Ok(())
+    }
+}
+
+#[test]
+fn circuit_too_large() {
+    type Large = ChainCircuit<200>;
+
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(5, &mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    Large::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    let gates = composer.statistics().gates;
+    let padded = (gates + 6).next_power_of_two();
+    let suggested_k =
+        PlonkKey::<TatePairing, JubjubAffine, Large>::required_domain_log2(
+            &Large::default(),
+        )
+        .expect("failed to synthesize");
+
+    let err = PlonkKey::<TatePairing, JubjubAffine, Large>::check_size(
+        &pp, &composer,
+    )
+    .expect_err("the circuit exceeds the params");
+
+    assert_eq!(err.gates, gates);
+    assert_eq!(err.padded, padded);
+    assert!(err.params_max < padded);
+    assert_eq!(err.suggested_k, suggested_k);
+    assert_eq!(1 << err.suggested_k, padded);
+
+    assert!(matches!(
+        PlonkKey::<TatePairing, JubjubAffine, Large>::compile(&pp),
+        Err(Error::PolynomialDegreeTooLarge)
+    ));
+
+    // the suggested size is enough
+    let pp = PlonkParams::<TatePairing>::setup(err.suggested_k, &mut rng);
+    assert_eq!(
+        PlonkKey::<TatePairing, JubjubAffine, Large>::check_size(
+            &pp, &composer
+        ),
+        Ok::<(), CircuitTooLarge>(())
+    );
+}
+
+#[test]
+fn setup_for_circuit() {
+    type Small = ChainCircuit<10>;
+    type Large = ChainCircuit<200>;
+
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = PlonkKey::<TatePairing, JubjubAffine, Large>::setup_for_circuit(
+        &Large::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, Large>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = Large::new(BlsScalar::random(&mut rng));
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // params sized for a smaller circuit are refused
+    let pp = PlonkKey::<TatePairing, JubjubAffine, Small>::setup_for_circuit(
+        &Small::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    assert!(matches!(
+        PlonkKey::<TatePairing, JubjubAffine, Large>::compile(&pp),
+        Err(Error::PolynomialDegreeTooLarge)
+    ));
+}
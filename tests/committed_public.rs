@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+/// Blinding generator of the test.
+///
+/// Its discrete logarithm is known, so it must not be used outside tests.
+fn blinding_generator() -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::from(0xdead_beefu64))
+        .into()
+}
+
+fn commit(value: u64, blinding: u64) -> JubjubAffine {
+    let value = JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::from(value);
+    let blinding = blinding_generator() * JubjubScalar::from(blinding);
+
+    (value + blinding).into()
+}
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    value: u64,
+    blinding: u64,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_public(self.a);
+
+        let w_value = composer.append_committed_public(
+            BlsScalar::from(self.value),
+            BlsScalar::from(self.blinding),
+            JubjubAffine::ADDITIVE_GENERATOR,
+            blinding_generator(),
+        )?;
+
+        let w_b = composer.append_public(self.b);
+
+        // the committed value is usable as any other witness
+        let constraint = Constraint::default().left(1).right(1).a(w_a).b(w_b);
+        let sum = composer.gate_add(constraint);
+        let constraint = Constraint::default().mult(1).a(sum).b(w_value);
+        composer.gate_mul(constraint);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn committed_public_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut pp = PlonkParams::<TatePairing>::setup(11, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    let circuit = DummyCircuit {
+        a: BlsScalar::random(&mut rng),
+        value: rng.gen(),
+        blinding: rng.gen(),
+        b: BlsScalar::random(&mut rng),
+    };
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    // a, the commitment coordinates and b
+    assert_eq!(public_inputs.len(), 4);
+
+    // the plain path is unchanged
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    let commitment = commit(circuit.value, circuit.blinding);
+    let instance = [
+        PublicInput::Scalar(public_inputs[0]),
+        PublicInput::Commitment(commitment),
+        PublicInput::Scalar(public_inputs[3]),
+    ];
+
+    verifier
+        .verify_with_commitments(&proof, &instance)
+        .expect("failed to verify proof with a commitment");
+
+    // commitment to a different value
+    let commitment = commit(circuit.value.wrapping_add(1), circuit.blinding);
+    let instance = [
+        PublicInput::Scalar(public_inputs[0]),
+        PublicInput::Commitment(commitment),
+        PublicInput::Scalar(public_inputs[3]),
+    ];
+
+    verifier
+        .verify_with_commitments(&proof, &instance)
+        .expect_err("commitment to another value must not verify");
+}
@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::CompileBudget;
+
+const ROUNDS: usize = 300;
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    x: BlsScalar,
+    y: BlsScalar,
+}
+
+impl DummyCircuit {
+    fn new(x: BlsScalar) -> Self {
+        let y = (0..ROUNDS).fold(x, |acc, _| acc * acc + x);
+
+        Self { x, y }
+    }
+}
+
+impl Default for DummyCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::from(2u64))
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let x = composer.append_witness(self.x);
+
+        // acc² + x, so every gate copies both wires of a previous one
+        let mut acc = x;
+        for _ in 0..ROUNDS {
+            let constraint =
+                Constraint::default().mult(1).fourth(1).a(acc).b(acc).d(x);
+            acc = composer.gate_mul(constraint);
+        }
+
+        let y = composer.append_public(self.y);
+        composer.assert_equal(acc, y);
+        composer.component_range(x, 64);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn chunked_compilation_is_identical() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(10, &mut rng);
+
+    let compile = |budget| {
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile_with_budget(
+            &pp,
+            b"budget",
+            &DummyCircuit::default(),
+            InitConfig::default(),
+            budget,
+        )
+        .expect("failed to compile circuit")
+    };
+
+    let (prover, verifier) = compile(CompileBudget::default());
+    let (chunked_prover, chunked_verifier) = compile(CompileBudget {
+        max_scratch_bytes: 0,
+    });
+
+    let circuit = DummyCircuit::new(BlsScalar::random(&mut rng));
+
+    // the blinding factors are drawn from the same seed, so the proofs only
+    // differ if the keys do
+    let (proof, public_inputs) = prover
+        .create_proof(&mut StdRng::seed_from_u64(1), &circuit)
+        .expect("failed to prove");
+    let (chunked_proof, chunked_public_inputs) = chunked_prover
+        .create_proof(&mut StdRng::seed_from_u64(1), &circuit)
+        .expect("failed to prove");
+
+    assert_eq!(proof.encode(), chunked_proof.encode());
+    assert_eq!(public_inputs, chunked_public_inputs);
+
+    verifier
+        .verify(&chunked_proof, &public_inputs)
+        .expect("failed to verify proof");
+    chunked_verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkplonk::CompositionOverflow;
+use zkstd::common::FftField;
+
+/// Decomposes `a` into `N` bits and bytes, and recomposes both
+#[derive(Debug, Default)]
+pub struct RoundTripCircuit<const N: usize> {
+    a: BlsScalar,
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for RoundTripCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+
+        let bits = composer.component_decomposition::<N>(a);
+        let composed = composer
+            .component_compose_bits(&bits)
+            .expect("the bits fit the field");
+        composer.assert_equal(composed, a);
+
+        let bytes = composer.component_decomposition_bytes::<8>(a);
+        let composed = composer
+            .component_compose_bytes(&bytes, false)
+            .expect("the bytes fit the field");
+        composer.assert_equal(composed, a);
+
+        Ok(())
+    }
+}
+
+fn round_trip<const N: usize>(rng: &mut StdRng, a: u64) {
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &RoundTripCircuit::<N>::default(),
+        &mut *rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, RoundTripCircuit<N>>::compile(
+            &pp,
+        )
+        .expect("failed to compile circuit");
+
+    let circuit = RoundTripCircuit::<N> {
+        a: BlsScalar::from(a),
+    };
+    let (proof, public_inputs) = prover
+        .create_proof(&mut *rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn compose_round_trip() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let a = rng.next_u64();
+
+    round_trip::<1>(&mut rng, a & 1);
+    round_trip::<8>(&mut rng, a & 0xff);
+    round_trip::<33>(&mut rng, a >> 31);
+    round_trip::<64>(&mut rng, a);
+}
+
+#[test]
+fn compose_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = BlsScalar::random(&mut rng);
+    let w = composer.append_witness(a);
+
+    let bits = composer.component_decomposition::<255>(w);
+    let composed = composer
+        .component_compose_bits(&bits)
+        .expect("255 bits fit the field");
+    assert_eq!(composer[composed], a);
+
+    let bytes = composer.component_decomposition_bytes::<32>(w);
+    let composed = composer
+        .component_compose_bytes(&bytes[..31], true)
+        .expect("31 bytes fit the field");
+    assert_eq!(
+        composer[composed],
+        a - composer[bytes[31]] * BlsScalar::pow_of_2(248)
+    );
+}
+
+#[test]
+fn compose_overflow() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let bits = [Plonk::<JubjubAffine>::ZERO; 256];
+    assert_eq!(
+        composer.component_compose_bits(&bits),
+        Err(CompositionOverflow {
+            bits: 256,
+            max: 255
+        })
+    );
+
+    assert_eq!(
+        composer.component_compose_bytes(&bits[..32], false),
+        Err(CompositionOverflow {
+            bits: 256,
+            max: 255
+        })
+    );
+}
@@ -0,0 +1,297 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::{Decode, Encode};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// `x^5` S-box in a single gate, with `x` in `a`, `x^2` in `b` and `x^5` in
+/// `c`
+struct Pow5Gate;
+
+impl CustomGate<BlsScalar> for Pow5Gate {
+    fn label(&self) -> &'static [u8] {
+        b"pow5 separation challenge"
+    }
+
+    fn wire_degree(&self) -> usize {
+        3
+    }
+
+    fn evaluate(
+        &self,
+        separation: &BlsScalar,
+        w: &GateWires<BlsScalar>,
+    ) -> BlsScalar {
+        let square = w.a * w.a - w.b;
+        let pow5 = w.b * w.b * w.a - w.c;
+
+        (square + pow5 * separation) * separation
+    }
+}
+
+/// Gate multiplying five wires, beyond the degree the quotient fits in four
+/// parts, with `x` in `a` and `x^5` in `c`
+struct Degree5Gate;
+
+impl CustomGate<BlsScalar> for Degree5Gate {
+    fn label(&self) -> &'static [u8] {
+        b"degree 5 separation challenge"
+    }
+
+    fn wire_degree(&self) -> usize {
+        5
+    }
+
+    fn evaluate(
+        &self,
+        separation: &BlsScalar,
+        w: &GateWires<BlsScalar>,
+    ) -> BlsScalar {
+        (w.a * w.a * w.a * w.a * w.a - w.c) * separation
+    }
+}
+
+const POW5: [&dyn CustomGate<BlsScalar>; 1] = [&Pow5Gate];
+
+/// Asserts the public output is the fifth power of the secret input, with
+/// the output witnessed as is
+#[derive(Debug, Clone, Default)]
+pub struct Pow5Circuit {
+    x: BlsScalar,
+    y: BlsScalar,
+}
+
+impl Pow5Circuit {
+    pub fn new(x: BlsScalar, y: BlsScalar) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Circuit<JubjubAffine> for Pow5Circuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let x = composer.append_witness(self.x);
+        let x2 = composer.append_witness(self.x * self.x);
+        let y = composer.append_witness(self.y);
+
+        let constraint = Constraint::default().a(x).b(x2).o(y);
+        composer.append_registered_gate(0, constraint);
+
+        let public = composer.append_public(self.y);
+        composer.assert_equal(y, public);
+
+        Ok(())
+    }
+}
+
+type Pow5Key = PlonkKey<TatePairing, JubjubAffine, Pow5Circuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = Pow5Key::setup_for_circuit(&Pow5Circuit::default(), rng)
+        .expect("failed to synthesize");
+
+    Pow5Key::compile_with_custom_gates(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+        &POW5,
+    )
+    .expect("failed to compile circuit")
+}
+
+fn pow5(x: BlsScalar) -> BlsScalar {
+    let x2 = x * x;
+
+    x2 * x2 * x
+}
+
+#[test]
+fn custom_gate_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for x in [
+        BlsScalar::zero(),
+        BlsScalar::one(),
+        BlsScalar::random(&mut rng),
+    ] {
+        let circuit = Pow5Circuit::new(x, pow5(x));
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        assert_eq!(public_inputs, vec![pow5(x)]);
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn custom_gate_is_enforced() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // only the custom gate relates the output to the input
+    let x = BlsScalar::random(&mut rng);
+    let circuit = Pow5Circuit::new(x, pow5(x) + BlsScalar::one());
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrong power must not verify");
+
+    // a valid proof doesn't verify against another output
+    let circuit = Pow5Circuit::new(x, pow5(x));
+    let (proof, _) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &[pow5(x) + BlsScalar::one()])
+        .expect_err("a proof must not verify with another output");
+}
+
+#[test]
+#[should_panic(expected = "custom gate that isn't registered")]
+fn custom_gate_must_be_registered() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = Pow5Key::setup_for_circuit(&Pow5Circuit::default(), &mut rng)
+        .expect("failed to synthesize");
+
+    let _ = Pow5Key::compile_with_circuit(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+    );
+}
+
+#[test]
+fn custom_gate_degree_is_checked() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = Pow5Key::setup_for_circuit(&Pow5Circuit::default(), &mut rng)
+        .expect("failed to synthesize");
+
+    let result = Pow5Key::compile_with_custom_gates(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+        &[&Degree5Gate],
+    );
+    assert!(matches!(result, Err(Error::PolynomialDegreeTooLarge)));
+}
+
+#[test]
+fn six_quotient_parts_work() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = Pow5Key::setup_for_circuit(&Pow5Circuit::default(), &mut rng)
+        .expect("failed to synthesize");
+
+    let result = Pow5Key::compile_with_quotient_parts(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+        &[&Degree5Gate],
+        QuotientParts::Four,
+    );
+    assert!(matches!(
+        result,
+        Err(CompileError::QuotientDegreeTooHigh {
+            parts: QuotientParts::Four,
+            ..
+        })
+    ));
+
+    let (prover, verifier) = Pow5Key::compile_with_quotient_parts(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+        &[&Degree5Gate],
+        QuotientParts::Six,
+    )
+    .expect("failed to compile circuit");
+
+    let x = BlsScalar::random(&mut rng);
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &Pow5Circuit::new(x, pow5(x)))
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // the proof carries two more quotient commitments
+    let bytes = proof.encode();
+    assert_eq!(
+        bytes.len(),
+        Proof::<TatePairing>::encoded_size()
+            + Proof::<TatePairing>::quotient_parts_size()
+    );
+
+    let decoded = Proof::<TatePairing>::try_from(bytes.as_slice())
+        .expect("failed to decode proof");
+    assert_eq!(decoded, proof);
+    let decoded = Proof::<TatePairing>::decode(&mut bytes.as_slice())
+        .expect("failed to decode proof");
+    assert_eq!(decoded, proof);
+    let mut reader = bytes.as_slice();
+    let decoded = Proof::<TatePairing>::decode_from_reader(&mut reader)
+        .expect("failed to read proof");
+    assert_eq!(decoded, proof);
+    assert!(reader.is_empty());
+
+    // the proof without its fifth and sixth parts is refused
+    let mut four = bytes[..Proof::<TatePairing>::encoded_size()].to_vec();
+    *four.last_mut().expect("the tag is encoded") = 0;
+    let four = Proof::<TatePairing>::try_from(four.as_slice())
+        .expect("failed to decode proof");
+    verifier
+        .verify(&four, &public_inputs)
+        .expect_err("the quotient parts are missing");
+
+    // the gate is enforced
+    let circuit = Pow5Circuit::new(x, pow5(x) + BlsScalar::one());
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrong power must not verify");
+}
+
+#[test]
+fn four_quotient_parts_keep_the_proof() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = Pow5Key::setup_for_circuit(&Pow5Circuit::default(), &mut rng)
+        .expect("failed to synthesize");
+
+    // the keys of four parts are the ones of the custom gates
+    let (prover, _) = Pow5Key::compile_with_custom_gates(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+        &POW5,
+    )
+    .expect("failed to compile circuit");
+    let (_, verifier) = Pow5Key::compile_with_quotient_parts(
+        &pp,
+        b"custom-gate",
+        &Pow5Circuit::default(),
+        &POW5,
+        QuotientParts::Four,
+    )
+    .expect("failed to compile circuit");
+
+    let x = BlsScalar::random(&mut rng);
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &Pow5Circuit::new(x, pow5(x)))
+        .expect("failed to prove");
+
+    assert_eq!(proof.encode().len(), Proof::<TatePairing>::encoded_size());
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
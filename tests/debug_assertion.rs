@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use jub_jub::JubjubAffine;
+use zkplonk::prelude::*;
+use zksnarks::constraint_system::ConstraintSystem;
+
+fn synthesize(expected: BlsScalar) -> Plonk<JubjubAffine> {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(3));
+    let b = composer.append_witness(BlsScalar::from(5));
+
+    let constraint = Constraint::default().mult(1).a(a).b(b);
+    let product = composer.gate_mul(constraint);
+
+    composer.append_debug_assertion(a, BlsScalar::from(3), "a");
+    composer.append_debug_assertion(product, expected, "product");
+
+    composer
+}
+
+#[test]
+fn debug_assertion_adds_no_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::from(3));
+
+    let m = ConstraintSystem::m(&composer);
+    composer.append_debug_assertion(a, BlsScalar::from(4), "a");
+
+    assert_eq!(ConstraintSystem::m(&composer), m);
+}
+
+#[test]
+fn debug_assertion_matching_is_silent() {
+    let composer = synthesize(BlsScalar::from(15));
+
+    assert!(composer.check_debug_assertions().is_empty());
+}
+
+#[test]
+#[cfg(feature = "debug-assertions")]
+fn debug_assertion_mismatch_is_reported() {
+    let composer = synthesize(BlsScalar::from(16));
+
+    let mismatches = composer.check_debug_assertions();
+
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].label, "product");
+    assert_eq!(mismatches[0].expected, BlsScalar::from(16));
+    assert_eq!(mismatches[0].actual, BlsScalar::from(15));
+    assert_eq!(mismatches[0].gate, ConstraintSystem::m(&composer));
+}
+
+#[test]
+#[cfg(not(feature = "debug-assertions"))]
+fn debug_assertion_disabled_is_noop() {
+    let composer = synthesize(BlsScalar::from(16));
+
+    assert!(composer.check_debug_assertions().is_empty());
+}
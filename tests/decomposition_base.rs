@@ -0,0 +1,184 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::{Decode, Encode};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Decomposes the public scalar into `N` digits in `BASE`
+#[derive(Debug, Default)]
+pub struct DigitsCircuit<const BASE: u8, const N: usize> {
+    a: BlsScalar,
+}
+
+impl<const BASE: u8, const N: usize> DigitsCircuit<BASE, N> {
+    pub fn new(a: BlsScalar) -> Self {
+        Self { a }
+    }
+}
+
+impl<const BASE: u8, const N: usize> Circuit<JubjubAffine>
+    for DigitsCircuit<BASE, N>
+{
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_public(self.a);
+        composer.component_decomposition_base::<N>(a, BASE);
+
+        Ok(())
+    }
+}
+
+fn keys<const BASE: u8, const N: usize>(
+    rng: &mut StdRng,
+) -> (JubjubProver, JubjubVerifier) {
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &DigitsCircuit::<BASE, N>::default(),
+        rng,
+    )
+    .expect("failed to synthesize");
+
+    PlonkKey::<TatePairing, JubjubAffine, DigitsCircuit<BASE, N>>::compile(&pp)
+        .expect("failed to compile circuit")
+}
+
+fn prove_and_verify<const BASE: u8, const N: usize>(
+    rng: &mut StdRng,
+    (prover, verifier): &(JubjubProver, JubjubVerifier),
+    a: BlsScalar,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, &DigitsCircuit::<BASE, N>::new(a))
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn decomposition_base_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    // the hexadecimal digits are the nibbles of the bytes
+    let a = BlsScalar::random(&mut rng);
+    let w = composer.append_witness(a);
+    let digits = composer.component_decomposition_base::<64>(w, 16);
+
+    for (i, digit) in digits.iter().enumerate() {
+        let byte = a.to_raw_bytes()[i / 2];
+        let nibble = (byte >> (4 * (i % 2))) & 0x0f;
+        assert_eq!(composer[*digit], BlsScalar::from(nibble as u64));
+    }
+
+    let w = composer.append_witness(BlsScalar::from(9_876_543_210u64));
+    let digits = composer.component_decomposition_base::<12>(w, 10);
+    let expected = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0];
+
+    for (digit, expected) in digits.iter().zip(expected.iter()) {
+        assert_eq!(composer[*digit], BlsScalar::from(*expected as u64));
+    }
+}
+
+#[test]
+fn decomposition_base_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::from(1234u64));
+
+    // a set of ten digits per digit, and a sum of four terms
+    let gates = composer.statistics().gates;
+    composer.component_decomposition_base::<4>(w, 10);
+    assert_eq!(composer.statistics().gates, gates + 4 * 10 + 2 + 1);
+
+    // a range check of four bits per digit
+    let gates = composer.statistics().gates;
+    composer.component_decomposition_base::<4>(w, 16);
+    assert_eq!(composer.statistics().gates, gates + 4 * 3 + 2 + 1);
+}
+
+#[test]
+fn decomposition_base_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let keys_4 = keys::<4, 128>(&mut rng);
+    let a = BlsScalar::random(&mut rng);
+    prove_and_verify::<4, 128>(&mut rng, &keys_4, a)
+        .expect("failed to verify proof");
+
+    // the maximum scalar fits 64 hexadecimal digits
+    let keys_16 = keys::<16, 64>(&mut rng);
+    for a in [BlsScalar::zero(), -BlsScalar::one()] {
+        prove_and_verify::<16, 64>(&mut rng, &keys_16, a)
+            .expect("failed to verify proof");
+    }
+
+    // and 77 decimal digits
+    let keys_10 = keys::<10, 77>(&mut rng);
+    for a in [BlsScalar::random(&mut rng), -BlsScalar::one()] {
+        prove_and_verify::<10, 77>(&mut rng, &keys_10, a)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn decomposition_base_high_digits() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let keys = keys::<10, 4>(&mut rng);
+
+    prove_and_verify::<10, 4>(&mut rng, &keys, BlsScalar::from(9999u64))
+        .expect("failed to verify proof");
+
+    // the digits above `N` aren't ignored, the scalar must fit them
+    let result =
+        prove_and_verify::<10, 4>(&mut rng, &keys, BlsScalar::from(10000u64));
+    assert!(result.is_err(), "the high digits must be zero");
+}
+
+#[test]
+fn decomposition_base_forged_digit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    fn forged<const BASE: u8>(rng: &mut StdRng) -> Result<(), Error> {
+        let (prover, verifier) = keys::<BASE, 4>(rng);
+        let circuit = DigitsCircuit::<BASE, 4>::new(BlsScalar::from(0x35u64));
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        circuit
+            .synthesize(&mut composer)
+            .expect("failed to synthesize");
+
+        // after the reserved zero and one, and the scalar, the gadget
+        // witnesses the digits; carrying the second digit into the first
+        // keeps the recomposition
+        let base = BlsScalar::from(BASE as u64);
+        let mut state = composer.serialize_witness_state();
+        for (i, offset) in [(0, base), (1, -BlsScalar::one())] {
+            let at = 30 + (3 + i) * 32;
+            let digit = BlsScalar::decode(&mut &state[at..at + 32])
+                .expect("the state holds scalars");
+            let digit = digit + offset;
+            state[at..at + 32].copy_from_slice(&digit.encode());
+        }
+        composer
+            .apply_witness_state(&state)
+            .expect("the state belongs to the composer");
+
+        prover
+            .create_proof_with_composer(rng, &composer)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+    }
+
+    assert!(
+        forged::<16>(&mut rng).is_err(),
+        "a forged digit must not verify"
+    );
+    assert!(
+        forged::<10>(&mut rng).is_err(),
+        "a forged digit must not verify"
+    );
+}
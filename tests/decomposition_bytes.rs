@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public values are the `N` low bytes of `a`
+#[derive(Debug)]
+pub struct BytesCircuit<const N: usize> {
+    a: BlsScalar,
+    bytes: [BlsScalar; N],
+}
+
+impl<const N: usize> BytesCircuit<N> {
+    pub fn new(a: BlsScalar) -> Self {
+        let raw = a.to_raw_bytes();
+        let bytes = core::array::from_fn(|i| BlsScalar::from(raw[i] as u64));
+
+        Self { a, bytes }
+    }
+}
+
+impl<const N: usize> Default for BytesCircuit<N> {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero())
+    }
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for BytesCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let bytes = composer.component_decomposition_bytes::<N>(a);
+
+        for (byte, expected) in bytes.iter().zip(self.bytes.iter()) {
+            let expected = composer.append_public(*expected);
+            composer.assert_equal(*byte, expected);
+        }
+
+        Ok(())
+    }
+}
+
+fn keys<const N: usize>(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &BytesCircuit::<N>::default(),
+        rng,
+    )
+    .expect("failed to synthesize");
+
+    PlonkKey::<TatePairing, JubjubAffine, BytesCircuit<N>>::compile(&pp)
+        .expect("failed to compile circuit")
+}
+
+#[test]
+fn decomposition_bytes_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = BlsScalar::random(&mut rng);
+    let w = composer.append_witness(a);
+    let bytes = composer.component_decomposition_bytes::<32>(w);
+
+    for (byte, raw) in bytes.iter().zip(a.to_raw_bytes().iter()) {
+        assert_eq!(composer[*byte], BlsScalar::from(*raw as u64));
+    }
+}
+
+#[test]
+fn decomposition_bytes_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let (prover, verifier) = keys::<32>(&mut rng);
+    let circuit = BytesCircuit::<32>::new(BlsScalar::random(&mut rng));
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    let (prover, verifier) = keys::<8>(&mut rng);
+    let circuit = BytesCircuit::<8>::new(BlsScalar::from(u64::MAX));
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn decomposition_bytes_high_bytes() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys::<8>(&mut rng);
+
+    // the bytes above `N` aren't ignored, the scalar must fit them
+    let a = BlsScalar::from(u64::MAX) + BlsScalar::one();
+    let result = prover
+        .create_proof(&mut rng, &BytesCircuit::<8>::new(a))
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "the high bytes must be zero");
+}
+
+#[test]
+fn decomposition_bytes_forged_byte() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys::<8>(&mut rng);
+
+    let mut circuit = BytesCircuit::<8>::new(BlsScalar::from(0x0123_4567u64));
+    circuit.bytes[1] += BlsScalar::one();
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged byte must not verify");
+}
@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::gadget::compression::{compress_point, decompress_point};
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the compressed point decompresses to the public point
+#[derive(Debug)]
+pub struct DecompressCircuit {
+    x: BlsScalar,
+    sign: BlsScalar,
+    point: JubjubAffine,
+}
+
+impl DecompressCircuit {
+    pub fn new(point: JubjubAffine) -> Self {
+        let (x, sign) = compress_point(point);
+
+        Self {
+            x,
+            sign: BlsScalar::from(sign as u64),
+            point,
+        }
+    }
+}
+
+impl Default for DecompressCircuit {
+    fn default() -> Self {
+        Self::new(JubjubAffine::ADDITIVE_GENERATOR)
+    }
+}
+
+impl Circuit<JubjubAffine> for DecompressCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let x = composer.append_witness(self.x);
+        let sign = composer.append_witness(self.sign);
+
+        let point = composer.component_decompress_point(x, sign);
+        composer.assert_equal_public_point(point, self.point);
+
+        Ok(())
+    }
+}
+
+type DecompressKey = PlonkKey<TatePairing, JubjubAffine, DecompressCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp =
+        DecompressKey::setup_for_circuit(&DecompressCircuit::default(), rng)
+            .expect("failed to synthesize");
+
+    DecompressKey::compile(&pp).expect("failed to compile circuit")
+}
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+#[test]
+fn decompress_point_round_trips() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for _ in 0..16 {
+        let point = random_point(&mut rng);
+        let (x, sign) = compress_point(point);
+
+        assert_eq!(decompress_point::<JubjubAffine>(x, sign), Some(point));
+        assert_eq!(
+            decompress_point::<JubjubAffine>(x, !sign),
+            Some(JubjubAffine::from_raw_unchecked(x, -point.get_y()))
+        );
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let w_x = composer.append_witness(x);
+        let w_sign = composer.append_witness(BlsScalar::from(sign as u64));
+        let w = composer.component_decompress_point(w_x, w_sign);
+
+        assert_eq!(composer[*w.x()], point.get_x());
+        assert_eq!(composer[*w.y()], point.get_y());
+    }
+}
+
+#[test]
+fn decompress_point_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let identity = JubjubAffine::ADDITIVE_IDENTITY;
+    for point in [identity, random_point(&mut rng), random_point(&mut rng)] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DecompressCircuit::new(point))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn decompress_point_flipped_sign() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let mut circuit = DecompressCircuit::new(random_point(&mut rng));
+    circuit.sign = BlsScalar::one() - circuit.sign;
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a flipped sign must not verify");
+}
+
+#[test]
+fn decompress_point_refuses_invalid_x() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let x = (0u64..)
+        .map(BlsScalar::from)
+        .find(|x| decompress_point::<JubjubAffine>(*x, false).is_none())
+        .expect("half of the coordinates have no point");
+
+    let circuit = DecompressCircuit {
+        x,
+        sign: BlsScalar::zero(),
+        point: JubjubAffine::ADDITIVE_GENERATOR,
+    };
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "an x without a point must not verify");
+}
+
+#[test]
+fn decompress_point_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let (x, sign) = compress_point(JubjubAffine::ADDITIVE_GENERATOR);
+    let x = composer.append_witness(x);
+    let sign = composer.append_witness(BlsScalar::from(sign as u64));
+
+    let gates = composer.statistics().gates;
+    composer.component_parity(x);
+    let parity = composer.statistics().gates - gates;
+
+    let gates = composer.statistics().gates;
+    composer.component_decompress_point(x, sign);
+    assert_eq!(composer.statistics().gates, gates + 4 + parity);
+}
@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::DivisionByZero;
+
+/// Asserts the public value is `a / b`
+#[derive(Debug)]
+pub struct DivCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    quotient: BlsScalar,
+}
+
+impl DivCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar, quotient: BlsScalar) -> Self {
+        Self { a, b, quotient }
+    }
+}
+
+impl Default for DivCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::one(), BlsScalar::one(), BlsScalar::one())
+    }
+}
+
+impl Circuit<JubjubAffine> for DivCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let quotient = composer.append_public(self.quotient);
+
+        let x = composer.component_div(a, b)?;
+        composer.assert_equal(x, quotient);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn div_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &DivCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DivCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let q = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &DivCircuit::new(q * b, b, q))
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // a tampered quotient
+    let circuit = DivCircuit::new(q * b, b, q + BlsScalar::one());
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+
+    // a zero divisor is refused before proving
+    let circuit = DivCircuit::new(q, BlsScalar::zero(), q);
+    assert!(matches!(
+        prover.create_proof(&mut rng, &circuit),
+        Err(Error::ProofVerificationError)
+    ));
+}
+
+#[test]
+fn div_by_zero() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(6u64));
+    let b = composer.append_witness(BlsScalar::from(3u64));
+    let zero = composer.append_witness(BlsScalar::zero());
+
+    let gates = composer.statistics().gates;
+    let o = composer.component_div(a, b).expect("3 isn't zero");
+    assert_eq!(composer[o], BlsScalar::from(2u64));
+    assert_eq!(composer.statistics().gates, gates + 1);
+
+    assert_eq!(
+        composer.component_div(a, zero),
+        Err(DivisionByZero { wire: zero.index() })
+    );
+    assert_eq!(composer.statistics().gates, gates + 1);
+}
@@ -0,0 +1,151 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkplonk::ZeroDivisor;
+
+const DIVISOR: u64 = 86_400;
+const BITS: usize = 48;
+
+/// Asserts the public values are the quotient and the remainder of the
+/// secret value by the constant divisor
+#[derive(Debug, Default)]
+pub struct DivRemCircuit {
+    a: BlsScalar,
+    q: BlsScalar,
+    r: BlsScalar,
+}
+
+impl DivRemCircuit {
+    pub fn new(a: u64) -> Self {
+        Self {
+            a: BlsScalar::from(a),
+            q: BlsScalar::from(a / DIVISOR),
+            r: BlsScalar::from(a % DIVISOR),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for DivRemCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let (q, r) = composer
+            .component_div_rem(a, DIVISOR, BITS)
+            .expect("the divisor isn't zero");
+
+        let public_q = composer.append_public(self.q);
+        let public_r = composer.append_public(self.r);
+        composer.assert_equal(q, public_q);
+        composer.assert_equal(r, public_r);
+
+        Ok(())
+    }
+}
+
+type DivRemKey = PlonkKey<TatePairing, JubjubAffine, DivRemCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = DivRemKey::setup_for_circuit(&DivRemCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    DivRemKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn div_rem_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let timestamp = rng.next_u64() % (1 << 40);
+    for a in [
+        0,
+        DIVISOR,
+        DIVISOR * 12_345,
+        DIVISOR - 1,
+        DIVISOR * 7 + DIVISOR - 1,
+        timestamp,
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DivRemCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn div_rem_values() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    for (a, divisor) in [(0, 1), (17, 1), (17, 5), (u64::MAX, u64::MAX), (3, 8)]
+    {
+        let w = composer.append_witness(BlsScalar::from(a));
+        let (q, r) = composer
+            .component_div_rem(w, divisor, 64)
+            .expect("the divisor isn't zero");
+
+        assert_eq!(composer[q], BlsScalar::from(a / divisor));
+        assert_eq!(composer[r], BlsScalar::from(a % divisor));
+    }
+}
+
+#[test]
+fn div_rem_rejects_zero_divisor() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::from(42u64));
+
+    let gates = composer.statistics().gates;
+    let result = composer.component_div_rem(a, 0, 64);
+    assert!(matches!(result, Err(ZeroDivisor)));
+    assert_eq!(composer.statistics().gates, gates);
+}
+
+#[test]
+fn div_rem_refuses_unreduced_remainder() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // `a = (q - 1) · divisor + (r + divisor)` holds, but the remainder isn't
+    // below the divisor
+    let a = DIVISOR * 3 + 5;
+    let q = BlsScalar::from(2u64);
+    let r = BlsScalar::from(DIVISOR + 5);
+    let circuit = DivRemCircuit {
+        a: BlsScalar::from(a),
+        q,
+        r,
+    };
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and `a`, the gadget witnesses the
+    // quotient and then the remainder
+    let mut state = composer.serialize_witness_state();
+    for (witness, value) in [(3, q), (4, r)] {
+        let offset = 30 + witness * 32;
+        state[offset..offset + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "an unreduced remainder must not verify");
+}
@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::{Decode, Encode};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::LengthMismatch;
+
+/// Asserts the public value is the inner product of two vectors of `N`
+/// witnesses
+#[derive(Debug)]
+pub struct DotCircuit<const N: usize> {
+    a: [BlsScalar; N],
+    b: [BlsScalar; N],
+    dot: BlsScalar,
+}
+
+impl<const N: usize> DotCircuit<N> {
+    pub fn random(rng: &mut StdRng) -> Self {
+        let a = [(); N].map(|_| BlsScalar::random(&mut *rng));
+        let b = [(); N].map(|_| BlsScalar::random(&mut *rng));
+        let dot = a
+            .iter()
+            .zip(b.iter())
+            .fold(BlsScalar::zero(), |acc, (a, b)| acc + a * b);
+
+        Self { a, b, dot }
+    }
+}
+
+impl<const N: usize> Default for DotCircuit<N> {
+    fn default() -> Self {
+        Self {
+            a: [BlsScalar::zero(); N],
+            b: [BlsScalar::zero(); N],
+            dot: BlsScalar::zero(),
+        }
+    }
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for DotCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = self.a.map(|a| composer.append_witness(a));
+        let b = self.b.map(|b| composer.append_witness(b));
+        let dot = composer.append_public(self.dot);
+
+        let x = composer
+            .component_dot(&a, &b)
+            .expect("the vectors have the same length");
+        composer.assert_equal(x, dot);
+
+        Ok(())
+    }
+}
+
+fn dot_works<const N: usize>() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &DotCircuit::<N>::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DotCircuit<N>>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = DotCircuit::<N>::random(&mut rng);
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn dot_empty() {
+    dot_works::<0>();
+}
+
+#[test]
+fn dot_single() {
+    dot_works::<1>();
+}
+
+#[test]
+fn dot_long() {
+    dot_works::<1000>();
+}
+
+#[test]
+fn dot_gates_and_lengths() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = [2u64, 3, 5].map(|x| composer.append_witness(BlsScalar::from(x)));
+    let b = [7u64, 11, 13].map(|x| composer.append_witness(BlsScalar::from(x)));
+
+    let gates = composer.statistics().gates;
+    let dot = composer.component_dot(&a, &b).expect("same length");
+    assert_eq!(composer[dot], BlsScalar::from(14u64 + 33 + 65));
+    assert_eq!(composer.statistics().gates, gates + 3);
+
+    assert_eq!(
+        composer.component_dot(&a, &b[..2]),
+        Err(LengthMismatch { left: 3, right: 2 })
+    );
+    assert_eq!(composer.statistics().gates, gates + 3);
+}
+
+#[test]
+fn dot_refuses_forged_accumulator() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &DotCircuit::<8>::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DotCircuit<8>>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = DotCircuit::<8>::random(&mut rng);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // the first running sum is the witness after the vectors and the
+    // public dot product; bump it through the witness state
+    let stats = composer.statistics();
+    let mut state = composer.serialize_witness_state();
+    let accumulator = stats.witnesses - 8;
+    let offset = 30 + accumulator * 32;
+
+    let value = BlsScalar::decode(&mut &state[offset..offset + 32])
+        .expect("the state holds encoded scalars");
+    let forged = value + BlsScalar::one();
+    state[offset..offset + 32].copy_from_slice(&forged.encode());
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged running sum must not verify");
+}
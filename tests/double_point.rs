@@ -0,0 +1,123 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the doubling of a point is the public point, and matches its
+/// addition with itself
+#[derive(Debug)]
+pub struct DoubleCircuit {
+    a: JubjubAffine,
+    doubled: JubjubAffine,
+}
+
+impl DoubleCircuit {
+    pub fn new(a: JubjubAffine) -> Self {
+        Self {
+            a,
+            doubled: (a + a).into(),
+        }
+    }
+}
+
+impl Default for DoubleCircuit {
+    fn default() -> Self {
+        Self::new(JubjubAffine::ADDITIVE_GENERATOR)
+    }
+}
+
+impl Circuit<JubjubAffine> for DoubleCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_point(self.a);
+
+        let doubled = composer.component_double_point(a);
+        composer.assert_equal_public_point(doubled, self.doubled);
+
+        let added = composer.component_add_point(a, a);
+        composer.assert_equal_point(doubled, added);
+
+        Ok(())
+    }
+}
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+#[test]
+fn double_point_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &DoubleCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DoubleCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let mut points = vec![JubjubAffine::ADDITIVE_IDENTITY];
+    points.extend((0..4).map(|_| random_point(&mut rng)));
+
+    for a in points {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DoubleCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // a doubling claimed to be the point itself
+    let a = random_point(&mut rng);
+    let circuit = DoubleCircuit { a, doubled: a };
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrong doubling must not verify");
+}
+
+#[test]
+fn double_point_gates() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = random_point(&mut rng);
+    let point = composer.append_point(a);
+
+    let gates = composer.statistics().gates;
+    let doubled = composer.component_double_point(point);
+    assert_eq!(composer.statistics().gates, gates + 2);
+
+    let expected: JubjubAffine = (a + a).into();
+    assert_eq!(composer[*doubled.x()], expected.get_x());
+    assert_eq!(composer[*doubled.y()], expected.get_y());
+
+    let bit = composer.append_witness(BlsScalar::one());
+    let gates = composer.statistics().gates;
+    composer.component_select_identity(bit, point);
+    let select = composer.statistics().gates - gates;
+
+    // the decomposition, and a doubling, a selection and an addition per bit
+    // but the top one, which selects the point without any curve operation
+    let scalar = composer.append_witness(BlsScalar::from(5u64));
+    let gates = composer.statistics().gates;
+    composer.component_mul_point(scalar, point);
+    assert_eq!(
+        composer.statistics().gates - gates,
+        2 * 252 + 1 + 251 * (2 + select + 2) + select
+    );
+}
@@ -17,6 +17,7 @@ use zkstd::common::Group;
 use zkstd::common::TwistedEdwardsCurve;
 
 #[test]
+#[allow(deprecated)]
 fn mul_generator_works() {
     let mut rng = StdRng::seed_from_u64(8349u64);
 
@@ -316,3 +317,16 @@ fn mul_point_works() {
             .expect_err("circuit is not satisfied");
     }
 }
+
+#[test]
+#[allow(deprecated)]
+fn mul_generator_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(JubjubScalar::from(7u64));
+
+    let gates = composer.statistics().gates;
+    composer
+        .component_mul_generator(a, JubjubAffine::ADDITIVE_GENERATOR)
+        .expect("the wNAF is valid");
+    assert_eq!(composer.statistics().gates, gates + 260);
+}
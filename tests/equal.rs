@@ -0,0 +1,151 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Selects `a` if `a == b` and zero otherwise, and asserts the bit is the
+/// public `eq`
+#[derive(Debug)]
+pub struct EqualCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    eq: BlsScalar,
+    selected: BlsScalar,
+}
+
+impl EqualCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        let (eq, selected) = match a == b {
+            true => (BlsScalar::one(), a),
+            false => (BlsScalar::zero(), BlsScalar::zero()),
+        };
+
+        Self { a, b, eq, selected }
+    }
+}
+
+impl Default for EqualCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero(), BlsScalar::zero())
+    }
+}
+
+impl Circuit<JubjubAffine> for EqualCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let eq = composer.append_public(self.eq);
+        let selected = composer.append_public(self.selected);
+
+        let x = composer.component_equal(a, b);
+        composer.assert_equal(x, eq);
+
+        let x = composer.component_select_zero(x, a);
+        composer.assert_equal(x, selected);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn component_equal_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, EqualCircuit>::setup_for_circuit(
+            &EqualCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, EqualCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+
+    // equal values
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &EqualCircuit::new(a, a))
+        .expect("failed to prove");
+    assert_eq!(public_inputs[0], BlsScalar::one());
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // unequal values
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &EqualCircuit::new(a, b))
+        .expect("failed to prove");
+    assert_eq!(public_inputs[0], BlsScalar::zero());
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn component_equal_refuses_false_claims() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, EqualCircuit>::setup_for_circuit(
+            &EqualCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, EqualCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+
+    // claim distinct values are equal
+    let mut circuit = EqualCircuit::new(a, b);
+    circuit.eq = BlsScalar::one();
+    circuit.selected = a;
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+
+    // claim equal values are distinct
+    let mut circuit = EqualCircuit::new(a, a);
+    circuit.eq = BlsScalar::zero();
+    circuit.selected = BlsScalar::zero();
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+}
+
+#[test]
+fn component_equal_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(5u64));
+    let b = composer.append_witness(BlsScalar::from(7u64));
+
+    let gates = composer.statistics().gates;
+    let eq = composer.component_equal(a, b);
+    assert_eq!(composer.statistics().gates, gates + 3);
+    assert_eq!(composer[eq], BlsScalar::zero());
+
+    let eq = composer.component_equal(a, a);
+    assert_eq!(composer[eq], BlsScalar::one());
+}
@@ -0,0 +1,77 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "examples")]
+
+use zkplonk::examples::{
+    compress, run_example, ExampleError, MerkleCircuit, EXAMPLES, MERKLE_DEPTH,
+};
+use zkplonk::prelude::*;
+
+/// Asserts the gates of the example are within `bounds`, so a gadget that
+/// gets more expensive is noticed
+fn assert_gates(name: &str, bounds: core::ops::RangeInclusive<usize>) {
+    let report = run_example(name).expect("the example must run");
+
+    assert!(
+        bounds.contains(&report.stats.gates),
+        "the `{}` example has {} gates, expected {:?}",
+        name,
+        report.stats.gates,
+        bounds
+    );
+
+    // the params are the smallest that fit the circuit
+    assert!(report.stats.gates < 1 << report.domain_log2);
+}
+
+#[test]
+fn examples_run() {
+    for name in EXAMPLES {
+        run_example(name).expect("the example must run");
+    }
+}
+
+#[test]
+fn transfer_gates() {
+    assert_gates("transfer", 1000..=1200);
+}
+
+#[test]
+fn merkle_gates() {
+    assert_gates("merkle", 130..=180);
+}
+
+#[test]
+fn schnorr_gates() {
+    assert_gates("schnorr", 3000..=3600);
+}
+
+#[test]
+fn merkle_root() {
+    let leaf = BlsScalar::from(5u64);
+    let siblings = [BlsScalar::from(3u64); MERKLE_DEPTH];
+
+    // the leftmost leaf is always the left child
+    let expected = siblings.iter().fold(leaf, |node, s| compress(node, *s));
+    assert_eq!(MerkleCircuit::new(leaf, siblings, 0).root(), expected);
+
+    // the rightmost leaf is always the right child
+    let expected = siblings.iter().fold(leaf, |node, s| compress(*s, node));
+    let position = (1 << MERKLE_DEPTH) - 1;
+    assert_eq!(
+        MerkleCircuit::new(leaf, siblings, position).root(),
+        expected
+    );
+}
+
+#[test]
+fn unknown_example() {
+    assert!(matches!(
+        run_example("sudoku"),
+        Err(ExampleError::Unknown(name)) if name == "sudoku"
+    ));
+}
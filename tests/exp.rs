@@ -0,0 +1,254 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use jub_jub::JubjubAffine;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+const EXPONENTS: [u64; 6] = [2, 3, 5, 7, 17, 65537];
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    x: BlsScalar,
+    e: u64,
+    result: BlsScalar,
+    bits: bool,
+}
+
+impl DummyCircuit {
+    pub fn new(x: BlsScalar, e: u64, bits: bool) -> Self {
+        Self {
+            x,
+            e,
+            result: x.pow(e),
+            bits,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let x = composer.append_witness(self.x);
+
+        let result = match self.bits {
+            true => {
+                let e = composer.append_witness(BlsScalar::from(self.e));
+                let bits = composer.component_decomposition::<17>(e);
+
+                composer.component_exp_bits(x, &bits)
+            }
+            false => composer.component_exp_const(x, self.e),
+        };
+
+        let expected = composer.append_public(self.result);
+        composer.assert_equal(result, expected);
+
+        Ok(())
+    }
+}
+
+fn exp_works(rng: &mut StdRng, e: u64, bits: bool) {
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut *rng);
+
+    let default = DummyCircuit::new(BlsScalar::zero(), e, bits);
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, b"exp", &default)
+            .expect("failed to compile circuit");
+
+    // native exponentiation matches
+    {
+        let circuit = DummyCircuit::new(BlsScalar::random(&mut *rng), e, bits);
+
+        let (proof, public_inputs) =
+            prover.create_proof(rng, &circuit).expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // a perturbed power fails
+    {
+        let mut circuit =
+            DummyCircuit::new(BlsScalar::random(&mut *rng), e, bits);
+        circuit.result += BlsScalar::one();
+
+        let result = prover
+            .create_proof(rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+
+        assert!(result.is_err(), "perturbed power must not verify");
+    }
+}
+
+#[test]
+fn exp_const_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for e in EXPONENTS {
+        exp_works(&mut rng, e, false);
+    }
+}
+
+#[test]
+fn exp_bits_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for e in EXPONENTS {
+        exp_works(&mut rng, e, true);
+    }
+}
+
+#[test]
+fn exp_const_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let x = BlsScalar::random(&mut rng);
+    let w = composer.append_witness(x);
+
+    for e in EXPONENTS.into_iter().chain([11, 255, 1 << 20, u64::MAX]) {
+        let result = composer.component_exp_const(w, e);
+        assert_eq!(composer[result], x.pow(e), "x^{} mismatch", e);
+    }
+}
+
+#[test]
+fn exp_const_gate_count() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let x = composer.append_witness(BlsScalar::from(3u64));
+
+    let m = ConstraintSystem::m(&composer);
+    let result = composer.component_exp_const(x, 5);
+    assert!(ConstraintSystem::m(&composer) - m <= 3);
+    assert_eq!(composer[result], BlsScalar::from(243u64));
+
+    // the trivial exponents append no gate
+    let m = ConstraintSystem::m(&composer);
+
+    let result = composer.component_exp_const(x, 0);
+    assert_eq!(composer[result], BlsScalar::one());
+
+    let result = composer.component_exp_const(x, 1);
+    assert_eq!(result, x);
+
+    assert_eq!(ConstraintSystem::m(&composer), m);
+}
+
+#[test]
+fn exp_bits_gate_count() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let x = composer.append_witness(BlsScalar::from(3u64));
+    let e = composer.append_witness(BlsScalar::from(5u64));
+    let bits = composer.component_decomposition::<3>(e);
+
+    let m = ConstraintSystem::m(&composer);
+    let result = composer.component_exp_bits(x, &bits);
+
+    assert_eq!(ConstraintSystem::m(&composer) - m, 3 * 3 - 2);
+    assert_eq!(composer[result], BlsScalar::from(243u64));
+}
+
+/// Asserts the public value is `x^e` for a 16 bits witness `e`
+#[derive(Debug, Default)]
+pub struct ExpCircuit {
+    x: BlsScalar,
+    e: BlsScalar,
+    result: BlsScalar,
+}
+
+impl ExpCircuit {
+    pub fn new(x: BlsScalar, e: u64) -> Self {
+        Self {
+            x,
+            e: BlsScalar::from(e),
+            result: x.pow(e),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for ExpCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let x = composer.append_witness(self.x);
+        let e = composer.append_witness(self.e);
+
+        let result = composer.component_exp::<16>(x, e);
+
+        let expected = composer.append_public(self.result);
+        composer.assert_equal(result, expected);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn exp_witness_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, b"exp", &ExpCircuit::default())
+            .expect("failed to compile circuit");
+
+    for e in [0, 1, 5, (1 << 16) - 1] {
+        let circuit = ExpCircuit::new(BlsScalar::random(&mut rng), e);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // a perturbed power fails
+    let mut circuit = ExpCircuit::new(BlsScalar::random(&mut rng), 17);
+    circuit.result += BlsScalar::one();
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "perturbed power must not verify");
+
+    // an exponent wider than the bits fails, even with the right power
+    let x = BlsScalar::random(&mut rng);
+    let circuit = ExpCircuit::new(x, 1 << 16);
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "the exponent doesn't fit the bits");
+}
+
+#[test]
+fn exp_witness_gate_count() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let x = composer.append_witness(BlsScalar::from(3u64));
+    let e = composer.append_witness(BlsScalar::from(5u64));
+
+    let m = ConstraintSystem::m(&composer);
+    let result = composer.component_exp::<3>(x, e);
+
+    assert_eq!(ConstraintSystem::m(&composer) - m, 5 * 3 - 1);
+    assert_eq!(composer[result], BlsScalar::from(243u64));
+}
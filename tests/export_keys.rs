@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "export-keys")]
+
+use codec::Encode;
+use ec_pairing::TatePairing;
+use jub_jub::JubjubAffine;
+use poly_commit::Fft;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+const BITS: usize = 64;
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        composer.append_logic_xor(w_a, w_b, BITS);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn export_selectors_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+    let (prover, _) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    let view = prover.selectors();
+    let n = view.n;
+
+    let names: Vec<_> = view.selectors.iter().map(|s| s.name).collect();
+    assert_eq!(
+        names,
+        [
+            "q_m",
+            "q_l",
+            "q_r",
+            "q_o",
+            "q_c",
+            "q_4",
+            "q_arith",
+            "q_range",
+            "q_logic",
+            "q_fixed_group_add",
+            "q_variable_group_add",
+            "s_sigma_1",
+            "s_sigma_2",
+            "s_sigma_3",
+            "s_sigma_4",
+        ]
+    );
+
+    view.selectors
+        .iter()
+        .for_each(|s| assert_eq!(s.evaluations.len(), n));
+
+    let nonzero = |name| {
+        view.get(name)
+            .expect("selector is exported")
+            .evaluations
+            .iter()
+            .filter(|e| **e != BlsScalar::zero())
+            .count()
+    };
+
+    // one logic gate per two bits, and the widgets that weren't used
+    assert_eq!(nonzero("q_logic"), BITS / 2);
+    assert_eq!(nonzero("q_range"), 0);
+    assert_eq!(nonzero("q_fixed_group_add"), 0);
+    assert_eq!(nonzero("q_variable_group_add"), 0);
+
+    // logic and arithmetic gates never share a row
+    let q_logic = &view.get("q_logic").unwrap().evaluations;
+    let q_arith = &view.get("q_arith").unwrap().evaluations;
+    q_logic.iter().zip(q_arith.iter()).for_each(|(l, a)| {
+        assert!(*l == BlsScalar::zero() || *a == BlsScalar::zero())
+    });
+
+    // the sigma mappings are a permutation of the wire positions k·ω^i
+    let fft = Fft::<BlsScalar>::new(n.trailing_zeros() as usize);
+    let ks = [1u64, 7, 13, 17].map(BlsScalar::from);
+
+    let mut identity: Vec<_> = ks
+        .iter()
+        .flat_map(|k| fft.elements.iter().map(move |root| (*k * root).encode()))
+        .collect();
+    let mut sigmas: Vec<_> = prover
+        .sigma_mappings()
+        .iter()
+        .flat_map(|sigma| sigma.iter().map(|s| s.encode()))
+        .collect();
+
+    assert_eq!(sigmas.len(), 4 * n);
+
+    identity.sort();
+    sigmas.sort();
+    assert_eq!(identity, sigmas);
+}
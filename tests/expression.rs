@@ -0,0 +1,258 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+
+/// Term of an expression, over the indexes of the witnesses: linear if it
+/// has a single index, and a product otherwise
+type Term = (BlsScalar, usize, Option<usize>);
+
+/// Evaluates the expression, asserts it equals the public result, and
+/// asserts the expression minus the result is zero
+#[derive(Debug, Clone)]
+pub struct ExpressionCircuit {
+    terms: Vec<Term>,
+    values: Vec<BlsScalar>,
+    constant: BlsScalar,
+    result: BlsScalar,
+}
+
+impl ExpressionCircuit {
+    /// Expression of `len` terms over `len / 2 + 1` witnesses, so some
+    /// wires repeat, with every third term a product
+    pub fn random(rng: &mut StdRng, len: usize) -> Self {
+        let width = len / 2 + 1;
+        let terms = (0..len)
+            .map(|i| {
+                let coefficient = BlsScalar::random(&mut *rng);
+                let a = (rng.next_u64() as usize) % width;
+                let b = (rng.next_u64() as usize) % width;
+
+                (coefficient, a, (i % 3 == 2).then_some(b))
+            })
+            .collect();
+        let values = (0..width).map(|_| BlsScalar::random(&mut *rng)).collect();
+        let constant = BlsScalar::random(&mut *rng);
+
+        Self::new(terms, values, constant)
+    }
+
+    pub fn new(
+        terms: Vec<Term>,
+        values: Vec<BlsScalar>,
+        constant: BlsScalar,
+    ) -> Self {
+        let result = terms.iter().fold(constant, |acc, (q, a, b)| match b {
+            Some(b) => acc + q * values[*a] * values[*b],
+            None => acc + q * values[*a],
+        });
+
+        Self {
+            terms,
+            values,
+            constant,
+            result,
+        }
+    }
+}
+
+impl Default for ExpressionCircuit {
+    fn default() -> Self {
+        Self::new(vec![], vec![], BlsScalar::zero())
+    }
+}
+
+impl Circuit<JubjubAffine> for ExpressionCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let values: Vec<_> = self
+            .values
+            .iter()
+            .map(|v| composer.append_witness(*v))
+            .collect();
+        let result = composer.append_public(self.result);
+
+        let expr = self.terms.iter().fold(
+            Expression::new().constant(self.constant),
+            |expr, (q, a, b)| match b {
+                Some(b) => expr.product(*q, values[*a], values[*b]),
+                None => expr.term(*q, values[*a]),
+            },
+        );
+
+        let x = composer.evaluate(expr.clone());
+        composer.assert_equal(x, result);
+
+        composer.enforce_zero(expr.term(-BlsScalar::one(), result));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn expression_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    for len in 0..=20 {
+        let circuit = ExpressionCircuit::random(&mut rng, len);
+        let (prover, verifier) =
+            PlonkKey::compile_with_circuit(&mut pp, b"expression", &circuit)
+                .expect("failed to compile circuit");
+
+        let values = circuit
+            .values
+            .iter()
+            .map(|_| BlsScalar::random(&mut rng))
+            .collect();
+        let circuit =
+            ExpressionCircuit::new(circuit.terms, values, circuit.constant);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // a wrong result is refused
+        let mut circuit = circuit;
+        circuit.result += BlsScalar::one();
+
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a wrong result must not verify");
+    }
+}
+
+#[test]
+fn expression_constant() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    let circuit = ExpressionCircuit::new(vec![], vec![], BlsScalar::from(5u64));
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, b"expression", &circuit)
+            .expect("failed to compile circuit");
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let gates = composer.statistics().gates;
+    let x = composer.evaluate(Expression::new().constant(5u64));
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[x], BlsScalar::from(5u64));
+
+    let x = composer.evaluate(Expression::new());
+    assert_eq!(composer[x], BlsScalar::zero());
+
+    let gates = composer.statistics().gates;
+    composer.enforce_zero(Expression::new());
+    assert_eq!(composer.statistics().gates, gates + 1);
+}
+
+#[test]
+fn expression_merges_terms() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let b = composer.append_witness(BlsScalar::from(5u64));
+
+    // 2a + 3a = 5a
+    let gates = composer.statistics().gates;
+    let x = composer.evaluate(Expression::new().term(2u64, a).term(3u64, a));
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[x], BlsScalar::from(15u64));
+
+    // the terms cancelling out are dropped
+    let gates = composer.statistics().gates;
+    let expr = Expression::new()
+        .term(1u64, a)
+        .term(-BlsScalar::one(), a)
+        .constant(4u64);
+    let x = composer.evaluate(expr);
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[x], BlsScalar::from(4u64));
+
+    // a · b and b · a are the same product, and its factors take the linear
+    // terms on them: 7ab + a + b in a single gate
+    let gates = composer.statistics().gates;
+    let expr = Expression::new()
+        .product(3u64, a, b)
+        .product(4u64, b, a)
+        .term(1u64, a)
+        .term(1u64, b);
+    let x = composer.evaluate(expr);
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[x], BlsScalar::from(7 * 15 + 3 + 5u64));
+
+    // a² + 2a
+    let x =
+        composer.evaluate(Expression::new().product(1u64, a, a).term(2u64, a));
+    assert_eq!(composer[x], BlsScalar::from(15u64));
+}
+
+#[test]
+fn expression_gates() {
+    /// Gates of evaluating and asserting `len` distinct linear terms
+    fn gates(len: usize) -> (usize, usize) {
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let expr = (0..len).fold(Expression::new(), |expr, i| {
+            let w = composer.append_witness(BlsScalar::from(i as u64));
+            expr.term(2u64, w)
+        });
+
+        let m = composer.statistics().gates;
+        let sum = composer.evaluate(expr.clone().constant(7u64));
+
+        let expected = (0..len as u64).sum::<u64>() * 2 + 7;
+        assert_eq!(composer[sum], BlsScalar::from(expected));
+
+        let n = composer.statistics().gates;
+        composer.enforce_zero(expr);
+
+        (n - m, composer.statistics().gates - n)
+    }
+
+    // the evaluation packs like `gate_sum`, and the assertion takes one more
+    // term in the output wire of its last gate
+    assert_eq!(gates(0), (1, 1));
+    assert_eq!(gates(1), (1, 1));
+    assert_eq!(gates(3), (1, 1));
+    assert_eq!(gates(4), (2, 1));
+    assert_eq!(gates(5), (2, 2));
+    assert_eq!(gates(6), (3, 2));
+    assert_eq!(gates(100), (1 + 49, 1 + 48));
+
+    // one gate per product
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w: Vec<_> = (0..6)
+        .map(|i| composer.append_witness(BlsScalar::from(i as u64)))
+        .collect();
+    let expr = Expression::new()
+        .product(1u64, w[0], w[1])
+        .product(1u64, w[2], w[3])
+        .product(1u64, w[4], w[5]);
+
+    let gates = composer.statistics().gates;
+    let x = composer.evaluate(expr);
+    assert_eq!(composer.statistics().gates, gates + 3);
+    assert_eq!(composer[x], BlsScalar::from(26u64));
+}
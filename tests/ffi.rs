@@ -0,0 +1,90 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "wasm")]
+
+use codec::{Decode, Encode};
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::ffi::{self, FfiError};
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+#[derive(Debug, Default, Encode, Decode)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    ab: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_ab = composer.append_public(self.ab);
+
+        let constraint = Constraint::default().mult(1).a(w_a).b(w_b);
+        let product = composer.gate_mul(constraint);
+
+        composer.assert_equal(product, w_ab);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn ffi_byte_api_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    ffi::register::<DummyCircuit>("dummy").expect("failed to register");
+    assert!(matches!(
+        ffi::register::<DummyCircuit>("dummy"),
+        Err(FfiError::DuplicateCircuit(_))
+    ));
+
+    let params = PlonkParams::<TatePairing>::setup(5, &mut rng).encode();
+
+    let (prover, verifier) =
+        ffi::compile(&params, "dummy").expect("failed to compile");
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let witness = DummyCircuit { a, b, ab: a * b }.encode();
+    let seed = [7u8; ffi::SEED_SIZE];
+
+    let (proof, public_inputs) =
+        ffi::prove(&prover, &witness, &seed).expect("failed to prove");
+
+    assert!(ffi::verify(&verifier, &proof, &public_inputs)
+        .expect("failed to decode"));
+
+    // a different statement is well formed but invalid
+    let other = vec![BlsScalar::random(&mut rng)].encode();
+    assert!(!ffi::verify(&verifier, &proof, &other).expect("failed to decode"));
+
+    // the errors are reported as strings across the boundary
+    let error: String = ffi::compile(&params, "unknown")
+        .expect_err("unknown circuit must fail")
+        .into();
+    assert_eq!(error, "unknown circuit `unknown`");
+
+    // prover and verifier bytes can't be swapped
+    ffi::verify(&prover, &proof, &public_inputs)
+        .expect_err("prover bytes must not verify");
+    ffi::prove(&prover, &witness, &seed[1..])
+        .expect_err("short seed must fail");
+    ffi::prove(&prover, &witness[1..], &seed)
+        .expect_err("malformed witness must fail");
+}
@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+// the table is read by the unconstrained fixed-base multiplication
+#![allow(deprecated)]
+
+use std::time::Instant;
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Multiplies the generator by every scalar, reading the multiples from a
+/// shared table if `cached` is set
+#[derive(Debug)]
+pub struct TableCircuit {
+    scalars: [JubjubScalar; 4],
+    cached: bool,
+}
+
+impl TableCircuit {
+    pub fn new(scalars: [JubjubScalar; 4], cached: bool) -> Self {
+        Self { scalars, cached }
+    }
+}
+
+impl Default for TableCircuit {
+    fn default() -> Self {
+        Self::new([JubjubScalar::one(); 4], false)
+    }
+}
+
+impl Circuit<JubjubAffine> for TableCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let table = FixedBaseTable::new(JubjubAffine::ADDITIVE_GENERATOR);
+
+        for scalar in self.scalars {
+            let w = composer.append_witness(scalar);
+            let point = match self.cached {
+                true => {
+                    composer.component_mul_generator_with_table(w, &table)?
+                }
+                false => composer.component_mul_generator(
+                    w,
+                    JubjubAffine::ADDITIVE_GENERATOR,
+                )?,
+            };
+
+            let expected = JubjubAffine::ADDITIVE_GENERATOR * scalar;
+            composer.assert_equal_public_point(point, expected);
+        }
+
+        Ok(())
+    }
+}
+
+type TableKey = PlonkKey<TatePairing, JubjubAffine, TableCircuit>;
+
+#[test]
+fn fixed_base_table_proofs_are_identical() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = TableKey::setup_for_circuit(&TableCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        TableKey::compile(&pp).expect("failed to compile circuit");
+
+    let scalars = [
+        JubjubScalar::zero(),
+        -JubjubScalar::one(),
+        JubjubScalar::random(&mut rng),
+        JubjubScalar::random(&mut rng),
+    ];
+
+    // the blinding factors are drawn from the same seed, so the proofs only
+    // differ if the circuits do
+    let (proof, public_inputs) = prover
+        .create_proof(
+            &mut StdRng::seed_from_u64(1),
+            &TableCircuit::new(scalars, false),
+        )
+        .expect("failed to prove");
+    let (cached_proof, cached_public_inputs) = prover
+        .create_proof(
+            &mut StdRng::seed_from_u64(1),
+            &TableCircuit::new(scalars, true),
+        )
+        .expect("failed to prove");
+
+    assert_eq!(proof.encode(), cached_proof.encode());
+    assert_eq!(public_inputs, cached_public_inputs);
+
+    verifier
+        .verify(&cached_proof, &cached_public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn fixed_base_table_matches_generator() {
+    let table =
+        FixedBaseTable::<JubjubAffine>::new(JubjubAffine::ADDITIVE_GENERATOR);
+
+    assert_eq!(
+        table.multiples().len(),
+        FixedBaseTable::<JubjubAffine>::BITS
+    );
+    assert_eq!(table.generator(), JubjubAffine::ADDITIVE_GENERATOR);
+
+    let two = JubjubScalar::from(2u64);
+    let expected: JubjubAffine =
+        (JubjubAffine::ADDITIVE_GENERATOR * two.pow(255)).into();
+    assert_eq!(table.multiples()[0], expected);
+}
+
+#[test]
+fn fixed_base_table_is_reused() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let scalars: Vec<_> =
+        (0..16).map(|_| JubjubScalar::random(&mut rng)).collect();
+
+    let start = Instant::now();
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    for scalar in scalars.iter() {
+        let w = composer.append_witness(*scalar);
+        composer
+            .component_mul_generator(w, JubjubAffine::ADDITIVE_GENERATOR)
+            .expect("the wNAF is valid");
+    }
+    let uncached = start.elapsed();
+
+    // the table is built once, and every multiplication reads it
+    let start = Instant::now();
+    let table = FixedBaseTable::new(JubjubAffine::ADDITIVE_GENERATOR);
+    let mut cached_composer = Plonk::<JubjubAffine>::initialize();
+    for scalar in scalars.iter() {
+        let w = cached_composer.append_witness(*scalar);
+        cached_composer
+            .component_mul_generator_with_table(w, &table)
+            .expect("the wNAF is valid");
+    }
+    let cached = start.elapsed();
+
+    println!(
+        "16 fixed-base multiplications: {uncached:?} uncached, {cached:?} \
+         with a shared table"
+    );
+
+    assert_eq!(
+        composer.serialize_witness_state(),
+        cached_composer.serialize_witness_state()
+    );
+    assert_eq!(composer.statistics(), cached_composer.statistics());
+}
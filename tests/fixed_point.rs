@@ -0,0 +1,173 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::gadget::fixed_point::Fixed;
+use zkplonk::prelude::*;
+
+const FRAC_BITS: usize = 16;
+
+/// Software model of the operations, `None` if the result doesn't fit
+fn model(a: u64, b: u64) -> Option<[u64; 5]> {
+    let (a, b) = (a as u128, b as u128);
+
+    let sum = a + b;
+    let diff = a.checked_sub(b)?;
+    let prod = (a * b) >> FRAC_BITS;
+    let quot = (a << FRAC_BITS).checked_div(b)?;
+    let lt = (b < a) as u128;
+
+    let results = [sum, diff, prod, quot, lt];
+    results
+        .iter()
+        .all(|r| *r <= u64::MAX as u128)
+        .then(|| results.map(|r| r as u64))
+}
+
+/// Proves the public values are the sum, difference, product, quotient and
+/// comparison `b < a` of two secret numbers
+#[derive(Debug, Default)]
+pub struct FixedCircuit {
+    a: u64,
+    b: u64,
+    results: [u64; 5],
+}
+
+impl FixedCircuit {
+    pub fn new(a: u64, b: u64) -> Self {
+        let results = model(a, b).unwrap_or_default();
+
+        Self { a, b, results }
+    }
+}
+
+impl Circuit<JubjubAffine> for FixedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = Fixed::<FRAC_BITS>::witness(composer, self.a);
+        let b = Fixed::<FRAC_BITS>::witness(composer, self.b);
+
+        let results = [
+            a.add(composer, &b).wire(),
+            a.sub(composer, &b).wire(),
+            a.mul(composer, &b).wire(),
+            a.div(composer, &b).wire(),
+            b.less_than(composer, &a),
+        ];
+
+        for (w, r) in results.iter().zip(self.results.iter()) {
+            let r = composer.append_public(BlsScalar::from(*r));
+            composer.assert_equal(*w, r);
+        }
+
+        Ok(())
+    }
+}
+
+type FixedKey = PlonkKey<TatePairing, JubjubAffine, FixedCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = FixedKey::setup_for_circuit(&FixedCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    FixedKey::compile(&pp).expect("failed to compile circuit")
+}
+
+fn prove(
+    rng: &mut StdRng,
+    prover: &JubjubProver,
+    verifier: &JubjubVerifier,
+    circuit: &FixedCircuit,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn fixed_point_matches_model() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for _ in 0..8 {
+        // below 2^39, so the product fits
+        let x = rng.next_u64() >> 25;
+        let y = (rng.next_u64() >> 25).max(1);
+        let (a, b) = (x.max(y), x.min(y));
+
+        let circuit = FixedCircuit::new(a, b);
+        prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+
+        // a result rounded up is refused
+        for i in 0..4 {
+            let mut circuit = FixedCircuit::new(a, b);
+            circuit.results[i] += 1;
+
+            assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+        }
+    }
+
+    // 2.5 · 1.5 = 3.75, and 2.5 / 1.5 = 1.666... is truncated
+    let one = 1u64 << FRAC_BITS;
+    let circuit = FixedCircuit::new(one * 5 / 2, one * 3 / 2);
+    assert_eq!(circuit.results[2], one * 15 / 4);
+    assert_eq!(circuit.results[3], (5 << FRAC_BITS) / 3);
+    prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+
+    // equal numbers
+    let circuit = FixedCircuit::new(one, one);
+    assert_eq!(circuit.results, [2 * one, 0, one, one, 0]);
+    prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+}
+
+#[test]
+fn fixed_point_overflow() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let one = 1u64 << FRAC_BITS;
+    let cases = [
+        // the sum doesn't fit 64 bits
+        (1u64 << 63, 1u64 << 63),
+        // the product doesn't fit 64 bits
+        (1u64 << 40, 1u64 << 40),
+        // the difference is negative
+        (one, 2 * one),
+        // division by zero
+        (one, 0),
+    ];
+
+    for (a, b) in cases {
+        assert!(model(a, b).is_none());
+
+        let circuit = FixedCircuit::new(a, b);
+        assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+    }
+}
+
+#[test]
+fn fixed_point_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = Fixed::<FRAC_BITS>::witness(&mut composer, 5 << FRAC_BITS);
+    let b = Fixed::<FRAC_BITS>::witness(&mut composer, 3 << FRAC_BITS);
+
+    let gates = composer.statistics().gates;
+    a.add(&mut composer, &b);
+    assert_eq!(composer.statistics().gates - gates, 11);
+
+    let gates = composer.statistics().gates;
+    a.mul(&mut composer, &b);
+    assert_eq!(composer.statistics().gates - gates, 11 + 4);
+
+    let gates = composer.statistics().gates;
+    let q = a.div(&mut composer, &b);
+    assert_eq!(composer.statistics().gates - gates, 32);
+    assert_eq!(q.value(&composer), (5 << FRAC_BITS) / 3);
+}
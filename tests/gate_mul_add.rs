@@ -0,0 +1,110 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Multiplies two complex numbers `(x0 + i·x1)(y0 + i·y1)`, with the
+/// public result
+#[derive(Debug, Default)]
+pub struct ComplexCircuit {
+    x: [BlsScalar; 2],
+    y: [BlsScalar; 2],
+    z: [BlsScalar; 2],
+}
+
+impl ComplexCircuit {
+    pub fn random(rng: &mut StdRng) -> Self {
+        let x = [(); 2].map(|_| BlsScalar::random(&mut *rng));
+        let y = [(); 2].map(|_| BlsScalar::random(&mut *rng));
+        let z = [x[0] * y[0] - x[1] * y[1], x[0] * y[1] + x[1] * y[0]];
+
+        Self { x, y, z }
+    }
+}
+
+impl Circuit<JubjubAffine> for ComplexCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let x = self.x.map(|v| composer.append_witness(v));
+        let y = self.y.map(|v| composer.append_witness(v));
+        let z = self.z.map(|v| composer.append_public(v));
+
+        // x0·y0 + x1·(-y1)
+        let constraint = Constraint::default().left(-BlsScalar::one()).a(y[1]);
+        let minus_y1 = composer.gate_add(constraint);
+
+        let real = composer.gate_mul_add(x[0], y[0], x[1], minus_y1);
+        let imaginary = composer.gate_mul_add(x[0], y[1], x[1], y[0]);
+
+        composer.assert_equal(real, z[0]);
+        composer.assert_equal(imaginary, z[1]);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn gate_mul_add_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &ComplexCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, ComplexCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    for _ in 0..4 {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &ComplexCircuit::random(&mut rng))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    let mut circuit = ComplexCircuit::random(&mut rng);
+    circuit.z[0] += BlsScalar::one();
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("a wrong product shouldn't verify");
+    }
+}
+
+#[test]
+fn gate_mul_add_values() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    for _ in 0..8 {
+        let values = [(); 4].map(|_| BlsScalar::random(&mut rng));
+        let [a, b, c, d] = values.map(|v| composer.append_witness(v));
+
+        let gates = composer.statistics().gates;
+        let madd = composer.gate_madd(a, b, c);
+        assert_eq!(composer.statistics().gates, gates + 1);
+        assert_eq!(composer[madd], values[0] * values[1] + values[2]);
+
+        let gates = composer.statistics().gates;
+        let mul_add = composer.gate_mul_add(a, b, c, d);
+        assert_eq!(composer.statistics().gates, gates + 2);
+        assert_eq!(
+            composer[mul_add],
+            values[0] * values[1] + values[2] * values[3]
+        );
+    }
+}
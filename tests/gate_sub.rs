@@ -0,0 +1,117 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts the public difference and negation of two witnesses
+#[derive(Debug, Default)]
+pub struct SubCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    diff: BlsScalar,
+    neg: BlsScalar,
+}
+
+impl SubCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self {
+            a,
+            b,
+            diff: a - b,
+            neg: -a,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for SubCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let diff = composer.append_public(self.diff);
+        let neg = composer.append_public(self.neg);
+
+        let x = composer.gate_sub(a, b);
+        composer.assert_equal(x, diff);
+
+        let x = composer.component_negate(a);
+        composer.assert_equal(x, neg);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn gate_sub_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &SubCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, SubCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    for _ in 0..4 {
+        let a = BlsScalar::random(&mut rng);
+        let b = BlsScalar::random(&mut rng);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &SubCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // the operands swapped, and the negation of `a` replaced by `a`
+    let a = BlsScalar::from(7u64);
+    let b = BlsScalar::from(3u64);
+
+    let mut circuit = SubCircuit::new(a, b);
+    circuit.diff = b - a;
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "b - a must not verify as a - b");
+
+    let mut circuit = SubCircuit::new(a, b);
+    circuit.neg = a;
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a must not verify as -a");
+}
+
+#[test]
+fn gate_sub_sign() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(7u64));
+    let b = composer.append_witness(BlsScalar::from(3u64));
+
+    let gates = composer.statistics().gates;
+    let diff = composer.gate_sub(a, b);
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[diff], BlsScalar::from(4u64));
+
+    let diff = composer.gate_sub(b, a);
+    assert_eq!(composer[diff], -BlsScalar::from(4u64));
+
+    let gates = composer.statistics().gates;
+    let neg = composer.component_negate(a);
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[neg], -BlsScalar::from(7u64));
+    assert_eq!(composer[neg] + BlsScalar::from(7u64), BlsScalar::zero());
+}
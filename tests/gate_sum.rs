@@ -0,0 +1,164 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts the public value is the linear combination of the witnesses
+#[derive(Debug, Clone, Default)]
+pub struct SumCircuit {
+    coefficients: Vec<BlsScalar>,
+    values: Vec<BlsScalar>,
+    constant: BlsScalar,
+    sum: BlsScalar,
+}
+
+impl SumCircuit {
+    pub fn random(rng: &mut StdRng, len: usize) -> Self {
+        let coefficients = (0..len).map(|_| BlsScalar::random(&mut *rng));
+        let coefficients: Vec<_> = coefficients.collect();
+        let values = (0..len).map(|_| BlsScalar::random(&mut *rng)).collect();
+        let constant = BlsScalar::random(&mut *rng);
+
+        Self::new(coefficients, values, constant)
+    }
+
+    pub fn new(
+        coefficients: Vec<BlsScalar>,
+        values: Vec<BlsScalar>,
+        constant: BlsScalar,
+    ) -> Self {
+        let sum = coefficients
+            .iter()
+            .zip(values.iter())
+            .fold(constant, |acc, (c, v)| acc + c * v);
+
+        Self {
+            coefficients,
+            values,
+            constant,
+            sum,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for SumCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let terms: Vec<_> = self
+            .coefficients
+            .iter()
+            .zip(self.values.iter())
+            .map(|(c, v)| (*c, composer.append_witness(*v)))
+            .collect();
+        let expected = composer.append_public(self.sum);
+
+        let sum = composer.gate_sum(&terms, self.constant);
+        composer.assert_equal(sum, expected);
+
+        Ok(())
+    }
+}
+
+/// Gates appended by `gate_sum` for `len` terms
+fn gates(len: usize) -> usize {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let terms: Vec<_> = (0..len)
+        .map(|i| {
+            let w = composer.append_witness(BlsScalar::from(i as u64));
+            (BlsScalar::from(2u64), w)
+        })
+        .collect();
+
+    let m = composer.statistics().gates;
+    let sum = composer.gate_sum(&terms, BlsScalar::from(7u64));
+
+    let expected = (0..len as u64).sum::<u64>() * 2 + 7;
+    assert_eq!(composer[sum], BlsScalar::from(expected));
+
+    composer.statistics().gates - m
+}
+
+#[test]
+fn gate_sum_gate_count() {
+    assert_eq!(gates(0), 1);
+    assert_eq!(gates(1), 1);
+    assert_eq!(gates(3), 1);
+    assert_eq!(gates(4), 2);
+    assert_eq!(gates(5), 2);
+    assert_eq!(gates(100), 1 + 49);
+}
+
+#[test]
+fn gate_sum_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    for len in [1, 3, 4, 100] {
+        let circuit = SumCircuit::random(&mut rng, len);
+        let (prover, verifier) =
+            PlonkKey::compile_with_circuit(&mut pp, b"sum", &circuit)
+                .expect("failed to compile circuit");
+
+        let values = (0..len).map(|_| BlsScalar::random(&mut rng)).collect();
+        let circuit =
+            SumCircuit::new(circuit.coefficients, values, circuit.constant);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // a wrong sum is refused
+        let mut circuit = circuit;
+        circuit.sum += BlsScalar::one();
+
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a wrong sum must not verify");
+    }
+}
+
+#[test]
+fn gate_sum_coefficients_are_the_circuit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    let circuit = SumCircuit::random(&mut rng, 32);
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, b"sum", &circuit)
+            .expect("failed to compile circuit");
+
+    // the coefficients are selectors, so tampering one changes the circuit
+    let mut coefficients = circuit.coefficients.clone();
+    coefficients[17] += BlsScalar::one();
+    let tampered =
+        SumCircuit::new(coefficients, circuit.values.clone(), circuit.constant);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+    let mut tampered_composer = Plonk::<JubjubAffine>::initialize();
+    tampered
+        .synthesize(&mut tampered_composer)
+        .expect("failed to synthesize");
+    assert_ne!(composer.circuit_id(), tampered_composer.circuit_id());
+
+    // and its proofs don't verify against the original keys
+    let result = prover
+        .create_proof(&mut rng, &tampered)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "the tampered circuit must not verify");
+}
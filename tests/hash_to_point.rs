@@ -0,0 +1,267 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::gadget::elligator::hash_to_point;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Raise `base` to the little endian integer `exp`
+fn pow(base: BlsScalar, exp: &[u8]) -> BlsScalar {
+    let mut acc = BlsScalar::one();
+    for byte in exp.iter().rev() {
+        for i in (0..8).rev() {
+            acc = acc * acc;
+            if (byte >> i) & 1 == 1 {
+                acc = acc * base;
+            }
+        }
+    }
+    acc
+}
+
+fn halve(bytes: &mut [u8]) {
+    let mut carry = 0;
+    for byte in bytes.iter_mut().rev() {
+        let low = *byte & 1;
+        *byte = (*byte >> 1) | (carry << 7);
+        carry = low;
+    }
+}
+
+fn p_minus_one() -> Vec<u8> {
+    (-BlsScalar::one()).to_raw_bytes().iter().copied().collect()
+}
+
+fn is_square(x: BlsScalar) -> bool {
+    let mut exp = p_minus_one();
+    halve(&mut exp);
+    pow(x, &exp) != -BlsScalar::one()
+}
+
+fn inv0(x: BlsScalar) -> BlsScalar {
+    x.invert().unwrap_or_else(BlsScalar::zero)
+}
+
+fn sgn0(x: BlsScalar) -> bool {
+    // rebuilt from its bytes, so the representation is canonical
+    let radix = BlsScalar::from(256u64);
+    let x = x
+        .to_raw_bytes()
+        .iter()
+        .rev()
+        .fold(BlsScalar::zero(), |acc, b| {
+            acc * radix + BlsScalar::from(*b as u64)
+        });
+
+    x.to_raw_bytes()[0] & 1 == 1
+}
+
+/// Tonelli-Shanks square root of a square
+fn sqrt(x: BlsScalar) -> BlsScalar {
+    if x == BlsScalar::zero() {
+        return x;
+    }
+
+    let mut q = p_minus_one();
+    let mut s = 0;
+    while q[0] & 1 == 0 {
+        halve(&mut q);
+        s += 1;
+    }
+
+    let z = (2u64..)
+        .map(BlsScalar::from)
+        .find(|z| !is_square(*z))
+        .unwrap();
+
+    let mut half = q.clone();
+    halve(&mut half);
+
+    let (mut m, mut c, mut t) = (s, pow(z, &q), pow(x, &q));
+    let mut r = pow(x, &half) * x;
+    while t != BlsScalar::one() {
+        let mut i = 0;
+        let mut t_i = t;
+        while t_i != BlsScalar::one() {
+            t_i = t_i * t_i;
+            i += 1;
+        }
+
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b * b;
+        }
+        m = i;
+        c = b * b;
+        t = t * c;
+        r = r * b;
+    }
+
+    assert_eq!(r * r, x);
+    r
+}
+
+/// `map_to_curve_elligator2` of RFC 9380 on the Montgomery form of JubJub,
+/// mapped to twisted Edwards coordinates and multiplied by the cofactor
+fn native(u: BlsScalar) -> JubjubAffine {
+    let g = JubjubAffine::ADDITIVE_GENERATOR;
+    let (gx, gy) = (g.get_x(), g.get_y());
+    let d = (gy * gy - gx * gx - BlsScalar::one()) * inv0(gx * gx * gy * gy);
+
+    let one = BlsScalar::one();
+    let j = BlsScalar::from(2u64) * (one - d) * inv0(one + d);
+    let k = -BlsScalar::from(4u64) * inv0(one + d);
+    let z = (2u64..)
+        .map(BlsScalar::from)
+        .find(|z| !is_square(*z))
+        .unwrap();
+
+    let j_k = j * inv0(k);
+    let curve = |x: BlsScalar| x * x * x + j_k * x * x + x * inv0(k * k);
+
+    let mut x1 = -j_k * inv0(one + z * u * u);
+    if x1 == BlsScalar::zero() {
+        x1 = -j_k;
+    }
+    let x2 = -x1 - j_k;
+
+    let (x, y) = match is_square(curve(x1)) {
+        true => {
+            let y = sqrt(curve(x1));
+            (x1, if sgn0(y) { y } else { -y })
+        }
+        false => {
+            let y = sqrt(curve(x2));
+            (x2, if sgn0(y) { -y } else { y })
+        }
+    };
+
+    let (s, t) = (x * k, y * k);
+    let p = match t == BlsScalar::zero() || s == -one {
+        true => JubjubAffine::ADDITIVE_IDENTITY,
+        false => JubjubAffine::from_raw_unchecked(
+            s * inv0(t),
+            (s - one) * inv0(s + one),
+        ),
+    };
+
+    let p = JubjubExtended::from(p);
+    JubjubAffine::from(p + p + p + p + p + p + p + p)
+}
+
+/// Asserts the public point is the map of the secret field element
+#[derive(Debug)]
+pub struct HashCircuit {
+    t: BlsScalar,
+    point: JubjubAffine,
+}
+
+impl HashCircuit {
+    pub fn new(t: BlsScalar) -> Self {
+        Self {
+            t,
+            point: native(t),
+        }
+    }
+}
+
+impl Default for HashCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::from(7u64))
+    }
+}
+
+impl Circuit<JubjubAffine> for HashCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let t = composer.append_witness(self.t);
+
+        let point = composer.component_hash_to_point(t);
+        composer.assert_equal_public_point(point, self.point);
+
+        Ok(())
+    }
+}
+
+type HashKey = PlonkKey<TatePairing, JubjubAffine, HashCircuit>;
+
+fn inputs(rng: &mut StdRng) -> Vec<BlsScalar> {
+    let mut inputs = vec![
+        BlsScalar::zero(),
+        BlsScalar::one(),
+        -BlsScalar::one(),
+        BlsScalar::from(2u64),
+    ];
+    inputs.extend((0..12).map(|_| BlsScalar::random(&mut *rng)));
+    inputs
+}
+
+#[test]
+fn hash_to_point_matches_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for t in inputs(&mut rng) {
+        let expected = native(t);
+        assert_eq!(hash_to_point::<JubjubAffine>(t), expected);
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let w = composer.append_witness(t);
+        let point = composer.component_hash_to_point(w);
+
+        assert_eq!(composer[*point.x()], expected.get_x());
+        assert_eq!(composer[*point.y()], expected.get_y());
+    }
+}
+
+#[test]
+fn hash_to_point_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = HashKey::setup_for_circuit(&HashCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        HashKey::compile(&pp).expect("failed to compile circuit");
+
+    for t in inputs(&mut rng).into_iter().take(6) {
+        let circuit = HashCircuit::new(t);
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // the map of another input is refused
+        let circuit = HashCircuit {
+            t: t + BlsScalar::one(),
+            ..circuit
+        };
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a wrong point must not verify");
+    }
+}
+
+#[test]
+fn hash_to_point_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let t = composer.append_witness(BlsScalar::from(7u64));
+
+    let gates = composer.statistics().gates;
+    composer.component_parity(t);
+    let parity = composer.statistics().gates - gates;
+
+    let gates = composer.statistics().gates;
+    composer.component_hash_to_point(t);
+    assert_eq!(composer.statistics().gates, gates + 35 + parity);
+}
@@ -0,0 +1,183 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use jub_jub::JubjubAffine;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+const X: u64 = 7;
+
+fn evaluate(coefficients: &[BlsScalar], x: BlsScalar) -> BlsScalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(BlsScalar::zero(), |acc, c| acc * x + *c)
+}
+
+#[derive(Debug)]
+pub struct DummyCircuit<const N: usize> {
+    coefficients: [BlsScalar; N],
+    x: BlsScalar,
+    result: BlsScalar,
+    constant: bool,
+}
+
+impl<const N: usize> DummyCircuit<N> {
+    pub fn new(coefficients: [BlsScalar; N], x: BlsScalar) -> Self {
+        let result = evaluate(&coefficients, x);
+
+        Self {
+            coefficients,
+            x,
+            result,
+            constant: false,
+        }
+    }
+
+    pub fn constant(coefficients: [BlsScalar; N]) -> Self {
+        Self {
+            constant: true,
+            ..Self::new(coefficients, BlsScalar::from(X))
+        }
+    }
+}
+
+impl<const N: usize> Default for DummyCircuit<N> {
+    fn default() -> Self {
+        Self::new([BlsScalar::zero(); N], BlsScalar::zero())
+    }
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for DummyCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let coefficients: Vec<_> = self
+            .coefficients
+            .iter()
+            .map(|c| composer.append_witness(*c))
+            .collect();
+
+        let result = match self.constant {
+            true => {
+                composer.component_horner_eval_at_constant(&coefficients, X)
+            }
+            false => {
+                let x = composer.append_witness(self.x);
+                composer.component_horner_eval(&coefficients, x)
+            }
+        };
+
+        let expected = composer.append_public(self.result);
+        composer.assert_equal(result, expected);
+
+        Ok(())
+    }
+}
+
+fn circuit<const N: usize>(
+    rng: &mut StdRng,
+    coefficients: [BlsScalar; N],
+    constant: bool,
+) -> DummyCircuit<N> {
+    match constant {
+        true => DummyCircuit::constant(coefficients),
+        false => DummyCircuit::new(coefficients, BlsScalar::random(rng)),
+    }
+}
+
+fn horner_eval_works<const N: usize>(rng: &mut StdRng, constant: bool) {
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut *rng);
+
+    let default = circuit(rng, [BlsScalar::zero(); N], constant);
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, b"horner", &default)
+            .expect("failed to compile circuit");
+
+    // native evaluation matches
+    {
+        let coefficients = [(); N].map(|_| BlsScalar::random(&mut *rng));
+        let circuit = circuit(rng, coefficients, constant);
+
+        let (proof, public_inputs) =
+            prover.create_proof(rng, &circuit).expect("failed to prove");
+
+        assert_eq!(public_inputs.len(), 1);
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // altered coefficient fails
+    {
+        let coefficients = [(); N].map(|_| BlsScalar::random(&mut *rng));
+        let mut circuit = circuit(rng, coefficients, constant);
+        circuit.coefficients[N / 2] += BlsScalar::one();
+
+        let result = prover
+            .create_proof(rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+
+        assert!(result.is_err(), "altered coefficient must not verify");
+    }
+}
+
+#[test]
+fn horner_eval_degrees_work() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for constant in [false, true] {
+        horner_eval_works::<1>(&mut rng, constant);
+        horner_eval_works::<2>(&mut rng, constant);
+        horner_eval_works::<6>(&mut rng, constant);
+        horner_eval_works::<65>(&mut rng, constant);
+    }
+}
+
+#[test]
+fn horner_eval_gate_count() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let coefficients: Vec<_> = (0..6u64)
+        .map(|c| composer.append_witness(BlsScalar::from(c)))
+        .collect();
+    let x = composer.append_witness(BlsScalar::from(X));
+
+    let m = ConstraintSystem::m(&composer);
+    composer.component_horner_eval(&coefficients, x);
+    assert_eq!(ConstraintSystem::m(&composer) - m, 5);
+
+    let m = ConstraintSystem::m(&composer);
+    composer.component_horner_eval_at_constant(&coefficients, X);
+    assert_eq!(ConstraintSystem::m(&composer) - m, 5);
+}
+
+#[test]
+fn horner_eval_empty_is_zero() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let x = composer.append_witness(BlsScalar::from(X));
+
+    let m = ConstraintSystem::m(&composer);
+
+    let result = composer.component_horner_eval(&[], x);
+    assert_eq!(result, Plonk::<JubjubAffine>::ZERO);
+
+    let result = composer.component_horner_eval_at_constant(&[], X);
+    assert_eq!(result, Plonk::<JubjubAffine>::ZERO);
+
+    assert_eq!(ConstraintSystem::m(&composer), m);
+}
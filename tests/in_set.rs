@@ -0,0 +1,141 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts the secret value belongs to the constant set
+#[derive(Debug, Clone, Default)]
+pub struct InSetCircuit {
+    set: Vec<BlsScalar>,
+    value: BlsScalar,
+}
+
+impl InSetCircuit {
+    pub fn new(set: Vec<BlsScalar>, value: BlsScalar) -> Self {
+        Self { set, value }
+    }
+
+    pub fn random(rng: &mut StdRng, len: usize) -> Self {
+        let set = (0..len).map(|_| BlsScalar::random(&mut *rng)).collect();
+
+        Self::new(set, BlsScalar::zero())
+    }
+}
+
+impl Circuit<JubjubAffine> for InSetCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let value = composer.append_witness(self.value);
+        composer.component_assert_in_set(value, &self.set);
+
+        Ok(())
+    }
+}
+
+type InSetKey = PlonkKey<TatePairing, JubjubAffine, InSetCircuit>;
+
+fn keys(
+    rng: &mut StdRng,
+    circuit: &InSetCircuit,
+) -> (JubjubProver, JubjubVerifier) {
+    let pp = InSetKey::setup_for_circuit(circuit, rng)
+        .expect("failed to synthesize");
+
+    InSetKey::compile_with_circuit(&pp, b"in-set", circuit)
+        .expect("failed to compile circuit")
+}
+
+#[test]
+fn in_set_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for len in [1, 2, 16] {
+        let circuit = InSetCircuit::random(&mut rng, len);
+        let (prover, verifier) = keys(&mut rng, &circuit);
+
+        // the first and the last elements
+        for value in [circuit.set[0], circuit.set[len - 1]] {
+            let circuit = InSetCircuit::new(circuit.set.clone(), value);
+            let (proof, public_inputs) = prover
+                .create_proof(&mut rng, &circuit)
+                .expect("failed to prove");
+
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect("failed to verify proof");
+        }
+
+        // a value out of the set
+        let value = BlsScalar::random(&mut rng);
+        let circuit = InSetCircuit::new(circuit.set.clone(), value);
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a value out of the set must not verify");
+    }
+}
+
+#[test]
+fn in_set_empty_is_unsatisfiable() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let circuit = InSetCircuit::new(vec![], BlsScalar::zero());
+    let (prover, verifier) = keys(&mut rng, &circuit);
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "no value belongs to the empty set");
+}
+
+#[test]
+fn in_set_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let value = composer.append_witness(BlsScalar::from(5u64));
+
+    for len in [0, 1, 2, 100] {
+        let set: Vec<_> = (0..len as u64).map(BlsScalar::from).collect();
+
+        let gates = composer.statistics().gates;
+        composer.component_assert_in_set(value, &set);
+        assert_eq!(composer.statistics().gates, gates + len.max(1));
+    }
+}
+
+#[test]
+fn in_set_is_the_circuit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let circuit = InSetCircuit::random(&mut rng, 8);
+    let (prover, verifier) = keys(&mut rng, &circuit);
+
+    // the set is made of selectors, so tampering an element changes the
+    // verification key
+    let mut set = circuit.set.clone();
+    set[3] += BlsScalar::one();
+    let tampered = InSetCircuit::new(set, circuit.set[3] + BlsScalar::one());
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+    let mut tampered_composer = Plonk::<JubjubAffine>::initialize();
+    tampered
+        .synthesize(&mut tampered_composer)
+        .expect("failed to synthesize");
+    assert_ne!(composer.circuit_id(), tampered_composer.circuit_id());
+
+    // and its proofs don't verify against the original keys
+    let result = prover
+        .create_proof(&mut rng, &tampered)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "the tampered circuit must not verify");
+}
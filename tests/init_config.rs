@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use jub_jub::JubjubAffine;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl DummyCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_ab = composer.append_public(self.a * self.b);
+
+        let constraint = Constraint::default().mult(1).a(w_a).b(w_b);
+        let product = composer.gate_mul(constraint);
+
+        composer.assert_equal(product, w_ab);
+
+        Ok(())
+    }
+}
+
+const BARE: InitConfig = InitConfig {
+    dummy_gate_sets: 0,
+    reserve_zero_one: false,
+    size_hint: SizeHint {
+        gates: 0,
+        witnesses: 0,
+    },
+};
+
+#[test]
+fn init_config_default_matches_initialize() {
+    let default = Plonk::<JubjubAffine>::initialize();
+    let custom = Plonk::<JubjubAffine>::with_config(InitConfig::default());
+
+    assert_eq!(default.config(), InitConfig::default());
+    assert_eq!(ConstraintSystem::m(&default), ConstraintSystem::m(&custom));
+}
+
+#[test]
+fn init_config_without_dummy_gates_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let circuit = DummyCircuit::new(a, b);
+
+    let mut default = Plonk::<JubjubAffine>::initialize();
+    let mut bare = Plonk::<JubjubAffine>::with_config(BARE);
+
+    circuit
+        .synthesize(&mut default)
+        .expect("failed to synthesize");
+    circuit.synthesize(&mut bare).expect("failed to synthesize");
+
+    // two constant assertions and two sets of two dummy gates
+    assert_eq!(
+        ConstraintSystem::m(&default) - ConstraintSystem::m(&bare),
+        6
+    );
+
+    let mut pp = PlonkParams::<TatePairing>::setup(4, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile_with_config(
+            &mut pp,
+            b"bare",
+            &DummyCircuit::default(),
+            BARE,
+        )
+        .expect("failed to compile circuit");
+
+    // the wire polynomials are still blinded by the prover
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    assert_eq!(public_inputs.len(), 1);
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // a wrong public input must fail
+    verifier
+        .verify(&proof, &[a + b])
+        .expect_err("verification of a wrong statement must fail");
+}
+
+#[test]
+#[should_panic(expected = "reserve the zero and one witnesses")]
+fn init_config_without_zero_one_panics() {
+    let mut composer = Plonk::<JubjubAffine>::with_config(BARE);
+
+    let scalar = composer.append_witness(BlsScalar::from(5));
+    composer.component_decomposition::<8>(scalar);
+}
@@ -0,0 +1,189 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::DivisionByZero;
+use zkstd::common::*;
+
+/// Asserts the public value is `a⁻¹`
+#[derive(Debug)]
+pub struct InverseCircuit {
+    a: BlsScalar,
+    inv: BlsScalar,
+}
+
+impl Default for InverseCircuit {
+    fn default() -> Self {
+        Self {
+            a: BlsScalar::one(),
+            inv: BlsScalar::one(),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for InverseCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let inv = composer.append_public(self.inv);
+
+        let x = composer.component_inverse(a)?;
+        composer.assert_equal(x, inv);
+
+        Ok(())
+    }
+}
+
+/// Asserts the public values are the inverse or zero of `a`, and its zero
+/// bit
+#[derive(Debug, Default)]
+pub struct InverseOrZeroCircuit {
+    a: BlsScalar,
+    inv: BlsScalar,
+    is_zero: BlsScalar,
+}
+
+impl InverseOrZeroCircuit {
+    pub fn new(a: BlsScalar) -> Self {
+        match a == BlsScalar::zero() {
+            true => Self {
+                a,
+                inv: BlsScalar::zero(),
+                is_zero: BlsScalar::one(),
+            },
+            false => Self {
+                a,
+                inv: a.invert().expect("a isn't zero"),
+                is_zero: BlsScalar::zero(),
+            },
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for InverseOrZeroCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let inv = composer.append_public(self.inv);
+        let is_zero = composer.append_public(self.is_zero);
+
+        let (x, z) = composer.component_inverse_or_zero(a);
+        composer.assert_equal(x, inv);
+        composer.assert_equal(z, is_zero);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn inverse_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &InverseCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, InverseCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let a = BlsScalar::random(&mut rng);
+    let inv = a.invert().expect("a isn't zero");
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &InverseCircuit { a, inv })
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // a forged inverse
+    let circuit = InverseCircuit {
+        a,
+        inv: inv + BlsScalar::one(),
+    };
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+
+    // zero is unprovable by design
+    let circuit = InverseCircuit {
+        a: BlsScalar::zero(),
+        inv: BlsScalar::zero(),
+    };
+    prover
+        .create_proof(&mut rng, &circuit)
+        .expect_err("zero has no inverse");
+}
+
+#[test]
+fn inverse_or_zero_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &InverseOrZeroCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, InverseOrZeroCircuit>::compile(
+            &pp,
+        )
+        .expect("failed to compile circuit");
+
+    for a in [BlsScalar::random(&mut rng), BlsScalar::zero()] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &InverseOrZeroCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // a forged zero bit
+        let mut circuit = InverseOrZeroCircuit::new(a);
+        circuit.is_zero = BlsScalar::one() - circuit.is_zero;
+
+        if let Ok((proof, public_inputs)) =
+            prover.create_proof(&mut rng, &circuit)
+        {
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect_err("the proof must not verify");
+        }
+    }
+}
+
+#[test]
+fn inverse_of_zero() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let zero = composer.append_witness(BlsScalar::zero());
+    let gates = composer.statistics().gates;
+
+    assert_eq!(
+        composer.component_inverse(zero),
+        Err(DivisionByZero { wire: zero.index() })
+    );
+    assert_eq!(composer.statistics().gates, gates);
+
+    let (inv, is_zero) = composer.component_inverse_or_zero(zero);
+    assert_eq!(composer[inv], BlsScalar::zero());
+    assert_eq!(composer[is_zero], BlsScalar::one());
+}
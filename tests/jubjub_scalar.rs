@@ -0,0 +1,123 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the scalar is a JubJub scalar, and multiplies the generator by it
+/// both as a constant and as a witness point
+#[derive(Debug, Clone)]
+pub struct ScalarCircuit {
+    a: BlsScalar,
+    point: JubjubAffine,
+}
+
+impl ScalarCircuit {
+    pub fn new(a: BlsScalar, point: JubjubAffine) -> Self {
+        Self { a, point }
+    }
+
+    pub fn honest(a: JubjubScalar) -> Self {
+        let point = JubjubAffine::ADDITIVE_GENERATOR * a;
+
+        Self::new(BlsScalar::from(a), point.into())
+    }
+}
+
+impl Default for ScalarCircuit {
+    fn default() -> Self {
+        Self::honest(JubjubScalar::from(7u64))
+    }
+}
+
+impl Circuit<JubjubAffine> for ScalarCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let point = composer.append_point(self.point);
+        let generator = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+
+        let a = composer.component_assert_jubjub_scalar(a);
+
+        let x = composer.component_mul_generator_typed(
+            a,
+            JubjubAffine::ADDITIVE_GENERATOR,
+        )?;
+        composer.assert_equal_point(x, point);
+
+        let x = composer.component_mul_point_typed(a, generator);
+        composer.assert_equal_point(x, point);
+
+        Ok(())
+    }
+}
+
+type ScalarKey = PlonkKey<TatePairing, JubjubAffine, ScalarCircuit>;
+
+#[test]
+fn assert_jubjub_scalar() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = ScalarKey::setup_for_circuit(&ScalarCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        ScalarKey::compile(&pp).expect("failed to compile circuit");
+
+    let identity = JubjubAffine::ADDITIVE_IDENTITY;
+    let max = BlsScalar::from(-JubjubScalar::one());
+    let one = BlsScalar::one();
+
+    let cases = [
+        (ScalarCircuit::honest(JubjubScalar::zero()), true),
+        (ScalarCircuit::honest(JubjubScalar::random(&mut rng)), true),
+        // just below the modulus
+        (ScalarCircuit::honest(-JubjubScalar::one()), true),
+        // the modulus, congruent to the identity
+        (ScalarCircuit::new(max + one, identity), false),
+        (ScalarCircuit::new(max + one + one, identity), false),
+        // fits the bits, but above the modulus
+        (
+            ScalarCircuit::new(BlsScalar::pow_of_2(252) - one, identity),
+            false,
+        ),
+    ];
+
+    for (circuit, canonical) in cases {
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+
+        assert_eq!(result.is_ok(), canonical);
+    }
+}
+
+#[test]
+fn assert_jubjub_scalar_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::from(3u64));
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_jubjub_scalar(a);
+    assert_eq!(composer.statistics().gates - gates, 3 * 252 + 1);
+}
+
+#[test]
+fn mul_generator_typed_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let a = composer.component_assert_jubjub_scalar(a);
+
+    // the rounds of the top digits of the wNAF are skipped
+    let gates = composer.statistics().gates;
+    composer
+        .component_mul_generator_typed(a, JubjubAffine::ADDITIVE_GENERATOR)
+        .expect("the wNAF is valid");
+    assert_eq!(composer.statistics().gates - gates, 257);
+}
@@ -0,0 +1,127 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::LabelError;
+
+#[derive(Debug, Default)]
+pub struct ProductCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for ProductCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let constraint = Constraint::default().mult(1).a(a).b(b);
+        let product = composer.gate_mul(constraint);
+
+        let constraint = Constraint::default().left(1).right(1).a(a).b(b);
+        let sum = composer.gate_add(constraint);
+
+        composer
+            .label_wire("product", product)
+            .expect("the label is new");
+        composer.label_wire("sum", sum).expect("the label is new");
+
+        Ok(())
+    }
+}
+
+#[test]
+fn dry_run_outputs() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for _ in 0..8 {
+        let a = BlsScalar::random(&mut rng);
+        let b = BlsScalar::random(&mut rng);
+
+        let report = Plonk::<JubjubAffine>::dry_run(&ProductCircuit { a, b })
+            .expect("failed to synthesize");
+
+        assert_eq!(report.output("product"), Some(a * b));
+        assert_eq!(report.output("sum"), Some(a + b));
+        assert_eq!(report.output("quotient"), None);
+        assert_eq!(report.outputs, vec![("product", a * b), ("sum", a + b)]);
+        assert_eq!(
+            report.stats.gates,
+            Plonk::<JubjubAffine>::dry_run(&ProductCircuit::default())
+                .expect("failed to synthesize")
+                .stats
+                .gates
+        );
+    }
+}
+
+#[test]
+fn labeled_value() {
+    let a = BlsScalar::from(6u64);
+    let b = BlsScalar::from(7u64);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let checkpoint = composer.checkpoint();
+
+    ProductCircuit { a, b }
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+    assert_eq!(composer.labeled_value("product"), Some(a * b));
+
+    // a label names a single wire
+    let w = composer.append_witness(a);
+    let product = composer.labeled_value("product");
+    assert!(matches!(
+        composer.label_wire("product", w),
+        Err(LabelError::Duplicate {
+            label: "product",
+            ..
+        })
+    ));
+    assert_eq!(composer.labeled_value("product"), product);
+
+    // and a wire can have several labels
+    composer.label_wire("a", w).expect("the label is new");
+    composer.label_wire("also a", w).expect("the label is new");
+    assert_eq!(composer.labeled_value("a"), Some(a));
+    assert_eq!(composer.labeled_value("also a"), Some(a));
+
+    // the labels of dropped wires are dropped
+    composer.rewind(checkpoint);
+    assert_eq!(composer.labeled_value("product"), None);
+    ProductCircuit { a: b, b }
+        .synthesize(&mut composer)
+        .expect("the labels were dropped");
+    assert_eq!(composer.labeled_value("product"), Some(b * b));
+}
+
+#[test]
+fn labels_are_free() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(4, &mut rng);
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, ProductCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = ProductCircuit {
+        a: BlsScalar::random(&mut rng),
+        b: BlsScalar::random(&mut rng),
+    };
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
@@ -0,0 +1,312 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cmp;
+use zkplonk::prelude::*;
+use zkstd::common::FftField;
+
+/// Asserts the public bit is `a < b` for `N` bits
+#[derive(Debug)]
+pub struct LessThanCircuit<const N: usize> {
+    a: BlsScalar,
+    b: BlsScalar,
+    lt: BlsScalar,
+}
+
+impl<const N: usize> LessThanCircuit<N> {
+    pub fn new(a: BlsScalar, b: BlsScalar, lt: bool) -> Self {
+        Self {
+            a,
+            b,
+            lt: BlsScalar::from(lt as u64),
+        }
+    }
+}
+
+impl<const N: usize> Default for LessThanCircuit<N> {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero(), BlsScalar::zero(), false)
+    }
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for LessThanCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let lt = composer.append_public(self.lt);
+
+        let x = composer.component_less_than::<N>(a, b);
+        composer.assert_equal(x, lt);
+
+        Ok(())
+    }
+}
+
+type Key<const N: usize> =
+    PlonkKey<TatePairing, JubjubAffine, LessThanCircuit<N>>;
+
+fn less_than<const N: usize>(cases: &[(BlsScalar, BlsScalar, bool)]) {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = Key::<N>::setup_for_circuit(&LessThanCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        Key::<N>::compile(&pp).expect("failed to compile circuit");
+
+    for (a, b, lt) in cases.iter().copied() {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &LessThanCircuit::<N>::new(a, b, lt))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // the prover lies about the comparison
+        let circuit = LessThanCircuit::<N>::new(a, b, !lt);
+        if let Ok((proof, public_inputs)) =
+            prover.create_proof(&mut rng, &circuit)
+        {
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect_err("the proof must not verify");
+        }
+    }
+}
+
+fn cases(bits: u64) -> Vec<(BlsScalar, BlsScalar, bool)> {
+    let max = BlsScalar::pow_of_2(bits) - BlsScalar::one();
+    let x = BlsScalar::from(0x1234u64);
+
+    vec![
+        (x, x, false),
+        (max, max, false),
+        (BlsScalar::zero(), x, true),
+        (BlsScalar::zero(), BlsScalar::zero(), false),
+        (x, BlsScalar::zero(), false),
+        (x, max, true),
+        (max, x, false),
+        (max - BlsScalar::one(), max, true),
+    ]
+}
+
+#[test]
+fn less_than_16_bits() {
+    less_than::<16>(&cases(16));
+}
+
+#[test]
+fn less_than_odd_bits() {
+    less_than::<17>(&cases(17));
+}
+
+#[test]
+fn less_than_252_bits() {
+    less_than::<252>(&cases(252));
+}
+
+/// Asserts the public values are `min(a, b)` and `max(a, b)` for 64 bits
+#[derive(Debug, Default)]
+pub struct MinMaxCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    min: BlsScalar,
+    max: BlsScalar,
+}
+
+impl MinMaxCircuit {
+    pub fn new(a: u64, b: u64) -> Self {
+        Self {
+            a: BlsScalar::from(a),
+            b: BlsScalar::from(b),
+            min: BlsScalar::from(cmp::min(a, b)),
+            max: BlsScalar::from(cmp::max(a, b)),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for MinMaxCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let min = composer.append_public(self.min);
+        let max = composer.append_public(self.max);
+
+        composer.component_range(a, 64);
+        composer.component_range(b, 64);
+
+        let x = composer.component_min::<64>(a, b);
+        composer.assert_equal(x, min);
+
+        let x = composer.component_max::<64>(a, b);
+        composer.assert_equal(x, max);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn min_max_randomized() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let circuit = MinMaxCircuit::default();
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &circuit, &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MinMaxCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    // small values collide often, so equal operands are covered too
+    let mut values = |i: usize| match i % 2 {
+        0 => rng.next_u64(),
+        _ => rng.next_u64() % 4,
+    };
+    let cases: Vec<_> = (0..16)
+        .map(|i| (values(i), values(i)))
+        .chain([(0, u64::MAX), (u64::MAX, u64::MAX), (u64::MAX, 0)])
+        .collect();
+
+    for (a, b) in cases {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &MinMaxCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // swapped results are refused, unless they coincide
+        if a != b {
+            let mut circuit = MinMaxCircuit::new(a, b);
+            core::mem::swap(&mut circuit.min, &mut circuit.max);
+
+            if let Ok((proof, public_inputs)) =
+                prover.create_proof(&mut rng, &circuit)
+            {
+                verifier
+                    .verify(&proof, &public_inputs)
+                    .expect_err("the proof must not verify");
+            }
+        }
+    }
+}
+
+/// Asserts `a < b` for `N` bits, with the inputs range checked by the gadget
+/// or by the circuit
+#[derive(Debug, Default)]
+pub struct AssertLessThanCircuit<const N: usize> {
+    a: BlsScalar,
+    b: BlsScalar,
+    unchecked: bool,
+}
+
+impl<const N: usize> AssertLessThanCircuit<N> {
+    pub fn new(a: BlsScalar, b: BlsScalar, unchecked: bool) -> Self {
+        Self { a, b, unchecked }
+    }
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for AssertLessThanCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        match self.unchecked {
+            true => {
+                composer.component_range(a, N);
+                composer.component_range(b, N);
+                composer.component_assert_less_than_unchecked::<N>(a, b);
+            }
+            false => composer.component_assert_less_than::<N>(a, b),
+        }
+
+        Ok(())
+    }
+}
+
+fn assert_less_than<const N: usize>(unchecked: bool) {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let circuit = AssertLessThanCircuit::<N>::new(
+        BlsScalar::zero(),
+        BlsScalar::one(),
+        unchecked,
+    );
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &circuit, &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) = PlonkKey::<
+        TatePairing,
+        JubjubAffine,
+        AssertLessThanCircuit<N>,
+    >::compile_with_circuit(
+        &pp, b"assert-less-than", &circuit
+    )
+    .expect("failed to compile circuit");
+
+    for (a, b, lt) in cases(N as u64) {
+        let circuit = AssertLessThanCircuit::<N>::new(a, b, unchecked);
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+
+        assert_eq!(result.is_ok(), lt);
+    }
+
+    // a wrapped around input is refused by the range checks
+    let circuit = AssertLessThanCircuit::<N>::new(
+        -BlsScalar::one(),
+        BlsScalar::from(0x1234u64),
+        unchecked,
+    );
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "-1 doesn't fit {} bits", N);
+}
+
+#[test]
+fn assert_less_than_checked() {
+    assert_less_than::<16>(false);
+    assert_less_than::<17>(false);
+}
+
+#[test]
+fn assert_less_than_unchecked() {
+    assert_less_than::<64>(true);
+}
+
+#[test]
+fn assert_less_than_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::from(3u64));
+    let b = composer.append_witness(BlsScalar::from(4u64));
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_less_than_unchecked::<64>(a, b);
+    assert_eq!(composer.statistics().gates - gates, 11);
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_less_than::<64>(a, b);
+    assert_eq!(composer.statistics().gates - gates, 31);
+}
@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+
+/// Asserts the public bit is `a < b` for integers of four limbs, least
+/// significant first, and optionally asserts they're equal
+#[derive(Debug, Default)]
+pub struct LimbsCircuit {
+    a: [u64; 4],
+    b: [u64; 4],
+    lt: BlsScalar,
+    assert_equal: bool,
+}
+
+impl LimbsCircuit {
+    pub fn new(a: [u64; 4], b: [u64; 4], assert_equal: bool) -> Self {
+        // the limbs compare as the integer, most significant first
+        let lt = a.iter().rev().lt(b.iter().rev());
+
+        Self {
+            a,
+            b,
+            lt: BlsScalar::from(lt as u64),
+            assert_equal,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for LimbsCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = self.a.map(|l| composer.append_witness(BlsScalar::from(l)));
+        let b = self.b.map(|l| composer.append_witness(BlsScalar::from(l)));
+        let lt = composer.append_public(self.lt);
+
+        let x = composer.component_limbs_less_than(&a, &b, true);
+        composer.assert_equal(x, lt);
+
+        if self.assert_equal {
+            composer.component_limbs_assert_equal(&a, &b);
+        }
+
+        Ok(())
+    }
+}
+
+type LimbsKey = PlonkKey<TatePairing, JubjubAffine, LimbsCircuit>;
+
+fn keys(
+    rng: &mut StdRng,
+    assert_equal: bool,
+) -> (JubjubProver, JubjubVerifier) {
+    let circuit = LimbsCircuit::new([0; 4], [0; 4], assert_equal);
+    let pp = LimbsKey::setup_for_circuit(&circuit, rng)
+        .expect("failed to synthesize");
+
+    LimbsKey::compile_with_circuit(&pp, b"limbs", &circuit)
+        .expect("failed to compile circuit")
+}
+
+fn prove(
+    rng: &mut StdRng,
+    prover: &JubjubProver,
+    verifier: &JubjubVerifier,
+    circuit: &LimbsCircuit,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn limbs_less_than_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng, false);
+
+    let x = [(); 4].map(|_| rng.next_u64() | 1);
+    let mut lsb = x;
+    lsb[0] -= 1;
+    let mut msb = x;
+    msb[3] -= 1;
+
+    // the lower limbs of `lower` are greater, but its top limb decides
+    let lower = [u64::MAX, u64::MAX, u64::MAX, 0];
+    let higher = [0, 0, 0, 1];
+
+    let mut cases = vec![
+        (x, x),
+        (lsb, x),
+        (x, lsb),
+        (msb, x),
+        (x, msb),
+        (lower, higher),
+        (higher, lower),
+        ([0; 4], [0; 4]),
+        ([u64::MAX; 4], [u64::MAX; 4]),
+    ];
+    for _ in 0..4 {
+        let a = [(); 4].map(|_| rng.next_u64());
+        let b = [(); 4].map(|_| rng.next_u64());
+        cases.push((a, b));
+    }
+
+    for (a, b) in cases {
+        let circuit = LimbsCircuit::new(a, b, false);
+        prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+
+        // the opposite bit is refused
+        let mut circuit = circuit;
+        circuit.lt = BlsScalar::one() - circuit.lt;
+        assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+    }
+}
+
+#[test]
+fn limbs_assert_equal_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng, true);
+
+    let x = [(); 4].map(|_| rng.next_u64() | 1);
+    let circuit = LimbsCircuit::new(x, x, true);
+    prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+
+    // values differing in a single limb
+    for i in 0..4 {
+        let mut y = x;
+        y[i] -= 1;
+
+        let circuit = LimbsCircuit::new(x, y, true);
+        assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+    }
+}
+
+#[test]
+fn limbs_less_than_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = [(); 4].map(|_| composer.append_witness(BlsScalar::one()));
+    let b = [(); 4].map(|_| composer.append_witness(BlsScalar::one()));
+
+    let gates = composer.statistics().gates;
+    composer.component_limbs_less_than(&a, &b, false);
+    assert_eq!(composer.statistics().gates - gates, 63);
+
+    let gates = composer.statistics().gates;
+    composer.component_limbs_less_than(&a, &b, true);
+    assert_eq!(composer.statistics().gates - gates, 63 + 80);
+
+    let gates = composer.statistics().gates;
+    composer.component_limbs_assert_equal(&a, &b);
+    assert_eq!(composer.statistics().gates - gates, 4);
+}
@@ -0,0 +1,175 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+const ROUNDS: usize = 8;
+
+/// Interleaves the range checks and the arithmetic of every round
+#[derive(Debug, Default)]
+pub struct MixedCircuit {
+    values: [BlsScalar; ROUNDS],
+    sum: BlsScalar,
+}
+
+impl MixedCircuit {
+    fn new(values: [BlsScalar; ROUNDS]) -> Self {
+        let sum = values.iter().fold(BlsScalar::zero(), |acc, v| acc + v * v);
+
+        Self { values, sum }
+    }
+}
+
+impl Circuit<JubjubAffine> for MixedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let public = composer.append_public(self.values[0]);
+        let values = self.values.map(|v| composer.append_witness(v));
+        composer.assert_equal(public, values[0]);
+
+        let mut sum = Plonk::<JubjubAffine>::ZERO;
+        for v in values {
+            composer.component_range(v, 32);
+
+            let constraint =
+                Constraint::default().mult(1).fourth(1).a(v).b(v).d(sum);
+            sum = composer.gate_mul(constraint);
+            composer.append_logic_xor(v, v, 32);
+        }
+
+        let expected = composer.append_public(self.sum);
+        composer.assert_equal(sum, expected);
+
+        Ok(())
+    }
+}
+
+fn values(seed: u64) -> [BlsScalar; ROUNDS] {
+    let mut value = seed;
+
+    [(); ROUNDS].map(|_| {
+        value = value.wrapping_mul(6364136223846793005).wrapping_add(1);
+        BlsScalar::from(value >> 32)
+    })
+}
+
+fn synthesize(circuit: &MixedCircuit) -> Plonk<JubjubAffine> {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    composer
+}
+
+#[test]
+fn reorder_is_deterministic() {
+    let original = synthesize(&MixedCircuit::new(values(1)));
+
+    let mut a = synthesize(&MixedCircuit::new(values(1)));
+    let mut b = synthesize(&MixedCircuit::new(values(2)));
+    a.reorder_for_locality();
+    b.reorder_for_locality();
+
+    // the order doesn't depend on the witnesses
+    assert_eq!(a.circuit_id(), b.circuit_id());
+    assert_ne!(a.circuit_id(), original.circuit_id());
+
+    // only the rows move
+    let (stats, reordered) = (original.statistics(), a.statistics());
+    assert_eq!(stats.gates, reordered.gates);
+    assert_eq!(stats.witnesses, reordered.witnesses);
+    assert_eq!(stats.public_inputs, reordered.public_inputs);
+    assert_eq!(stats.copy, reordered.copy);
+}
+
+#[test]
+fn reordered_keys() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::setup_for_circuit(
+            &MixedCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::compile_for_locality(
+            &pp,
+            b"plonk",
+            &MixedCircuit::default(),
+            InitConfig::default(),
+        )
+        .expect("failed to compile circuit");
+    let (_, original_verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = MixedCircuit::new(values(3));
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    // the public inputs keep their order
+    assert_eq!(public_inputs, vec![circuit.values[0], circuit.sum]);
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // the keys must match the row order
+    original_verifier
+        .verify(&proof, &public_inputs)
+        .expect_err("the keys describe other rows");
+
+    // and so must the composer of the prover
+    let mut composer = synthesize(&circuit);
+    assert!(prover.check_composer(&composer).is_err());
+
+    composer.reorder_for_locality();
+    let (proof, public_inputs) = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn reordered_unsatisfied() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::setup_for_circuit(
+            &MixedCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MixedCircuit>::compile_for_locality(
+            &pp,
+            b"plonk",
+            &MixedCircuit::default(),
+            InitConfig::default(),
+        )
+        .expect("failed to compile circuit");
+
+    // a wrong sum is still refused
+    let mut circuit = MixedCircuit::new(values(4));
+    circuit.sum += BlsScalar::one();
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+}
@@ -8,10 +8,11 @@ use bls_12_381::Fr as BlsScalar;
 use ec_pairing::TatePairing;
 use jub_jub::JubjubAffine;
 use rand::rngs::StdRng;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 use zkplonk::Plonk;
 use zkplonk::PlonkKey;
 use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
 use zksnarks::error::Error;
 use zksnarks::keypair::Keypair;
 use zksnarks::plonk::PlonkParams;
@@ -331,3 +332,739 @@ fn logic_xor_works() {
             .expect("failed to compile circuit");
     }
 }
+
+#[test]
+fn logic_or_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 8;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+        bits: usize,
+    }
+
+    impl DummyCircuit {
+        pub fn new(a: BlsScalar, b: BlsScalar, bits: usize) -> Self {
+            let x = BlsScalar::pow_of_2(bits as u64) - BlsScalar::one();
+
+            let a = a & x;
+            let b = b & x;
+            // the bits of `a ^ b` and `a & b` are disjoint
+            let c = ((a ^ b) & x) + (a & b & x);
+
+            Self { a, b, c, bits }
+        }
+    }
+
+    impl Default for DummyCircuit {
+        fn default() -> Self {
+            Self::new(7u64.into(), 8u64.into(), 256)
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_c = composer.append_witness(self.c);
+
+            let w_x = composer.append_logic_or(w_a, w_b, self.bits);
+
+            composer.assert_equal(w_c, w_x);
+
+            Ok(())
+        }
+    }
+
+    for bits in [8, 32, 256] {
+        let a = BlsScalar::random(&mut rng);
+        let b = BlsScalar::random(&mut rng);
+
+        let circuit = DummyCircuit::new(a, b, bits);
+
+        let (prover, verifier) =
+            PlonkKey::compile_with_circuit(&mut pp, label, &circuit)
+                .expect("failed to compile circuit");
+
+        // positive works
+        {
+            let a = BlsScalar::random(&mut rng);
+            let b = BlsScalar::random(&mut rng);
+
+            let (proof, public_inputs) = prover
+                .create_proof(&mut rng, &DummyCircuit::new(a, b, bits))
+                .expect("failed to prove");
+
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect("failed to verify proof");
+        }
+
+        // negative works
+        {
+            let a = BlsScalar::random(&mut rng);
+            let b = BlsScalar::random(&mut rng);
+
+            let mut circuit = DummyCircuit::new(a, b, bits);
+            let c = circuit.c;
+            circuit.c = c + BlsScalar::one();
+
+            prover
+                .create_proof(&mut rng, &circuit)
+                .expect_err("the provided proof isn't valid");
+        }
+    }
+}
+
+#[test]
+fn component_not_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 8;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+        c: BlsScalar,
+        bits: usize,
+    }
+
+    impl DummyCircuit {
+        pub fn new(a: BlsScalar, bits: usize) -> Self {
+            let x = BlsScalar::pow_of_2(bits as u64) - BlsScalar::one();
+
+            // `!a` over the first `bits` is the mask minus the truncated `a`
+            let c = x - (a & x);
+
+            Self { a, c, bits }
+        }
+    }
+
+    impl Default for DummyCircuit {
+        fn default() -> Self {
+            Self::new(7u64.into(), 256)
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_c = composer.append_witness(self.c);
+
+            let w_x = composer.component_not(w_a, self.bits);
+
+            composer.assert_equal(w_c, w_x);
+
+            Ok(())
+        }
+    }
+
+    for bits in [0, 8, 256] {
+        let a = BlsScalar::random(&mut rng);
+        let circuit = DummyCircuit::new(a, bits);
+
+        let (prover, verifier) =
+            PlonkKey::compile_with_circuit(&mut pp, label, &circuit)
+                .expect("failed to compile circuit");
+
+        // positive works
+        {
+            let a = BlsScalar::random(&mut rng);
+
+            let (proof, public_inputs) = prover
+                .create_proof(&mut rng, &DummyCircuit::new(a, bits))
+                .expect("failed to prove");
+
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect("failed to verify proof");
+        }
+
+        // negative works
+        {
+            let a = BlsScalar::random(&mut rng);
+
+            let mut circuit = DummyCircuit::new(a, bits);
+            circuit.c += BlsScalar::one();
+
+            prover
+                .create_proof(&mut rng, &circuit)
+                .expect_err("the provided proof isn't valid");
+        }
+    }
+}
+
+#[test]
+fn component_not_chains_with_and() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 9;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    const BITS: usize = 32;
+
+    // SHA-256 choice function, `ch(e, f, g) = (e & f) ^ (!e & g)`
+    #[derive(Debug)]
+    pub struct ChoiceCircuit {
+        e: BlsScalar,
+        f: BlsScalar,
+        g: BlsScalar,
+        ch: BlsScalar,
+    }
+
+    impl ChoiceCircuit {
+        pub fn new(e: u32, f: u32, g: u32) -> Self {
+            let ch = (e & f) ^ (!e & g);
+
+            Self {
+                e: BlsScalar::from(e as u64),
+                f: BlsScalar::from(f as u64),
+                g: BlsScalar::from(g as u64),
+                ch: BlsScalar::from(ch as u64),
+            }
+        }
+    }
+
+    impl Default for ChoiceCircuit {
+        fn default() -> Self {
+            Self::new(0, 0, 0)
+        }
+    }
+
+    impl Circuit<JubjubAffine> for ChoiceCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let e = composer.append_witness(self.e);
+            let f = composer.append_witness(self.f);
+            let g = composer.append_witness(self.g);
+            let ch = composer.append_public(self.ch);
+
+            let e_and_f = composer.append_logic_and(e, f, BITS);
+            let not_e = composer.component_not(e, BITS);
+            let not_e_and_g = composer.append_logic_and(not_e, g, BITS);
+            let x = composer.append_logic_xor(e_and_f, not_e_and_g, BITS);
+
+            composer.assert_equal(x, ch);
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = PlonkKey::compile_with_circuit(
+        &mut pp,
+        label,
+        &ChoiceCircuit::default(),
+    )
+    .expect("failed to compile circuit");
+
+    let e = rng.next_u32();
+    let f = rng.next_u32();
+    let g = rng.next_u32();
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &ChoiceCircuit::new(e, f, g))
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    let mut circuit = ChoiceCircuit::new(e, f, g);
+    circuit.ch += BlsScalar::one();
+
+    prover
+        .create_proof(&mut rng, &circuit)
+        .expect_err("the provided proof isn't valid");
+}
+
+#[test]
+fn logic_odd_bits_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 8;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+        bits: usize,
+        xor: bool,
+    }
+
+    impl DummyCircuit {
+        // `a` and `b` aren't truncated, so the bits above `bits` must be
+        // ignored by the gate
+        pub fn new(a: BlsScalar, b: BlsScalar, bits: usize, xor: bool) -> Self {
+            let x = BlsScalar::pow_of_2(bits as u64) - BlsScalar::one();
+
+            let c = match xor {
+                true => (a ^ b) & x,
+                false => a & b & x,
+            };
+
+            Self { a, b, c, bits, xor }
+        }
+    }
+
+    impl Default for DummyCircuit {
+        fn default() -> Self {
+            Self::new(7u64.into(), 8u64.into(), 55, false)
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_c = composer.append_witness(self.c);
+
+            let w_x = match self.xor {
+                true => composer.append_logic_xor(w_a, w_b, self.bits),
+                false => composer.append_logic_and(w_a, w_b, self.bits),
+            };
+
+            composer.assert_equal(w_c, w_x);
+
+            Ok(())
+        }
+    }
+
+    for bits in [1, 3, 55, 255] {
+        for xor in [false, true] {
+            let a = BlsScalar::random(&mut rng);
+            let b = BlsScalar::random(&mut rng);
+
+            let circuit = DummyCircuit::new(a, b, bits, xor);
+
+            let (prover, verifier) =
+                PlonkKey::compile_with_circuit(&mut pp, label, &circuit)
+                    .expect("failed to compile circuit");
+
+            // positive works
+            {
+                let a = BlsScalar::random(&mut rng);
+                let b = BlsScalar::random(&mut rng);
+
+                let (proof, public_inputs) = prover
+                    .create_proof(&mut rng, &DummyCircuit::new(a, b, bits, xor))
+                    .expect("failed to prove");
+
+                verifier
+                    .verify(&proof, &public_inputs)
+                    .expect("failed to verify proof");
+            }
+
+            // the padding bit can't leak into the result
+            {
+                let a = BlsScalar::random(&mut rng);
+                let b = BlsScalar::random(&mut rng);
+
+                let x = BlsScalar::pow_of_2(bits as u64 + 1) - BlsScalar::one();
+                let c = match xor {
+                    true => (a ^ b) & x,
+                    false => a & b & x,
+                };
+
+                let mut circuit = DummyCircuit::new(a, b, bits, xor);
+                circuit.c = match circuit.c == c {
+                    true => c + BlsScalar::one(),
+                    false => c,
+                };
+
+                prover
+                    .create_proof(&mut rng, &circuit)
+                    .expect_err("the provided proof isn't valid");
+            }
+        }
+    }
+}
+
+#[test]
+fn logic_xor3_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 9;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+        d: BlsScalar,
+        bits: usize,
+    }
+
+    impl DummyCircuit {
+        pub fn new(
+            a: BlsScalar,
+            b: BlsScalar,
+            c: BlsScalar,
+            bits: usize,
+        ) -> Self {
+            let x = BlsScalar::pow_of_2(bits as u64) - BlsScalar::one();
+            let d = (a ^ b ^ c) & x;
+
+            Self { a, b, c, d, bits }
+        }
+    }
+
+    impl Default for DummyCircuit {
+        fn default() -> Self {
+            Self::new(7u64.into(), 8u64.into(), 9u64.into(), 64)
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_c = composer.append_witness(self.c);
+            let w_d = composer.append_witness(self.d);
+
+            let w_x = composer.append_logic_xor3(w_a, w_b, w_c, self.bits);
+
+            composer.assert_equal(w_d, w_x);
+
+            Ok(())
+        }
+    }
+
+    for bits in [1, 32, 55, 255] {
+        let circuit = DummyCircuit::new(
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            bits,
+        );
+
+        let (prover, verifier) =
+            PlonkKey::compile_with_circuit(&mut pp, label, &circuit)
+                .expect("failed to compile circuit");
+
+        let a = BlsScalar::random(&mut rng);
+        let b = BlsScalar::random(&mut rng);
+        let c = BlsScalar::random(&mut rng);
+
+        // positive works
+        {
+            let (proof, public_inputs) = prover
+                .create_proof(&mut rng, &DummyCircuit::new(a, b, c, bits))
+                .expect("failed to prove");
+
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect("failed to verify proof");
+        }
+
+        // negative works
+        {
+            let mut circuit = DummyCircuit::new(a, b, c, bits);
+            circuit.d =
+                (a ^ b) & (BlsScalar::pow_of_2(bits as u64) - BlsScalar::one());
+            if circuit.d == DummyCircuit::new(a, b, c, bits).d {
+                circuit.d += BlsScalar::one();
+            }
+
+            prover
+                .create_proof(&mut rng, &circuit)
+                .expect_err("the provided proof isn't valid");
+        }
+    }
+}
+
+#[test]
+fn logic_xor3_gates() {
+    for bits in [32, 55] {
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let a = composer.append_witness(BlsScalar::from(7u64));
+        let b = composer.append_witness(BlsScalar::from(8u64));
+        let c = composer.append_witness(BlsScalar::from(9u64));
+
+        let gates = composer.statistics().gates;
+        composer.append_logic_xor3(a, b, c, bits);
+        let xor3 = composer.statistics().gates - gates;
+
+        let gates = composer.statistics().gates;
+        let ab = composer.append_logic_xor(a, b, bits);
+        composer.append_logic_xor(ab, c, bits);
+        let baseline = composer.statistics().gates - gates;
+
+        let quads = (bits + 1) / 2;
+        match bits % 2 {
+            0 => {
+                assert_eq!(xor3, 2 * (quads + 1));
+                assert_eq!(baseline, xor3);
+            }
+            _ => {
+                assert_eq!(xor3, 2 * (quads + 1) + 3);
+                assert_eq!(baseline, xor3 + 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn component_xor3_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 4;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit {
+        bits: [BlsScalar; 3],
+        d: BlsScalar,
+    }
+
+    impl DummyCircuit {
+        pub fn new(a: bool, b: bool, c: bool) -> Self {
+            Self {
+                bits: [a, b, c].map(|bit| BlsScalar::from(bit as u64)),
+                d: BlsScalar::from((a ^ b ^ c) as u64),
+            }
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let [a, b, c] = self.bits.map(|bit| {
+                let bit = composer.append_witness(bit);
+                composer.component_boolean(bit);
+                bit
+            });
+            let w_d = composer.append_witness(self.d);
+
+            let w_x = composer.component_xor3(a, b, c);
+
+            composer.assert_equal(w_d, w_x);
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = PlonkKey::compile_with_circuit(
+        &mut pp,
+        label,
+        &DummyCircuit::default(),
+    )
+    .expect("failed to compile circuit");
+
+    for i in 0..8u8 {
+        let (a, b, c) = (i & 1 == 1, i & 2 == 2, i & 4 == 4);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DummyCircuit::new(a, b, c))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        let mut circuit = DummyCircuit::new(a, b, c);
+        circuit.d = BlsScalar::one() - circuit.d;
+
+        prover
+            .create_proof(&mut rng, &circuit)
+            .expect_err("the provided proof isn't valid");
+    }
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::one());
+    let gates = composer.statistics().gates;
+    composer.component_xor3(a, a, a);
+    assert_eq!(composer.statistics().gates - gates, 2);
+}
+
+#[test]
+fn component_ch_maj_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 5;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit {
+        bits: [BlsScalar; 3],
+        ch: BlsScalar,
+        maj: BlsScalar,
+        boolean: bool,
+    }
+
+    impl DummyCircuit {
+        pub fn new(a: bool, b: bool, c: bool) -> Self {
+            Self {
+                bits: [a, b, c].map(|bit| BlsScalar::from(bit as u64)),
+                ch: BlsScalar::from(((a & b) ^ (!a & c)) as u64),
+                maj: BlsScalar::from(((a & b) ^ (a & c) ^ (b & c)) as u64),
+                boolean: true,
+            }
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let [a, b, c] = self.bits.map(|bit| {
+                let bit = composer.append_witness(bit);
+                if self.boolean {
+                    composer.component_boolean(bit);
+                }
+                bit
+            });
+            let w_ch = composer.append_witness(self.ch);
+            let w_maj = composer.append_witness(self.maj);
+
+            let w_x = composer.component_ch(a, b, c);
+            composer.assert_equal(w_ch, w_x);
+
+            let w_x = composer.component_maj(a, b, c);
+            composer.assert_equal(w_maj, w_x);
+
+            Ok(())
+        }
+    }
+
+    let circuit = DummyCircuit::new(false, false, false);
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, label, &circuit)
+            .expect("failed to compile circuit");
+
+    for i in 0..8u8 {
+        let (a, b, c) = (i & 1 == 1, i & 2 == 2, i & 4 == 4);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DummyCircuit::new(a, b, c))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        let mut circuit = DummyCircuit::new(a, b, c);
+        circuit.ch = BlsScalar::one() - circuit.ch;
+
+        prover
+            .create_proof(&mut rng, &circuit)
+            .expect_err("the provided proof isn't valid");
+
+        let mut circuit = DummyCircuit::new(a, b, c);
+        circuit.maj = BlsScalar::one() - circuit.maj;
+
+        prover
+            .create_proof(&mut rng, &circuit)
+            .expect_err("the provided proof isn't valid");
+    }
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let a = composer.append_witness(BlsScalar::one());
+
+    let gates = composer.statistics().gates;
+    composer.component_ch(a, a, a);
+    assert_eq!(composer.statistics().gates - gates, 2);
+
+    let gates = composer.statistics().gates;
+    composer.component_maj(a, a, a);
+    assert_eq!(composer.statistics().gates - gates, 3);
+}
+
+#[test]
+fn component_ch_maj_non_boolean() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let n = 5;
+    let label = b"demo";
+    let mut pp = PlonkParams::setup(n, &mut rng);
+
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit {
+        bits: [BlsScalar; 3],
+        ch: BlsScalar,
+        maj: BlsScalar,
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let [a, b, c] = self.bits.map(|bit| composer.append_witness(bit));
+            let w_ch = composer.append_witness(self.ch);
+            let w_maj = composer.append_witness(self.maj);
+
+            let w_x = composer.component_ch(a, b, c);
+            composer.assert_equal(w_ch, w_x);
+
+            let w_x = composer.component_maj(a, b, c);
+            composer.assert_equal(w_maj, w_x);
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = PlonkKey::compile_with_circuit(
+        &mut pp,
+        label,
+        &DummyCircuit::default(),
+    )
+    .expect("failed to compile circuit");
+
+    // without boolean constraints, `a = 2` scales `b - c` to a result that
+    // isn't a bit
+    let two = BlsScalar::from(2u64);
+    let circuit = DummyCircuit {
+        bits: [two, BlsScalar::one(), BlsScalar::zero()],
+        ch: two,
+        maj: two,
+    };
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("the unconstrained inputs are accepted");
+}
@@ -0,0 +1,243 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::{Decode, Encode};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Rows `(a, b, a op b)` of a 4-bit operation
+fn table(op: fn(u64, u64) -> u64) -> Vec<[BlsScalar; 3]> {
+    (0..16)
+        .flat_map(|a| (0..16).map(move |b| (a, b)))
+        .map(|(a, b)| [a, b, op(a, b)].map(BlsScalar::from))
+        .collect()
+}
+
+/// Looks up `(a, b, o)` in the XOR table, or in the AND table if `and` is
+/// set
+#[derive(Debug, Clone, Default)]
+pub struct LookupCircuit {
+    rows: Vec<(u64, u64, u64)>,
+    and: bool,
+}
+
+impl LookupCircuit {
+    pub fn new(and: bool) -> Self {
+        let op: fn(u64, u64) -> u64 = match and {
+            true => |a, b| a & b,
+            false => |a, b| a ^ b,
+        };
+        let rows = [(3, 5), (15, 0), (3, 5), (9, 12)]
+            .into_iter()
+            .map(|(a, b)| (a, b, op(a, b)))
+            .collect();
+
+        Self { rows, and }
+    }
+}
+
+impl Circuit<JubjubAffine> for LookupCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let xor = composer.append_lookup_table(&table(|a, b| a ^ b));
+        let and = composer.append_lookup_table(&table(|a, b| a & b));
+        let table = match self.and {
+            true => and,
+            false => xor,
+        };
+
+        for (a, b, o) in self.rows.iter() {
+            let a = composer.append_witness(*a);
+            let b = composer.append_witness(*b);
+            let o = composer.append_witness(*o);
+
+            composer.component_lookup(a, b, o, table);
+        }
+
+        // the lookup gates sit next to the usual ones
+        let x = composer.append_witness(7u64);
+        let y = composer.append_public(7u64);
+        composer.assert_equal(x, y);
+
+        Ok(())
+    }
+}
+
+type LookupKey = PlonkKey<TatePairing, JubjubAffine, LookupCircuit>;
+
+fn keys(rng: &mut StdRng, and: bool) -> (JubjubProver, JubjubVerifier) {
+    let circuit = LookupCircuit::new(and);
+    let pp = LookupKey::setup_for_circuit(&circuit, rng)
+        .expect("failed to synthesize");
+
+    LookupKey::compile_with_circuit(&pp, b"lookup", &circuit)
+        .expect("failed to compile circuit")
+}
+
+fn prove(
+    rng: &mut StdRng,
+    prover: &JubjubProver,
+    verifier: &JubjubVerifier,
+    circuit: &LookupCircuit,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn lookup_xor_works() {
+    let mut rng = StdRng::seed_from_u64(0x100c);
+    let (prover, verifier) = keys(&mut rng, false);
+
+    prove(&mut rng, &prover, &verifier, &LookupCircuit::new(false))
+        .expect("failed to prove");
+}
+
+#[test]
+fn lookup_refuses_rows_out_of_the_table() {
+    let mut rng = StdRng::seed_from_u64(0x100d);
+    let (prover, verifier) = keys(&mut rng, false);
+
+    // a triple that isn't a row of the table
+    let mut circuit = LookupCircuit::new(false);
+    circuit.rows[1].2 ^= 1;
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+
+    // the rows of the AND table aren't rows of the XOR table
+    let circuit = LookupCircuit {
+        and: false,
+        ..LookupCircuit::new(true)
+    };
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+}
+
+#[test]
+fn lookup_reads_the_selected_table() {
+    let mut rng = StdRng::seed_from_u64(0x100e);
+    let (prover, verifier) = keys(&mut rng, true);
+
+    prove(&mut rng, &prover, &verifier, &LookupCircuit::new(true))
+        .expect("failed to prove");
+
+    let mut circuit = LookupCircuit::new(true);
+    circuit.rows[3].2 = 9 ^ 12;
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+}
+
+#[test]
+fn lookup_proof_encoding() {
+    let mut rng = StdRng::seed_from_u64(0x100f);
+    let (prover, verifier) = keys(&mut rng, false);
+
+    let (proof, pi) = prover
+        .create_proof(&mut rng, &LookupCircuit::new(false))
+        .expect("failed to prove");
+
+    let bytes = proof.encode();
+    assert_eq!(
+        bytes.len(),
+        Proof::<TatePairing>::encoded_size()
+            + Proof::<TatePairing>::lookup_size()
+    );
+
+    let decoded = Proof::<TatePairing>::try_from(bytes.as_slice())
+        .expect("failed to decode proof");
+    assert_eq!(decoded, proof);
+    let decoded_codec = Proof::<TatePairing>::decode(&mut bytes.as_slice())
+        .expect("failed to decode proof");
+    assert_eq!(decoded_codec, proof);
+    verifier.verify(&decoded, &pi).expect("failed to verify");
+
+    let mut reader = bytes.as_slice();
+    let decoded_reader = Proof::<TatePairing>::decode_from_reader(&mut reader)
+        .expect("failed to read proof");
+    assert_eq!(decoded_reader, proof);
+    assert!(reader.is_empty());
+
+    // consecutive proofs are read from the same stream
+    let stream = [bytes.as_slice(), bytes.as_slice()].concat();
+    let mut reader = stream.as_slice();
+    for _ in 0..2 {
+        let decoded = Proof::<TatePairing>::decode_from_reader(&mut reader)
+            .expect("failed to read framed proof");
+        assert_eq!(decoded, proof);
+    }
+    assert!(reader.is_empty());
+
+    // the proof without its lookup argument is refused
+    let mut base = bytes[..Proof::<TatePairing>::encoded_size()].to_vec();
+    *base.last_mut().expect("the tag is encoded") = 0;
+    let base = Proof::<TatePairing>::try_from(base.as_slice())
+        .expect("failed to decode proof");
+    assert_eq!(base.encode().len(), Proof::<TatePairing>::encoded_size());
+    assert!(verifier.verify(&base, &pi).is_err());
+
+    // a tag announcing the lookup argument without its bytes is truncated
+    let err = Proof::<TatePairing>::try_from(
+        &bytes[..Proof::<TatePairing>::encoded_size()],
+    )
+    .expect_err("the lookup argument is missing");
+    assert_eq!(err.field, "lookup_m_comm");
+    assert_eq!(err.reason, DecodeReason::Truncated);
+
+    let aggregated = prover
+        .aggregate(&[(proof.clone(), pi.clone()), (proof, pi.clone())])
+        .expect("failed to aggregate");
+    let decoded = AggregatedProof::<TatePairing>::decode(
+        &mut aggregated.encode().as_slice(),
+    )
+    .expect("failed to decode aggregated proof");
+    assert_eq!(decoded, aggregated);
+    verifier
+        .verify_aggregated(&decoded, &[pi.clone(), pi])
+        .expect("failed to verify");
+}
+
+#[test]
+fn proofs_without_tables_keep_their_size() {
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit;
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let x = composer.append_witness(7u64);
+            let y = composer.append_public(7u64);
+            composer.assert_equal(x, y);
+
+            Ok(())
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(0x1010);
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::setup_for_circuit(
+            &DummyCircuit,
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let (proof, pi) = prover
+        .create_proof(&mut rng, &DummyCircuit)
+        .expect("failed to prove");
+    let bytes = proof.encode();
+    assert_eq!(bytes.len(), Proof::<TatePairing>::encoded_size());
+
+    let decoded = Proof::<TatePairing>::try_from(bytes.as_slice())
+        .expect("failed to decode proof");
+    verifier.verify(&decoded, &pi).expect("failed to verify");
+}
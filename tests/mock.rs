@@ -0,0 +1,374 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "test-utils")]
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::{MockError, MockProver, MockVerifier};
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::*;
+
+fn compile_mock<C>(
+    rng: &mut StdRng,
+) -> (
+    MockProver<TatePairing, JubjubAffine>,
+    MockVerifier<TatePairing>,
+)
+where
+    C: Circuit<JubjubAffine, ConstraintSystem = Plonk<JubjubAffine>>,
+{
+    let pp = PlonkParams::<TatePairing>::setup(1, rng);
+
+    PlonkKey::<TatePairing, JubjubAffine, C>::compile_mock(&pp)
+        .expect("failed to compile circuit")
+}
+
+#[test]
+fn mock_boolean_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+    }
+
+    impl DummyCircuit {
+        pub fn new(a: BlsScalar) -> Self {
+            Self { a }
+        }
+    }
+
+    impl Default for DummyCircuit {
+        fn default() -> Self {
+            Self::new(1u64.into())
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+
+            composer.component_boolean(w_a);
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = compile_mock::<DummyCircuit>(&mut rng);
+
+    // default works
+    {
+        let a = BlsScalar::one();
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DummyCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        let a = BlsScalar::zero();
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DummyCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // negative works
+    {
+        let a = BlsScalar::from(2u64);
+
+        prover
+            .create_proof(&mut rng, &DummyCircuit::new(a))
+            .expect_err("invalid circuit");
+    }
+}
+
+#[test]
+fn mock_decomposition_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit<const N: usize> {
+        a: BlsScalar,
+        bits: [BlsScalar; N],
+    }
+
+    impl<const N: usize> DummyCircuit<N> {
+        pub fn new(a: BlsScalar) -> Self {
+            let mut bits = [BlsScalar::zero(); N];
+
+            bits.iter_mut()
+                .zip(a.to_bits().iter().rev())
+                .for_each(|(b, v)| *b = BlsScalar::from(*v as u64));
+
+            Self { a, bits }
+        }
+    }
+
+    impl<const N: usize> Default for DummyCircuit<N> {
+        fn default() -> Self {
+            Self::new(BlsScalar::from(23u64))
+        }
+    }
+
+    impl<const N: usize> Circuit<JubjubAffine> for DummyCircuit<N> {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let mut w_bits: [PrivateWire; N] = [Plonk::<JubjubAffine>::ZERO; N];
+
+            w_bits
+                .iter_mut()
+                .zip(self.bits.iter())
+                .for_each(|(w, b)| *w = composer.append_witness(*b));
+
+            let w_x: [PrivateWire; N] = composer.component_decomposition(w_a);
+
+            w_bits.iter().zip(w_x.iter()).for_each(|(w, b)| {
+                composer.assert_equal(*w, *b);
+            });
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = compile_mock::<DummyCircuit<256>>(&mut rng);
+
+    // default works
+    {
+        let a = BlsScalar::random(&mut rng);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &DummyCircuit::<256>::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // negative works
+    {
+        let a = BlsScalar::random(&mut rng);
+
+        let mut circuit = DummyCircuit::<256>::new(a);
+
+        circuit.bits[10] = circuit.bits[10] ^ BlsScalar::one();
+
+        prover
+            .create_proof(&mut rng, &circuit)
+            .expect_err("invalid proof");
+    }
+}
+
+#[test]
+fn mock_public_inputs_must_match() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            composer.append_public(self.a);
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = compile_mock::<DummyCircuit>(&mut rng);
+
+    let a = BlsScalar::random(&mut rng);
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &DummyCircuit { a })
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    verifier
+        .verify(&proof, &[public_inputs[0] + BlsScalar::one()])
+        .expect_err("wrong public input must not verify");
+    verifier
+        .verify(&proof, &[])
+        .expect_err("missing public input must not verify");
+}
+
+#[test]
+fn mock_range_matches_real_prover() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    #[derive(Debug)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+    }
+
+    impl Default for DummyCircuit {
+        fn default() -> Self {
+            Self { a: 7u64.into() }
+        }
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+
+            composer.component_range(w_a, 8);
+
+            Ok(())
+        }
+    }
+
+    let (mock_prover, mock_verifier) = compile_mock::<DummyCircuit>(&mut rng);
+
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::setup_for_circuit(
+            &DummyCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    for (a, valid) in [(0u64, true), (255, true), (256, false), (300, false)] {
+        let circuit = DummyCircuit { a: a.into() };
+
+        let mock = mock_prover
+            .create_proof(&mut rng, &circuit)
+            .map(|(proof, pi)| mock_verifier.verify(&proof, &pi));
+        let real = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+
+        assert_eq!(matches!(mock, Ok(Ok(()))), valid);
+        assert_eq!(real.is_ok(), valid);
+
+        if !valid {
+            assert!(matches!(mock, Err(MockError::Unsatisfied { .. })));
+        }
+    }
+}
+
+#[test]
+fn mock_refuses_unsupported_gates() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+
+            composer.append_logic_xor(w_a, w_b, 8);
+
+            Ok(())
+        }
+    }
+
+    let (prover, _) = compile_mock::<DummyCircuit>(&mut rng);
+
+    // the logic gates aren't evaluated, so even valid witnesses are refused
+    let circuit = DummyCircuit {
+        a: 3u64.into(),
+        b: 5u64.into(),
+    };
+    let result = prover.create_proof(&mut rng, &circuit);
+    assert!(matches!(
+        result,
+        Err(MockError::Unsupported {
+            family: Some(SelectorFamily::Logic),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn mock_checks_lookups() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    #[derive(Debug, Default)]
+    pub struct DummyCircuit {
+        o: BlsScalar,
+    }
+
+    impl Circuit<JubjubAffine> for DummyCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let rows: Vec<[BlsScalar; 3]> = (0..4u64)
+                .flat_map(|a| (0..4u64).map(move |b| [a, b, a ^ b]))
+                .map(|row| row.map(BlsScalar::from))
+                .collect();
+            let table = composer.append_lookup_table(&rows);
+
+            let w_a = composer.append_witness(3u64);
+            let w_b = composer.append_witness(1u64);
+            let w_o = composer.append_witness(self.o);
+
+            composer.component_lookup(w_a, w_b, w_o, table);
+
+            Ok(())
+        }
+    }
+
+    let (prover, verifier) = compile_mock::<DummyCircuit>(&mut rng);
+
+    let circuit = DummyCircuit { o: 2u64.into() };
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify");
+
+    // a triple that isn't a row of the table
+    let circuit = DummyCircuit { o: 3u64.into() };
+    let result = prover.create_proof(&mut rng, &circuit);
+    assert!(matches!(result, Err(MockError::Unsatisfied { .. })));
+}
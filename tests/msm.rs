@@ -0,0 +1,167 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public point is the multi-scalar multiplication of `K` pairs
+#[derive(Debug)]
+pub struct MsmCircuit<const K: usize> {
+    scalars: [JubjubScalar; K],
+    points: [JubjubAffine; K],
+    result: JubjubAffine,
+}
+
+impl<const K: usize> MsmCircuit<K> {
+    pub fn new(scalars: [JubjubScalar; K], points: [JubjubAffine; K]) -> Self {
+        let result = scalars
+            .iter()
+            .zip(points.iter())
+            .fold(JubjubExtended::ADDITIVE_IDENTITY, |acc, (s, p)| {
+                acc + *p * *s
+            });
+
+        Self {
+            scalars,
+            points,
+            result: result.into(),
+        }
+    }
+
+    pub fn random(rng: &mut StdRng) -> Self {
+        let scalars = [(); K].map(|_| JubjubScalar::random(&mut *rng));
+        let points = [(); K].map(|_| {
+            let s = JubjubScalar::random(&mut *rng);
+            (JubjubAffine::ADDITIVE_GENERATOR * s).into()
+        });
+
+        Self::new(scalars, points)
+    }
+}
+
+impl<const K: usize> Default for MsmCircuit<K> {
+    fn default() -> Self {
+        let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+        Self::new([JubjubScalar::one(); K], [g; K])
+    }
+}
+
+impl<const K: usize> Circuit<JubjubAffine> for MsmCircuit<K> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let pairs: Vec<_> = self
+            .scalars
+            .iter()
+            .zip(self.points.iter())
+            .map(|(s, p)| {
+                (composer.append_witness(*s), composer.append_point(*p))
+            })
+            .collect();
+
+        let result = composer.component_msm(&pairs);
+        composer.assert_equal_public_point(result, self.result);
+
+        Ok(())
+    }
+}
+
+fn msm_works<const K: usize>() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &MsmCircuit::<K>::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MsmCircuit<K>>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    // random pairs, and pairs of zero scalars and of the identity
+    let mut zeros = MsmCircuit::<K>::random(&mut rng);
+    zeros.scalars[0] = JubjubScalar::zero();
+    zeros.points[K - 1] = JubjubAffine::ADDITIVE_IDENTITY;
+    let zeros = MsmCircuit::new(zeros.scalars, zeros.points);
+
+    for circuit in [MsmCircuit::<K>::random(&mut rng), zeros] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // a result missing the last pair
+    let circuit = MsmCircuit::<K>::random(&mut rng);
+    let mut scalars = circuit.scalars;
+    scalars[K - 1] = JubjubScalar::zero();
+    let wrong = MsmCircuit::new(scalars, circuit.points);
+    let circuit = MsmCircuit {
+        result: wrong.result,
+        ..circuit
+    };
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrong result must not verify");
+}
+
+#[test]
+fn msm_one_pair() {
+    msm_works::<1>();
+}
+
+#[test]
+fn msm_two_pairs() {
+    msm_works::<2>();
+}
+
+#[test]
+fn msm_four_pairs() {
+    msm_works::<4>();
+}
+
+#[test]
+fn msm_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+    let pairs: Vec<_> = (0..4u64)
+        .map(|i| {
+            let s = composer.append_witness(BlsScalar::from(i));
+            (s, composer.append_point(g))
+        })
+        .collect();
+
+    for k in 1..=4 {
+        let gates = composer.statistics().gates;
+        composer.component_msm(&pairs[..k]);
+        assert_eq!(composer.statistics().gates - gates, 1513 * k + 500);
+    }
+
+    // two separate multiplications and their sum
+    let gates = composer.statistics().gates;
+    let a = composer.component_mul_point(pairs[0].0, pairs[0].1);
+    let b = composer.component_mul_point(pairs[1].0, pairs[1].1);
+    composer.component_add_point(a, b);
+    assert_eq!(composer.statistics().gates - gates, 1513 * 2 + 500 + 502);
+
+    // no pair is the identity, without any gate
+    let gates = composer.statistics().gates;
+    let identity = composer.component_msm(&[]);
+    assert_eq!(composer.statistics().gates, gates);
+    assert_eq!(composer[*identity.x()], BlsScalar::zero());
+    assert_eq!(composer[*identity.y()], BlsScalar::one());
+}
@@ -0,0 +1,141 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Rounds of the fixed-base multiplication, one per digit of a scalar of
+/// 252 bits and one for its sign
+const ROUNDS: usize = 253;
+
+/// Asserts the public point is the multiplication of the generator by the
+/// secret scalar
+#[derive(Debug)]
+pub struct GeneratorCircuit {
+    scalar: BlsScalar,
+    point: JubjubAffine,
+}
+
+impl GeneratorCircuit {
+    pub fn new(scalar: JubjubScalar) -> Self {
+        Self {
+            scalar: scalar.into(),
+            point: (JubjubAffine::ADDITIVE_GENERATOR * scalar).into(),
+        }
+    }
+}
+
+impl Default for GeneratorCircuit {
+    fn default() -> Self {
+        Self::new(JubjubScalar::from(7u64))
+    }
+}
+
+impl Circuit<JubjubAffine> for GeneratorCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let scalar = composer.append_witness(self.scalar);
+        let scalar = composer.component_assert_jubjub_scalar(scalar);
+
+        let point = composer.component_mul_generator_typed(
+            scalar,
+            JubjubAffine::ADDITIVE_GENERATOR,
+        )?;
+        composer.assert_equal_public_point(point, self.point);
+
+        Ok(())
+    }
+}
+
+type GeneratorKey = PlonkKey<TatePairing, JubjubAffine, GeneratorCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = GeneratorKey::setup_for_circuit(&GeneratorCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    GeneratorKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn mul_generator_honest_proofs() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for scalar in [
+        JubjubScalar::zero(),
+        -JubjubScalar::one(),
+        JubjubScalar::random(&mut rng),
+        JubjubScalar::random(&mut rng),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &GeneratorCircuit::new(scalar))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn mul_generator_refuses_non_canonical_scalar() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // the modulus plus one, congruent to one
+    let modulus = BlsScalar::from(-JubjubScalar::one()) + BlsScalar::one();
+    let circuit = GeneratorCircuit {
+        scalar: modulus + BlsScalar::one(),
+        point: JubjubAffine::ADDITIVE_GENERATOR,
+    };
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a non canonical scalar must not verify");
+}
+
+#[test]
+fn mul_generator_refuses_forged_accumulator() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let circuit = GeneratorCircuit::new(JubjubScalar::random(&mut rng));
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // every round appends the point accumulator, the scalar accumulator and
+    // the cross product of the added point, and the final accumulators
+    // follow the last round
+    let witnesses = composer.statistics().witnesses;
+    let round = witnesses - 3 - 4 * ROUNDS + 4 * (ROUNDS / 2);
+
+    // the x coordinate of the point and the scalar of a middle round
+    for i in [round, round + 2] {
+        let mut forged = composer.clone();
+
+        let mut state = forged.serialize_witness_state();
+        let at = 30 + i * 32;
+        state[at..at + 32].copy_from_slice(&BlsScalar::from(5u64).encode());
+        forged
+            .apply_witness_state(&state)
+            .expect("the state belongs to the composer");
+
+        let result = prover
+            .create_proof_with_composer(&mut rng, &forged)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a forged accumulator must not verify");
+    }
+}
@@ -0,0 +1,157 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Multiplies the generator by every scalar with the windowed
+/// multiplication, and asserts the result is `expected`
+#[derive(Debug)]
+pub struct WindowedCircuit {
+    scalars: [BlsScalar; 3],
+    expected: [JubjubAffine; 3],
+}
+
+impl WindowedCircuit {
+    pub fn new(scalars: [JubjubScalar; 3]) -> Self {
+        let expected =
+            scalars.map(|s| (JubjubAffine::ADDITIVE_GENERATOR * s).into());
+
+        Self {
+            scalars: scalars.map(BlsScalar::from),
+            expected,
+        }
+    }
+}
+
+impl Default for WindowedCircuit {
+    fn default() -> Self {
+        Self::new([JubjubScalar::one(); 3])
+    }
+}
+
+impl Circuit<JubjubAffine> for WindowedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let table = composer
+            .append_windowed_base_table(JubjubAffine::ADDITIVE_GENERATOR);
+
+        for (scalar, expected) in self.scalars.iter().zip(self.expected) {
+            let w = composer.append_witness(*scalar);
+            let point = composer.component_mul_generator_windowed(w, &table);
+
+            composer.assert_equal_public_point(point, expected);
+        }
+
+        Ok(())
+    }
+}
+
+type WindowedKey = PlonkKey<TatePairing, JubjubAffine, WindowedCircuit>;
+
+fn prove(
+    rng: &mut StdRng,
+    prover: &JubjubProver,
+    verifier: &JubjubVerifier,
+    circuit: &WindowedCircuit,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn mul_generator_windowed_works() {
+    let mut rng = StdRng::seed_from_u64(0xf1ed);
+
+    let pp =
+        WindowedKey::setup_for_circuit(&WindowedCircuit::default(), &mut rng)
+            .expect("failed to synthesize");
+    let (prover, verifier) =
+        WindowedKey::compile(&pp).expect("failed to compile circuit");
+
+    // the extreme digits and random scalars
+    let scalars = [
+        JubjubScalar::zero(),
+        -JubjubScalar::one(),
+        JubjubScalar::random(&mut rng),
+    ];
+    prove(&mut rng, &prover, &verifier, &WindowedCircuit::new(scalars))
+        .expect("failed to prove");
+
+    let scalars = [(); 3].map(|_| JubjubScalar::random(&mut rng));
+    prove(&mut rng, &prover, &verifier, &WindowedCircuit::new(scalars))
+        .expect("failed to prove");
+
+    // a wrong result
+    let mut circuit = WindowedCircuit::new(scalars);
+    circuit.expected[1] = circuit.expected[2];
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+
+    // a scalar above 2^253, whose top digit isn't in the table
+    let mut circuit = WindowedCircuit::new(scalars);
+    circuit.scalars[0] = -BlsScalar::one();
+    circuit.expected[0] =
+        (JubjubAffine::ADDITIVE_GENERATOR * -JubjubScalar::one()).into();
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+}
+
+#[test]
+fn mul_generator_windowed_matches_wnaf() {
+    let mut rng = StdRng::seed_from_u64(0xf1ee);
+
+    for _ in 0..8 {
+        let scalar = JubjubScalar::random(&mut rng);
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let table = composer
+            .append_windowed_base_table(JubjubAffine::ADDITIVE_GENERATOR);
+        let w = composer.append_witness(scalar);
+
+        let windowed = composer.component_mul_generator_windowed(w, &table);
+        let typed = composer.component_assert_jubjub_scalar(w);
+        let wnaf = composer
+            .component_mul_generator_typed(
+                typed,
+                JubjubAffine::ADDITIVE_GENERATOR,
+            )
+            .expect("the wNAF is valid");
+
+        assert_eq!(
+            composer.point_value(&windowed),
+            composer.point_value(&wnaf)
+        );
+    }
+}
+
+#[test]
+fn mul_generator_windowed_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let table =
+        composer.append_windowed_base_table(JubjubAffine::ADDITIVE_GENERATOR);
+
+    assert_eq!(
+        table.tables().len(),
+        WindowedBaseTable::<JubjubAffine>::WINDOWS
+    );
+    assert_eq!(table.generator(), JubjubAffine::ADDITIVE_GENERATOR);
+
+    let w = composer.append_witness(JubjubScalar::from(7u64));
+    let gates = composer.statistics().gates;
+    composer.component_mul_generator_windowed(w, &table);
+    assert_eq!(composer.statistics().gates, gates + 111);
+
+    // the tables are shared by the multiplications
+    let w = composer.append_witness(JubjubScalar::from(9u64));
+    composer.component_mul_generator_windowed(w, &table);
+    assert_eq!(composer.statistics().gates, gates + 2 * 111);
+    assert_eq!(composer.statistics().domain_size, 1 << 13);
+}
@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public point is the multiplication of a point by a scalar of
+/// 64 bits
+#[derive(Debug)]
+pub struct ShortCircuit {
+    scalar: BlsScalar,
+    point: JubjubAffine,
+    result: JubjubAffine,
+}
+
+impl ShortCircuit {
+    pub fn new(scalar: u64, point: JubjubAffine) -> Self {
+        Self {
+            scalar: BlsScalar::from(scalar),
+            point,
+            result: (point * JubjubScalar::from(scalar)).into(),
+        }
+    }
+}
+
+impl Default for ShortCircuit {
+    fn default() -> Self {
+        Self::new(1, JubjubAffine::ADDITIVE_GENERATOR)
+    }
+}
+
+impl Circuit<JubjubAffine> for ShortCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let scalar = composer.append_witness(self.scalar);
+        let point = composer.append_point(self.point);
+
+        let result = composer.component_mul_point_bits::<64>(scalar, point);
+        composer.assert_equal_public_point(result, self.result);
+
+        Ok(())
+    }
+}
+
+type ShortKey = PlonkKey<TatePairing, JubjubAffine, ShortCircuit>;
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+#[test]
+fn mul_point_bits_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = ShortKey::setup_for_circuit(&ShortCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        ShortKey::compile(&pp).expect("failed to compile circuit");
+
+    let mut scalars = vec![0, 1, u64::MAX];
+    scalars.extend((0..4).map(|_| rng.next_u64()));
+
+    for scalar in scalars {
+        let point = random_point(&mut rng);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &ShortCircuit::new(scalar, point))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // a scalar of 65 bits, whose product is claimed correctly
+    let point = random_point(&mut rng);
+    let scalar = BlsScalar::pow_of_2(64) + BlsScalar::one();
+    let result = point * (JubjubScalar::pow_of_2(64) + JubjubScalar::one());
+    let circuit = ShortCircuit {
+        scalar,
+        point,
+        result: result.into(),
+    };
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a scalar over 64 bits must not verify");
+}
+
+#[test]
+fn mul_point_bits_matches_mul_point() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let scalar = JubjubScalar::random(&mut rng);
+    let point = random_point(&mut rng);
+    let expected: JubjubAffine = (point * scalar).into();
+
+    let w_scalar = composer.append_witness(scalar);
+    let w_point = composer.append_point(point);
+
+    let full = composer.component_mul_point(w_scalar, w_point);
+    let bits = composer.component_mul_point_bits::<252>(w_scalar, w_point);
+
+    for result in [full, bits] {
+        assert_eq!(composer[*result.x()], expected.get_x());
+        assert_eq!(composer[*result.y()], expected.get_y());
+    }
+}
+
+#[test]
+fn mul_point_bits_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let scalar = composer.append_witness(BlsScalar::from(5u64));
+    let point = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+
+    let gates = composer.statistics().gates;
+    composer.component_mul_point_bits::<1>(scalar, point);
+    let one = composer.statistics().gates - gates;
+
+    let gates = composer.statistics().gates;
+    composer.component_mul_point_bits::<64>(scalar, point);
+    let short = composer.statistics().gates - gates;
+
+    let gates = composer.statistics().gates;
+    composer.component_mul_point(scalar, point);
+    let full = composer.statistics().gates - gates;
+
+    assert_eq!(one, 8 - 3);
+    assert_eq!(short, 8 * 64 - 3);
+    assert_eq!(full, 8 * 252 - 3);
+}
@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public point is the multiplication of a point by a scalar
+#[derive(Debug)]
+pub struct GlvCircuit {
+    scalar: JubjubScalar,
+    point: JubjubAffine,
+    result: JubjubAffine,
+}
+
+impl GlvCircuit {
+    pub fn new(scalar: JubjubScalar, point: JubjubAffine) -> Self {
+        Self {
+            scalar,
+            point,
+            result: (point * scalar).into(),
+        }
+    }
+}
+
+impl Default for GlvCircuit {
+    fn default() -> Self {
+        Self::new(JubjubScalar::one(), JubjubAffine::ADDITIVE_GENERATOR)
+    }
+}
+
+impl Circuit<JubjubAffine> for GlvCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let scalar = composer.append_witness(self.scalar);
+        let point = composer.append_point(self.point);
+
+        let result = composer.component_mul_point_glv(scalar, point);
+        composer.assert_equal_public_point(result, self.result);
+
+        Ok(())
+    }
+}
+
+type GlvKey = PlonkKey<TatePairing, JubjubAffine, GlvCircuit>;
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+#[test]
+fn mul_point_glv_matches_mul_point() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for _ in 0..8 {
+        let scalar = JubjubScalar::random(&mut rng);
+        let point = random_point(&mut rng);
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let w_scalar = composer.append_witness(scalar);
+        let w_point = composer.append_point(point);
+
+        let gates = composer.statistics().gates;
+        let glv = composer.component_mul_point_glv(w_scalar, w_point);
+        let glv_gates = composer.statistics().gates - gates;
+
+        let gates = composer.statistics().gates;
+        let plain = composer.component_mul_point(w_scalar, w_point);
+        let plain_gates = composer.statistics().gates - gates;
+
+        assert_eq!(composer[*glv.x()], composer[*plain.x()]);
+        assert_eq!(composer[*glv.y()], composer[*plain.y()]);
+        assert_eq!(glv_gates, plain_gates);
+
+        let expected: JubjubAffine = (point * scalar).into();
+        assert_eq!(composer[*glv.x()], expected.get_x());
+        assert_eq!(composer[*glv.y()], expected.get_y());
+    }
+}
+
+#[test]
+fn mul_point_glv_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = GlvKey::setup_for_circuit(&GlvCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        GlvKey::compile(&pp).expect("failed to compile circuit");
+
+    for _ in 0..3 {
+        let scalar = JubjubScalar::random(&mut rng);
+        let circuit = GlvCircuit::new(scalar, random_point(&mut rng));
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // a wrong result is refused
+        let circuit = GlvCircuit {
+            result: random_point(&mut rng),
+            ..circuit
+        };
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a wrong result must not verify");
+    }
+}
@@ -0,0 +1,199 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::gadget::nonnative::{NonNativeFieldWitness, NonNativeModulus};
+use zkplonk::prelude::*;
+
+type Limbs = [u64; 4];
+
+/// Base field modulus of secp256k1, `2^256 - 2^32 - 977`
+const P: Limbs = [0xffff_fffe_ffff_fc2f, u64::MAX, u64::MAX, u64::MAX];
+
+fn below(a: &Limbs, b: &Limbs) -> bool {
+    a.iter().rev().cmp(b.iter().rev()) == std::cmp::Ordering::Less
+}
+
+fn add_mod(a: Limbs, b: Limbs) -> Limbs {
+    let mut carry = 0u128;
+    let mut sum: Limbs = core::array::from_fn(|i| {
+        let t = a[i] as u128 + b[i] as u128 + carry;
+        carry = t >> 64;
+
+        t as u64
+    });
+
+    if carry == 1 || !below(&sum, &P) {
+        let mut borrow = false;
+        for (s, p) in sum.iter_mut().zip(P) {
+            let (d, x) = s.overflowing_sub(p);
+            let (d, y) = d.overflowing_sub(borrow as u64);
+            *s = d;
+            borrow = x || y;
+        }
+    }
+
+    sum
+}
+
+/// `a · b mod p` by double and add
+fn mul_mod(a: Limbs, b: Limbs) -> Limbs {
+    (0..256).rev().fold([0; 4], |acc, bit| {
+        let acc = add_mod(acc, acc);
+        match b[bit / 64] >> (bit % 64) & 1 {
+            1 => add_mod(acc, a),
+            _ => acc,
+        }
+    })
+}
+
+fn random(rng: &mut StdRng) -> Limbs {
+    loop {
+        let limbs = [(); 4].map(|_| rng.next_u64());
+        if below(&limbs, &P) {
+            return limbs;
+        }
+    }
+}
+
+/// Asserts the public value is the product modulo `p` of the secret
+/// operands, optionally with a given quotient and remainder
+#[derive(Debug, Default)]
+pub struct MulCircuit {
+    a: Limbs,
+    b: Limbs,
+    c: Limbs,
+    hint: Option<(Limbs, Limbs)>,
+}
+
+impl MulCircuit {
+    pub fn new(a: Limbs, b: Limbs) -> Self {
+        let c = mul_mod(a, b);
+
+        Self {
+            a,
+            b,
+            c,
+            hint: None,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for MulCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let p = NonNativeModulus::new(P);
+
+        let a = NonNativeFieldWitness::witness(composer, self.a, &p);
+        let b = NonNativeFieldWitness::witness(composer, self.b, &p);
+
+        let c = match self.hint {
+            Some((k, r)) => {
+                composer.component_nonnative_mul_hinted(&a, &b, &p, k, r)
+            }
+            None => composer.component_nonnative_mul(&a, &b, &p),
+        };
+
+        for (w, v) in c.value().limbs().iter().zip(self.c) {
+            let public = composer.append_public(BlsScalar::from(v));
+            composer.assert_equal(*w, public);
+        }
+
+        Ok(())
+    }
+}
+
+type MulKey = PlonkKey<TatePairing, JubjubAffine, MulCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = MulKey::setup_for_circuit(&MulCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    MulKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn nonnative_mul_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let minus_one = [P[0] - 1, P[1], P[2], P[3]];
+    for (a, b) in [
+        ([0; 4], [0; 4]),
+        ([1, 0, 0, 0], minus_one),
+        (minus_one, minus_one),
+        (random(&mut rng), random(&mut rng)),
+        (random(&mut rng), random(&mut rng)),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &MulCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn nonnative_mul_matches_software() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let p = NonNativeModulus::new(P);
+
+    for _ in 0..8 {
+        let (x, y) = (random(&mut rng), random(&mut rng));
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let a = NonNativeFieldWitness::witness(&mut composer, x, &p);
+        let b = NonNativeFieldWitness::witness(&mut composer, y, &p);
+
+        let c = composer.component_nonnative_mul(&a, &b, &p);
+        assert_eq!(c.value().value(&composer), mul_mod(x, y));
+    }
+}
+
+#[test]
+fn nonnative_mul_refuses_wrong_quotient() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // 2 · (p + 5) / 2 = 1 · p + 5
+    let a = [2, 0, 0, 0];
+    let b = [
+        (P[0] + 5) >> 1 | P[1] << 63,
+        P[1] >> 1 | P[2] << 63,
+        P[2] >> 1 | P[3] << 63,
+        P[3] >> 1,
+    ];
+    let honest = MulCircuit::new(a, b);
+    assert_eq!(honest.c, [5, 0, 0, 0]);
+
+    // a quotient one lower satisfies the product with an unreduced
+    // remainder, `0 · p + (p + 5)`
+    let unreduced = [P[0] + 5, P[1], P[2], P[3]];
+    let forged = MulCircuit {
+        c: unreduced,
+        hint: Some(([0; 4], unreduced)),
+        ..MulCircuit::new(a, b)
+    };
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &forged) {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("an unreduced remainder shouldn't verify");
+    }
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &honest)
+        .expect("failed to prove");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
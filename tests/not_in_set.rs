@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+const LEN: usize = 6;
+
+/// Asserts the secret value doesn't belong to the constant set
+#[derive(Debug, Clone, Default)]
+pub struct NotInSetCircuit {
+    set: Vec<BlsScalar>,
+    value: BlsScalar,
+}
+
+impl NotInSetCircuit {
+    pub fn new(set: Vec<BlsScalar>, value: BlsScalar) -> Self {
+        Self { set, value }
+    }
+}
+
+impl Circuit<JubjubAffine> for NotInSetCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let value = composer.append_witness(self.value);
+        composer.component_assert_not_in_set(value, &self.set);
+
+        Ok(())
+    }
+}
+
+type NotInSetKey = PlonkKey<TatePairing, JubjubAffine, NotInSetCircuit>;
+
+fn keys(
+    rng: &mut StdRng,
+    circuit: &NotInSetCircuit,
+) -> (JubjubProver, JubjubVerifier) {
+    let pp = NotInSetKey::setup_for_circuit(circuit, rng)
+        .expect("failed to synthesize");
+
+    NotInSetKey::compile_with_circuit(&pp, b"not-in-set", circuit)
+        .expect("failed to compile circuit")
+}
+
+fn random_set(rng: &mut StdRng) -> Vec<BlsScalar> {
+    (0..LEN).map(|_| BlsScalar::random(&mut *rng)).collect()
+}
+
+#[test]
+fn not_in_set_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let set = random_set(&mut rng);
+    let circuit = NotInSetCircuit::new(set.clone(), BlsScalar::zero());
+    let (prover, verifier) = keys(&mut rng, &circuit);
+
+    for value in [BlsScalar::zero(), BlsScalar::random(&mut rng)] {
+        let circuit = NotInSetCircuit::new(set.clone(), value);
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // a collision with any element is refused
+    for value in set.iter() {
+        let circuit = NotInSetCircuit::new(set.clone(), *value);
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a value of the set must not verify");
+    }
+}
+
+#[test]
+fn not_in_set_empty() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let value = composer.append_witness(BlsScalar::random(&mut rng));
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_not_in_set(value, &[]);
+    assert_eq!(composer.statistics().gates, gates);
+
+    let set = random_set(&mut rng);
+    composer.component_assert_not_in_set(value, &set);
+    assert_eq!(composer.statistics().gates, gates + LEN + 1);
+}
+
+#[test]
+fn not_in_set_refuses_forged_inverse() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let set = random_set(&mut rng);
+    let circuit = NotInSetCircuit::new(set, BlsScalar::random(&mut rng));
+    let (prover, verifier) = keys(&mut rng, &circuit);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and the value, the gadget witnesses
+    // a running product per element and then the inverse
+    let mut state = composer.serialize_witness_state();
+    let offset = 30 + (3 + LEN) * 32;
+    let forged = BlsScalar::random(&mut rng);
+    state[offset..offset + 32].copy_from_slice(&forged.encode());
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged inverse must not verify");
+}
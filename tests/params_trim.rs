@@ -0,0 +1,98 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use poly_commit::Coefficients;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_public(self.b);
+
+        let constraint = Constraint::default().left(1).right(1).a(w_a).b(w_b);
+        composer.gate_add(constraint);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn small_circuit_large_params_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    // the committing key is trimmed to the circuit size on compile
+    for k in [4, 10] {
+        let mut pp = PlonkParams::<TatePairing>::setup(k, &mut rng);
+
+        let (prover, verifier) =
+            PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(
+                &mut pp,
+            )
+            .expect("failed to compile circuit");
+
+        let circuit = DummyCircuit {
+            a: BlsScalar::random(&mut rng),
+            b: BlsScalar::random(&mut rng),
+        };
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+/// Random polynomial of `n` coefficients
+fn random_poly(n: usize, rng: &mut StdRng) -> Coefficients<BlsScalar> {
+    Coefficients::new((0..n).map(|_| BlsScalar::random(&mut *rng)).collect())
+}
+
+#[test]
+fn trimmed_key_commits_as_full_key() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = PlonkParams::<TatePairing>::setup(10, &mut rng);
+    let trimmed = pp.trim(1 << 4);
+
+    // the commitment only reads the powers up to the degree of the
+    // polynomial, so the keys agree on every polynomial the trimmed one holds
+    for n in [1, 7, 1 << 4] {
+        let poly = random_poly(n, &mut rng);
+
+        let full = pp.commit(&poly).expect("failed to commit");
+        let short = trimmed.commit(&poly).expect("failed to commit");
+        assert_eq!(full, short);
+    }
+}
+
+#[test]
+fn trimmed_key_refuses_oversized_polynomial() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = PlonkParams::<TatePairing>::setup(10, &mut rng);
+    let trimmed = pp.trim(1 << 4);
+
+    // the full key holds the polynomial, and the trimmed one refuses it
+    // instead of reading past its powers
+    let poly = random_poly(1 << 8, &mut rng);
+    pp.commit(&poly).expect("failed to commit");
+    assert!(trimmed.commit(&poly).is_err());
+}
@@ -0,0 +1,154 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+fn parity(a: BlsScalar) -> bool {
+    a.to_raw_bytes()[0] & 1 == 1
+}
+
+/// Selects between two public values with the parity of the secret one
+#[derive(Debug, Default)]
+pub struct ParityCircuit {
+    a: BlsScalar,
+    odd: BlsScalar,
+    even: BlsScalar,
+    selected: BlsScalar,
+}
+
+impl ParityCircuit {
+    pub fn new(a: BlsScalar) -> Self {
+        let odd = BlsScalar::from(3u64);
+        let even = BlsScalar::from(7u64);
+        let selected = match parity(a) {
+            true => odd,
+            false => even,
+        };
+
+        Self {
+            a,
+            odd,
+            even,
+            selected,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for ParityCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let bit = composer.component_parity(a);
+
+        let odd = composer.append_public(self.odd);
+        let even = composer.append_public(self.even);
+        let selected = composer.append_public(self.selected);
+
+        let x = composer.component_select(bit, odd, even);
+        composer.assert_equal(x, selected);
+
+        Ok(())
+    }
+}
+
+type ParityKey = PlonkKey<TatePairing, JubjubAffine, ParityCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = ParityKey::setup_for_circuit(&ParityCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    ParityKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn parity_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let max = -BlsScalar::one();
+    for a in [
+        BlsScalar::zero(),
+        BlsScalar::one(),
+        max,
+        max - BlsScalar::one(),
+        max - BlsScalar::from(2u64),
+        BlsScalar::random(&mut rng),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &ParityCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn parity_values() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    // p - 1 is even and p - 2 is odd
+    let max = -BlsScalar::one();
+    for a in [max, max - BlsScalar::one(), BlsScalar::random(&mut rng)] {
+        let w = composer.append_witness(a);
+        let bit = composer.component_parity(w);
+
+        assert_eq!(composer[bit], BlsScalar::from(parity(a) as u64));
+    }
+
+    for _ in 0..8 {
+        let a = rng.next_u64();
+        let w = composer.append_witness(BlsScalar::from(a));
+        let bit = composer.component_parity_bounded(w, 64);
+
+        assert_eq!(composer[bit], BlsScalar::from(a & 1));
+    }
+}
+
+#[test]
+fn parity_refuses_wrapped_half() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // `4 + p` is odd, with the half `(p + 3) / 2`, that is `3 / 2` in the
+    // field
+    let a = BlsScalar::from(4u64);
+    let half = BlsScalar::from(3u64)
+        * BlsScalar::from(2u64).invert().expect("two is invertible");
+
+    let mut circuit = ParityCircuit::new(a);
+    circuit.selected = circuit.odd;
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and `a`, the gadget witnesses the bit
+    // and then the half
+    let mut state = composer.serialize_witness_state();
+    for (witness, value) in [(3, BlsScalar::one()), (4, half)] {
+        let offset = 30 + witness * 32;
+        state[offset..offset + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrapped half must not verify");
+}
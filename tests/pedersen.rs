@@ -0,0 +1,146 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Blinding generator of the test.
+///
+/// Its discrete logarithm is known, so it must not be used outside tests.
+fn blinding_generator() -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::from(0xdead_beefu64))
+        .into()
+}
+
+fn commit(value: JubjubScalar, blinder: JubjubScalar) -> JubjubAffine {
+    let value = JubjubAffine::ADDITIVE_GENERATOR * value;
+    let blinder = blinding_generator() * blinder;
+
+    (value + blinder).into()
+}
+
+/// Opens the public commitment to the secret value and blinder
+#[derive(Debug)]
+pub struct OpeningCircuit {
+    value: JubjubScalar,
+    blinder: JubjubScalar,
+    commitment: JubjubAffine,
+}
+
+impl OpeningCircuit {
+    pub fn new(value: JubjubScalar, blinder: JubjubScalar) -> Self {
+        Self {
+            value,
+            blinder,
+            commitment: commit(value, blinder),
+        }
+    }
+}
+
+impl Default for OpeningCircuit {
+    fn default() -> Self {
+        Self::new(JubjubScalar::from(7u64), JubjubScalar::from(8u64))
+    }
+}
+
+impl Circuit<JubjubAffine> for OpeningCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let value = composer.append_witness(self.value);
+        let blinder = composer.append_witness(self.blinder);
+
+        composer.assert_pedersen_opening(
+            value,
+            blinder,
+            JubjubAffine::ADDITIVE_GENERATOR,
+            blinding_generator(),
+            self.commitment,
+        )
+    }
+}
+
+type OpeningKey = PlonkKey<TatePairing, JubjubAffine, OpeningCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = OpeningKey::setup_for_circuit(&OpeningCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    OpeningKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn pedersen_commit_matches_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let value = JubjubScalar::random(&mut rng);
+    let blinder = JubjubScalar::random(&mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w_value = composer.append_witness(value);
+    let w_blinder = composer.append_witness(blinder);
+
+    let point = composer
+        .component_pedersen_commit(
+            w_value,
+            w_blinder,
+            JubjubAffine::ADDITIVE_GENERATOR,
+            blinding_generator(),
+        )
+        .expect("the scalars fit Fr");
+
+    let expected = commit(value, blinder);
+    assert_eq!(composer[*point.x()], expected.get_x());
+    assert_eq!(composer[*point.y()], expected.get_y());
+}
+
+#[test]
+fn pedersen_opening_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for (value, blinder) in [
+        (JubjubScalar::zero(), JubjubScalar::zero()),
+        (-JubjubScalar::one(), -JubjubScalar::one()),
+        (
+            JubjubScalar::random(&mut rng),
+            JubjubScalar::random(&mut rng),
+        ),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &OpeningCircuit::new(value, blinder))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn pedersen_opening_refuses_wrong_blinder() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let value = JubjubScalar::random(&mut rng);
+    let blinder = JubjubScalar::random(&mut rng);
+
+    let circuit = OpeningCircuit {
+        blinder: blinder + JubjubScalar::one(),
+        ..OpeningCircuit::new(value, blinder)
+    };
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("a wrong blinder shouldn't verify");
+    }
+}
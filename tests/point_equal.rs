@@ -0,0 +1,189 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Selects the `x` of `a` if `a == b` and zero otherwise, and asserts the bit
+/// is the public `eq`
+#[derive(Debug)]
+pub struct PointEqualCircuit {
+    a: JubjubAffine,
+    b: JubjubAffine,
+    eq: BlsScalar,
+    selected: BlsScalar,
+}
+
+impl PointEqualCircuit {
+    pub fn new(a: JubjubAffine, b: JubjubAffine) -> Self {
+        let (eq, selected) = match a == b {
+            true => (BlsScalar::one(), a.get_x()),
+            false => (BlsScalar::zero(), BlsScalar::zero()),
+        };
+
+        Self { a, b, eq, selected }
+    }
+}
+
+impl Default for PointEqualCircuit {
+    fn default() -> Self {
+        let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+        Self::new(g, g)
+    }
+}
+
+impl Circuit<JubjubAffine> for PointEqualCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_point(self.a);
+        let b = composer.append_point(self.b);
+        let eq = composer.append_public(self.eq);
+        let selected = composer.append_public(self.selected);
+
+        let bit = composer.component_point_equal(a, b);
+        composer.assert_equal(bit, eq);
+
+        let x = composer.component_select_zero(bit, *a.x());
+        composer.assert_equal(x, selected);
+
+        Ok(())
+    }
+}
+
+type PointEqualKey = PlonkKey<TatePairing, JubjubAffine, PointEqualCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp =
+        PointEqualKey::setup_for_circuit(&PointEqualCircuit::default(), rng)
+            .expect("failed to synthesize");
+
+    PointEqualKey::compile(&pp).expect("failed to compile circuit")
+}
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+/// `point` with the opposite `y`, which lies on the curve as well
+fn flip_y(point: JubjubAffine) -> JubjubAffine {
+    JubjubAffine::from_raw_unchecked(point.get_x(), -point.get_y())
+}
+
+#[test]
+fn point_equal_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let a = random_point(&mut rng);
+    let b = random_point(&mut rng);
+    let identity = JubjubAffine::ADDITIVE_IDENTITY;
+
+    for (a, b, eq) in [
+        (a, a, true),
+        (identity, identity, true),
+        (a, b, false),
+        (a, -a, false),
+        (a, flip_y(a), false),
+        (a, identity, false),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &PointEqualCircuit::new(a, b))
+            .expect("failed to prove");
+        assert_eq!(public_inputs[0], BlsScalar::from(eq as u64));
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn point_equal_refuses_false_claims() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let a = random_point(&mut rng);
+
+    // claim points differing only in y are equal
+    let mut circuit = PointEqualCircuit::new(a, flip_y(a));
+    circuit.eq = BlsScalar::one();
+    circuit.selected = a.get_x();
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a false equality must not verify");
+
+    // claim equal points are distinct
+    let mut circuit = PointEqualCircuit::new(a, a);
+    circuit.eq = BlsScalar::zero();
+    circuit.selected = BlsScalar::zero();
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a false inequality must not verify");
+}
+
+#[test]
+fn point_equal_refuses_forged_bit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let a = random_point(&mut rng);
+    let b = random_point(&mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w_a = composer.append_point(a);
+    let w_b = composer.append_point(b);
+    let _ = composer.append_public(BlsScalar::zero());
+    let _ = composer.append_public(BlsScalar::zero());
+    let bit = composer.component_point_equal(w_a, w_b);
+    assert_eq!(composer[bit], BlsScalar::zero());
+
+    // the circuit of the keys, with the bit of distinct points forged to one
+    let circuit = PointEqualCircuit::new(a, b);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    let mut state = composer.serialize_witness_state();
+    let at = 30 + bit.index() * 32;
+    state[at..at + 32].copy_from_slice(&BlsScalar::one().encode());
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged equality bit must not verify");
+}
+
+#[test]
+fn point_equal_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+    let a = composer.append_point(g);
+    let b = composer.append_point(-g);
+
+    let gates = composer.statistics().gates;
+    let bit = composer.component_point_equal(a, b);
+    assert_eq!(composer.statistics().gates, gates + 7);
+    assert_eq!(composer[bit], BlsScalar::zero());
+
+    let bit = composer.component_point_equal(a, a);
+    assert_eq!(composer[bit], BlsScalar::one());
+}
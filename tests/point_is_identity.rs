@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public bit tells whether the point is the identity
+#[derive(Debug)]
+pub struct IdentityCircuit {
+    p: JubjubAffine,
+    is_identity: BlsScalar,
+}
+
+impl IdentityCircuit {
+    pub fn new(p: JubjubAffine) -> Self {
+        let is_identity = p == JubjubAffine::ADDITIVE_IDENTITY;
+
+        Self {
+            p,
+            is_identity: BlsScalar::from(is_identity as u64),
+        }
+    }
+}
+
+impl Default for IdentityCircuit {
+    fn default() -> Self {
+        Self::new(JubjubAffine::ADDITIVE_GENERATOR)
+    }
+}
+
+impl Circuit<JubjubAffine> for IdentityCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let p = composer.append_point(self.p);
+        let is_identity = composer.append_public(self.is_identity);
+
+        let bit = composer.component_point_is_identity(p);
+        composer.assert_equal(bit, is_identity);
+
+        Ok(())
+    }
+}
+
+type IdentityKey = PlonkKey<TatePairing, JubjubAffine, IdentityCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = IdentityKey::setup_for_circuit(&IdentityCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    IdentityKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn point_is_identity_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let random: JubjubAffine = (JubjubAffine::ADDITIVE_GENERATOR
+        * JubjubScalar::random(&mut rng))
+    .into();
+
+    for p in [
+        JubjubAffine::ADDITIVE_IDENTITY,
+        JubjubAffine::ADDITIVE_GENERATOR,
+        random,
+    ] {
+        let circuit = IdentityCircuit::new(p);
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // the opposite bit is refused
+        let mut circuit = circuit;
+        circuit.is_identity = BlsScalar::one() - circuit.is_identity;
+
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a wrong bit must not verify");
+    }
+}
+
+#[test]
+fn point_is_identity_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let identity = composer.append_point(JubjubAffine::ADDITIVE_IDENTITY);
+    let generator = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+
+    let gates = composer.statistics().gates;
+    let bit = composer.component_point_is_identity(identity);
+    assert_eq!(composer.statistics().gates, gates + 6);
+    assert_eq!(composer[bit], BlsScalar::one());
+
+    let bit = composer.component_point_is_identity(generator);
+    assert_eq!(composer[bit], BlsScalar::zero());
+}
+
+#[test]
+fn point_is_identity_forged_bits() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // the generator claimed to be the identity
+    let mut circuit = IdentityCircuit::default();
+    circuit.is_identity = BlsScalar::one();
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, the coordinates and the public bit,
+    // the inverse and the zero bit of x, y - 1, and the inverse and the zero
+    // bit of y - 1, then their product: setting both zero bits and clearing
+    // the inverses satisfies every gate but `x · z = 0`
+    let one = BlsScalar::one();
+    let zero = BlsScalar::zero();
+    let mut state = composer.serialize_witness_state();
+    for (i, value) in [(5, zero), (6, one), (8, zero), (9, one), (10, one)] {
+        let at = 30 + i * 32;
+        state[at..at + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged identity must not verify");
+}
@@ -0,0 +1,72 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+#[test]
+fn point_value_round_trips() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let a = random_point(&mut rng);
+    let b = random_point(&mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w_a = composer.append_point(a);
+    let w_b = composer.append_point(b);
+    assert_eq!(composer.point_value(&w_a), a);
+    assert_eq!(composer.point_value(&w_b), b);
+
+    let sum = composer.component_add_point(w_a, w_b);
+    let expected: JubjubAffine =
+        (JubjubExtended::from(a) + JubjubExtended::from(b)).into();
+    assert_eq!(composer.point_value(&sum), expected);
+
+    let identity = composer.append_point(JubjubAffine::ADDITIVE_IDENTITY);
+    assert_eq!(
+        composer.point_value(&identity),
+        JubjubAffine::ADDITIVE_IDENTITY
+    );
+}
+
+#[test]
+fn witness_value_round_trips() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let value = BlsScalar::random(&mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(value);
+    assert_eq!(composer.witness_value(w), value);
+    assert_eq!(composer.witness_value(w), composer[w]);
+
+    let point = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+    assert_eq!(
+        composer.witness_value(*point.x()),
+        JubjubAffine::ADDITIVE_GENERATOR.get_x()
+    );
+    assert_eq!(
+        composer.witness_value(*point.y()),
+        JubjubAffine::ADDITIVE_GENERATOR.get_y()
+    );
+}
+
+#[test]
+fn witness_point_displays_its_wires() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let point = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+
+    let expected = format!(
+        "(x: wire {}, y: wire {})",
+        point.x().index(),
+        point.y().index()
+    );
+    assert_eq!(point.to_string(), expected);
+}
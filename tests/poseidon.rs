@@ -0,0 +1,187 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::gadget::poseidon::PoseidonParams;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Permutation of `[0, 1, 2]` with the default parameters
+const PERM_012: [[u8; 32]; 3] = [
+    [
+        0x61, 0x86, 0xc4, 0xbb, 0x70, 0x7a, 0x78, 0x43, 0xb9, 0x5f, 0x8a, 0x0d,
+        0x8c, 0xc7, 0xe5, 0x26, 0x55, 0x80, 0x11, 0xb8, 0x68, 0xdb, 0xce, 0x52,
+        0xab, 0xf6, 0xa4, 0xe6, 0x01, 0x3b, 0x2f, 0x3b,
+    ],
+    [
+        0xe6, 0xe1, 0x2d, 0x37, 0x04, 0x9c, 0x74, 0xb7, 0xed, 0x3c, 0xc1, 0xdd,
+        0x88, 0x74, 0x6c, 0xf2, 0x89, 0x80, 0x95, 0x2a, 0xf6, 0x70, 0x35, 0x92,
+        0x64, 0x96, 0x27, 0x67, 0xc8, 0x56, 0xf7, 0x14,
+    ],
+    [
+        0x5e, 0xce, 0x4b, 0xbf, 0xb1, 0xaa, 0xd7, 0x74, 0x37, 0x90, 0x1b, 0x72,
+        0xfb, 0x60, 0xec, 0xb5, 0xdc, 0x07, 0x74, 0xd2, 0x3f, 0xd4, 0xf9, 0x90,
+        0x08, 0x69, 0x70, 0x18, 0xc6, 0x96, 0xe8, 0x19,
+    ],
+];
+
+/// Hashes of `[]`, `[1, 2]` and `[1, 2, 3, 4, 5]` with the default
+/// parameters
+const HASHES: [(u64, [u8; 32]); 3] = [
+    (
+        0,
+        [
+            0x36, 0xcf, 0x8c, 0xe6, 0x73, 0xcd, 0x6c, 0xef, 0x96, 0xd5, 0x36,
+            0xc2, 0xc7, 0x7a, 0xf9, 0xe1, 0x30, 0x1d, 0x6f, 0x32, 0x49, 0xbd,
+            0xd0, 0x02, 0x1a, 0xad, 0xfd, 0xb4, 0xa8, 0x69, 0x15, 0x2a,
+        ],
+    ),
+    (
+        2,
+        [
+            0xb0, 0x64, 0x71, 0x59, 0x35, 0x21, 0x93, 0xda, 0xa3, 0xb0, 0x26,
+            0xc7, 0x08, 0x5f, 0x3b, 0xed, 0x2c, 0x36, 0x38, 0xd8, 0xb8, 0xab,
+            0xe0, 0x83, 0x45, 0x08, 0xd1, 0xa4, 0x5e, 0x74, 0x46, 0x4b,
+        ],
+    ),
+    (
+        5,
+        [
+            0x86, 0xf5, 0x3e, 0x3f, 0xea, 0x7d, 0xa5, 0x18, 0x64, 0xe4, 0x5d,
+            0x69, 0x6b, 0x91, 0x61, 0x4d, 0x89, 0x2b, 0x7b, 0x07, 0x39, 0xdf,
+            0xf8, 0x07, 0xee, 0xf7, 0x84, 0xa9, 0x89, 0xa6, 0x76, 0x2a,
+        ],
+    ),
+];
+
+/// `[1, 2, ..., len]`
+fn inputs(len: u64) -> Vec<BlsScalar> {
+    (1..=len).map(BlsScalar::from).collect()
+}
+
+/// Asserts the public value is the hash of the secret inputs
+#[derive(Debug, Default)]
+pub struct HashCircuit {
+    inputs: [BlsScalar; 4],
+    digest: BlsScalar,
+}
+
+impl HashCircuit {
+    pub fn new(inputs: [BlsScalar; 4]) -> Self {
+        let digest = PoseidonParams::default().hash(&inputs);
+
+        Self { inputs, digest }
+    }
+}
+
+impl Circuit<JubjubAffine> for HashCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let params = PoseidonParams::default();
+
+        let inputs = self.inputs.map(|x| composer.append_witness(x));
+        let digest = composer.component_poseidon_hash(&params, &inputs);
+
+        let expected = composer.append_public(self.digest);
+        composer.assert_equal(digest, expected);
+
+        Ok(())
+    }
+}
+
+type HashKey = PlonkKey<TatePairing, JubjubAffine, HashCircuit>;
+
+#[test]
+fn poseidon_known_answers() {
+    let params = PoseidonParams::<BlsScalar, 3>::default();
+
+    let state = [0u64, 1, 2].map(BlsScalar::from);
+    let native = params.permute(state);
+    native
+        .iter()
+        .zip(PERM_012)
+        .for_each(|(x, expected)| assert_eq!(x.to_raw_bytes(), expected));
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let wires = state.map(|x| composer.append_witness(x));
+    let gates = composer.statistics().gates;
+    let permuted = composer.component_poseidon_perm(&params, wires);
+
+    assert_eq!(composer.statistics().gates - gates, params.gates());
+    permuted
+        .iter()
+        .zip(native)
+        .for_each(|(w, x)| assert_eq!(composer[*w], x));
+
+    for (len, expected) in HASHES {
+        let inputs = inputs(len);
+        let native = params.hash(&inputs);
+        assert_eq!(native.to_raw_bytes(), expected);
+
+        let wires: Vec<_> =
+            inputs.iter().map(|x| composer.append_witness(*x)).collect();
+        let digest = composer.component_poseidon_hash(&params, &wires);
+        assert_eq!(composer[digest], native);
+    }
+}
+
+#[test]
+fn poseidon_custom_instantiation() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let params = PoseidonParams::<BlsScalar, 5>::generate(b"width-5", 8, 60);
+
+    let state = [(); 5].map(|_| BlsScalar::random(&mut rng));
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let wires = state.map(|x| composer.append_witness(x));
+
+    let gates = composer.statistics().gates;
+    let permuted = composer.component_poseidon_perm(&params, wires);
+    assert_eq!(composer.statistics().gates - gates, params.gates());
+
+    permuted
+        .iter()
+        .zip(params.permute(state))
+        .for_each(|(w, x)| assert_eq!(composer[*w], x));
+
+    // the hash length is bound to the digest
+    let values = inputs(9);
+    let wires: Vec<_> =
+        values.iter().map(|x| composer.append_witness(*x)).collect();
+    let digest = composer.component_poseidon_hash(&params, &wires);
+    assert_eq!(composer[digest], params.hash(&values));
+    assert_ne!(params.hash(&values), params.hash(&values[..8]));
+}
+
+#[test]
+fn poseidon_hash_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = HashKey::setup_for_circuit(&HashCircuit::default(), &mut rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        HashKey::compile(&pp).expect("failed to compile circuit");
+
+    let inputs = [(); 4].map(|_| BlsScalar::random(&mut rng));
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &HashCircuit::new(inputs))
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+
+    // a wrong digest is refused
+    let mut circuit = HashCircuit::new(inputs);
+    circuit.digest += BlsScalar::one();
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrong digest must not verify");
+}
@@ -0,0 +1,133 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+/// Items exported by `zkplonk::prelude`, sorted.
+///
+/// Removing any of them is a breaking change. Adding one only requires
+/// updating the list.
+const PRELUDE: &[&str] = &[
+    "AggregatedProof",
+    "BlindingConfig",
+    "BlindingError",
+    "BlsScalar",
+    "BoolWire",
+    "Checkpoint",
+    "Circuit",
+    "CircuitStats",
+    "Constraint",
+    "ConstraintSystem",
+    "DebugMismatch",
+    "DecodeReason",
+    "Error",
+    "Group",
+    "InitConfig",
+    "JubjubAffine",
+    "JubjubExtended",
+    "JubjubPlonk",
+    "JubjubProver",
+    "JubjubScalar",
+    "JubjubVerifier",
+    "Keypair",
+    "Plonk",
+    "PlonkKey",
+    "PlonkParams",
+    "PrivateWire",
+    "Proof",
+    "ProofDecodeError",
+    "Prover",
+    "ProverOptions",
+    "PublicInput",
+    "PublicInputError",
+    "PublicParameters",
+    "RangeWire",
+    "SanityError",
+    "SelectorFamily",
+    "TableId",
+    "TatePairing",
+    "Verifier",
+    "WirePosition",
+    "WitnessPoint",
+];
+
+// every item must resolve, so a removal fails to compile
+#[allow(unused_imports)]
+use zkplonk::prelude::{
+    AggregatedProof, BlindingConfig, BlindingError, BlsScalar, BoolWire,
+    Checkpoint, Circuit, CircuitStats, Constraint, ConstraintSystem,
+    DebugMismatch, DecodeReason, Error, Group, InitConfig, JubjubAffine,
+    JubjubExtended, JubjubPlonk, JubjubProver, JubjubScalar, JubjubVerifier,
+    Keypair, Plonk, PlonkKey, PlonkParams, PrivateWire, Proof,
+    ProofDecodeError, Prover, ProverOptions, PublicInput, PublicInputError,
+    PublicParameters, RangeWire, SanityError, SelectorFamily, TableId,
+    TatePairing, Verifier, WirePosition, WitnessPoint,
+};
+
+/// Names exported by the `pub use` and `pub type` items of the source
+fn exported_names(source: &str) -> Vec<String> {
+    let code: String = source
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut names = Vec::new();
+
+    for item in code.split(';') {
+        let item = item.trim();
+
+        if let Some(path) = item.strip_prefix("pub use ") {
+            let list = match path.find('{') {
+                Some(start) => &path[start + 1..path.rfind('}').unwrap()],
+                None => path,
+            };
+
+            names.extend(
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| match s.split_once(" as ") {
+                        Some((_, alias)) => alias.trim().to_string(),
+                        None => s.rsplit("::").next().unwrap().to_string(),
+                    }),
+            );
+        } else if let Some(alias) = item
+            .lines()
+            .map(str::trim)
+            .find_map(|l| l.strip_prefix("pub type "))
+        {
+            let name = alias.split(|c: char| c == '=' || c == '<').next();
+            names.push(name.unwrap().trim().to_string());
+        }
+    }
+
+    names.sort();
+    names
+}
+
+#[test]
+fn prelude_snapshot() {
+    let source = include_str!("../src/prelude.rs");
+
+    assert_eq!(
+        exported_names(source),
+        PRELUDE.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+        "the prelude changed, update the snapshot if it is intended"
+    );
+}
+
+#[test]
+fn prelude_is_self_sufficient() {
+    let mut composer = JubjubPlonk::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(2u64));
+    let b: PrivateWire = composer.append_witness(BlsScalar::from(3u64));
+
+    let constraint = Constraint::default().mult(1).a(a).b(b);
+    let c = composer.gate_mul(constraint);
+
+    assert_eq!(composer[c], BlsScalar::from(6u64));
+    assert!(ConstraintSystem::m(&composer) > 0);
+}
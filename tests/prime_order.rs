@@ -0,0 +1,112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the secret point lies in the prime-order subgroup
+#[derive(Debug)]
+pub struct PrimeOrderCircuit {
+    point: JubjubAffine,
+}
+
+impl PrimeOrderCircuit {
+    pub fn new(point: JubjubAffine) -> Self {
+        Self { point }
+    }
+}
+
+impl Default for PrimeOrderCircuit {
+    fn default() -> Self {
+        Self::new(JubjubAffine::ADDITIVE_GENERATOR)
+    }
+}
+
+impl Circuit<JubjubAffine> for PrimeOrderCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let point = composer.append_point(self.point);
+        composer.component_assert_prime_order(point);
+
+        Ok(())
+    }
+}
+
+type PrimeOrderKey = PlonkKey<TatePairing, JubjubAffine, PrimeOrderCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp =
+        PrimeOrderKey::setup_for_circuit(&PrimeOrderCircuit::default(), rng)
+            .expect("failed to synthesize");
+
+    PrimeOrderKey::compile(&pp).expect("failed to compile circuit")
+}
+
+/// The point `(0, -1)` of order `2`
+fn torsion() -> JubjubAffine {
+    JubjubAffine::from_raw_unchecked(BlsScalar::zero(), -BlsScalar::one())
+}
+
+#[test]
+fn prime_order_points_work() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    for scalar in [
+        JubjubScalar::zero(),
+        JubjubScalar::one(),
+        JubjubScalar::random(&mut rng),
+    ] {
+        let point = (JubjubAffine::ADDITIVE_GENERATOR * scalar).into();
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &PrimeOrderCircuit::new(point))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn prime_order_refuses_torsion() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // a point of small order, and a point of the subgroup moved out of it by
+    // the same torsion
+    let point: JubjubAffine = (JubjubAffine::ADDITIVE_GENERATOR
+        * JubjubScalar::random(&mut rng))
+    .into();
+    let mixed =
+        (JubjubExtended::from(point) + JubjubExtended::from(torsion())).into();
+
+    for point in [torsion(), mixed] {
+        let result = prover
+            .create_proof(&mut rng, &PrimeOrderCircuit::new(point))
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(
+            result.is_err(),
+            "a point outside the subgroup must not verify"
+        );
+    }
+}
+
+#[test]
+fn prime_order_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let point = composer.append_point(JubjubAffine::ADDITIVE_GENERATOR);
+
+    let gates = composer.statistics().gates;
+    composer.component_assert_prime_order(point);
+    assert_eq!(composer.statistics().gates, gates + 11);
+}
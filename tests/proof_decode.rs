@@ -0,0 +1,152 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::Group;
+
+#[derive(Debug, Default)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_public(self.b);
+
+        let constraint = Constraint::default().mult(1).a(w_a).b(w_b);
+        composer.gate_mul(constraint);
+
+        Ok(())
+    }
+}
+
+/// Generate a corpus of valid encoded proofs
+fn corpus(rng: &mut StdRng, size: usize) -> Vec<Vec<u8>> {
+    let mut pp = PlonkParams::<TatePairing>::setup(4, rng);
+
+    let (prover, _) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    (0..size)
+        .map(|_| {
+            let circuit = DummyCircuit {
+                a: BlsScalar::random(&mut *rng),
+                b: BlsScalar::random(&mut *rng),
+            };
+
+            let (proof, _) =
+                prover.create_proof(rng, &circuit).expect("failed to prove");
+
+            proof.encode()
+        })
+        .collect()
+}
+
+#[test]
+fn decode_roundtrip_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for bytes in corpus(&mut rng, 2) {
+        assert_eq!(bytes.len(), Proof::<TatePairing>::encoded_size());
+
+        let proof = Proof::<TatePairing>::try_from(bytes.as_slice())
+            .expect("failed to decode valid proof");
+        assert_eq!(proof.encode(), bytes);
+
+        let mut reader = bytes.as_slice();
+        let decoded = Proof::<TatePairing>::decode_from_reader(&mut reader)
+            .expect("failed to read valid proof");
+        assert_eq!(decoded, proof);
+        assert!(reader.is_empty());
+    }
+}
+
+#[test]
+fn decode_reader_is_framed() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let corpus = corpus(&mut rng, 3);
+    let stream = corpus.concat();
+    let mut reader = stream.as_slice();
+
+    for bytes in corpus.iter() {
+        let proof = Proof::<TatePairing>::decode_from_reader(&mut reader)
+            .expect("failed to read framed proof");
+        assert_eq!(&proof.encode(), bytes);
+    }
+
+    let err = Proof::<TatePairing>::decode_from_reader(&mut reader)
+        .expect_err("empty stream isn't a proof");
+    assert_eq!(err.field, "a_comm");
+    assert_eq!(err.reason, DecodeReason::Truncated);
+}
+
+#[test]
+fn decode_truncated_fails() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for bytes in corpus(&mut rng, 1) {
+        for len in 0..bytes.len() {
+            let err = Proof::<TatePairing>::try_from(&bytes[..len])
+                .expect_err("truncated proof must not decode");
+
+            assert_eq!(err.reason, DecodeReason::Truncated);
+            assert!(!err.field.is_empty());
+        }
+
+        let mut extended = bytes.clone();
+        extended.push(0);
+
+        let err = Proof::<TatePairing>::try_from(extended.as_slice())
+            .expect_err("trailing bytes must not decode");
+        assert_eq!(err.reason, DecodeReason::TrailingBytes);
+    }
+}
+
+#[test]
+fn decode_bit_flipped_never_panics() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for bytes in corpus(&mut rng, 1) {
+        for _ in 0..512 {
+            let mut flipped = bytes.clone();
+
+            let idx = rng.gen_range(0..flipped.len());
+            let bit = rng.gen_range(0..8);
+            flipped[idx] ^= 1 << bit;
+
+            if let Err(err) = Proof::<TatePairing>::try_from(flipped.as_slice())
+            {
+                assert!(!err.field.is_empty());
+
+                // a flipped tag may announce optional parts that aren't
+                // encoded, every other field has the bytes it reads
+                let optional = err.field.starts_with("lookup_")
+                    || err.field == "t_5_comm"
+                    || err.field == "t_6_comm";
+                if !optional {
+                    assert_ne!(err.reason, DecodeReason::Truncated);
+                }
+            }
+        }
+    }
+}
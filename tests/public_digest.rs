@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkplonk::{public_digest, PublicInputKind, DIGEST_ALGORITHM};
+
+/// Binds a document between two scalar public inputs
+#[derive(Debug, Default)]
+pub struct DocumentCircuit {
+    a: BlsScalar,
+    digest: BlsScalar,
+    b: BlsScalar,
+}
+
+impl DocumentCircuit {
+    fn new(a: BlsScalar, document: &[u8], b: BlsScalar) -> Self {
+        Self {
+            a,
+            digest: public_digest(document),
+            b,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for DocumentCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_public(self.a);
+        let digest = composer.append_public_digest(self.digest);
+        let b = composer.append_public(self.b);
+
+        // the digest is also usable as a witness
+        let constraint = Constraint::default().left(1).right(1).a(a).b(digest);
+        let c = composer.gate_add(constraint);
+        let constraint = Constraint::default().left(1).right(1).a(c).b(b);
+        composer.gate_add(constraint);
+
+        Ok(())
+    }
+}
+
+fn document(rng: &mut StdRng) -> Vec<u8> {
+    let mut document = vec![0u8; 10 * 1024];
+    rng.fill_bytes(&mut document);
+
+    document
+}
+
+#[test]
+fn public_digest_reduction() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let document = document(&mut rng);
+
+    assert_eq!(public_digest(&document), public_digest(&document));
+    assert_ne!(public_digest(&document), public_digest(&document[1..]));
+    assert_ne!(public_digest(b""), BlsScalar::zero());
+}
+
+#[test]
+fn verify_with_preimage() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(6, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DocumentCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let document = document(&mut rng);
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let circuit = DocumentCircuit::new(a, &document, b);
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    // the digest keeps its position among the plain inputs
+    assert_eq!(
+        verifier.public_input_layout(),
+        &[
+            PublicInputKind::Scalar,
+            PublicInputKind::Digest {
+                algorithm: DIGEST_ALGORITHM
+            },
+            PublicInputKind::Scalar,
+        ]
+    );
+    assert_eq!(public_inputs, vec![a, public_digest(&document), b]);
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+    verifier
+        .verify_with_preimage(&proof, &[&document], &[a, b])
+        .expect("failed to verify proof");
+
+    // the plain inputs are in order
+    assert!(matches!(
+        verifier.verify_with_preimage(&proof, &[&document], &[b, a]),
+        Err(Error::ProofVerificationError)
+    ));
+
+    // a single byte differs
+    let mut tampered = document.clone();
+    tampered[5000] ^= 1;
+    assert!(matches!(
+        verifier.verify_with_preimage(&proof, &[&tampered], &[a, b]),
+        Err(Error::ProofVerificationError)
+    ));
+
+    // the preimages and plain inputs are counted separately
+    assert!(matches!(
+        verifier.verify_with_preimage(&proof, &[], &[a, b, b]),
+        Err(Error::InconsistentPublicInputsLen {
+            expected: 1,
+            provided: 0,
+        })
+    ));
+}
@@ -0,0 +1,122 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+
+const PUBLIC_INPUTS: u64 = 24;
+
+#[derive(Debug)]
+pub struct ManyPublicCircuit {
+    offset: u64,
+}
+
+impl Default for ManyPublicCircuit {
+    fn default() -> Self {
+        Self { offset: 1 }
+    }
+}
+
+impl Circuit<JubjubAffine> for ManyPublicCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        for i in 0..PUBLIC_INPUTS {
+            let w = composer.append_witness(BlsScalar::from(self.offset + i));
+
+            // interleave private gates so the public gates aren't contiguous
+            composer.component_range(w, 8);
+            composer.append_public(BlsScalar::from(self.offset + i));
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn public_inputs_deterministic_order() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(10, &mut rng);
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, ManyPublicCircuit>::compile(
+            &mut pp,
+        )
+        .expect("failed to compile circuit");
+
+    let circuit = ManyPublicCircuit { offset: 100 };
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    assert_eq!(public_inputs.len(), PUBLIC_INPUTS as usize);
+
+    // every compilation and proof orders the public inputs by gate
+    for _ in 0..4 {
+        let (prover_again, verifier_again) =
+            PlonkKey::<TatePairing, JubjubAffine, ManyPublicCircuit>::compile(
+                &mut pp,
+            )
+            .expect("failed to compile circuit");
+
+        let (proof_again, public_inputs_again) = prover_again
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        assert_eq!(public_inputs, public_inputs_again);
+
+        verifier_again
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+        verifier
+            .verify(&proof_again, &public_inputs_again)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn public_inputs_duplicate_gate() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let gate = ConstraintSystem::m(&composer);
+    composer.append_public(BlsScalar::from(7u64));
+
+    assert_eq!(
+        composer.set_public_input(gate, BlsScalar::from(8u64)),
+        Err(PublicInputError::Duplicate { gate })
+    );
+    assert_eq!(composer.statistics().public_inputs, 1);
+
+    let missing = ConstraintSystem::m(&composer);
+    assert_eq!(
+        composer.set_public_input(missing, BlsScalar::from(8u64)),
+        Err(PublicInputError::MissingGate { gate: missing })
+    );
+
+    let w = composer.append_witness(BlsScalar::from(8u64));
+    let gate = ConstraintSystem::m(&composer);
+    composer.assert_equal_constant(w, 0, None);
+
+    composer
+        .set_public_input(gate, -BlsScalar::from(8u64))
+        .expect("the gate has no public input");
+    assert_eq!(composer.statistics().public_inputs, 2);
+
+    assert_eq!(
+        composer.set_public_input(gate, -BlsScalar::from(8u64)),
+        Err(PublicInputError::Duplicate { gate })
+    );
+}
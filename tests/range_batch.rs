@@ -0,0 +1,187 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Range checks every value to its bits, either batched or one by one
+#[derive(Debug, Clone)]
+pub struct BatchCircuit {
+    values: Vec<BlsScalar>,
+    bits: Vec<usize>,
+    batched: bool,
+}
+
+impl BatchCircuit {
+    pub fn new(
+        values: Vec<BlsScalar>,
+        bits: Vec<usize>,
+        batched: bool,
+    ) -> Self {
+        Self {
+            values,
+            bits,
+            batched,
+        }
+    }
+}
+
+impl Default for BatchCircuit {
+    fn default() -> Self {
+        Self::new(vec![BlsScalar::zero()], vec![2], true)
+    }
+}
+
+impl Circuit<JubjubAffine> for BatchCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let items: Vec<_> = self
+            .values
+            .iter()
+            .zip(self.bits.iter())
+            .map(|(v, bits)| (composer.append_witness(*v), *bits))
+            .collect();
+
+        match self.batched {
+            true => composer.component_range_batch(&items),
+            false => items
+                .iter()
+                .for_each(|(w, bits)| composer.component_range(*w, *bits)),
+        }
+
+        Ok(())
+    }
+}
+
+type BatchKey = PlonkKey<TatePairing, JubjubAffine, BatchCircuit>;
+
+fn keys(
+    rng: &mut StdRng,
+    circuit: &BatchCircuit,
+) -> (JubjubProver, JubjubVerifier) {
+    let pp = BatchKey::setup_for_circuit(circuit, rng)
+        .expect("failed to synthesize");
+
+    BatchKey::compile_with_circuit(&pp, b"range-batch", circuit)
+        .expect("failed to compile circuit")
+}
+
+#[test]
+fn range_batch_matches_individual() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let bits: Vec<usize> = (0..6)
+        .map(|_| 2 + 2 * (rng.next_u64() % 32) as usize)
+        .collect();
+    let zeros = vec![BlsScalar::zero(); bits.len()];
+
+    let batch = BatchCircuit::new(zeros.clone(), bits.clone(), true);
+    let single = BatchCircuit::new(zeros, bits.clone(), false);
+    let (batch_prover, batch_verifier) = keys(&mut rng, &batch);
+    let (single_prover, single_verifier) = keys(&mut rng, &single);
+
+    for _ in 0..4 {
+        // every value either fits its bits or overflows them by a few bits
+        let values: Vec<BlsScalar> = bits
+            .iter()
+            .map(|bits| {
+                let extra = (rng.next_u64() % 3) as usize;
+                let v = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+                let v = v >> (128 - bits - extra);
+                BlsScalar::from(v as u64)
+                    + BlsScalar::from((v >> 64) as u64)
+                        * BlsScalar::pow_of_2(64)
+            })
+            .collect();
+
+        let batch = BatchCircuit::new(values.clone(), bits.clone(), true);
+        let single = BatchCircuit::new(values, bits.clone(), false);
+
+        let batch_ok = batch_prover
+            .create_proof(&mut rng, &batch)
+            .and_then(|(proof, pi)| batch_verifier.verify(&proof, &pi))
+            .is_ok();
+        let single_ok = single_prover
+            .create_proof(&mut rng, &single)
+            .and_then(|(proof, pi)| single_verifier.verify(&proof, &pi))
+            .is_ok();
+
+        assert_eq!(batch_ok, single_ok);
+    }
+}
+
+#[test]
+fn range_batch_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let items: Vec<_> = (0..100)
+        .map(|i| (composer.append_witness(BlsScalar::from(i as u64)), 8))
+        .collect();
+
+    let gates = composer.statistics().gates;
+    composer.component_range_batch(&items);
+    assert_eq!(composer.statistics().gates - gates, 200);
+
+    let gates = composer.statistics().gates;
+    items
+        .iter()
+        .for_each(|(w, bits)| composer.component_range(*w, *bits));
+    assert_eq!(composer.statistics().gates - gates, 300);
+}
+
+#[test]
+fn range_batch_forged_accumulators() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let bits = vec![2, 2];
+    let circuit = BatchCircuit::new(
+        vec![BlsScalar::from(5u64), BlsScalar::from(2u64)],
+        bits.clone(),
+        true,
+    );
+    let (prover, verifier) = keys(&mut rng, &circuit);
+
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "5 doesn't fit 2 bits");
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and both values, the accumulators
+    // `5` and `4 · 5 + 2` satisfy both reconstructions, but the first quad
+    // isn't a base-4 digit
+    let mut state = composer.serialize_witness_state();
+    for (i, acc) in [(4, 5u64), (5, 22u64)] {
+        let at = 30 + i * 32;
+        state[at..at + 32].copy_from_slice(&BlsScalar::from(acc).encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "forged accumulators must not verify");
+}
+
+#[test]
+#[should_panic(expected = "the range gates check an even number of bits")]
+fn range_batch_odd_bits() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::from(3u64));
+
+    composer.component_range_batch(&[(w, 2), (w, 3)]);
+}
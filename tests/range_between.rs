@@ -0,0 +1,150 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the secret value lies in the constant range `[lo, hi)`
+#[derive(Debug, Clone)]
+pub struct BetweenCircuit {
+    w: BlsScalar,
+    lo: BlsScalar,
+    hi: BlsScalar,
+    num_bits: usize,
+}
+
+impl BetweenCircuit {
+    pub fn new(
+        w: BlsScalar,
+        lo: BlsScalar,
+        hi: BlsScalar,
+        num_bits: usize,
+    ) -> Self {
+        Self {
+            w,
+            lo,
+            hi,
+            num_bits,
+        }
+    }
+}
+
+impl Default for BetweenCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero(), BlsScalar::zero(), BlsScalar::one(), 2)
+    }
+}
+
+impl Circuit<JubjubAffine> for BetweenCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w = composer.append_witness(self.w);
+        composer.component_range_between(w, self.lo, self.hi, self.num_bits);
+
+        Ok(())
+    }
+}
+
+type BetweenKey = PlonkKey<TatePairing, JubjubAffine, BetweenCircuit>;
+
+/// Prove and verify every value of `values` against the keys of the range,
+/// returning whether each one succeeded
+fn prove(
+    rng: &mut StdRng,
+    lo: BlsScalar,
+    hi: BlsScalar,
+    num_bits: usize,
+    values: &[BlsScalar],
+) -> Vec<bool> {
+    let circuit = BetweenCircuit::new(lo, lo, hi, num_bits);
+    let pp = BetweenKey::setup_for_circuit(&circuit, rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        BetweenKey::compile_with_circuit(&pp, b"range-between", &circuit)
+            .expect("failed to compile circuit");
+
+    values
+        .iter()
+        .map(|w| {
+            let circuit = BetweenCircuit::new(*w, lo, hi, num_bits);
+
+            prover
+                .create_proof(rng, &circuit)
+                .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+                .is_ok()
+        })
+        .collect()
+}
+
+#[test]
+fn range_between_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let lo = BlsScalar::from(1000u64);
+    let hi = BlsScalar::from(1100u64);
+    let one = BlsScalar::one();
+
+    let values = [lo, hi - one, lo + BlsScalar::from(50u64), hi, lo - one];
+    assert_eq!(
+        prove(&mut rng, lo, hi, 8, &values),
+        vec![true, true, true, false, false]
+    );
+
+    // far above or below, the differences wrap around the modulus
+    let values = [BlsScalar::zero(), -one, BlsScalar::pow_of_2(128)];
+    assert_eq!(
+        prove(&mut rng, lo, hi, 8, &values),
+        vec![false, false, false]
+    );
+}
+
+#[test]
+fn range_between_large_bounds() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let one = BlsScalar::one();
+    let two_250 = BlsScalar::pow_of_2(250);
+
+    // a narrow range around 2^250
+    let lo = two_250 - BlsScalar::from(5u64);
+    let hi = two_250 + BlsScalar::from(3u64);
+    let values = [lo, hi - one, two_250, hi, lo - one];
+    assert_eq!(
+        prove(&mut rng, lo, hi, 4, &values),
+        vec![true, true, true, false, false]
+    );
+
+    // a wide range up to 2^250
+    let lo = BlsScalar::from(7u64);
+    let values = [lo, two_250 - one, two_250, lo - one];
+    assert_eq!(
+        prove(&mut rng, lo, two_250, 250, &values),
+        vec![true, true, false, false]
+    );
+}
+
+#[test]
+#[should_panic(expected = "the lower bound must be below the upper bound")]
+fn range_between_empty() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::from(3u64));
+
+    composer.component_range_between(w, 3u64, 3u64, 2);
+}
+
+#[test]
+#[should_panic(expected = "the range doesn't fit 8 bits")]
+fn range_between_too_wide() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::from(3u64));
+
+    composer.component_range_between(w, 0u64, 257u64, 8);
+}
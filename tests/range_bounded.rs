@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::{FftField, Group};
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    w: BlsScalar,
+    bound: BlsScalar,
+    num_bits: Option<usize>,
+}
+
+impl DummyCircuit {
+    pub fn new(
+        w: BlsScalar,
+        bound: BlsScalar,
+        num_bits: Option<usize>,
+    ) -> Self {
+        Self { w, bound, num_bits }
+    }
+}
+
+impl Default for DummyCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero(), BlsScalar::one(), None)
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w = composer.append_witness(self.w);
+
+        match self.num_bits {
+            Some(num_bits) => {
+                composer.component_range_bounded(w, self.bound, num_bits)
+            }
+            None => composer.component_range_bounded_auto(w, self.bound),
+        }
+
+        Ok(())
+    }
+}
+
+/// Prove and verify `w < bound`, returning whether it succeeded
+fn prove(
+    pp: &PlonkParams<TatePairing>,
+    rng: &mut StdRng,
+    bound: BlsScalar,
+    num_bits: Option<usize>,
+    w: BlsScalar,
+) -> bool {
+    // the bound is part of the circuit description
+    let (prover, verifier) = PlonkKey::compile_with_circuit(
+        pp,
+        b"range-bounded",
+        &DummyCircuit::new(BlsScalar::zero(), bound, num_bits),
+    )
+    .expect("failed to compile circuit");
+
+    prover
+        .create_proof(rng, &DummyCircuit::new(w, bound, num_bits))
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+        .is_ok()
+}
+
+#[test]
+fn range_bounded_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    let one = BlsScalar::one();
+
+    for (bound, num_bits) in [
+        (BlsScalar::from(1000), Some(10)),
+        (BlsScalar::from(1000), Some(16)),
+        (BlsScalar::from(1000), None),
+        (BlsScalar::from(u32::MAX as u64), None),
+    ] {
+        assert!(prove(&pp, &mut rng, bound, num_bits, BlsScalar::zero()));
+        assert!(prove(&pp, &mut rng, bound, num_bits, bound - one));
+        assert!(!prove(&pp, &mut rng, bound, num_bits, bound));
+        assert!(!prove(&pp, &mut rng, bound, num_bits, bound + one));
+        assert!(!prove(&pp, &mut rng, bound, num_bits, -one));
+    }
+}
+
+#[test]
+fn range_bounded_one() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    let one = BlsScalar::one();
+
+    // only zero is below one
+    assert!(prove(&pp, &mut rng, one, None, BlsScalar::zero()));
+    assert!(!prove(&pp, &mut rng, one, None, one));
+    assert!(prove(&pp, &mut rng, one, Some(8), BlsScalar::zero()));
+    assert!(!prove(&pp, &mut rng, one, Some(8), one));
+}
+
+#[test]
+fn range_bounded_close_to_power_of_two() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(7, &mut rng);
+
+    let one = BlsScalar::one();
+    let bound = BlsScalar::pow_of_2(64) - one;
+
+    assert!(prove(&pp, &mut rng, bound, Some(64), bound - one));
+    assert!(!prove(&pp, &mut rng, bound, Some(64), bound));
+    assert!(!prove(&pp, &mut rng, bound, Some(64), bound + one));
+}
+
+#[test]
+#[should_panic(expected = "the bound doesn't fit 64 bits")]
+fn range_bounded_bound_too_large() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::zero());
+
+    composer.component_range_bounded(w, BlsScalar::pow_of_2(64), 64);
+}
+
+#[test]
+#[should_panic(expected = "the bound must not be zero")]
+fn range_bounded_zero_bound() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let w = composer.append_witness(BlsScalar::zero());
+
+    composer.component_range_bounded_auto(w, BlsScalar::zero());
+}
@@ -0,0 +1,154 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Powers `a^1, ..., a^GATES` appended as witnesses, constrained to be
+/// consecutive with a bulk of gates
+#[derive(Debug, Default)]
+pub struct PowersCircuit<const GATES: usize> {
+    a: BlsScalar,
+    result: BlsScalar,
+}
+
+impl<const GATES: usize> PowersCircuit<GATES> {
+    fn new(a: BlsScalar) -> Self {
+        let result = (1..GATES).fold(a, |acc, _| acc * a);
+
+        Self { a, result }
+    }
+}
+
+impl<const GATES: usize> Circuit<JubjubAffine> for PowersCircuit<GATES> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let mut power = self.a;
+        let mut powers = vec![composer.append_witness(power)];
+        for _ in 1..GATES {
+            power *= self.a;
+            powers.push(composer.append_witness(power));
+        }
+
+        // a^i · a - a^(i + 1) = 0
+        let a = powers[0];
+        let constraints = powers.windows(2).map(|w| {
+            Constraint::default()
+                .mult(1)
+                .output(-BlsScalar::one())
+                .a(w[0])
+                .b(a)
+                .o(w[1])
+        });
+        composer.append_gates(constraints);
+
+        composer.assert_equal_constant(
+            powers[GATES - 1],
+            BlsScalar::zero(),
+            Some(self.result),
+        );
+
+        Ok(())
+    }
+}
+
+type Powers = PowersCircuit<100>;
+
+#[test]
+fn reserve_avoids_reallocation() {
+    let hint = PlonkKey::<TatePairing, JubjubAffine, Powers>::size_hint(
+        &Powers::default(),
+    )
+    .expect("failed to synthesize");
+
+    let config = InitConfig {
+        size_hint: hint,
+        ..InitConfig::default()
+    };
+    let mut composer = Plonk::<JubjubAffine>::with_config(config);
+    let capacity = composer.capacity();
+    assert!(capacity.gates >= hint.gates);
+    assert!(capacity.witnesses >= hint.witnesses);
+
+    Powers::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // the whole circuit fit the reserved capacity
+    assert_eq!(composer.size(), hint);
+    assert_eq!(composer.capacity(), capacity);
+
+    // while the default composer grew along the way
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let capacity = composer.capacity();
+    Powers::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+    assert_ne!(composer.capacity(), capacity);
+}
+
+#[test]
+fn reserve_proofs_match() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+
+    let circuit = Powers::new(BlsScalar::from(3u64));
+    let hint =
+        PlonkKey::<TatePairing, JubjubAffine, Powers>::size_hint(&circuit)
+            .expect("failed to synthesize");
+    let config = InitConfig {
+        size_hint: hint,
+        ..InitConfig::default()
+    };
+
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&pp, b"reserve", &circuit)
+            .expect("failed to compile circuit");
+    let (reserved_prover, reserved_verifier) =
+        PlonkKey::compile_with_config(&pp, b"reserve", &circuit, config)
+            .expect("failed to compile circuit");
+
+    // the reservation doesn't change the circuit nor its proofs
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (reserved_proof, reserved_public_inputs) = reserved_prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    assert_eq!(proof, reserved_proof);
+    assert_eq!(public_inputs, reserved_public_inputs);
+
+    verifier
+        .verify(&reserved_proof, &public_inputs)
+        .expect("failed to verify proof");
+    reserved_verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn append_gates_matches_append_gate() {
+    let mut bulk = Plonk::<JubjubAffine>::initialize();
+    let mut single = Plonk::<JubjubAffine>::initialize();
+
+    let constraints: Vec<_> = (1..=8u64)
+        .map(|i| Constraint::default().left(i).a(Plonk::<JubjubAffine>::ZERO))
+        .collect();
+
+    bulk.append_gates(constraints.clone());
+    constraints.into_iter().for_each(|c| single.append_gate(c));
+
+    assert_eq!(bulk.size(), single.size());
+    assert_eq!(bulk.circuit_id(), single.circuit_id());
+}
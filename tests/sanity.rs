@@ -0,0 +1,177 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::WirePosition;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+
+#[derive(Debug)]
+pub struct MixedWiresCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    wrong_wire: bool,
+    extra_gate: bool,
+}
+
+impl Default for MixedWiresCircuit {
+    fn default() -> Self {
+        Self {
+            a: BlsScalar::from(5u64),
+            b: BlsScalar::from(6u64),
+            wrong_wire: false,
+            extra_gate: false,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for MixedWiresCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        composer.assert_equal_constant(a, BlsScalar::from(5u64), None);
+        composer.assert_equal_constant(b, BlsScalar::from(6u64), None);
+
+        // the gate is satisfied with either wire, but only `a` belongs to
+        // the copy constraints of the compiled circuit
+        let w = match self.wrong_wire {
+            true => b,
+            false => a,
+        };
+        composer.assert_equal(w, w);
+
+        if self.extra_gate {
+            composer.assert_equal(a, a);
+        }
+
+        Ok(())
+    }
+}
+
+fn compile() -> (
+    StdRng,
+    Prover<TatePairing, JubjubAffine>,
+    Verifier<TatePairing>,
+) {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(5, &mut rng);
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, MixedWiresCircuit>::compile(
+            &mut pp,
+        )
+        .expect("failed to compile circuit");
+
+    (rng, prover, verifier)
+}
+
+#[test]
+fn sanity_valid_composer() {
+    let (mut rng, prover, verifier) = compile();
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    MixedWiresCircuit::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    prover
+        .check_composer(&composer)
+        .expect("the composer describes the compiled circuit");
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &MixedWiresCircuit::default())
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn sanity_copy_constraint_violation() {
+    let (mut rng, mut prover, verifier) = compile();
+
+    let circuit = MixedWiresCircuit {
+        wrong_wire: true,
+        ..Default::default()
+    };
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // the first wire of `a` is linked to the wire of the mixed up gate
+    let gate = ConstraintSystem::m(&composer) - 1;
+    match prover.check_composer(&composer) {
+        Err(SanityError::CopyConstraintViolation {
+            to,
+            from_value,
+            to_value,
+            ..
+        }) => {
+            assert_eq!(to, WirePosition { gate, column: 0 });
+            assert_eq!(from_value, BlsScalar::from(5u64));
+            assert_eq!(to_value, BlsScalar::from(6u64));
+        }
+        e => panic!("expected a copy constraint violation, got {:?}", e),
+    }
+
+    assert!(matches!(
+        prover.create_proof(&mut rng, &circuit),
+        Err(Error::ProofVerificationError)
+    ));
+
+    // without the check, the mismatch goes undetected until the proof fails
+    // to be committed or verified
+    prover.set_options(ProverOptions {
+        skip_sanity: true,
+        ..Default::default()
+    });
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+}
+
+#[test]
+fn sanity_constraint_count() {
+    let (_, prover, _) = compile();
+
+    let circuit = MixedWiresCircuit {
+        extra_gate: true,
+        ..Default::default()
+    };
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    let found = ConstraintSystem::m(&composer);
+    assert_eq!(
+        prover.check_composer(&composer),
+        Err(SanityError::ConstraintCount {
+            expected: found - 1,
+            found,
+        })
+    );
+}
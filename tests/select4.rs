@@ -0,0 +1,132 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts the public value is the option picked by the two bits
+#[derive(Debug, Default)]
+pub struct Select4Circuit {
+    b0: BlsScalar,
+    b1: BlsScalar,
+    x: [BlsScalar; 4],
+    res: BlsScalar,
+}
+
+impl Select4Circuit {
+    pub fn new(index: usize, x: [BlsScalar; 4]) -> Self {
+        Self {
+            b0: BlsScalar::from((index & 1) as u64),
+            b1: BlsScalar::from((index >> 1) as u64),
+            x,
+            res: x[index],
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for Select4Circuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let b0 = composer.append_witness(self.b0);
+        let b1 = composer.append_witness(self.b1);
+        let x = self.x.map(|x| composer.append_witness(x));
+        let res = composer.append_public(self.res);
+
+        composer.component_boolean(b0);
+        composer.component_boolean(b1);
+
+        let w = composer.component_select4(b0, b1, x[0], x[1], x[2], x[3]);
+        composer.assert_equal(w, res);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn select4_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &Select4Circuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, Select4Circuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let x = [(); 4].map(|_| BlsScalar::random(&mut rng));
+
+    for index in 0..4 {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &Select4Circuit::new(index, x))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        // any other option is refused
+        let mut circuit = Select4Circuit::new(index, x);
+        circuit.res = x[(index + 1) % 4];
+
+        if let Ok((proof, public_inputs)) =
+            prover.create_proof(&mut rng, &circuit)
+        {
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect_err("the proof must not verify");
+        }
+    }
+}
+
+#[test]
+fn select4_refuses_non_boolean_selector() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &Select4Circuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, Select4Circuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let x = [(); 4].map(|_| BlsScalar::random(&mut rng));
+
+    // b0 = 2 extrapolates the pairs, which a boolean selector can't reach
+    let mut circuit = Select4Circuit::new(0, x);
+    circuit.b0 = BlsScalar::from(2u64);
+    circuit.res = x[0] + (x[1] - x[0]) * BlsScalar::from(2u64);
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("the proof must not verify");
+    }
+}
+
+#[test]
+fn select4_gate_count() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let b0 = composer.append_witness(BlsScalar::one());
+    let b1 = composer.append_witness(BlsScalar::zero());
+    let x =
+        [3u64, 5, 7, 11].map(|x| composer.append_witness(BlsScalar::from(x)));
+
+    let gates = composer.statistics().gates;
+    let w = composer.component_select4(b0, b1, x[0], x[1], x[2], x[3]);
+
+    assert_eq!(composer.statistics().gates, gates + 6);
+    assert_eq!(composer[w], BlsScalar::from(5u64));
+}
@@ -0,0 +1,204 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts the public value is the option at the index of the bits
+#[derive(Debug, Clone)]
+pub struct SelectNCircuit {
+    bits: Vec<BlsScalar>,
+    x: Vec<BlsScalar>,
+    res: BlsScalar,
+}
+
+impl SelectNCircuit {
+    pub fn new(index: usize, num_bits: usize, x: &[BlsScalar]) -> Self {
+        let bits = (0..num_bits)
+            .map(|i| BlsScalar::from(((index >> i) & 1) as u64))
+            .collect();
+        let res = x.get(index).copied().unwrap_or(BlsScalar::zero());
+
+        Self {
+            bits,
+            x: x.to_vec(),
+            res,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for SelectNCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let bits: Vec<_> = self
+            .bits
+            .iter()
+            .map(|b| {
+                let b = composer.append_witness(*b);
+                composer.component_boolean(b);
+                b
+            })
+            .collect();
+        let x: Vec<_> =
+            self.x.iter().map(|x| composer.append_witness(*x)).collect();
+        let res = composer.append_public(self.res);
+
+        let w = composer.component_select_n(&bits, &x);
+        composer.assert_equal(w, res);
+
+        Ok(())
+    }
+}
+
+type SelectNKey = PlonkKey<TatePairing, JubjubAffine, SelectNCircuit>;
+
+fn keys(
+    rng: &mut StdRng,
+    circuit: &SelectNCircuit,
+) -> (JubjubProver, JubjubVerifier) {
+    let pp = SelectNKey::setup_for_circuit(circuit, rng)
+        .expect("failed to synthesize");
+
+    SelectNKey::compile_with_circuit(&pp, b"select-n", circuit)
+        .expect("failed to compile circuit")
+}
+
+fn prove(
+    rng: &mut StdRng,
+    prover: &JubjubProver,
+    verifier: &JubjubVerifier,
+    circuit: &SelectNCircuit,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn select_n_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for (k, num_bits) in [(1, 0), (1, 2), (5, 3), (8, 3), (64, 6)] {
+        let x: Vec<_> = (0..k).map(|_| BlsScalar::random(&mut rng)).collect();
+        let (prover, verifier) =
+            keys(&mut rng, &SelectNCircuit::new(0, num_bits, &x));
+
+        let mut indexes = vec![0, k - 1, k / 2];
+        indexes.dedup();
+
+        for index in indexes {
+            let circuit = SelectNCircuit::new(index, num_bits, &x);
+            prove(&mut rng, &prover, &verifier, &circuit)
+                .expect("failed to prove");
+
+            // any other option is refused
+            if k > 1 {
+                let mut circuit = circuit.clone();
+                circuit.res = x[(index + 1) % k];
+
+                assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+            }
+        }
+    }
+}
+
+#[test]
+fn select_n_out_of_range() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let x: Vec<_> = (0..5).map(|_| BlsScalar::random(&mut rng)).collect();
+    let (prover, verifier) = keys(&mut rng, &SelectNCircuit::new(0, 3, &x));
+
+    // the indexes without an option can't pick any option
+    for index in 5..8 {
+        for res in x.iter().copied().chain([BlsScalar::zero()]) {
+            let mut circuit = SelectNCircuit::new(index, 3, &x);
+            circuit.res = res;
+
+            assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+        }
+    }
+
+    // a single option with index bits forces them to zero
+    let x = [BlsScalar::random(&mut rng)];
+    let (prover, verifier) = keys(&mut rng, &SelectNCircuit::new(0, 2, &x));
+
+    let mut circuit = SelectNCircuit::new(2, 2, &x);
+    circuit.res = x[0];
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+}
+
+#[test]
+fn select_n_forged_selection() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let x = [BlsScalar::from(3u64), BlsScalar::from(5u64)];
+    let (prover, verifier) = keys(&mut rng, &SelectNCircuit::new(0, 1, &x));
+
+    // the index picks `x0`, but the public value is `x1`
+    let mut circuit = SelectNCircuit::new(0, 1, &x);
+    circuit.res = x[1];
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, the bit, the options and the public
+    // value, the selection witnesses its partial sum and its result
+    let mut state = composer.serialize_witness_state();
+    for i in [6, 7] {
+        let at = 30 + i * 32;
+        state[at..at + 32].copy_from_slice(&x[1].encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged selection must not verify");
+}
+
+#[test]
+fn select_n_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let bits: Vec<_> = [0u64, 0, 1]
+        .iter()
+        .map(|b| composer.append_witness(BlsScalar::from(*b)))
+        .collect();
+    let x: Vec<_> = (0..8u64)
+        .map(|x| composer.append_witness(BlsScalar::from(x + 10)))
+        .collect();
+
+    let gates = composer.statistics().gates;
+    let w = composer.component_select_n(&bits, &x);
+    assert_eq!(composer.statistics().gates - gates, 14);
+    assert_eq!(composer[w], BlsScalar::from(14u64));
+
+    let gates = composer.statistics().gates;
+    let w = composer.component_select_n(&bits, &x[..5]);
+    assert_eq!(composer.statistics().gates - gates, 8 + 3);
+    assert_eq!(composer[w], BlsScalar::from(14u64));
+}
+
+#[test]
+#[should_panic(expected = "the index bits must address every option")]
+fn select_n_too_many_options() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let bit = composer.append_witness(BlsScalar::one());
+    let x = [composer.append_witness(BlsScalar::one()); 3];
+
+    composer.component_select_n(&[bit], &x);
+}
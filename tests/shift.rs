@@ -0,0 +1,250 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkstd::common::FftField;
+
+const BITS: usize = 32;
+
+#[derive(Debug)]
+pub struct ShiftCircuit {
+    a: BlsScalar,
+    c: BlsScalar,
+    shift: usize,
+    left: bool,
+}
+
+impl ShiftCircuit {
+    pub fn new(a: u32, shift: usize, left: bool) -> Self {
+        let c = match (left, shift < BITS) {
+            (_, false) => 0,
+            (true, true) => a << shift,
+            (false, true) => a >> shift,
+        };
+
+        Self {
+            a: BlsScalar::from(a as u64),
+            c: BlsScalar::from(c as u64),
+            shift,
+            left,
+        }
+    }
+}
+
+impl Default for ShiftCircuit {
+    fn default() -> Self {
+        Self::new(0, 0, true)
+    }
+}
+
+impl Circuit<JubjubAffine> for ShiftCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let c = composer.append_public(self.c);
+
+        let x = match self.left {
+            true => composer.component_shl(a, self.shift, BITS),
+            false => composer.component_shr(a, self.shift, BITS),
+        };
+
+        composer.assert_equal(x, c);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn shift_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+    let label = b"shift";
+
+    for left in [true, false] {
+        for shift in [0, 1, BITS - 1, BITS, BITS + 7] {
+            let (prover, verifier) = PlonkKey::compile_with_circuit(
+                &mut pp,
+                label,
+                &ShiftCircuit::new(0, shift, left),
+            )
+            .expect("failed to compile circuit");
+
+            let a = rng.next_u32();
+            let (proof, public_inputs) = prover
+                .create_proof(&mut rng, &ShiftCircuit::new(a, shift, left))
+                .expect("failed to prove");
+
+            verifier
+                .verify(&proof, &public_inputs)
+                .expect("failed to verify proof");
+        }
+    }
+}
+
+#[test]
+fn shift_off_by_one_bit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+    let label = b"shift";
+
+    for left in [true, false] {
+        for shift in [0, 1, BITS - 1] {
+            let (prover, verifier) = PlonkKey::compile_with_circuit(
+                &mut pp,
+                label,
+                &ShiftCircuit::new(0, shift, left),
+            )
+            .expect("failed to compile circuit");
+
+            let a = rng.next_u32();
+            let mut circuit = ShiftCircuit::new(a, shift, left);
+
+            // flip the lowest bit the shift can produce
+            let c = match left {
+                true => (a << shift) ^ (1 << shift),
+                false => (a >> shift) ^ 1,
+            };
+            circuit.c = BlsScalar::from(c as u64);
+
+            if let Ok((proof, public_inputs)) =
+                prover.create_proof(&mut rng, &circuit)
+            {
+                verifier
+                    .verify(&proof, &public_inputs)
+                    .expect_err("the proof must not verify");
+            }
+        }
+    }
+}
+
+#[test]
+fn shift_refuses_wide_input() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+    let label = b"shift";
+
+    let (prover, _) = PlonkKey::compile_with_circuit(
+        &mut pp,
+        label,
+        &ShiftCircuit::new(0, 1, false),
+    )
+    .expect("failed to compile circuit");
+
+    // `a` must fit the width of the shift
+    let mut circuit = ShiftCircuit::new(2, 1, false);
+    circuit.a += BlsScalar::pow_of_2(BITS as u64);
+
+    prover
+        .create_proof(&mut rng, &circuit)
+        .expect_err("the input doesn't fit the width");
+}
+
+#[derive(Debug)]
+pub struct RotateCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+    r: usize,
+}
+
+impl RotateCircuit {
+    /// `c = rotr(a, r) ^ b`, so the rotation feeds a logic gate
+    pub fn new(a: u32, b: u32, r: usize) -> Self {
+        let c = a.rotate_right(r as u32) ^ b;
+
+        Self {
+            a: BlsScalar::from(a as u64),
+            b: BlsScalar::from(b as u64),
+            c: BlsScalar::from(c as u64),
+            r,
+        }
+    }
+}
+
+impl Default for RotateCircuit {
+    fn default() -> Self {
+        Self::new(0, 0, 0)
+    }
+}
+
+impl Circuit<JubjubAffine> for RotateCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let c = composer.append_public(self.c);
+
+        let x = composer.component_rotr_u32(a, self.r);
+        let x = composer.append_logic_xor(x, b, BITS);
+
+        composer.assert_equal(x, c);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn rotate_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+    let label = b"rotate";
+
+    for r in [0, 1, 7, 31, 32] {
+        let (prover, verifier) = PlonkKey::compile_with_circuit(
+            &mut pp,
+            label,
+            &RotateCircuit::new(0, 0, r),
+        )
+        .expect("failed to compile circuit");
+
+        let a = rng.next_u32();
+        let b = rng.next_u32();
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &RotateCircuit::new(a, b, r))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+
+        let mut circuit = RotateCircuit::new(a, b, r);
+        circuit.c = BlsScalar::from((a.rotate_left(r as u32) ^ b) as u64);
+
+        if a.rotate_left(r as u32) != a.rotate_right(r as u32) {
+            if let Ok((proof, public_inputs)) =
+                prover.create_proof(&mut rng, &circuit)
+            {
+                verifier
+                    .verify(&proof, &public_inputs)
+                    .expect_err("the proof must not verify");
+            }
+        }
+    }
+}
+
+#[test]
+fn rotate_identities() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(0xdead_beef_u64));
+    let rotl = composer.component_rotl_u32(a, 0);
+    let rotr = composer.component_rotr_u32(a, 32);
+    let left = composer.component_rotl_u32(a, 8);
+    let right = composer.component_rotr_u32(a, 24);
+
+    assert_eq!(composer[rotl], BlsScalar::from(0xdead_beef_u64));
+    assert_eq!(composer[rotr], BlsScalar::from(0xdead_beef_u64));
+    assert_eq!(composer[left], BlsScalar::from(0xadbe_efde_u64));
+    assert_eq!(composer[left], composer[right]);
+}
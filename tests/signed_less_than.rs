@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public bit is `a < b` for the two's complement encodings of
+/// 64 bits
+#[derive(Debug, Default)]
+pub struct SignedCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    lt: BlsScalar,
+}
+
+impl SignedCircuit {
+    pub fn new(a: i64, b: i64) -> Self {
+        Self {
+            a: BlsScalar::from(a as u64),
+            b: BlsScalar::from(b as u64),
+            lt: BlsScalar::from((a < b) as u64),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for SignedCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+        let lt = composer.append_public(self.lt);
+
+        let x = composer.component_signed_less_than::<64>(a, b);
+        composer.assert_equal(x, lt);
+
+        Ok(())
+    }
+}
+
+type SignedKey = PlonkKey<TatePairing, JubjubAffine, SignedCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = SignedKey::setup_for_circuit(&SignedCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    SignedKey::compile(&pp).expect("failed to compile circuit")
+}
+
+fn prove(
+    rng: &mut StdRng,
+    prover: &JubjubProver,
+    verifier: &JubjubVerifier,
+    circuit: &SignedCircuit,
+) -> Result<(), Error> {
+    prover
+        .create_proof(rng, circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi))
+}
+
+#[test]
+fn signed_less_than_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let mut cases = vec![
+        (i64::MIN, i64::MAX),
+        (i64::MAX, i64::MIN),
+        (i64::MIN, i64::MIN),
+        (-5, -5),
+        (-5, -4),
+        (-4, -5),
+        (0, -1),
+        (-1, 0),
+        (0, 0),
+        (0, 1),
+    ];
+
+    // small values collide often, so equal operands are covered too
+    for i in 0..8 {
+        let x = rng.next_u64() as i64;
+        let y = match i % 2 {
+            0 => rng.next_u64() as i64,
+            _ => (rng.next_u64() % 4) as i64 - 2,
+        };
+        cases.push((x, y));
+        cases.push((y, x % 3));
+    }
+
+    for (a, b) in cases {
+        let circuit = SignedCircuit::new(a, b);
+        prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+
+        // the opposite bit is refused
+        let mut circuit = SignedCircuit::new(a, b);
+        circuit.lt = BlsScalar::one() - circuit.lt;
+        assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+    }
+}
+
+#[test]
+fn signed_less_than_encoding() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // zero has a single encoding, and the field negation of an integer isn't
+    // its two's complement encoding
+    let mut circuit = SignedCircuit::new(-1, 0);
+    circuit.a = -BlsScalar::one();
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+
+    let mut circuit = SignedCircuit::new(0, 0);
+    circuit.a = -BlsScalar::zero();
+    prove(&mut rng, &prover, &verifier, &circuit).expect("failed to prove");
+
+    // an encoding above 64 bits
+    let mut circuit = SignedCircuit::new(0, 1);
+    circuit.a = BlsScalar::pow_of_2(64);
+    assert!(prove(&mut rng, &prover, &verifier, &circuit).is_err());
+}
+
+#[test]
+fn signed_less_than_forged_sign() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // -1 < 1, claimed to be false by flipping the sign of `a`
+    let mut circuit = SignedCircuit::new(-1, 1);
+    circuit.lt = BlsScalar::zero();
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, both encodings and the public bit,
+    // the sign of `a` and the bits below it; clearing the sign keeps the
+    // recomposition with the low part shifted up
+    let low = BlsScalar::from(u64::MAX);
+    let mut state = composer.serialize_witness_state();
+    for (i, value) in [(5, BlsScalar::zero()), (6, low)] {
+        let at = 30 + i * 32;
+        state[at..at + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged sign must not verify");
+}
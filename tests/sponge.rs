@@ -0,0 +1,183 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::gadget::hash::{CircuitHasher, Mimc};
+use zkplonk::gadget::sponge::{CircuitSponge, Sponge};
+use zkplonk::prelude::*;
+
+const LABEL: &[u8] = b"zkplonk-sponge-test";
+
+/// Squeezes of the native sponge after every chunk
+fn native(label: &[u8], chunks: &[Vec<BlsScalar>]) -> Vec<BlsScalar> {
+    let mut sponge = Sponge::<BlsScalar>::new(label);
+
+    chunks
+        .iter()
+        .map(|chunk| {
+            sponge.absorb(chunk);
+            sponge.squeeze()
+        })
+        .collect()
+}
+
+/// Squeezes of the circuit sponge after every chunk
+fn circuit(label: &[u8], chunks: &[Vec<BlsScalar>]) -> Vec<BlsScalar> {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let chunks: Vec<Vec<_>> = chunks
+        .iter()
+        .map(|chunk| {
+            chunk.iter().map(|v| composer.append_witness(*v)).collect()
+        })
+        .collect();
+
+    let mut sponge = CircuitSponge::<JubjubAffine>::new(&mut composer, label);
+    let squeezes: Vec<_> = chunks
+        .iter()
+        .map(|chunk| {
+            sponge.absorb(chunk);
+            sponge.squeeze()
+        })
+        .collect();
+
+    squeezes.iter().map(|w| composer[*w]).collect()
+}
+
+#[test]
+fn mimc_gadget() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let left = BlsScalar::random(&mut rng);
+    let right = BlsScalar::random(&mut rng);
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let l = composer.append_witness(left);
+    let r = composer.append_witness(right);
+
+    let gates = composer.statistics().gates;
+    let hash = Mimc::gadget(&mut composer, l, r);
+
+    assert_eq!(composer[hash], Mimc::hash(left, right));
+    assert_ne!(Mimc::hash(left, right), Mimc::hash(right, left));
+    assert_eq!(composer.statistics().gates - gates, Mimc::GATES);
+}
+
+#[test]
+fn sponge_native_matches_circuit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut random = |n| -> Vec<BlsScalar> {
+        (0..n).map(|_| BlsScalar::random(&mut rng)).collect()
+    };
+
+    let patterns = [
+        vec![random(1)],
+        vec![random(3)],
+        vec![random(0), random(2)],
+        vec![random(2), random(1), random(0)],
+    ];
+
+    for chunks in patterns.iter() {
+        assert_eq!(native(LABEL, chunks), circuit(LABEL, chunks));
+    }
+}
+
+#[test]
+fn sponge_padding() {
+    let a = BlsScalar::from(5u64);
+    let b = BlsScalar::from(7u64);
+
+    let squeezes = |chunks: &[Vec<BlsScalar>]| {
+        let mut sponge = Sponge::<BlsScalar>::new(LABEL);
+        chunks.iter().for_each(|chunk| sponge.absorb(chunk));
+        sponge.squeeze()
+    };
+
+    // the boundaries of the chunks are absorbed
+    assert_ne!(squeezes(&[vec![a, b]]), squeezes(&[vec![a], vec![b]]));
+    assert_ne!(squeezes(&[vec![a]]), squeezes(&[vec![a], vec![]]));
+    assert_ne!(squeezes(&[vec![a, b]]), squeezes(&[vec![b, a]]));
+
+    // every squeeze is a new challenge
+    let mut sponge = Sponge::<BlsScalar>::new(LABEL);
+    sponge.absorb(&[a]);
+    assert_ne!(sponge.squeeze(), sponge.squeeze());
+}
+
+#[test]
+fn sponge_domain_separation() {
+    let chunks = [vec![BlsScalar::from(5u64)]];
+
+    let native_a = native(b"label-a", &chunks);
+    let native_b = native(b"label-b", &chunks);
+    assert_ne!(native_a, native_b);
+
+    assert_eq!(circuit(b"label-a", &chunks), native_a);
+    assert_eq!(circuit(b"label-b", &chunks), native_b);
+}
+
+/// Proves the challenge squeezed from secret values
+#[derive(Debug, Default)]
+pub struct ChallengeCircuit {
+    values: [BlsScalar; 3],
+    challenge: BlsScalar,
+}
+
+impl ChallengeCircuit {
+    fn new(values: [BlsScalar; 3]) -> Self {
+        let mut sponge = Sponge::<BlsScalar>::new(LABEL);
+        sponge.absorb(&values);
+
+        Self {
+            values,
+            challenge: sponge.squeeze(),
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for ChallengeCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let values = self.values.map(|v| composer.append_witness(v));
+
+        let mut sponge = CircuitSponge::<JubjubAffine>::new(composer, LABEL);
+        sponge.absorb(&values);
+        let challenge = sponge.squeeze();
+
+        let expected = composer.append_public(self.challenge);
+        composer.assert_equal(challenge, expected);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn sponge_proof() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp = PlonkKey::<TatePairing, JubjubAffine, ChallengeCircuit>::setup_for_circuit(
+        &ChallengeCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, ChallengeCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let values = [(); 3].map(|_| BlsScalar::random(&mut rng));
+    let circuit = ChallengeCircuit::new(values);
+
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+    assert_eq!(public_inputs, vec![circuit.challenge]);
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
@@ -0,0 +1,106 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Asserts the public square and cube of a witness
+#[derive(Debug, Default)]
+pub struct PowersCircuit {
+    a: BlsScalar,
+    square: BlsScalar,
+    cube: BlsScalar,
+}
+
+impl PowersCircuit {
+    pub fn new(a: BlsScalar) -> Self {
+        Self {
+            a,
+            square: a * a,
+            cube: a * a * a,
+        }
+    }
+}
+
+impl Circuit<JubjubAffine> for PowersCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let square = composer.append_public(self.square);
+        let cube = composer.append_public(self.cube);
+
+        let x = composer.component_square(a);
+        composer.assert_equal(x, square);
+
+        let x = composer.component_cube(a);
+        composer.assert_equal(x, cube);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn square_cube_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkKey::<TatePairing, JubjubAffine, _>::setup_for_circuit(
+        &PowersCircuit::default(),
+        &mut rng,
+    )
+    .expect("failed to synthesize");
+
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, PowersCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let values = [BlsScalar::zero(), BlsScalar::one(), -BlsScalar::one()];
+    let randoms = [(); 4].map(|_| BlsScalar::random(&mut rng));
+
+    for a in values.into_iter().chain(randoms) {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &PowersCircuit::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    for i in 0..2 {
+        let mut circuit = PowersCircuit::new(BlsScalar::random(&mut rng));
+        match i {
+            0 => circuit.square += BlsScalar::one(),
+            _ => circuit.cube += BlsScalar::one(),
+        }
+
+        let result = prover
+            .create_proof(&mut rng, &circuit)
+            .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+        assert!(result.is_err(), "a wrong power shouldn't verify");
+    }
+}
+
+#[test]
+fn square_cube_gates() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let value = BlsScalar::random(&mut rng);
+    let a = composer.append_witness(value);
+
+    let gates = composer.statistics().gates;
+    let square = composer.component_square(a);
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[square], value * value);
+
+    let gates = composer.statistics().gates;
+    let cube = composer.component_cube(a);
+    assert_eq!(composer.statistics().gates, gates + 2);
+    assert_eq!(composer[cube], value * value * value);
+}
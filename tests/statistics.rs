@@ -0,0 +1,135 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::SelectorFamily;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+
+#[derive(Debug, Default)]
+pub struct LogicCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for LogicCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let and = composer.append_logic_and(w_a, w_b, 256);
+        let xor = composer.append_logic_xor(w_a, w_b, 30);
+
+        composer.assert_equal(and, and);
+        composer.assert_equal(xor, xor);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RangeCircuit {
+    a: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for RangeCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_a = composer.append_witness(self.a);
+
+        composer.component_range(w_a, 76);
+        composer.component_range(w_a, 64);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn statistics_logic_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let initial = composer.statistics();
+
+    LogicCircuit::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    let stats = composer.statistics();
+
+    // `num_bits / 2 + 1` gates per call
+    assert_eq!(stats.logic, (256 / 2 + 1) + (30 / 2 + 1));
+    assert_eq!(stats.logic, stats.family(SelectorFamily::Logic));
+    assert_eq!(stats.copy - initial.copy, 2);
+    assert_eq!(stats.range, 0);
+    assert_eq!(stats.gates, ConstraintSystem::m(&composer));
+    assert_eq!(
+        stats.gates,
+        stats.arithmetic
+            + stats.range
+            + stats.logic
+            + stats.fixed_base
+            + stats.variable_base
+            + stats.empty
+    );
+}
+
+#[test]
+fn statistics_range_gates() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    RangeCircuit::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    let stats = composer.statistics();
+
+    // `ceil(num_bits / 8) + 1` gates per call
+    assert_eq!(stats.range, (10 + 1) + (8 + 1));
+    assert_eq!(stats.logic, 0);
+    assert_eq!(stats.domain_size, stats.gates.next_power_of_two());
+}
+
+#[test]
+fn statistics_compile_callback() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(8, &mut rng);
+
+    let mut reported = None;
+
+    PlonkKey::<TatePairing, JubjubAffine, LogicCircuit>::compile_with_stats(
+        &pp,
+        b"stats",
+        &LogicCircuit::default(),
+        InitConfig::default(),
+        |stats| reported = Some(*stats),
+    )
+    .expect("failed to compile circuit");
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    LogicCircuit::default()
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    let stats = reported.expect("statistics must be reported");
+    assert_eq!(stats, composer.statistics());
+
+    let json = stats.to_json();
+    assert!(json.starts_with('{') && json.ends_with('}'));
+    assert!(json.contains(&format!("\"logic\":{}", stats.logic)));
+    assert!(json.contains(&format!("\"gates\":{}", stats.gates)));
+}
@@ -0,0 +1,147 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Decode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+
+/// Scalar whose limbs hold `2^256 - 1`, above the field order
+fn non_canonical() -> BlsScalar {
+    BlsScalar::decode(&mut &[0xff; 32][..])
+        .expect("the limbs are decoded as they are")
+}
+
+/// Decomposes a witness in bits and checks it fits 254 bits
+#[derive(Debug, Default)]
+pub struct BitsCircuit {
+    value: BlsScalar,
+}
+
+impl Circuit<JubjubAffine> for BitsCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w = composer.append_witness(self.value);
+
+        composer.component_decomposition::<256>(w);
+        composer.component_range(w, 256);
+
+        Ok(())
+    }
+}
+
+#[test]
+#[should_panic(expected = "the witness of wire 0 isn't canonical")]
+fn strict_rejects_non_canonical() {
+    let mut composer = Plonk::<JubjubAffine>::with_config(InitConfig {
+        dummy_gate_sets: 0,
+        reserve_zero_one: false,
+        ..InitConfig::default()
+    });
+    composer.set_strict_witnesses(true);
+
+    composer.append_witness(non_canonical());
+}
+
+#[test]
+fn decomposition_is_canonical() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    composer.set_strict_witnesses(false);
+
+    let w = composer.append_witness(non_canonical());
+    let bits = composer.component_decomposition::<256>(w);
+
+    // the bits recompose to the field value, not to the raw integer
+    let recomposed = bits
+        .iter()
+        .rev()
+        .fold(BlsScalar::zero(), |acc, b| acc.double() + composer[*b]);
+    assert_eq!(recomposed, composer[w]);
+}
+
+#[test]
+fn non_canonical_proves_without_strict() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, BitsCircuit>::setup_for_circuit(
+            &BitsCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+    let (mut prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, BitsCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    prover.set_options(ProverOptions {
+        strict_witnesses: false,
+        ..Default::default()
+    });
+
+    let circuit = BitsCircuit {
+        value: non_canonical(),
+    };
+    let (proof, public_inputs) = prover
+        .create_proof(&mut rng, &circuit)
+        .expect("failed to prove");
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn strict_without_overhead() {
+    let circuit = BitsCircuit {
+        value: BlsScalar::from(0xdead_beef_u64),
+    };
+
+    let synthesize = |strict| {
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        composer.set_strict_witnesses(strict);
+        circuit
+            .synthesize(&mut composer)
+            .expect("canonical witness");
+
+        composer
+    };
+
+    let strict = synthesize(true);
+    let lenient = synthesize(false);
+
+    // the check appends nothing to the circuit
+    assert_eq!(strict.statistics(), lenient.statistics());
+    assert_eq!(strict.circuit_id(), lenient.circuit_id());
+    assert_eq!(
+        strict.serialize_witness_state(),
+        lenient.serialize_witness_state()
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "isn't canonical")]
+fn prover_is_strict_in_debug() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let pp =
+        PlonkKey::<TatePairing, JubjubAffine, BitsCircuit>::setup_for_circuit(
+            &BitsCircuit::default(),
+            &mut rng,
+        )
+        .expect("failed to synthesize");
+    let (prover, _) =
+        PlonkKey::<TatePairing, JubjubAffine, BitsCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let circuit = BitsCircuit {
+        value: non_canonical(),
+    };
+    let _ = prover.create_proof(&mut rng, &circuit);
+}
@@ -0,0 +1,163 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Asserts the public point is `a - b`
+#[derive(Debug)]
+pub struct SubCircuit {
+    a: JubjubAffine,
+    b: JubjubAffine,
+    diff: JubjubAffine,
+}
+
+impl SubCircuit {
+    pub fn new(a: JubjubAffine, b: JubjubAffine) -> Self {
+        let b_ext: JubjubExtended = b.into();
+
+        Self {
+            a,
+            b,
+            diff: (a + JubjubAffine::from(-b_ext)).into(),
+        }
+    }
+}
+
+impl Default for SubCircuit {
+    fn default() -> Self {
+        let g = JubjubAffine::ADDITIVE_GENERATOR;
+
+        Self::new(g, g)
+    }
+}
+
+impl Circuit<JubjubAffine> for SubCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_point(self.a);
+        let b = composer.append_point(self.b);
+
+        let diff = composer.component_sub_point(a, b);
+        composer.assert_equal_public_point(diff, self.diff);
+
+        Ok(())
+    }
+}
+
+type SubKey = PlonkKey<TatePairing, JubjubAffine, SubCircuit>;
+
+fn random_point(rng: &mut StdRng) -> JubjubAffine {
+    (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::random(rng)).into()
+}
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let pp = SubKey::setup_for_circuit(&SubCircuit::default(), rng)
+        .expect("failed to synthesize");
+
+    SubKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn sub_point_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let p = random_point(&mut rng);
+    let q = random_point(&mut rng);
+    let identity = JubjubAffine::ADDITIVE_IDENTITY;
+
+    // P - P = O, P - O = P and O - P = -P
+    let circuit = SubCircuit::new(p, p);
+    assert_eq!(circuit.diff.get_x(), BlsScalar::zero());
+    assert_eq!(circuit.diff.get_y(), BlsScalar::one());
+
+    let circuit = SubCircuit::new(p, identity);
+    assert_eq!(circuit.diff, p);
+
+    for (a, b) in [(p, p), (p, identity), (identity, p), (p, q), (q, p)] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &SubCircuit::new(a, b))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+
+    // the sum claimed as the difference
+    let circuit = SubCircuit {
+        a: p,
+        b: q,
+        diff: (p + q).into(),
+    };
+    let result = prover
+        .create_proof(&mut rng, &circuit)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a + b must not verify as a - b");
+}
+
+#[test]
+fn sub_point_values() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let p = random_point(&mut rng);
+    let point = composer.append_point(p);
+
+    let gates = composer.statistics().gates;
+    let neg = composer.component_neg_point(point);
+    assert_eq!(composer.statistics().gates, gates + 1);
+    assert_eq!(composer[*neg.x()], -p.get_x());
+    assert_eq!(neg.y(), point.y());
+
+    let gates = composer.statistics().gates;
+    let diff = composer.component_sub_point(point, point);
+    assert_eq!(composer.statistics().gates, gates + 3);
+    assert_eq!(composer[*diff.x()], BlsScalar::zero());
+    assert_eq!(composer[*diff.y()], BlsScalar::one());
+}
+
+#[test]
+fn sub_point_forged_negation() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    // P - Q claimed to be P + Q
+    let p = random_point(&mut rng);
+    let q = random_point(&mut rng);
+    let mut circuit = SubCircuit::new(p, q);
+    circuit.diff = (p + q).into();
+
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and the coordinates of both points,
+    // the negated x of `b`, then `x_a · y_b` and the sum: forging the
+    // negation to x itself and the sum to P + Q only breaks the negation
+    let sum: JubjubAffine = (p + q).into();
+    let mut state = composer.serialize_witness_state();
+    for (i, value) in [(6, q.get_x()), (8, sum.get_x()), (9, sum.get_y())] {
+        let at = 30 + i * 32;
+        state[at..at + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a forged negation must not verify");
+}
@@ -0,0 +1,247 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::{Group, TwistedEdwardsAffine};
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    bit: BlsScalar,
+    a: BlsScalar,
+    b: BlsScalar,
+    constrain_bit: bool,
+}
+
+impl DummyCircuit {
+    pub fn new(bit: BlsScalar, a: BlsScalar, b: BlsScalar) -> Self {
+        Self {
+            bit,
+            a,
+            b,
+            constrain_bit: true,
+        }
+    }
+}
+
+impl Default for DummyCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::zero(), BlsScalar::zero(), BlsScalar::zero())
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let w_bit = composer.append_witness(self.bit);
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        // native delta trick, matching the gadget for any bit value
+        let delta = self.bit * (self.b - self.a);
+        let first = composer.append_public(self.a + delta);
+        let second = composer.append_public(self.b - delta);
+
+        let (w_first, w_second) =
+            composer.component_swap(w_bit, w_a, w_b, self.constrain_bit);
+
+        composer.assert_equal(w_first, first);
+        composer.assert_equal(w_second, second);
+
+        Ok(())
+    }
+}
+
+fn prove_and_verify(
+    rng: &mut StdRng,
+    constrain_bit: bool,
+    circuit: DummyCircuit,
+) -> Result<(), Error> {
+    let mut pp = PlonkParams::<TatePairing>::setup(5, &mut *rng);
+
+    let default = DummyCircuit {
+        constrain_bit,
+        ..Default::default()
+    };
+    let (prover, verifier) =
+        PlonkKey::compile_with_circuit(&mut pp, b"swap", &default)?;
+
+    let circuit = DummyCircuit {
+        constrain_bit,
+        ..circuit
+    };
+    let (proof, public_inputs) = prover.create_proof(rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)
+}
+
+/// Outputs of the gadget as evaluated by the composer
+fn swap(bit: BlsScalar, a: BlsScalar, b: BlsScalar) -> (BlsScalar, BlsScalar) {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let w_bit = composer.append_witness(bit);
+    let w_a = composer.append_witness(a);
+    let w_b = composer.append_witness(b);
+
+    let m = ConstraintSystem::m(&composer);
+    let (first, second) = composer.component_swap(w_bit, w_a, w_b, false);
+    assert_eq!(ConstraintSystem::m(&composer) - m, 3);
+
+    (composer[first], composer[second])
+}
+
+#[test]
+fn swap_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+
+    // bit == 0 keeps the order
+    assert_eq!(swap(BlsScalar::zero(), a, b), (a, b));
+    prove_and_verify(
+        &mut rng,
+        true,
+        DummyCircuit::new(BlsScalar::zero(), a, b),
+    )
+    .expect("failed to prove and verify");
+
+    // bit == 1 swaps
+    assert_eq!(swap(BlsScalar::one(), a, b), (b, a));
+    prove_and_verify(&mut rng, true, DummyCircuit::new(BlsScalar::one(), a, b))
+        .expect("failed to prove and verify");
+
+    // a == b
+    for bit in [BlsScalar::zero(), BlsScalar::one()] {
+        assert_eq!(swap(bit, a, a), (a, a));
+        prove_and_verify(&mut rng, true, DummyCircuit::new(bit, a, a))
+            .expect("failed to prove and verify");
+    }
+}
+
+#[test]
+fn swap_non_boolean_bit() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let bit = BlsScalar::from(2);
+
+    // without the boolean constraint, the outputs aren't a permutation of
+    // the inputs
+    let outputs = swap(bit, a, b);
+    assert_ne!(outputs, (a, b));
+    assert_ne!(outputs, (b, a));
+
+    prove_and_verify(&mut rng, false, DummyCircuit::new(bit, a, b))
+        .expect("unconstrained bit is accepted");
+
+    prove_and_verify(&mut rng, true, DummyCircuit::new(bit, a, b))
+        .expect_err("non boolean bit must not verify");
+}
+
+#[test]
+fn swap_point_works() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = JubjubAffine::ADDITIVE_GENERATOR;
+    let b: JubjubAffine =
+        (JubjubAffine::ADDITIVE_GENERATOR * JubjubScalar::from(2u64)).into();
+
+    let w_a = composer.append_point(a);
+    let w_b = composer.append_point(b);
+
+    for (bit, expected) in [(0u64, (a, b)), (1, (b, a))] {
+        let w_bit = composer.append_witness(BlsScalar::from(bit));
+
+        let m = ConstraintSystem::m(&composer);
+        let (first, second) =
+            composer.component_swap_point(w_bit, w_a, w_b, true);
+        assert_eq!(ConstraintSystem::m(&composer) - m, 7);
+
+        assert_eq!(composer[*first.x()], expected.0.get_x());
+        assert_eq!(composer[*first.y()], expected.0.get_y());
+        assert_eq!(composer[*second.x()], expected.1.get_x());
+        assert_eq!(composer[*second.y()], expected.1.get_y());
+    }
+}
+
+#[test]
+fn swap_refuses_mixture() {
+    #[derive(Debug, Default)]
+    pub struct MixtureCircuit {
+        bit: BlsScalar,
+        a: BlsScalar,
+        b: BlsScalar,
+        first: BlsScalar,
+        second: BlsScalar,
+    }
+
+    impl Circuit<JubjubAffine> for MixtureCircuit {
+        type ConstraintSystem = Plonk<JubjubAffine>;
+        fn synthesize(
+            &self,
+            composer: &mut Plonk<JubjubAffine>,
+        ) -> Result<(), Error> {
+            let w_bit = composer.append_witness(self.bit);
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let first = composer.append_public(self.first);
+            let second = composer.append_public(self.second);
+
+            let (w_first, w_second) =
+                composer.component_swap(w_bit, w_a, w_b, true);
+
+            composer.assert_equal(w_first, first);
+            composer.assert_equal(w_second, second);
+
+            Ok(())
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let mut pp = PlonkParams::<TatePairing>::setup(5, &mut rng);
+
+    let (prover, verifier) = PlonkKey::compile_with_circuit(
+        &mut pp,
+        b"swap",
+        &MixtureCircuit::default(),
+    )
+    .expect("failed to compile circuit");
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+
+    // the outputs must be `(a, b)` or `(b, a)`, never a mixture of them
+    for bit in [BlsScalar::zero(), BlsScalar::one()] {
+        for (first, second) in [(a, a), (b, b)] {
+            let circuit = MixtureCircuit {
+                bit,
+                a,
+                b,
+                first,
+                second,
+            };
+
+            if let Ok((proof, public_inputs)) =
+                prover.create_proof(&mut rng, &circuit)
+            {
+                verifier
+                    .verify(&proof, &public_inputs)
+                    .expect_err("the proof must not verify");
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "track-callers")]
+
+use jub_jub::JubjubAffine;
+use zkplonk::prelude::*;
+use zksnarks::constraint_system::ConstraintSystem;
+
+#[test]
+fn track_callers_works() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let a = composer.append_witness(BlsScalar::from(3));
+    let witness_line = line!() - 1;
+
+    let m = ConstraintSystem::m(&composer);
+    let constraint = Constraint::default().mult(1).a(a).b(a);
+    let o = composer.gate_mul(constraint);
+    let gate_line = line!() - 1;
+
+    // the gate is unsatisfied, but its location is still reported
+    composer.assert_equal_constant(o, 10, None);
+    let assert_line = line!() - 1;
+
+    let location = composer.witness_location(a).expect("witness exists");
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), witness_line);
+
+    let location = composer.witness_location(o).expect("witness exists");
+    assert_eq!(location.line(), gate_line);
+
+    let location = composer.constraint_location(m).expect("gate exists");
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), gate_line);
+
+    let location = composer.constraint_location(m + 1).expect("gate exists");
+    assert_eq!(location.line(), assert_line);
+
+    assert!(composer.constraint_location(m + 2).is_none());
+}
@@ -0,0 +1,152 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use codec::Encode;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkstd::common::*;
+
+/// Low `num_bits` bits of the canonical representation of `a`
+fn low_bits(a: BlsScalar, num_bits: usize) -> BlsScalar {
+    a.to_raw_bytes()
+        .iter()
+        .enumerate()
+        .flat_map(|(i, byte)| (0..8).map(move |j| (i * 8 + j, byte >> j & 1)))
+        .filter(|(i, bit)| *i < num_bits && *bit == 1)
+        .fold(BlsScalar::zero(), |acc, (i, _)| {
+            acc + BlsScalar::pow_of_2(i as u64)
+        })
+}
+
+/// Asserts the public value is the truncation of the secret one
+#[derive(Debug, Default)]
+pub struct TruncateCircuit<const N: usize> {
+    a: BlsScalar,
+    low: BlsScalar,
+}
+
+impl<const N: usize> TruncateCircuit<N> {
+    pub fn new(a: BlsScalar) -> Self {
+        Self {
+            a,
+            low: low_bits(a, N),
+        }
+    }
+}
+
+impl<const N: usize> Circuit<JubjubAffine> for TruncateCircuit<N> {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let x = composer.component_truncate(a, N);
+
+        let low = composer.append_public(self.low);
+        composer.assert_equal(x, low);
+
+        Ok(())
+    }
+}
+
+type TruncateKey<const N: usize> =
+    PlonkKey<TatePairing, JubjubAffine, TruncateCircuit<N>>;
+
+fn truncate_works<const N: usize>(rng: &mut StdRng) {
+    let pp = TruncateKey::<N>::setup_for_circuit(&Default::default(), rng)
+        .expect("failed to synthesize");
+    let (prover, verifier) =
+        TruncateKey::<N>::compile(&pp).expect("failed to compile circuit");
+
+    let max = -BlsScalar::one();
+    for a in [
+        BlsScalar::zero(),
+        BlsScalar::pow_of_2(N as u64) - BlsScalar::one(),
+        BlsScalar::pow_of_2(N as u64),
+        max,
+        max - BlsScalar::pow_of_2(N as u64),
+        BlsScalar::random(&mut *rng),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut *rng, &TruncateCircuit::<N>::new(a))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn truncate_widths() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    truncate_works::<1>(&mut rng);
+    truncate_works::<64>(&mut rng);
+    truncate_works::<128>(&mut rng);
+    truncate_works::<251>(&mut rng);
+    truncate_works::<254>(&mut rng);
+}
+
+#[test]
+fn truncate_values() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    // the modulus minus one has all the bits of its high part set
+    let max = -BlsScalar::one();
+    let a = composer.append_witness(max);
+    let low = composer.component_truncate(a, 64);
+
+    assert_eq!(composer[low], low_bits(max, 64));
+    assert_eq!(composer[low], BlsScalar::from(0xffff_ffff_0000_0000u64));
+}
+
+#[test]
+fn truncate_refuses_wrapped_split() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp =
+        TruncateKey::<64>::setup_for_circuit(&Default::default(), &mut rng)
+            .expect("failed to synthesize");
+    let (prover, verifier) =
+        TruncateKey::<64>::compile(&pp).expect("failed to compile circuit");
+
+    // `5 + p` splits into `L + 6` and `H`, where `(H, L)` is the split of
+    // `p - 1`, and the low part exceeds `L`
+    let max = -BlsScalar::one();
+    let max_low = low_bits(max, 64);
+    let max_high = (max - max_low)
+        * BlsScalar::pow_of_2(64)
+            .invert()
+            .expect("powers of two are invertible");
+    let forged_low = max_low + BlsScalar::from(6u64);
+
+    let circuit = TruncateCircuit::<64> {
+        a: BlsScalar::from(5u64),
+        low: forged_low,
+    };
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    // after the reserved zero and one, and `a`, the gadget witnesses the low
+    // part and then the high part
+    let mut state = composer.serialize_witness_state();
+    for (witness, value) in [(3, forged_low), (4, max_high)] {
+        let offset = 30 + witness * 32;
+        state[offset..offset + 32].copy_from_slice(&value.encode());
+    }
+    composer
+        .apply_witness_state(&state)
+        .expect("the state belongs to the composer");
+
+    let result = prover
+        .create_proof_with_composer(&mut rng, &composer)
+        .and_then(|(proof, pi)| verifier.verify(&proof, &pi));
+    assert!(result.is_err(), "a wrapped split must not verify");
+}
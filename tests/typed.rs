@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use ec_pairing::TatePairing;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zksnarks::circuit::Circuit;
+use zksnarks::constraint_system::ConstraintSystem;
+use zksnarks::error::Error;
+use zksnarks::keypair::Keypair;
+use zksnarks::plonk::wire::PrivateWire;
+use zksnarks::plonk::PlonkParams;
+use zksnarks::public_params::PublicParameters;
+use zkstd::common::{Group, TwistedEdwardsCurve};
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    bit: BlsScalar,
+    a: BlsScalar,
+    b: BlsScalar,
+    small: BlsScalar,
+    point_a: JubjubAffine,
+    point_b: JubjubAffine,
+}
+
+impl DummyCircuit {
+    pub fn new(bit: bool, a: BlsScalar, b: BlsScalar, small: u8) -> Self {
+        let generator = JubjubAffine::ADDITIVE_GENERATOR;
+
+        Self {
+            bit: BlsScalar::from(bit as u64),
+            a,
+            b,
+            small: BlsScalar::from(small as u64),
+            point_a: (generator * JubjubScalar::from(5u64)).into(),
+            point_b: (generator * JubjubScalar::from(7u64)).into(),
+        }
+    }
+
+    fn bit(&self) -> bool {
+        self.bit == BlsScalar::one()
+    }
+}
+
+impl Default for DummyCircuit {
+    fn default() -> Self {
+        Self::new(false, BlsScalar::one(), BlsScalar::one(), 1)
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let bit = composer.append_witness(self.bit);
+        let bit = composer.component_boolean_typed(bit);
+
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let (first, second) = match self.bit() {
+            true => (self.a, self.b),
+            false => (self.b, self.a),
+        };
+
+        let selected = composer.component_select_typed(bit, a, b);
+        let expected = composer.append_public(first);
+        composer.assert_equal(selected, expected);
+
+        let (x, y) = composer.component_swap_typed(bit, a, b);
+        let expected = composer.append_witness(second);
+        composer.assert_equal(x, expected);
+        let expected = composer.append_witness(first);
+        composer.assert_equal(y, expected);
+
+        let selected = composer.component_select_zero_typed(bit, a);
+        let expected = match self.bit() {
+            true => self.a,
+            false => BlsScalar::zero(),
+        };
+        let expected = composer.append_witness(expected);
+        composer.assert_equal(selected, expected);
+
+        let selected = composer.component_select_one_typed(bit, a);
+        let expected = match self.bit() {
+            true => self.a,
+            false => BlsScalar::one(),
+        };
+        let expected = composer.append_witness(expected);
+        composer.assert_equal(selected, expected);
+
+        let point_a = composer.append_point(self.point_a);
+        let point_b = composer.append_point(self.point_b);
+
+        let selected =
+            composer.component_select_point_typed(bit, point_a, point_b);
+        let expected = match self.bit() {
+            true => self.point_a,
+            false => self.point_b,
+        };
+        let expected = composer.append_point(expected);
+        composer.assert_equal_point(selected, expected);
+
+        let (x, _) = composer.component_swap_point_typed(bit, point_a, point_b);
+        let expected = match self.bit() {
+            true => self.point_b,
+            false => self.point_a,
+        };
+        let expected = composer.append_point(expected);
+        composer.assert_equal_point(x, expected);
+
+        let selected = composer.component_select_identity_typed(bit, point_a);
+        let expected = match self.bit() {
+            true => self.point_a,
+            false => JubjubAffine::ADDITIVE_IDENTITY,
+        };
+        let expected = composer.append_point(expected);
+        composer.assert_equal_point(selected, expected);
+
+        let small = composer.append_witness(self.small);
+        let small: RangeWire<8> = composer.component_range_typed(small);
+
+        let bits: [BoolWire; 8] =
+            composer.component_decomposition_typed(small.wire());
+        // the least significant bit of `0xa5` is set
+        let lowest = composer.component_select_typed(bits[0], a, b);
+        composer.assert_equal(lowest, a);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn typed_gadgets_work() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    let mut pp = PlonkParams::<TatePairing>::setup(9, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&mut pp)
+            .expect("failed to compile circuit");
+
+    for bit in [false, true] {
+        let a = BlsScalar::random(&mut rng);
+        let b = BlsScalar::random(&mut rng);
+        let circuit = DummyCircuit::new(bit, a, b, 0xa5);
+
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &circuit)
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn typed_wires_unwrap() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+
+    let w = composer.append_witness(BlsScalar::one());
+    let bit = composer.component_boolean_typed(w);
+
+    assert_eq!(bit.wire(), w);
+    assert_eq!(PrivateWire::from(bit), w);
+    assert_eq!(BoolWire::assume(w), bit);
+
+    let range: RangeWire<16> = composer.component_range_typed(w);
+    assert_eq!(range.wire(), w);
+}
@@ -0,0 +1,213 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use zkplonk::gadget::uint32::Word32;
+use zkplonk::prelude::*;
+
+/// One round of the SHA-256 compression function
+fn round(state: [u32; 8], k: u32, w: u32) -> [u32; 8] {
+    let [a, b, c, d, e, f, g, h] = state;
+
+    let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+    let ch = (e & f) ^ (!e & g);
+    let t1 = h
+        .wrapping_add(s1)
+        .wrapping_add(ch)
+        .wrapping_add(k)
+        .wrapping_add(w);
+
+    let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+    let maj = (a & b) ^ (a & c) ^ (b & c);
+    let t2 = s0.wrapping_add(maj);
+
+    [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g]
+}
+
+/// Proves the public state is one round of SHA-256 applied to a secret
+/// state and message word
+#[derive(Debug, Default)]
+pub struct RoundCircuit {
+    state: [u32; 8],
+    w: u32,
+    next: [u32; 8],
+}
+
+impl RoundCircuit {
+    const K: u32 = 0x428a_2f98;
+
+    pub fn new(state: [u32; 8], w: u32) -> Self {
+        let next = round(state, Self::K, w);
+
+        Self { state, w, next }
+    }
+}
+
+impl Circuit<JubjubAffine> for RoundCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let mut state = Vec::with_capacity(8);
+        for v in self.state {
+            state.push(Word32::witness(composer, v));
+        }
+        let (mut a, b, c, d) = (state[0], state[1], state[2], state[3]);
+        let (mut e, f, g, h) = (state[4], state[5], state[6], state[7]);
+
+        let k = Word32::constant(composer, Self::K);
+        let w = Word32::witness(composer, self.w);
+
+        let x = e.rotr(composer, 6);
+        let y = e.rotr(composer, 11);
+        let z = e.rotr(composer, 25);
+        let s1 = x.xor(composer, &y).xor(composer, &z);
+
+        let e_f = e.and(composer, &f);
+        let not_e_g = e.not(composer).and(composer, &g);
+        let ch = e_f.xor(composer, &not_e_g);
+
+        let t1 = Word32::add_many(composer, &[h, s1, ch, k, w]);
+
+        let x = a.rotr(composer, 2);
+        let y = a.rotr(composer, 13);
+        let z = a.rotr(composer, 22);
+        let s0 = x.xor(composer, &y).xor(composer, &z);
+
+        let a_b = a.and(composer, &b);
+        let a_c = a.and(composer, &c);
+        let b_c = b.and(composer, &c);
+        let maj = a_b.xor(composer, &a_c).xor(composer, &b_c);
+
+        let t2 = Word32::add_many(composer, &[s0, maj]);
+
+        let next_a = Word32::add_many(composer, &[t1, t2]);
+        let next_e = Word32::add_many(composer, &[d, t1]);
+
+        let next = [next_a, a, b, c, next_e, e, f, g];
+        for (word, value) in next.iter().zip(self.next) {
+            let public = composer.append_public(BlsScalar::from(value as u64));
+            composer.assert_equal(word.wire(), public);
+        }
+
+        Ok(())
+    }
+}
+
+type RoundKey = PlonkKey<TatePairing, JubjubAffine, RoundCircuit>;
+
+fn keys(rng: &mut StdRng) -> (JubjubProver, JubjubVerifier) {
+    let circuit = RoundCircuit::default();
+    let pp = RoundKey::setup_for_circuit(&circuit, rng)
+        .expect("failed to synthesize");
+
+    RoundKey::compile(&pp).expect("failed to compile circuit")
+}
+
+#[test]
+fn sha256_round_works() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let iv = [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ];
+    let random = [(); 8].map(|_| rng.next_u32());
+
+    for (state, w) in [
+        (iv, 0x6162_6380),
+        ([u32::MAX; 8], u32::MAX),
+        ([0; 8], 0),
+        (random, rng.next_u32()),
+    ] {
+        let (proof, public_inputs) = prover
+            .create_proof(&mut rng, &RoundCircuit::new(state, w))
+            .expect("failed to prove");
+
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect("failed to verify proof");
+    }
+}
+
+#[test]
+fn sha256_round_refuses_wrong_state() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let (prover, verifier) = keys(&mut rng);
+
+    let state = [(); 8].map(|_| rng.next_u32());
+    let w = rng.next_u32();
+
+    let mut circuit = RoundCircuit::new(state, w);
+    circuit.next[0] = circuit.next[0].wrapping_add(1);
+
+    if let Ok((proof, public_inputs)) = prover.create_proof(&mut rng, &circuit)
+    {
+        verifier
+            .verify(&proof, &public_inputs)
+            .expect_err("a wrong state shouldn't verify");
+    }
+}
+
+#[test]
+fn word_ops_match_native() {
+    let mut rng = StdRng::seed_from_u64(8349u64);
+
+    for _ in 0..8 {
+        let (x, y, z) = (rng.next_u32(), rng.next_u32(), rng.next_u32());
+        let k = rng.next_u32() as usize % 32;
+
+        let mut composer = Plonk::<JubjubAffine>::initialize();
+        let mut a = Word32::witness(&mut composer, x);
+        let b = Word32::witness(&mut composer, y);
+        let c = Word32::witness(&mut composer, z);
+
+        let sum = Word32::add_many(&mut composer, &[a, b, c]);
+        assert_eq!(sum.value(&composer), x.wrapping_add(y).wrapping_add(z));
+
+        let xor = a.xor(&mut composer, &b);
+        assert_eq!(xor.value(&composer), x ^ y);
+
+        let and = a.and(&mut composer, &b);
+        assert_eq!(and.value(&composer), x & y);
+
+        let not = a.not(&mut composer);
+        assert_eq!(not.value(&composer), !x);
+
+        let rotr = a.rotr(&mut composer, k);
+        assert_eq!(rotr.value(&composer), x.rotate_right(k as u32));
+
+        let shr = a.shr(&mut composer, k);
+        assert_eq!(shr.value(&composer), x >> k);
+    }
+}
+
+#[test]
+fn word_decomposition_is_reused() {
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    let mut a = Word32::witness(&mut composer, 0xdead_beef);
+
+    let gates = composer.statistics().gates;
+    a.rotr(&mut composer, 2);
+    let first = composer.statistics().gates - gates;
+
+    let gates = composer.statistics().gates;
+    a.rotr(&mut composer, 13);
+    a.shr(&mut composer, 3);
+    let chained = composer.statistics().gates - gates;
+
+    assert_eq!(first, 65 + 16);
+    assert_eq!(chained, 16 + 14);
+}
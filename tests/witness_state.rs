@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use zkplonk::prelude::*;
+use zkplonk::WitnessStateError;
+
+#[derive(Debug)]
+pub struct DummyCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl DummyCircuit {
+    fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Default for DummyCircuit {
+    fn default() -> Self {
+        Self::new(BlsScalar::from(3u64), BlsScalar::from(4u64))
+    }
+}
+
+impl Circuit<JubjubAffine> for DummyCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let constraint = Constraint::default().mult(1).a(a).b(b);
+        let c = composer.gate_mul(constraint);
+
+        let expected = composer.append_public(self.a * self.b);
+        composer.assert_equal(c, expected);
+        composer.component_range(a, 32);
+
+        Ok(())
+    }
+}
+
+/// Same witnesses, but the range is checked on `b`
+#[derive(Debug, Default)]
+pub struct OtherCircuit(DummyCircuit);
+
+impl Circuit<JubjubAffine> for OtherCircuit {
+    type ConstraintSystem = Plonk<JubjubAffine>;
+    fn synthesize(
+        &self,
+        composer: &mut Plonk<JubjubAffine>,
+    ) -> Result<(), Error> {
+        let a = composer.append_witness(self.0.a);
+        let b = composer.append_witness(self.0.b);
+
+        let constraint = Constraint::default().mult(1).a(a).b(b);
+        let c = composer.gate_mul(constraint);
+
+        let expected = composer.append_public(self.0.a * self.0.b);
+        composer.assert_equal(c, expected);
+        composer.component_range(b, 32);
+
+        Ok(())
+    }
+}
+
+fn synthesize<C>(circuit: &C) -> Plonk<JubjubAffine>
+where
+    C: Circuit<JubjubAffine, ConstraintSystem = Plonk<JubjubAffine>>,
+{
+    let mut composer = Plonk::<JubjubAffine>::initialize();
+    circuit
+        .synthesize(&mut composer)
+        .expect("failed to synthesize");
+
+    composer
+}
+
+#[test]
+fn witness_state_round_trip() {
+    let circuit = DummyCircuit::new(BlsScalar::from(11u64), 13u64.into());
+    let sender = synthesize(&circuit);
+    let state = sender.serialize_witness_state();
+
+    let mut receiver = synthesize(&DummyCircuit::default());
+    assert_eq!(receiver.circuit_id(), sender.circuit_id());
+    assert_ne!(receiver.serialize_witness_state(), state);
+
+    receiver
+        .apply_witness_state(&state)
+        .expect("the state belongs to the circuit");
+    assert_eq!(receiver.serialize_witness_state(), state);
+
+    // the receiver proves the statement of the sender
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(6, &mut rng);
+    let (prover, verifier) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    let (proof, public_inputs) = prover
+        .prove_with_witness_state::<_, DummyCircuit>(&mut rng, &state)
+        .expect("failed to prove");
+    assert_eq!(public_inputs, vec![BlsScalar::from(143u64)]);
+
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("failed to verify proof");
+}
+
+#[test]
+fn witness_state_other_circuit() {
+    let state = synthesize(&OtherCircuit::default()).serialize_witness_state();
+
+    let mut receiver = synthesize(&DummyCircuit::default());
+    let untouched = receiver.serialize_witness_state();
+
+    assert!(matches!(
+        receiver.apply_witness_state(&state),
+        Err(WitnessStateError::CircuitMismatch { .. })
+    ));
+    assert_eq!(receiver.serialize_witness_state(), untouched);
+
+    let mut rng = StdRng::seed_from_u64(8349u64);
+    let pp = PlonkParams::<TatePairing>::setup(6, &mut rng);
+    let (prover, _) =
+        PlonkKey::<TatePairing, JubjubAffine, DummyCircuit>::compile(&pp)
+            .expect("failed to compile circuit");
+
+    assert!(matches!(
+        prover.prove_with_witness_state::<_, DummyCircuit>(&mut rng, &state),
+        Err(Error::ProofVerificationError)
+    ));
+}
+
+#[test]
+fn witness_state_malformed() {
+    let state = synthesize(&DummyCircuit::default()).serialize_witness_state();
+    let mut receiver = synthesize(&DummyCircuit::default());
+
+    assert_eq!(
+        receiver.apply_witness_state(&state[..10]),
+        Err(WitnessStateError::Truncated)
+    );
+    assert_eq!(
+        receiver.apply_witness_state(&state[..state.len() - 1]),
+        Err(WitnessStateError::Truncated)
+    );
+
+    let mut trailing = state.clone();
+    trailing.push(0);
+    assert_eq!(
+        receiver.apply_witness_state(&trailing),
+        Err(WitnessStateError::Malformed)
+    );
+
+    let mut magic = state.clone();
+    magic[0] ^= 1;
+    assert_eq!(
+        receiver.apply_witness_state(&magic),
+        Err(WitnessStateError::BadMagic)
+    );
+
+    let mut version = state;
+    version[4] = 0xff;
+    assert_eq!(
+        receiver.apply_witness_state(&version),
+        Err(WitnessStateError::UnsupportedVersion(0xff))
+    );
+}
+
+#[cfg(feature = "witness-compression")]
+#[test]
+fn witness_state_compressed() {
+    let circuit = DummyCircuit::new(BlsScalar::from(11u64), 13u64.into());
+    let sender = synthesize(&circuit);
+    let state = sender.serialize_witness_state_compressed(3);
+
+    let mut receiver = synthesize(&DummyCircuit::default());
+    receiver
+        .apply_witness_state(&state)
+        .expect("the state belongs to the circuit");
+
+    assert_eq!(
+        receiver.serialize_witness_state(),
+        sender.serialize_witness_state()
+    );
+}